@@ -84,7 +84,7 @@
 //! [`animated_field`]: crate::animated_field
 
 use core::{
-    any::TypeId,
+    any::{Any, TypeId},
     fmt::{self, Debug, Formatter},
     marker::PhantomData,
 };
@@ -92,6 +92,10 @@ use core::{
 #[cfg(feature = "bevy_mesh")]
 pub use crate::morph::*;
 use crate::{
+    compression::{
+        AnimationCompressionReport, AnimationCompressionSettings, CompressedCurve,
+        SerializedAnimationCurve,
+    },
     graph::AnimationNodeIndex,
     prelude::{Animatable, BlendInput},
     AnimationEntityMut, AnimationEvaluationError,
@@ -99,10 +103,11 @@ use crate::{
 use bevy_ecs::component::{Component, Mutable};
 use bevy_math::curve::{
     cores::{UnevenCore, UnevenCoreError},
-    Curve, Interval,
+    Curve, Interval, UnevenSampleAutoCurve,
 };
 use bevy_platform::hash::Hashed;
 use bevy_reflect::{FromReflect, Reflect, Reflectable, TypeInfo, Typed};
+use bevy_transform::components::Transform;
 use downcast_rs::{impl_downcast, Downcast};
 
 /// A trait for exposing a value in an entity so that it can be animated.
@@ -362,10 +367,9 @@ where
     }
 
     fn create_evaluator(&self) -> Box<dyn AnimationCurveEvaluator> {
-        Box::new(AnimatableCurveEvaluator::<P::Property> {
-            evaluator: BasicAnimationCurveEvaluator::default(),
-            property: Box::new(self.property.clone()),
-        })
+        Box::new(AnimatableCurveEvaluator::new(Box::new(
+            self.property.clone(),
+        )))
     }
 
     fn apply(
@@ -379,15 +383,71 @@ where
             .downcast_mut::<AnimatableCurveEvaluator<P::Property>>()
             .unwrap();
         let value = self.curve.sample_clamped(t);
-        curve_evaluator
-            .evaluator
+        curve_evaluator.push(value, weight, graph_node);
+        Ok(())
+    }
+
+    fn compress(
+        &self,
+        settings: &AnimationCompressionSettings,
+    ) -> Option<(Box<dyn AnimationCurve>, AnimationCompressionReport)> {
+        // Only linearly-interpolated keyframes can be reduced without changing
+        // the shape of the curve between them.
+        let curve: &dyn Any = &self.curve;
+        let (times, values) =
+            if let Some(curve) = curve.downcast_ref::<AnimatableKeyframeCurve<P::Property>>() {
+                (&curve.core.times[..], &curve.core.samples[..])
+            } else if let Some(curve) = curve.downcast_ref::<UnevenSampleAutoCurve<P::Property>>() {
+                (curve.times(), curve.samples())
+            } else {
+                return None;
+            };
+
+        let (curve, report) =
+            CompressedCurve::from_keyframes(self.property.clone(), times, values, settings)?;
+        Some((Box::new(curve), report))
+    }
+
+    fn serialize_keyframes(&self) -> Option<SerializedAnimationCurve> {
+        let curve: &dyn Any = &self.curve;
+        let (times, values) =
+            if let Some(curve) = curve.downcast_ref::<AnimatableKeyframeCurve<P::Property>>() {
+                (&curve.core.times[..], &curve.core.samples[..])
+            } else if let Some(curve) = curve.downcast_ref::<UnevenSampleAutoCurve<P::Property>>() {
+                (curve.times(), curve.samples())
+            } else {
+                return None;
+            };
+        SerializedAnimationCurve::from_keyframes(&self.property.evaluator_id(), times, values)
+    }
+}
+
+impl<A: Animatable> AnimatableCurveEvaluator<A> {
+    /// Creates an evaluator that writes to the given property.
+    pub(crate) fn new(property: Box<dyn AnimatableProperty<Property = A>>) -> Self {
+        Self {
+            evaluator: BasicAnimationCurveEvaluator::default(),
+            property,
+        }
+    }
+
+    /// Pushes a value sampled from a curve onto the evaluation stack.
+    pub(crate) fn push(&mut self, value: A, weight: f32, graph_node: AnimationNodeIndex) {
+        self.evaluator
             .stack
             .push(BasicAnimationCurveEvaluatorStackElement {
                 value,
                 weight,
                 graph_node,
             });
-        Ok(())
+    }
+
+    /// Pops the evaluated value off the evaluation stack without writing it to
+    /// the property, and clears the stack.
+    pub(crate) fn take(&mut self) -> Option<A> {
+        let value = self.evaluator.stack.pop().map(|element| element.value);
+        self.evaluator.stack.clear();
+        value
     }
 }
 
@@ -600,6 +660,31 @@ pub trait AnimationCurve: Debug + Send + Sync + 'static {
         weight: f32,
         graph_node: AnimationNodeIndex,
     ) -> Result<(), AnimationEvaluationError>;
+
+    /// Returns a compressed copy of this curve and a report of how much it
+    /// was compressed, or `None` if this curve can't be compressed.
+    ///
+    /// This is used by [`AnimationClip::compress`]. The default
+    /// implementation returns `None`.
+    ///
+    /// [`AnimationClip::compress`]: crate::AnimationClip::compress
+    fn compress(
+        &self,
+        _settings: &AnimationCompressionSettings,
+    ) -> Option<(Box<dyn AnimationCurve>, AnimationCompressionReport)> {
+        None
+    }
+
+    /// Returns the keyframes of this curve in a form that can be saved, or
+    /// `None` if this curve can't be saved.
+    ///
+    /// This is used by [`AnimationClipSaver`]. The default implementation
+    /// returns `None`.
+    ///
+    /// [`AnimationClipSaver`]: crate::compression::AnimationClipSaver
+    fn serialize_keyframes(&self) -> Option<SerializedAnimationCurve> {
+        None
+    }
 }
 
 /// The [`EvaluatorId`] is used to look up the [`AnimationCurveEvaluator`] for an [`AnimatableProperty`].
@@ -618,6 +703,27 @@ pub enum EvaluatorId<'a> {
     Type(TypeId),
 }
 
+impl EvaluatorId<'_> {
+    /// Returns the name of the [`Transform`] field this evaluator animates, if
+    /// it animates one.
+    pub(crate) fn transform_field(&self) -> Option<&'static str> {
+        let EvaluatorId::ComponentField(field) = self else {
+            return None;
+        };
+        let (type_id, field_index) = ***field;
+        if type_id != TypeId::of::<Transform>() {
+            return None;
+        }
+        Some(
+            Transform::type_info()
+                .as_struct()
+                .ok()?
+                .field_at(field_index)?
+                .name(),
+        )
+    }
+}
+
 /// A low-level trait for use in [`VariableCurve`](`crate::VariableCurve`) that provides fine
 /// control over how animations are evaluated.
 ///
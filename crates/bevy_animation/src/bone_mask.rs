@@ -0,0 +1,407 @@
+//! Bone masks, which restrict animation layers to part of a skeleton.
+
+use std::io;
+
+use bevy_asset::{io::Reader, Asset, AssetLoader, Assets, LoadContext};
+use bevy_ecs::{
+    entity::Entity,
+    hierarchy::Children,
+    name::Name,
+    system::{Query, Res},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    graph::{AnimationGraph, AnimationGraphHandle, AnimationNodeType},
+    AnimationPlayer, AnimationTargetId,
+};
+
+/// A reusable set of per-joint weights that restricts an animation layer to
+/// part of a skeleton, such as the upper body.
+///
+/// Bone masks are used by [`AnimationNodeType::OverrideLayer`] and
+/// [`AnimationNodeType::AdditiveLayer`] nodes: the layers of such a node
+/// affect each animation target (bone) in proportion to its weight in the
+/// mask. Targets that aren't in the mask have a weight of 0.0 and are
+/// unaffected by the layers.
+///
+/// Unlike the mask bits of [`AnimationGraphNode`]s, which switch animation
+/// targets on or off, bone masks have a weight per joint, which allows layers
+/// to fade in smoothly along a chain of joints.
+///
+/// Bone masks are assets and can be serialized to and loaded from [RON] files.
+/// Canonically, such files have a `.bonemask.ron` extension.
+///
+/// Since the animation targets of a skeleton are only known once it has been
+/// spawned, a mask can also refer to joints by name, with
+/// [`BoneMask::add_named_joint`]. Each named joint adds itself and all its
+/// descendants to the mask. Named joints are resolved against the skeleton
+/// below each [`AnimationPlayer`] using the mask, every frame before the
+/// animation targets are animated, so the same mask can be used with any
+/// skeleton that has joints with these names:
+///
+/// ```ron
+/// (
+///     weights: {},
+///     joints: [
+///         (name: "Spine", weight: 0.5),
+///         (name: "Chest", weight: 1.0),
+///     ],
+/// )
+/// ```
+///
+/// [`AnimationNodeType::OverrideLayer`]: crate::graph::AnimationNodeType::OverrideLayer
+/// [`AnimationNodeType::AdditiveLayer`]: crate::graph::AnimationNodeType::AdditiveLayer
+/// [`AnimationGraphNode`]: crate::graph::AnimationGraphNode
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+/// [RON]: https://github.com/ron-rs/ron
+#[derive(Asset, Reflect, Clone, Debug, Default, Serialize, Deserialize)]
+#[reflect(Clone, Debug, Default)]
+pub struct BoneMask {
+    /// The weight of each animation target in the mask.
+    ///
+    /// These take precedence over the weights of the named [`joints`](Self::joints).
+    pub weights: HashMap<AnimationTargetId, f32>,
+    /// Joints that are added to the mask, along with their descendants, by name.
+    ///
+    /// Later joints take precedence over earlier ones, so a joint further down
+    /// the skeleton can be given a different weight than its ancestor.
+    #[serde(default)]
+    pub joints: Vec<BoneMaskJoint>,
+}
+
+/// A joint in a [`BoneMask`], which is looked up by name in the skeleton of
+/// each [`AnimationPlayer`] using the mask.
+#[derive(Reflect, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[reflect(Clone, Debug, PartialEq)]
+pub struct BoneMaskJoint {
+    /// The [`Name`] of the joint.
+    pub name: String,
+    /// The weight of the joint and all its descendants.
+    pub weight: f32,
+}
+
+/// A query over the skeleton hierarchy, used to build [`BoneMask`]s from the
+/// joints below a named joint.
+pub type BoneMaskHierarchyQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static Name>,
+        Option<&'static AnimationTargetId>,
+        Option<&'static Children>,
+    ),
+>;
+
+impl BoneMask {
+    /// Creates an empty bone mask.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a bone mask containing the joint named `joint` and all its
+    /// descendants with a weight of 1.0.
+    ///
+    /// The joint is searched for among `root` and its descendants, usually
+    /// starting from the entity with the [`AnimationPlayer`]. Returns `None`
+    /// if no such joint exists.
+    ///
+    /// [`AnimationPlayer`]: crate::AnimationPlayer
+    pub fn from_joint(
+        root: Entity,
+        joint: &str,
+        hierarchy: &BoneMaskHierarchyQuery,
+    ) -> Option<Self> {
+        let mut mask = Self::new();
+        mask.add_joint(root, joint, 1.0, hierarchy).then_some(mask)
+    }
+
+    /// Adds the joint named `joint` and all its descendants to this mask with
+    /// the given weight, replacing their existing weights.
+    ///
+    /// The joint is searched for among `root` and its descendants. Returns
+    /// false if no such joint exists.
+    pub fn add_joint(
+        &mut self,
+        root: Entity,
+        joint: &str,
+        weight: f32,
+        hierarchy: &BoneMaskHierarchyQuery,
+    ) -> bool {
+        let Some(joint) = find_joint(root, joint, hierarchy) else {
+            return false;
+        };
+        self.add_subtree(joint, weight, hierarchy);
+        true
+    }
+
+    /// Adds the joint named `joint` and all its descendants to this mask with
+    /// the given weight.
+    ///
+    /// Unlike [`BoneMask::add_joint`], the joint is looked up in the skeleton
+    /// of each [`AnimationPlayer`] using the mask, so
+    /// the mask can be created without a skeleton and saved as an asset.
+    pub fn add_named_joint(&mut self, joint: impl Into<String>, weight: f32) -> &mut Self {
+        self.joints.push(BoneMaskJoint {
+            name: joint.into(),
+            weight,
+        });
+        self
+    }
+
+    /// Looks up the named [`joints`](Self::joints) among `root` and its
+    /// descendants, returning the weight of every animation target below them.
+    ///
+    /// Joints that don't exist are ignored.
+    pub fn resolve_joints(
+        &self,
+        root: Entity,
+        hierarchy: &BoneMaskHierarchyQuery,
+    ) -> HashMap<AnimationTargetId, f32> {
+        let mut weights = HashMap::default();
+        for joint in &self.joints {
+            if let Some(entity) = find_joint(root, &joint.name, hierarchy) {
+                add_subtree(&mut weights, entity, joint.weight, hierarchy);
+            }
+        }
+        weights
+    }
+
+    /// Sets the weight of a single animation target.
+    pub fn set_weight(&mut self, target: AnimationTargetId, weight: f32) -> &mut Self {
+        self.weights.insert(target, weight);
+        self
+    }
+
+    /// Removes an animation target from the mask, so that it has a weight of
+    /// 0.0.
+    pub fn remove(&mut self, target: AnimationTargetId) -> &mut Self {
+        self.weights.remove(&target);
+        self
+    }
+
+    /// Returns the weight of the given animation target, or 0.0 if it isn't
+    /// in the mask.
+    ///
+    /// This ignores the named [`joints`](Self::joints). See
+    /// [`BoneMask::weight_with_joints`].
+    pub fn weight(&self, target: AnimationTargetId) -> f32 {
+        self.weights.get(&target).copied().unwrap_or_default()
+    }
+
+    /// Returns the weight of the given animation target, falling back to the
+    /// weights of the named joints as returned by [`BoneMask::resolve_joints`].
+    pub fn weight_with_joints(
+        &self,
+        target: AnimationTargetId,
+        resolved_joints: Option<&HashMap<AnimationTargetId, f32>>,
+    ) -> f32 {
+        self.weights
+            .get(&target)
+            .or_else(|| resolved_joints?.get(&target))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn add_subtree(&mut self, entity: Entity, weight: f32, hierarchy: &BoneMaskHierarchyQuery) {
+        add_subtree(&mut self.weights, entity, weight, hierarchy);
+    }
+}
+
+/// Sets the weight of `entity` and all its descendants in `weights`.
+fn add_subtree(
+    weights: &mut HashMap<AnimationTargetId, f32>,
+    entity: Entity,
+    weight: f32,
+    hierarchy: &BoneMaskHierarchyQuery,
+) {
+    let Ok((_, target, children)) = hierarchy.get(entity) else {
+        return;
+    };
+    if let Some(&target) = target {
+        weights.insert(target, weight);
+    }
+    for &child in children.into_iter().flatten() {
+        add_subtree(weights, child, weight, hierarchy);
+    }
+}
+
+/// A system that resolves the named joints of the [`BoneMask`]s used by the
+/// layer nodes of each [`AnimationPlayer`]'s graph against the skeleton below
+/// the player.
+///
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+pub fn resolve_bone_masks(
+    graphs: Res<Assets<AnimationGraph>>,
+    bone_masks: Res<Assets<BoneMask>>,
+    mut players: Query<(Entity, &mut AnimationPlayer, &AnimationGraphHandle)>,
+    hierarchy: BoneMaskHierarchyQuery,
+) {
+    for (entity, mut player, graph_handle) in &mut players {
+        let Some(graph) = graphs.get(graph_handle) else {
+            continue;
+        };
+        let mut resolved = HashMap::default();
+        for node in graph.graph.node_weights() {
+            let (AnimationNodeType::OverrideLayer(Some(ref mask))
+            | AnimationNodeType::AdditiveLayer(Some(ref mask))) = node.node_type
+            else {
+                continue;
+            };
+            let Some(bone_mask) = bone_masks.get(mask) else {
+                continue;
+            };
+            if bone_mask.joints.is_empty() {
+                continue;
+            }
+            resolved
+                .entry(mask.id())
+                .or_insert_with(|| bone_mask.resolve_joints(entity, &hierarchy));
+        }
+        if player.resolved_bone_masks != resolved {
+            player.resolved_bone_masks = resolved;
+        }
+    }
+}
+
+/// Finds the entity named `joint` among `entity` and its descendants, in
+/// depth-first order.
+fn find_joint(entity: Entity, joint: &str, hierarchy: &BoneMaskHierarchyQuery) -> Option<Entity> {
+    let (name, _, children) = hierarchy.get(entity).ok()?;
+    if name.is_some_and(|name| name.as_str() == joint) {
+        return Some(entity);
+    }
+    children
+        .into_iter()
+        .flatten()
+        .find_map(|&child| find_joint(child, joint, hierarchy))
+}
+
+/// An [`AssetLoader`] that can load [`BoneMask`]s as assets.
+///
+/// The canonical extension for [`BoneMask`]s is `.bonemask.ron`. Plain
+/// `.bonemask` is supported as well.
+#[derive(Default, TypePath)]
+pub struct BoneMaskAssetLoader;
+
+/// Errors that can occur when deserializing bone masks from RON.
+#[derive(Error, Debug)]
+pub enum BoneMaskLoadError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error(transparent)]
+    SpannedRon(#[from] SpannedError),
+}
+
+impl AssetLoader for BoneMaskAssetLoader {
+    type Asset = BoneMask;
+
+    type Settings = ();
+
+    type Error = BoneMaskLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bonemask", "bonemask.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+
+    use super::*;
+
+    #[test]
+    fn bone_mask_from_joint() {
+        let mut world = World::new();
+        let names = ["root", "hips", "spine", "arm", "leg"];
+        let [root, hips, spine, arm, leg] = names.map(|name| {
+            world
+                .spawn((
+                    Name::new(name),
+                    AnimationTargetId::from_name(&Name::new(name)),
+                ))
+                .id()
+        });
+        world.entity_mut(root).add_child(hips);
+        world.entity_mut(hips).add_children(&[spine, leg]);
+        world.entity_mut(spine).add_child(arm);
+
+        let mask = world
+            .run_system_once(move |hierarchy: BoneMaskHierarchyQuery| {
+                let mut mask = BoneMask::from_joint(root, "spine", &hierarchy).unwrap();
+                assert!(!mask.add_joint(root, "tail", 1.0, &hierarchy));
+                mask.set_weight(AnimationTargetId::from_name(&Name::new("hips")), 0.25);
+                mask
+            })
+            .unwrap();
+
+        let weight = |name| mask.weight(AnimationTargetId::from_name(&Name::new(name)));
+        assert_eq!(weight("root"), 0.0);
+        assert_eq!(weight("hips"), 0.25);
+        assert_eq!(weight("spine"), 1.0);
+        assert_eq!(weight("arm"), 1.0);
+        assert_eq!(weight("leg"), 0.0);
+
+        let ron = ron::ser::to_string(&mask).unwrap();
+        let loaded: BoneMask = ron::de::from_str(&ron).unwrap();
+        assert_eq!(loaded.weights, mask.weights);
+    }
+
+    #[test]
+    fn bone_mask_named_joints() {
+        let mut world = World::new();
+        let names = ["root", "hips", "spine", "arm", "leg"];
+        let [root, hips, spine, arm, leg] = names.map(|name| {
+            world
+                .spawn((
+                    Name::new(name),
+                    AnimationTargetId::from_name(&Name::new(name)),
+                ))
+                .id()
+        });
+        world.entity_mut(root).add_child(hips);
+        world.entity_mut(hips).add_children(&[spine, leg]);
+        world.entity_mut(spine).add_child(arm);
+
+        let mut mask = BoneMask::new();
+        mask.add_named_joint("spine", 0.5)
+            .add_named_joint("arm", 1.0)
+            .add_named_joint("tail", 1.0)
+            .set_weight(AnimationTargetId::from_name(&Name::new("leg")), 0.25);
+
+        let ron = ron::ser::to_string(&mask).unwrap();
+        let mask: BoneMask = ron::de::from_str(&ron).unwrap();
+        assert_eq!(mask.joints.len(), 3);
+
+        let resolved = world
+            .run_system_once(move |hierarchy: BoneMaskHierarchyQuery| {
+                mask.resolve_joints(root, &hierarchy)
+            })
+            .unwrap();
+        let weight = |name| resolved.get(&AnimationTargetId::from_name(&Name::new(name)));
+        assert_eq!(weight("root"), None);
+        assert_eq!(weight("hips"), None);
+        assert_eq!(weight("spine"), Some(&0.5));
+        assert_eq!(weight("arm"), Some(&1.0));
+        assert_eq!(weight("leg"), None);
+    }
+}
@@ -0,0 +1,1234 @@
+//! Compression of imported animation data.
+//!
+//! Animation clips imported from glTF files and motion capture usually have a
+//! keyframe for every frame of the source animation, most of which can be
+//! reconstructed by interpolating their neighbors. Compression removes such
+//! redundant keyframes as long as the curve stays within an error tolerance,
+//! and optionally quantizes rotations to 48 bits.
+//!
+//! Clips can be compressed at runtime with [`AnimationClip::compress`], or
+//! ahead of time in the asset processor with an [`AnimationCompressor`]. Since
+//! animation curves can't be serialized in general, the compressed clips are
+//! saved with the [`AnimationClipSaver`], which writes the reduced keyframes of
+//! [`Transform`] and morph target weight curves to `.animclip.ron` files that
+//! the [`AnimationClipLoader`] loads back. Together, they form the
+//! `LoadTransformAndSave<AnimationClipLoader, AnimationCompressor,
+//! AnimationClipSaver>` process, which the [`AnimationPlugin`] registers with
+//! the asset processor.
+//!
+//! [`AnimationClip::compress`]: crate::AnimationClip::compress
+//! [`AnimationPlugin`]: crate::AnimationPlugin
+
+use core::{
+    any::Any,
+    convert::Infallible,
+    f32::consts::FRAC_1_SQRT_2,
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    mem::size_of,
+    ops::AddAssign,
+};
+use std::io;
+
+use bevy_asset::{
+    io::{Reader, Writer},
+    saver::{AssetSaver, SavedAsset},
+    transformer::{AssetTransformer, TransformedAsset},
+    Asset, AssetLoader, AssetPath, AsyncWriteExt, LoadContext,
+};
+
+use bevy_math::{
+    curve::{
+        cores::{uneven_interp, InterpolationDatum},
+        Curve, Interval,
+    },
+    ops, Quat, Vec2, Vec3, Vec3A, Vec4,
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::TypePath;
+use bevy_transform::components::Transform;
+use ron::de::SpannedError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+#[cfg(feature = "bevy_mesh")]
+use crate::{gltf_curves::WideLinearKeyframeCurve, morph::WeightsCurve};
+
+use crate::{
+    animatable::Animatable,
+    animated_field,
+    animation_curves::{
+        AnimatableCurveEvaluator, AnimatableProperty, AnimatedField, AnimationCurve,
+        AnimationCurveEvaluator, EvaluatorId,
+    },
+    graph::AnimationNodeIndex,
+    AnimationClip, AnimationEvaluationError, AnimationTargetId, VariableCurve,
+};
+
+/// Settings that control how animation curves are compressed.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnimationCompressionSettings {
+    /// The maximum error allowed for translations, scales, morph target
+    /// weights and other non-rotation values, in the units of the value.
+    pub tolerance: f32,
+    /// The maximum error allowed for rotations, in radians.
+    pub rotation_tolerance: f32,
+    /// Whether rotations are quantized to 48 bits.
+    ///
+    /// Quantization introduces an error of about 10⁻⁴ radians, which counts
+    /// toward [`Self::rotation_tolerance`].
+    pub quantize_rotations: bool,
+}
+
+impl Default for AnimationCompressionSettings {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-4,
+            rotation_tolerance: 1e-3,
+            quantize_rotations: true,
+        }
+    }
+}
+
+/// Statistics about the compression of one or more animation curves.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AnimationCompressionReport {
+    /// The number of curves that were compressed.
+    pub curves: usize,
+    /// The number of keyframes in the curves before compression.
+    pub original_keyframes: usize,
+    /// The number of keyframes in the curves after compression.
+    pub compressed_keyframes: usize,
+    /// The size of the keyframe data before compression, in bytes.
+    pub original_bytes: usize,
+    /// The size of the keyframe data after compression, in bytes.
+    pub compressed_bytes: usize,
+    /// The largest difference between a non-rotation value of the original
+    /// curves and the compressed curves, measured at the original keyframes.
+    pub max_error: f32,
+    /// The largest angle between a rotation of the original curves and the
+    /// compressed curves, measured at the original keyframes, in radians.
+    pub max_rotation_error: f32,
+}
+
+impl AnimationCompressionReport {
+    /// Returns the ratio between the original size and the compressed size of
+    /// the keyframe data.
+    ///
+    /// This is 1.0 if nothing was compressed.
+    pub fn ratio(&self) -> f32 {
+        if self.compressed_bytes == 0 {
+            1.0
+        } else {
+            self.original_bytes as f32 / self.compressed_bytes as f32
+        }
+    }
+}
+
+impl AddAssign for AnimationCompressionReport {
+    fn add_assign(&mut self, other: Self) {
+        self.curves += other.curves;
+        self.original_keyframes += other.original_keyframes;
+        self.compressed_keyframes += other.compressed_keyframes;
+        self.original_bytes += other.original_bytes;
+        self.compressed_bytes += other.compressed_bytes;
+        self.max_error = self.max_error.max(other.max_error);
+        self.max_rotation_error = self.max_rotation_error.max(other.max_rotation_error);
+    }
+}
+
+/// A unit quaternion quantized to 48 bits.
+///
+/// The quaternion is stored as its three smallest components with 15 bits
+/// each, along with the index of the largest component, which is
+/// reconstructed from the others.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct QuantizedQuat([u16; 3]);
+
+impl QuantizedQuat {
+    /// The largest magnitude of the three smallest components of a unit
+    /// quaternion.
+    const RANGE: f32 = FRAC_1_SQRT_2;
+
+    /// The largest quantized value of a component.
+    const MAX: f32 = 32767.0;
+
+    /// Quantizes the given rotation.
+    pub fn from_quat(quat: Quat) -> Self {
+        let components = quat.normalize().to_array();
+        let largest = (0..4)
+            .max_by(|&a, &b| components[a].abs().total_cmp(&components[b].abs()))
+            .unwrap_or_default();
+        // `q` and `-q` are the same rotation, so flip the sign to make the
+        // largest component positive.
+        let sign = if components[largest] < 0.0 { -1.0 } else { 1.0 };
+
+        let mut words = [0; 3];
+        for (word, index) in words.iter_mut().zip((0..4).filter(|&i| i != largest)) {
+            let component = (components[index] * sign).clamp(-Self::RANGE, Self::RANGE);
+            let quantized = ((component + Self::RANGE) / (2.0 * Self::RANGE) * Self::MAX).round();
+            *word = (quantized as u16) << 1;
+        }
+        words[0] |= (largest & 1) as u16;
+        words[1] |= (largest >> 1) as u16;
+        Self(words)
+    }
+
+    /// Reconstructs the rotation.
+    pub fn to_quat(self) -> Quat {
+        let largest = (self.0[0] & 1) as usize | (((self.0[1] & 1) as usize) << 1);
+        let mut components = [0.0; 4];
+        let mut length_squared = 0.0;
+        for (word, index) in self.0.iter().zip((0..4).filter(|&i| i != largest)) {
+            let component = f32::from(word >> 1) / Self::MAX * 2.0 * Self::RANGE - Self::RANGE;
+            components[index] = component;
+            length_squared += component * component;
+        }
+        components[largest] = ops::sqrt((1.0 - length_squared).max(0.0));
+        Quat::from_array(components).normalize()
+    }
+}
+
+/// A keyframe value type that can be compressed.
+trait Keyframe: Animatable + Copy {
+    /// Whether this type is a rotation, which uses the rotation tolerance.
+    const ROTATION: bool = false;
+
+    /// Returns the error between a compressed value and an original value.
+    fn error(compressed: &Self, original: &Self) -> f32;
+
+    /// Stores compressed keyframe values.
+    fn into_values(values: Vec<Self>) -> CompressedValues;
+}
+
+macro_rules! impl_vector_keyframe {
+    ($ty: ident) => {
+        impl Keyframe for $ty {
+            fn error(compressed: &Self, original: &Self) -> f32 {
+                compressed.distance(*original)
+            }
+
+            fn into_values(values: Vec<Self>) -> CompressedValues {
+                CompressedValues::$ty(values)
+            }
+        }
+    };
+}
+
+impl_vector_keyframe!(Vec2);
+impl_vector_keyframe!(Vec3);
+impl_vector_keyframe!(Vec3A);
+impl_vector_keyframe!(Vec4);
+
+impl Keyframe for f32 {
+    fn error(compressed: &Self, original: &Self) -> f32 {
+        (compressed - original).abs()
+    }
+
+    fn into_values(values: Vec<Self>) -> CompressedValues {
+        CompressedValues::F32(values)
+    }
+}
+
+impl Keyframe for Quat {
+    const ROTATION: bool = true;
+
+    fn error(compressed: &Self, original: &Self) -> f32 {
+        // `Quat::angle_between` goes through `acos` of the dot product, which
+        // can't resolve the tiny angles that matter here.
+        let difference = compressed.inverse() * *original;
+        2.0 * ops::atan2(difference.xyz().length(), difference.w.abs())
+    }
+
+    fn into_values(values: Vec<Self>) -> CompressedValues {
+        CompressedValues::Quat(values)
+    }
+}
+
+/// Returns the error between a compressed keyframe value and an original
+/// value, or `None` if values of this type can't be compressed.
+///
+/// The error is the distance between the values for scalars and vectors, and
+/// the angle between the rotations in radians for quaternions. Together with
+/// [`max_curve_error`], this can be used to check compressed curves against
+/// the original ones.
+pub fn keyframe_error<T: 'static>(compressed: &T, original: &T) -> Option<f32> {
+    fn error<T: 'static, K: Keyframe>(compressed: &T, original: &T) -> Option<f32> {
+        let compressed = (compressed as &dyn Any).downcast_ref::<K>()?;
+        let original = (original as &dyn Any).downcast_ref::<K>()?;
+        Some(K::error(compressed, original))
+    }
+
+    error::<T, f32>(compressed, original)
+        .or_else(|| error::<T, Vec2>(compressed, original))
+        .or_else(|| error::<T, Vec3>(compressed, original))
+        .or_else(|| error::<T, Vec3A>(compressed, original))
+        .or_else(|| error::<T, Vec4>(compressed, original))
+        .or_else(|| error::<T, Quat>(compressed, original))
+}
+
+/// Returns the largest [`keyframe_error`] between a compressed curve and the
+/// original curve at the given times, or `None` if values of this type can't
+/// be compared.
+pub fn max_curve_error<T: 'static>(
+    compressed: &impl Curve<T>,
+    original: &impl Curve<T>,
+    times: impl IntoIterator<Item = f32>,
+) -> Option<f32> {
+    times.into_iter().try_fold(0.0f32, |max_error, t| {
+        let error = keyframe_error(&compressed.sample_clamped(t), &original.sample_clamped(t))?;
+        Some(max_error.max(error))
+    })
+}
+
+/// Chooses the keyframes to keep so that interpolating between them stays
+/// within `tolerance` of every original keyframe.
+///
+/// `error(start, end, index, s)` returns the error at the original keyframe
+/// `index` when interpolating between the kept keyframes `start` and `end`
+/// with the factor `s`. The first and last keyframes are always kept.
+fn reduce_keyframes(
+    times: &[f32],
+    tolerance: f32,
+    error: impl Fn(usize, usize, usize, f32) -> f32,
+) -> Vec<usize> {
+    let segment_error = |start: usize, end: usize| {
+        (start + 1..end)
+            .map(|index| {
+                let s = (times[index] - times[start]) / (times[end] - times[start]);
+                error(start, end, index, s)
+            })
+            .fold(0.0f32, f32::max)
+    };
+
+    // Greedily extend each segment for as long as it stays within the
+    // tolerance.
+    let last = times.len() - 1;
+    let mut kept = vec![0];
+    let mut start = 0;
+    let mut end = 1;
+    while end < last {
+        if segment_error(start, end + 1) <= tolerance {
+            end += 1;
+        } else {
+            kept.push(end);
+            start = end;
+            end = start + 1;
+        }
+    }
+    kept.push(last);
+    kept
+}
+
+/// Returns the largest error of the reduced keyframes at all the original
+/// keyframes, including the kept ones.
+fn reduced_error(
+    kept: &[usize],
+    times: &[f32],
+    error: impl Fn(usize, usize, usize, f32) -> f32,
+) -> f32 {
+    kept.windows(2)
+        .flat_map(|segment| {
+            let (start, end) = (segment[0], segment[1]);
+            (start..=end).map(move |index| (start, end, index))
+        })
+        .map(|(start, end, index)| {
+            let s = (times[index] - times[start]) / (times[end] - times[start]);
+            error(start, end, index, s)
+        })
+        .fold(0.0f32, f32::max)
+}
+
+/// Compresses linearly-interpolated keyframes.
+///
+/// Returns `None` if the values can't be compressed or if compression
+/// doesn't make them smaller.
+fn compress_keyframes<T: 'static>(
+    times: &[f32],
+    values: &[T],
+    settings: &AnimationCompressionSettings,
+) -> Option<(Vec<f32>, CompressedValues, AnimationCompressionReport)> {
+    fn compress<K: Keyframe>(
+        times: &[f32],
+        originals: &[K],
+        settings: &AnimationCompressionSettings,
+    ) -> (Vec<f32>, CompressedValues, AnimationCompressionReport) {
+        let tolerance = if K::ROTATION {
+            settings.rotation_tolerance
+        } else {
+            settings.tolerance
+        };
+        let quantized: Option<Vec<QuantizedQuat>> = (settings.quantize_rotations && K::ROTATION)
+            .then(|| downcast_values::<K, Quat>(originals))
+            .flatten()
+            .map(|rotations| {
+                rotations
+                    .into_iter()
+                    .map(QuantizedQuat::from_quat)
+                    .collect()
+            });
+        // Measure the error against the values that will actually be stored.
+        let keys: Vec<K> = match &quantized {
+            Some(quantized) => quantized
+                .iter()
+                .map(|quantized| cast(quantized.to_quat()))
+                .collect(),
+            None => originals.to_vec(),
+        };
+
+        let error = |start: usize, end: usize, index: usize, s: f32| {
+            K::error(
+                &K::interpolate(&keys[start], &keys[end], s),
+                &originals[index],
+            )
+        };
+        let kept = reduce_keyframes(times, tolerance, error);
+        let max_error = reduced_error(&kept, times, error);
+
+        let compressed_times: Vec<f32> = kept.iter().map(|&index| times[index]).collect();
+        let compressed_values = match quantized {
+            Some(quantized) => CompressedValues::QuantizedQuat(
+                kept.iter().map(|&index| quantized[index]).collect(),
+            ),
+            None => K::into_values(kept.iter().map(|&index| originals[index]).collect()),
+        };
+        let report = AnimationCompressionReport {
+            curves: 1,
+            original_keyframes: times.len(),
+            compressed_keyframes: kept.len(),
+            original_bytes: times.len() * (size_of::<f32>() + size_of::<K>()),
+            compressed_bytes: compressed_times.len() * size_of::<f32>()
+                + compressed_values.byte_len(),
+            max_error: if K::ROTATION { 0.0 } else { max_error },
+            max_rotation_error: if K::ROTATION { max_error } else { 0.0 },
+        };
+        (compressed_times, compressed_values, report)
+    }
+
+    let (times, values, report) = if let Some(values) = downcast_values::<T, f32>(values) {
+        compress(times, &values, settings)
+    } else if let Some(values) = downcast_values::<T, Vec2>(values) {
+        compress(times, &values, settings)
+    } else if let Some(values) = downcast_values::<T, Vec3>(values) {
+        compress(times, &values, settings)
+    } else if let Some(values) = downcast_values::<T, Vec3A>(values) {
+        compress(times, &values, settings)
+    } else if let Some(values) = downcast_values::<T, Vec4>(values) {
+        compress(times, &values, settings)
+    } else if let Some(values) = downcast_values::<T, Quat>(values) {
+        compress(times, &values, settings)
+    } else {
+        return None;
+    };
+    (report.compressed_bytes < report.original_bytes).then_some((times, values, report))
+}
+
+/// Returns the values as keyframes of type `K`, or `None` if `T` isn't `K`.
+fn downcast_values<T: 'static, K: Keyframe>(values: &[T]) -> Option<Vec<K>> {
+    values
+        .iter()
+        .map(|value| (value as &dyn Any).downcast_ref::<K>().copied())
+        .collect()
+}
+
+/// Converts a value to a type that's known to be the same at runtime.
+///
+/// # Panics
+///
+/// Panics if `T` and `U` are different types.
+fn cast<T: 'static, U: 'static>(value: T) -> U {
+    let mut value = Some(value);
+    (&mut value as &mut dyn Any)
+        .downcast_mut::<Option<U>>()
+        .and_then(Option::take)
+        .expect("compressed keyframes should have the type of the animated property")
+}
+
+/// The keyframe values of a [`CompressedCurve`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+enum CompressedValues {
+    F32(Vec<f32>),
+    Vec2(Vec<Vec2>),
+    Vec3(Vec<Vec3>),
+    Vec3A(Vec<Vec3A>),
+    Vec4(Vec<Vec4>),
+    Quat(Vec<Quat>),
+    QuantizedQuat(Vec<QuantizedQuat>),
+}
+
+impl CompressedValues {
+    /// Stores the given keyframe values, or returns `None` if values of this
+    /// type can't be stored.
+    fn from_keyframes<T: 'static>(values: &[T]) -> Option<Self> {
+        downcast_values::<T, f32>(values)
+            .map(Self::F32)
+            .or_else(|| downcast_values::<T, Vec2>(values).map(Self::Vec2))
+            .or_else(|| downcast_values::<T, Vec3>(values).map(Self::Vec3))
+            .or_else(|| downcast_values::<T, Vec3A>(values).map(Self::Vec3A))
+            .or_else(|| downcast_values::<T, Vec4>(values).map(Self::Vec4))
+            .or_else(|| downcast_values::<T, Quat>(values).map(Self::Quat))
+    }
+
+    /// Returns the number of stored values.
+    fn len(&self) -> usize {
+        match self {
+            Self::F32(values) => values.len(),
+            Self::Vec2(values) => values.len(),
+            Self::Vec3(values) => values.len(),
+            Self::Vec3A(values) => values.len(),
+            Self::Vec4(values) => values.len(),
+            Self::Quat(values) => values.len(),
+            Self::QuantizedQuat(values) => values.len(),
+        }
+    }
+
+    /// Returns the value of the keyframe with the given index.
+    ///
+    /// `T` must be the type of the stored values.
+    fn get<T: 'static>(&self, index: usize) -> T {
+        match self {
+            Self::F32(values) => cast(values[index]),
+            Self::Vec2(values) => cast(values[index]),
+            Self::Vec3(values) => cast(values[index]),
+            Self::Vec3A(values) => cast(values[index]),
+            Self::Vec4(values) => cast(values[index]),
+            Self::Quat(values) => cast(values[index]),
+            Self::QuantizedQuat(values) => cast(values[index].to_quat()),
+        }
+    }
+
+    /// Returns the size of the values in bytes.
+    fn byte_len(&self) -> usize {
+        match self {
+            Self::F32(values) => size_of_val(&values[..]),
+            Self::Vec2(values) => size_of_val(&values[..]),
+            Self::Vec3(values) => size_of_val(&values[..]),
+            Self::Vec3A(values) => size_of_val(&values[..]),
+            Self::Vec4(values) => size_of_val(&values[..]),
+            Self::Quat(values) => size_of_val(&values[..]),
+            Self::QuantizedQuat(values) => size_of_val(&values[..]),
+        }
+    }
+}
+
+/// A compact, linearly-interpolated animation curve produced by compressing
+/// the keyframes of an [`AnimatableCurve`].
+///
+/// This animates the same property as the original curve and blends with
+/// other curves in the same way.
+///
+/// [`AnimatableCurve`]: crate::animation_curves::AnimatableCurve
+pub struct CompressedCurve<P> {
+    property: P,
+    times: Vec<f32>,
+    values: CompressedValues,
+}
+
+impl<P> CompressedCurve<P>
+where
+    P: AnimatableProperty,
+{
+    /// Compresses linearly-interpolated keyframes of the given property.
+    ///
+    /// Returns `None` if values of the property's type can't be compressed,
+    /// or if compression wouldn't make the keyframes smaller. Scalars,
+    /// vectors and rotations can be compressed.
+    pub fn from_keyframes(
+        property: P,
+        times: &[f32],
+        values: &[P::Property],
+        settings: &AnimationCompressionSettings,
+    ) -> Option<(Self, AnimationCompressionReport)> {
+        if times.len() < 2 || times.len() != values.len() {
+            return None;
+        }
+        let (times, values, report) = compress_keyframes(times, values, settings)?;
+        Some((
+            Self {
+                property,
+                times,
+                values,
+            },
+            report,
+        ))
+    }
+
+    /// Returns the number of keyframes of this curve.
+    pub fn keyframe_count(&self) -> usize {
+        self.times.len()
+    }
+}
+
+impl<P: Clone> Clone for CompressedCurve<P> {
+    fn clone(&self) -> Self {
+        Self {
+            property: self.property.clone(),
+            times: self.times.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<P> Debug for CompressedCurve<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressedCurve")
+            .field("times", &self.times)
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
+impl<P> Curve<P::Property> for CompressedCurve<P>
+where
+    P: AnimatableProperty,
+{
+    fn domain(&self) -> Interval {
+        Interval::new(self.times[0], self.times[self.times.len() - 1])
+            .expect("compressed curves should have at least two keyframes")
+    }
+
+    fn sample_unchecked(&self, t: f32) -> P::Property {
+        match uneven_interp(&self.times, t) {
+            InterpolationDatum::Exact(index)
+            | InterpolationDatum::LeftTail(index)
+            | InterpolationDatum::RightTail(index) => self.values.get(index),
+            InterpolationDatum::Between(lower, upper, s) => {
+                P::Property::interpolate(&self.values.get(lower), &self.values.get(upper), s)
+            }
+        }
+    }
+}
+
+impl<P> AnimationCurve for CompressedCurve<P>
+where
+    P: AnimatableProperty + Clone,
+{
+    fn clone_value(&self) -> Box<dyn AnimationCurve> {
+        Box::new(self.clone())
+    }
+
+    fn domain(&self) -> Interval {
+        Curve::domain(self)
+    }
+
+    fn evaluator_id(&self) -> EvaluatorId<'_> {
+        self.property.evaluator_id()
+    }
+
+    fn create_evaluator(&self) -> Box<dyn AnimationCurveEvaluator> {
+        Box::new(AnimatableCurveEvaluator::new(Box::new(
+            self.property.clone(),
+        )))
+    }
+
+    fn apply(
+        &self,
+        curve_evaluator: &mut dyn AnimationCurveEvaluator,
+        t: f32,
+        weight: f32,
+        graph_node: AnimationNodeIndex,
+    ) -> Result<(), AnimationEvaluationError> {
+        let curve_evaluator = curve_evaluator
+            .downcast_mut::<AnimatableCurveEvaluator<P::Property>>()
+            .unwrap();
+        curve_evaluator.push(self.sample_clamped(t), weight, graph_node);
+        Ok(())
+    }
+
+    fn serialize_keyframes(&self) -> Option<SerializedAnimationCurve> {
+        Some(SerializedAnimationCurve {
+            property: SerializedAnimatedProperty::of(&self.property.evaluator_id())?,
+            times: self.times.clone(),
+            values: self.values.clone(),
+        })
+    }
+}
+
+/// Compresses the keyframes of a morph target weights curve.
+///
+/// All the weights of a keyframe are kept or removed together.
+#[cfg(feature = "bevy_mesh")]
+pub(crate) fn compress_weights(
+    curve: &WideLinearKeyframeCurve<f32>,
+    settings: &AnimationCompressionSettings,
+) -> Option<(WideLinearKeyframeCurve<f32>, AnimationCompressionReport)> {
+    let (times, values, width) = (curve.times(), curve.values(), curve.width());
+    let keyframe = |index: usize| &values[index * width..(index + 1) * width];
+    let error = |start: usize, end: usize, index: usize, s: f32| {
+        keyframe(start)
+            .iter()
+            .zip(keyframe(end))
+            .zip(keyframe(index))
+            .map(|((start, end), original)| (f32::interpolate(start, end, s) - original).abs())
+            .fold(0.0f32, f32::max)
+    };
+    let kept = reduce_keyframes(times, settings.tolerance, error);
+    let max_error = reduced_error(&kept, times, error);
+
+    let compressed = WideLinearKeyframeCurve::new(
+        kept.iter().map(|&index| times[index]),
+        kept.iter()
+            .flat_map(|&index| keyframe(index).iter().copied()),
+    )
+    .ok()?;
+    let report = AnimationCompressionReport {
+        curves: 1,
+        original_keyframes: times.len(),
+        compressed_keyframes: kept.len(),
+        original_bytes: size_of_val(times) + size_of_val(values),
+        compressed_bytes: size_of_val(compressed.times()) + size_of_val(compressed.values()),
+        max_error,
+        max_rotation_error: 0.0,
+    };
+    (report.compressed_bytes < report.original_bytes).then_some((compressed, report))
+}
+
+/// The property a [`SerializedAnimationCurve`] animates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SerializedAnimatedProperty {
+    /// [`Transform::translation`].
+    Translation,
+    /// [`Transform::rotation`].
+    Rotation,
+    /// [`Transform::scale`].
+    Scale,
+    /// The weights of the morph targets of a mesh.
+    MorphWeights,
+}
+
+impl SerializedAnimatedProperty {
+    /// Returns the [`Transform`] field that curves with the given evaluator
+    /// animate, if any.
+    fn of(evaluator_id: &EvaluatorId) -> Option<Self> {
+        match evaluator_id.transform_field()? {
+            "translation" => Some(Self::Translation),
+            "rotation" => Some(Self::Rotation),
+            "scale" => Some(Self::Scale),
+            _ => None,
+        }
+    }
+}
+
+/// The linearly-interpolated keyframes of an animation curve, in a form that
+/// can be saved.
+///
+/// This is created by [`AnimationCurve::serialize_keyframes`]. Only curves
+/// that animate the fields of a [`Transform`] or morph target weights can be
+/// saved.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializedAnimationCurve {
+    property: SerializedAnimatedProperty,
+    times: Vec<f32>,
+    values: CompressedValues,
+}
+
+impl SerializedAnimationCurve {
+    /// Stores linearly-interpolated keyframes of the property with the given
+    /// evaluator, or returns `None` if they can't be saved.
+    pub(crate) fn from_keyframes<T: 'static>(
+        evaluator_id: &EvaluatorId,
+        times: &[f32],
+        values: &[T],
+    ) -> Option<Self> {
+        Some(Self {
+            property: SerializedAnimatedProperty::of(evaluator_id)?,
+            times: times.to_vec(),
+            values: CompressedValues::from_keyframes(values)?,
+        })
+    }
+
+    /// Stores the keyframes of a morph target weights curve.
+    #[cfg(feature = "bevy_mesh")]
+    pub(crate) fn from_weights(curve: &WideLinearKeyframeCurve<f32>) -> Self {
+        Self {
+            property: SerializedAnimatedProperty::MorphWeights,
+            times: curve.times().to_vec(),
+            values: CompressedValues::F32(curve.values().to_vec()),
+        }
+    }
+
+    /// Returns the property this curve animates.
+    pub fn property(&self) -> SerializedAnimatedProperty {
+        self.property
+    }
+
+    /// Returns the number of keyframes of this curve.
+    pub fn keyframe_count(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Reconstructs the curve, or returns `None` if the keyframes are invalid.
+    fn into_curve(self) -> Option<VariableCurve> {
+        let Self {
+            property,
+            times,
+            values,
+        } = self;
+        if property == SerializedAnimatedProperty::MorphWeights {
+            #[cfg(feature = "bevy_mesh")]
+            if let CompressedValues::F32(values) = values {
+                let curve = WideLinearKeyframeCurve::new(times, values).ok()?;
+                return Some(VariableCurve::new(WeightsCurve(curve)));
+            }
+            return None;
+        }
+
+        if times.len() < 2
+            || times.len() != values.len()
+            || !times.iter().all(|time| time.is_finite())
+            || !times.windows(2).all(|pair| pair[0] < pair[1])
+        {
+            return None;
+        }
+        match (property, &values) {
+            (SerializedAnimatedProperty::Translation, CompressedValues::Vec3(_)) => {
+                Some(VariableCurve::new(CompressedCurve {
+                    property: animated_field!(Transform::translation),
+                    times,
+                    values,
+                }))
+            }
+            (
+                SerializedAnimatedProperty::Rotation,
+                CompressedValues::Quat(_) | CompressedValues::QuantizedQuat(_),
+            ) => Some(VariableCurve::new(CompressedCurve {
+                property: animated_field!(Transform::rotation),
+                times,
+                values,
+            })),
+            (SerializedAnimatedProperty::Scale, CompressedValues::Vec3(_)) => {
+                Some(VariableCurve::new(CompressedCurve {
+                    property: animated_field!(Transform::scale),
+                    times,
+                    values,
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A serialized form of an [`AnimationClip`] made of linearly-interpolated
+/// keyframes, as written by the [`AnimationClipSaver`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializedAnimationClip {
+    /// The duration of the clip, in seconds.
+    pub duration: f32,
+    /// The curves of each animation target.
+    pub curves: HashMap<AnimationTargetId, Vec<SerializedAnimationCurve>>,
+}
+
+impl TryFrom<&AnimationClip> for SerializedAnimationClip {
+    type Error = AnimationClipSaveError;
+
+    fn try_from(clip: &AnimationClip) -> Result<Self, Self::Error> {
+        if !clip.events.is_empty() {
+            return Err(AnimationClipSaveError::UnsupportedEvents);
+        }
+        let curves = clip
+            .curves
+            .iter()
+            .map(|(&target, curves)| {
+                let curves = curves
+                    .iter()
+                    .map(|curve| curve.0.serialize_keyframes())
+                    .collect::<Option<_>>()
+                    .ok_or(AnimationClipSaveError::UnsupportedCurve(target))?;
+                Ok((target, curves))
+            })
+            .collect::<Result<_, AnimationClipSaveError>>()?;
+        Ok(Self {
+            duration: clip.duration,
+            curves,
+        })
+    }
+}
+
+impl TryFrom<SerializedAnimationClip> for AnimationClip {
+    type Error = AnimationClipLoadError;
+
+    fn try_from(serialized: SerializedAnimationClip) -> Result<Self, Self::Error> {
+        let mut clip = AnimationClip::default();
+        for (target, curves) in serialized.curves {
+            for curve in curves {
+                let curve = curve
+                    .into_curve()
+                    .ok_or(AnimationClipLoadError::InvalidCurve(target))?;
+                clip.add_variable_curve_to_target(target, curve);
+            }
+        }
+        clip.set_duration(serialized.duration);
+        Ok(clip)
+    }
+}
+
+/// An [`AssetLoader`] that loads [`AnimationClip`]s saved by the
+/// [`AnimationClipSaver`].
+///
+/// The canonical extension is `.animclip.ron`. Plain `.animclip` is supported
+/// as well.
+#[derive(Default, TypePath)]
+pub struct AnimationClipLoader;
+
+/// Errors that can occur when loading an [`AnimationClip`] with the
+/// [`AnimationClipLoader`].
+#[derive(Error, Debug)]
+pub enum AnimationClipLoadError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON deserialization, and the location of the error
+    /// is supplied.
+    #[error(transparent)]
+    SpannedRon(#[from] SpannedError),
+    /// A curve of the animation target has invalid keyframes.
+    #[error("a curve of the animation target {0:?} has invalid keyframes")]
+    InvalidCurve(AnimationTargetId),
+}
+
+impl AssetLoader for AnimationClipLoader {
+    type Asset = AnimationClip;
+
+    type Settings = ();
+
+    type Error = AnimationClipLoadError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _: &Self::Settings,
+        _: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let serialized: SerializedAnimationClip = ron::de::from_bytes(&bytes)?;
+        serialized.try_into()
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["animclip", "animclip.ron"]
+    }
+}
+
+/// An [`AssetSaver`] that writes the keyframes of [`AnimationClip`]s as RON,
+/// to be loaded again with the [`AnimationClipLoader`].
+///
+/// Only clips made of linearly-interpolated [`Transform`] and morph target
+/// weight curves, such as the compressed curves of an [`AnimationCompressor`],
+/// can be saved. Animation events can't be saved.
+#[derive(Default, TypePath)]
+pub struct AnimationClipSaver;
+
+/// Errors that can occur when saving an [`AnimationClip`] with the
+/// [`AnimationClipSaver`].
+#[derive(Error, Debug)]
+pub enum AnimationClipSaveError {
+    /// An I/O error occurred.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// An error occurred in RON serialization.
+    #[error(transparent)]
+    Ron(#[from] ron::Error),
+    /// A curve of the animation target can't be saved.
+    #[error("a curve of the animation target {0:?} can't be saved")]
+    UnsupportedCurve(AnimationTargetId),
+    /// The clip has animation events, which can't be saved.
+    #[error("animation events can't be saved")]
+    UnsupportedEvents,
+}
+
+impl AssetSaver for AnimationClipSaver {
+    type Asset = AnimationClip;
+    type Settings = ();
+    type OutputLoader = AnimationClipLoader;
+    type Error = AnimationClipSaveError;
+
+    async fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, '_, Self::Asset>,
+        _settings: &Self::Settings,
+        _asset_path: AssetPath<'_>,
+    ) -> Result<(), Self::Error> {
+        let serialized = SerializedAnimationClip::try_from(&*asset)?;
+        let ron = ron::ser::to_string(&serialized)?;
+        writer.write_all(ron.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// An [`AssetTransformer`] that compresses [`AnimationClip`]s in the asset
+/// processor.
+///
+/// The asset is compressed if it's an [`AnimationClip`], and so are all its
+/// labeled [`AnimationClip`] subassets. Everything else is passed through
+/// unchanged. The compression ratio is logged for each processed asset.
+///
+/// Like all transformers, this must be paired with an [`AssetSaver`] for the
+/// asset type in a [`LoadTransformAndSave`] process. For [`AnimationClip`]s,
+/// that's the [`AnimationClipSaver`].
+///
+/// [`LoadTransformAndSave`]: bevy_asset::processor::LoadTransformAndSave
+#[derive(TypePath)]
+pub struct AnimationCompressor<A: Asset = AnimationClip> {
+    _phantom: PhantomData<fn(A) -> A>,
+}
+
+impl<A: Asset> AnimationCompressor<A> {
+    /// Creates a new [`AnimationCompressor`].
+    pub const fn new() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Asset> Default for AnimationCompressor<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Asset> AssetTransformer for AnimationCompressor<A> {
+    type AssetInput = A;
+    type AssetOutput = A;
+    type Settings = AnimationCompressionSettings;
+    type Error = Infallible;
+
+    async fn transform<'a>(
+        &'a self,
+        mut asset: TransformedAsset<Self::AssetInput>,
+        settings: &'a Self::Settings,
+    ) -> Result<TransformedAsset<Self::AssetOutput>, Self::Error> {
+        let mut report = AnimationCompressionReport::default();
+        if let Some(clip) = (asset.get_mut() as &mut dyn Any).downcast_mut::<AnimationClip>() {
+            report += clip.compress(settings);
+        }
+        let labels: Vec<String> = asset.iter_labels().map(ToString::to_string).collect();
+        for label in labels {
+            if let Some(mut clip) = asset.get_labeled::<AnimationClip, _>(label.as_str()) {
+                report += clip.get_mut().compress(settings);
+            }
+        }
+
+        if report.curves > 0 {
+            info!(
+                "Compressed {} animation curves from {} to {} keyframes, {} to {} bytes ({:.1}x)",
+                report.curves,
+                report.original_keyframes,
+                report.compressed_keyframes,
+                report.original_bytes,
+                report.compressed_bytes,
+                report.ratio(),
+            );
+        }
+        Ok(asset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::LoadedAsset;
+    use bevy_ecs::name::Name;
+    use bevy_math::curve::{ConstantCurve, UnevenSampleAutoCurve};
+    use bevy_platform::future::block_on;
+
+    use super::*;
+    use crate::animation_curves::{AnimatableCurve, AnimatableKeyframeCurve};
+
+    #[test]
+    fn quantized_quat_round_trip() {
+        for rotation in [
+            Quat::IDENTITY,
+            Quat::from_rotation_x(2.5),
+            Quat::from_euler(bevy_math::EulerRot::YXZ, -1.0, 0.3, 2.0),
+            -Quat::from_rotation_z(0.7),
+        ] {
+            let quantized = QuantizedQuat::from_quat(rotation).to_quat();
+            assert!(keyframe_error(&quantized, &rotation).unwrap() < 2e-4);
+        }
+    }
+
+    /// Returns dense, mocap-like keyframes: a linear walk with a slight sway,
+    /// and a turn that speeds up and slows down.
+    fn mocap_keyframes() -> (Vec<f32>, Vec<Vec3>, Vec<Quat>) {
+        let times: Vec<f32> = (0..=120).map(|frame| frame as f32 / 60.0).collect();
+        let translations = times
+            .iter()
+            .map(|&t| Vec3::new(0.05 * ops::sin(t * 3.0), 0.0, 1.5 * t))
+            .collect();
+        let rotations = times
+            .iter()
+            .map(|&t| Quat::from_rotation_y(ops::sin(t)))
+            .collect();
+        (times, translations, rotations)
+    }
+
+    #[test]
+    fn compressed_curves_stay_within_tolerance() {
+        let (times, translations, rotations) = mocap_keyframes();
+        let original_translations =
+            AnimatableKeyframeCurve::new(times.iter().copied().zip(translations.iter().copied()))
+                .unwrap();
+        let original_rotations =
+            UnevenSampleAutoCurve::new(times.iter().copied().zip(rotations.iter().copied()))
+                .unwrap();
+
+        let settings = AnimationCompressionSettings::default();
+        let (translation_curve, translation_report) = CompressedCurve::from_keyframes(
+            animated_field!(Transform::translation),
+            &times,
+            &translations,
+            &settings,
+        )
+        .unwrap();
+        let (rotation_curve, rotation_report) = CompressedCurve::from_keyframes(
+            animated_field!(Transform::rotation),
+            &times,
+            &rotations,
+            &settings,
+        )
+        .unwrap();
+
+        assert!(translation_curve.keyframe_count() < times.len());
+        assert!(rotation_curve.keyframe_count() < times.len());
+        assert!(rotation_report.ratio() > 2.0);
+
+        // The reported error matches the error measured at the original
+        // keyframes, and both are within the tolerance.
+        let translation_error =
+            max_curve_error(&translation_curve, &original_translations, times.clone()).unwrap();
+        let rotation_error =
+            max_curve_error(&rotation_curve, &original_rotations, times.clone()).unwrap();
+        assert!(translation_error <= settings.tolerance);
+        assert!(rotation_error <= settings.rotation_tolerance);
+        assert!((translation_error - translation_report.max_error).abs() < 1e-6);
+        assert!((rotation_error - rotation_report.max_rotation_error).abs() < 1e-6);
+
+        // Compressing a whole clip compresses the same curves.
+        let target = AnimationTargetId::from_name(&Name::new("root"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                original_translations,
+            ),
+        );
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(animated_field!(Transform::rotation), original_rotations),
+        );
+        let mut expected = translation_report;
+        expected += rotation_report;
+        assert_eq!(clip.compress(&settings), expected);
+        assert_eq!(
+            clip.compress(&settings),
+            AnimationCompressionReport::default()
+        );
+    }
+
+    #[test]
+    fn processed_clips_stay_within_tolerance() {
+        let (times, translations, rotations) = mocap_keyframes();
+        let original_translations =
+            AnimatableKeyframeCurve::new(times.iter().copied().zip(translations)).unwrap();
+        let original_rotations =
+            UnevenSampleAutoCurve::new(times.iter().copied().zip(rotations)).unwrap();
+        // Too short to be compressed, so it's saved as it is.
+        let original_scales =
+            AnimatableKeyframeCurve::new([(0.0, Vec3::ONE), (2.0, Vec3::splat(2.0))]).unwrap();
+
+        let target = AnimationTargetId::from_name(&Name::new("root"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                original_translations.clone(),
+            ),
+        );
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::rotation),
+                original_rotations.clone(),
+            ),
+        );
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(animated_field!(Transform::scale), original_scales),
+        );
+
+        // Run the clip through the same steps as the asset processor.
+        let settings = AnimationCompressionSettings::default();
+        let asset = TransformedAsset::from_loaded(LoadedAsset::from(clip).into()).unwrap();
+        let asset =
+            block_on(AnimationCompressor::<AnimationClip>::new().transform(asset, &settings))
+                .unwrap();
+        let mut bytes = Vec::new();
+        block_on(AnimationClipSaver.save(
+            &mut bytes,
+            SavedAsset::from_asset(asset.get()),
+            &(),
+            AssetPath::from("walk.animclip.ron"),
+        ))
+        .unwrap();
+        let serialized: SerializedAnimationClip = ron::de::from_bytes(&bytes).unwrap();
+
+        // The saved keyframes stay within the tolerance of the original
+        // curves.
+        let saved_curves = &serialized.curves[&target];
+        assert_eq!(saved_curves.len(), 3);
+        for curve in saved_curves {
+            match curve.property() {
+                SerializedAnimatedProperty::Translation => {
+                    let saved = UnevenSampleAutoCurve::new(
+                        (0..curve.keyframe_count())
+                            .map(|index| (curve.times[index], curve.values.get::<Vec3>(index))),
+                    )
+                    .unwrap();
+                    let error =
+                        max_curve_error(&saved, &original_translations, times.clone()).unwrap();
+                    assert!(curve.keyframe_count() < times.len());
+                    assert!(error <= settings.tolerance, "{error}");
+                }
+                SerializedAnimatedProperty::Rotation => {
+                    let saved = UnevenSampleAutoCurve::new(
+                        (0..curve.keyframe_count())
+                            .map(|index| (curve.times[index], curve.values.get::<Quat>(index))),
+                    )
+                    .unwrap();
+                    let error =
+                        max_curve_error(&saved, &original_rotations, times.clone()).unwrap();
+                    assert!(curve.keyframe_count() < times.len());
+                    assert!(error <= settings.rotation_tolerance, "{error}");
+                }
+                SerializedAnimatedProperty::Scale => assert_eq!(curve.keyframe_count(), 2),
+                SerializedAnimatedProperty::MorphWeights => unreachable!(),
+            }
+        }
+
+        let loaded = AnimationClip::try_from(serialized).unwrap();
+        assert_eq!(loaded.duration(), 2.0);
+        assert_eq!(loaded.curves_for_target(target).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn clips_with_unsupported_curves_cannot_be_saved() {
+        let target = AnimationTargetId::from_name(&Name::new("root"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                ConstantCurve::new(Interval::UNIT, Vec3::ONE),
+            ),
+        );
+        assert!(matches!(
+            SerializedAnimationClip::try_from(&clip),
+            Err(AnimationClipSaveError::UnsupportedCurve(id)) if id == target
+        ));
+    }
+}
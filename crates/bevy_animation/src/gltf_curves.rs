@@ -221,6 +221,22 @@ impl<T> WideLinearKeyframeCurve<T> {
             core: ChunkedUnevenCore::new_width_inferred(times, values)?,
         })
     }
+
+    /// The keyframe times of this curve, in increasing order.
+    pub fn times(&self) -> &[f32] {
+        &self.core.times
+    }
+
+    /// The keyframe values of this curve. Each keyframe has [`Self::width`]
+    /// consecutive values.
+    pub fn values(&self) -> &[T] {
+        &self.core.values
+    }
+
+    /// The number of values in each keyframe of this curve.
+    pub fn width(&self) -> usize {
+        self.core.width()
+    }
 }
 
 /// A keyframe-defined curve that uses stepped "interpolation" over many samples at once, backed
@@ -31,7 +31,11 @@ use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use thiserror::Error;
 
-use crate::{AnimationClip, AnimationTargetId};
+use crate::{
+    bone_mask::BoneMask,
+    state_machine::{AnimationStateMachine, BlendSpace1d, BlendSpace2d},
+    AnimationClip, AnimationTargetId,
+};
 
 /// A graph structure that describes how animation clips are to be blended
 /// together.
@@ -78,6 +82,17 @@ use crate::{AnimationClip, AnimationTargetId};
 /// their weights will be halved and finally blended with the Idle animation.
 /// Thus the weight of Run and Walk are effectively half of the weight of Idle.
 ///
+/// Besides these, *state machine nodes* and *blend space nodes* blend their
+/// children like blend nodes do, but with weights that are computed every
+/// frame from the [parameters] of the animation player. See the
+/// [`state_machine`](crate::state_machine) module for more information.
+///
+/// *Layer nodes* play their first child as a base and layer the rest of their
+/// children on top of it, either overriding or adding to the base. A layer
+/// node can have a [`BoneMask`] that restricts its layers to part of the
+/// skeleton, so that, for example, a reload animation can play on the torso
+/// while locomotion plays on the legs.
+///
 /// Nodes can optionally have a *mask*, a bitfield that restricts the set of
 /// animation targets that the node and its descendants affect. Each bit in the
 /// mask corresponds to a *mask group*, which is a set of animation targets
@@ -108,6 +123,8 @@ use crate::{AnimationClip, AnimationTargetId};
 ///
 /// [RON]: https://github.com/ron-rs/ron
 ///
+/// [parameters]: crate::state_machine::AnimationParameters
+///
 /// [RFC 51]: https://github.com/bevyengine/rfcs/blob/main/rfcs/51-animation-composition.md
 #[derive(Asset, Reflect, Clone, Debug)]
 #[reflect(Debug, Clone)]
@@ -235,6 +252,48 @@ pub enum AnimationNodeType {
     /// top of a running animation to produce an animation of a character
     /// attacking while running.
     Add,
+
+    /// A *state machine node*, which blends its children, the states of the
+    /// machine, according to which state is active.
+    ///
+    /// The machine switches between states according to its transitions,
+    /// cross-fading the states as it does so.
+    StateMachine(AnimationStateMachine),
+
+    /// A *1D blend space node*, which blends its children according to the
+    /// value of a float parameter.
+    BlendSpace1d(BlendSpace1d),
+
+    /// A *2D blend space node*, which blends its children according to the
+    /// values of two float parameters.
+    BlendSpace2d(BlendSpace2d),
+
+    /// An *override layer node*, which plays its first child (in order of
+    /// node index) as a base, and then interpolates towards each of its
+    /// other children in turn.
+    ///
+    /// Each layer overrides the result below it by its weight multiplied by
+    /// the weight of the animation target in the [`BoneMask`], if any. Thus a
+    /// layer with weight 1.0 completely replaces the base on the joints of
+    /// the mask, and leaves the other joints untouched. The weight of the base
+    /// is ignored.
+    ///
+    /// While the bone mask is loading, the layers don't affect any animation
+    /// targets.
+    OverrideLayer(Option<Handle<BoneMask>>),
+
+    /// An *additive layer node*, which plays its first child (in order of
+    /// node index) as a base, and then adds each of its other children on
+    /// top of it.
+    ///
+    /// Each layer is multiplied by its weight and by the weight of the
+    /// animation target in the [`BoneMask`], if any, before being added. As
+    /// with [`AnimationNodeType::Add`] nodes, the layers should be additive
+    /// animations.
+    ///
+    /// While the bone mask is loading, the layers don't affect any animation
+    /// targets.
+    AdditiveLayer(Option<Handle<BoneMask>>),
 }
 
 /// An [`AssetLoader`] that can load [`AnimationGraph`]s as assets.
@@ -371,6 +430,12 @@ pub struct ThreadedAnimationGraph {
     /// A 1 in bit position N indicates that this node doesn't animate any
     /// targets of mask group N.
     pub computed_masks: Vec<u64>,
+
+    /// A mapping from node index to the index of the parent of that node, if
+    /// it has one.
+    ///
+    /// If a node has several parents, this is one of them.
+    pub parents: Vec<Option<AnimationNodeIndex>>,
 }
 
 /// A version of [`AnimationGraph`] suitable for serializing as an asset.
@@ -415,6 +480,16 @@ pub enum SerializedAnimationNodeType {
     Blend,
     /// Corresponds to [`AnimationNodeType::Add`].
     Add,
+    /// Corresponds to [`AnimationNodeType::StateMachine`].
+    StateMachine(AnimationStateMachine),
+    /// Corresponds to [`AnimationNodeType::BlendSpace1d`].
+    BlendSpace1d(BlendSpace1d),
+    /// Corresponds to [`AnimationNodeType::BlendSpace2d`].
+    BlendSpace2d(BlendSpace2d),
+    /// Corresponds to [`AnimationNodeType::OverrideLayer`].
+    OverrideLayer(Option<AssetPath<'static>>),
+    /// Corresponds to [`AnimationNodeType::AdditiveLayer`].
+    AdditiveLayer(Option<AssetPath<'static>>),
 }
 
 /// The type of an animation mask bitfield.
@@ -611,6 +686,116 @@ impl AnimationGraph {
         node_index
     }
 
+    /// Adds a state machine node to the animation graph with the given weight
+    /// and returns its index.
+    ///
+    /// The state machine node will be placed under the supplied `parent` node.
+    /// Its states should be added as its children, and then registered with
+    /// the machine through [`AnimationGraph::state_machine_mut`]. The state
+    /// machine node will have no mask.
+    pub fn add_state_machine(
+        &mut self,
+        machine: AnimationStateMachine,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::StateMachine(machine),
+            mask: 0,
+            weight,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds a 1D blend space node to the animation graph with the given weight
+    /// and returns its index.
+    ///
+    /// The blend space node will be placed under the supplied `parent` node.
+    /// Its points should be added as its children, and then registered with
+    /// the blend space through [`AnimationGraph::blend_space_1d_mut`]. The
+    /// blend space node will have no mask.
+    pub fn add_blend_space_1d(
+        &mut self,
+        blend_space: BlendSpace1d,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::BlendSpace1d(blend_space),
+            mask: 0,
+            weight,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds a 2D blend space node to the animation graph with the given weight
+    /// and returns its index.
+    ///
+    /// The blend space node will be placed under the supplied `parent` node.
+    /// Its points should be added as its children, and then registered with
+    /// the blend space through [`AnimationGraph::blend_space_2d_mut`]. The
+    /// blend space node will have no mask.
+    pub fn add_blend_space_2d(
+        &mut self,
+        blend_space: BlendSpace2d,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::BlendSpace2d(blend_space),
+            mask: 0,
+            weight,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds an override layer node to the animation graph with the given
+    /// weight and returns its index.
+    ///
+    /// The layer node will be placed under the supplied `parent` node. Its
+    /// first child is the base, and its other children are the layers, which
+    /// are restricted to the given bone mask, if any. The layer node will have
+    /// no mask bits.
+    pub fn add_override_layer(
+        &mut self,
+        bone_mask: Option<Handle<BoneMask>>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::OverrideLayer(bone_mask),
+            mask: 0,
+            weight,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
+    /// Adds an additive layer node to the animation graph with the given
+    /// weight and returns its index.
+    ///
+    /// The layer node will be placed under the supplied `parent` node. Its
+    /// first child is the base, and its other children are the additive
+    /// layers, which are restricted to the given bone mask, if any. The layer
+    /// node will have no mask bits.
+    pub fn add_additive_layer(
+        &mut self,
+        bone_mask: Option<Handle<BoneMask>>,
+        weight: f32,
+        parent: AnimationNodeIndex,
+    ) -> AnimationNodeIndex {
+        let node_index = self.graph.add_node(AnimationGraphNode {
+            node_type: AnimationNodeType::AdditiveLayer(bone_mask),
+            mask: 0,
+            weight,
+        });
+        self.graph.add_edge(parent, node_index, ());
+        node_index
+    }
+
     /// Adds an edge from the edge `from` to `to`, making `to` a child of
     /// `from`.
     ///
@@ -646,6 +831,51 @@ impl AnimationGraph {
         self.graph.node_weight_mut(animation)
     }
 
+    /// Returns a mutable reference to the [`AnimationStateMachine`] of the
+    /// state machine node with the given index.
+    ///
+    /// If the node doesn't exist or isn't a state machine node, returns
+    /// `None`.
+    pub fn state_machine_mut(
+        &mut self,
+        animation: AnimationNodeIndex,
+    ) -> Option<&mut AnimationStateMachine> {
+        match self.get_mut(animation)?.node_type {
+            AnimationNodeType::StateMachine(ref mut machine) => Some(machine),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the [`BlendSpace1d`] of the 1D blend
+    /// space node with the given index.
+    ///
+    /// If the node doesn't exist or isn't a 1D blend space node, returns
+    /// `None`.
+    pub fn blend_space_1d_mut(
+        &mut self,
+        animation: AnimationNodeIndex,
+    ) -> Option<&mut BlendSpace1d> {
+        match self.get_mut(animation)?.node_type {
+            AnimationNodeType::BlendSpace1d(ref mut blend_space) => Some(blend_space),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the [`BlendSpace2d`] of the 2D blend
+    /// space node with the given index.
+    ///
+    /// If the node doesn't exist or isn't a 2D blend space node, returns
+    /// `None`.
+    pub fn blend_space_2d_mut(
+        &mut self,
+        animation: AnimationNodeIndex,
+    ) -> Option<&mut BlendSpace2d> {
+        match self.get_mut(animation)?.node_type {
+            AnimationNodeType::BlendSpace2d(ref mut blend_space) => Some(blend_space),
+            _ => None,
+        }
+    }
+
     /// Returns an iterator over the [`AnimationGraphNode`]s in this graph.
     pub fn nodes(&self) -> impl Iterator<Item = AnimationNodeIndex> {
         self.graph.node_indices()
@@ -782,6 +1012,25 @@ impl AssetLoader for AnimationGraphAssetLoader {
                     }
                     SerializedAnimationNodeType::Blend => AnimationNodeType::Blend,
                     SerializedAnimationNodeType::Add => AnimationNodeType::Add,
+                    SerializedAnimationNodeType::StateMachine(ref machine) => {
+                        AnimationNodeType::StateMachine(machine.clone())
+                    }
+                    SerializedAnimationNodeType::BlendSpace1d(ref blend_space) => {
+                        AnimationNodeType::BlendSpace1d(blend_space.clone())
+                    }
+                    SerializedAnimationNodeType::BlendSpace2d(ref blend_space) => {
+                        AnimationNodeType::BlendSpace2d(blend_space.clone())
+                    }
+                    SerializedAnimationNodeType::OverrideLayer(ref path) => {
+                        AnimationNodeType::OverrideLayer(
+                            path.as_ref().map(|path| load_context.load(path.clone())),
+                        )
+                    }
+                    SerializedAnimationNodeType::AdditiveLayer(ref path) => {
+                        AnimationNodeType::AdditiveLayer(
+                            path.as_ref().map(|path| load_context.load(path.clone())),
+                        )
+                    }
                 },
                 mask: serialized_node.mask,
                 weight: serialized_node.weight,
@@ -825,6 +1074,21 @@ impl TryFrom<AnimationGraph> for SerializedAnimationGraph {
                     },
                     AnimationNodeType::Blend => SerializedAnimationNodeType::Blend,
                     AnimationNodeType::Add => SerializedAnimationNodeType::Add,
+                    AnimationNodeType::StateMachine(ref machine) => {
+                        SerializedAnimationNodeType::StateMachine(machine.clone())
+                    }
+                    AnimationNodeType::BlendSpace1d(ref blend_space) => {
+                        SerializedAnimationNodeType::BlendSpace1d(blend_space.clone())
+                    }
+                    AnimationNodeType::BlendSpace2d(ref blend_space) => {
+                        SerializedAnimationNodeType::BlendSpace2d(blend_space.clone())
+                    }
+                    AnimationNodeType::OverrideLayer(ref mask) => {
+                        SerializedAnimationNodeType::OverrideLayer(bone_mask_path(mask.as_ref())?)
+                    }
+                    AnimationNodeType::AdditiveLayer(ref mask) => {
+                        SerializedAnimationNodeType::AdditiveLayer(bone_mask_path(mask.as_ref())?)
+                    }
                 },
             });
         }
@@ -839,9 +1103,17 @@ impl TryFrom<AnimationGraph> for SerializedAnimationGraph {
     }
 }
 
+/// Returns the asset path of an optional [`BoneMask`] handle.
+fn bone_mask_path(
+    mask: Option<&Handle<BoneMask>>,
+) -> Result<Option<AssetPath<'static>>, NonPathHandleError> {
+    mask.map(|mask| mask.path().cloned().ok_or(NonPathHandleError))
+        .transpose()
+}
+
 /// Error for when only path [`Handle`]s are supported.
 #[derive(Error, Debug)]
-#[error("AnimationGraph contains a handle to an AnimationClip that does not correspond to an asset path")]
+#[error("AnimationGraph contains a handle to an AnimationClip or BoneMask that does not correspond to an asset path")]
 pub struct NonPathHandleError;
 
 /// A system that creates, updates, and removes [`ThreadedAnimationGraph`]
@@ -914,6 +1186,9 @@ impl ThreadedAnimationGraph {
 
         self.computed_masks.clear();
         self.computed_masks.extend(iter::repeat_n(0, node_count));
+
+        self.parents.clear();
+        self.parents.extend(iter::repeat_n(None, node_count));
     }
 
     /// Recursively constructs the [`ThreadedAnimationGraph`] for the subtree
@@ -945,6 +1220,7 @@ impl ThreadedAnimationGraph {
 
         // Recurse. (This is a postorder traversal.)
         for kid in kids.into_iter().rev() {
+            self.parents[kid.index()] = Some(node_index);
             self.build_from(graph, kid, mask);
         }
 
@@ -0,0 +1,738 @@
+//! Inverse kinematics, which bends chains of joints so that their end reaches
+//! a target.
+//!
+//! Inverse kinematics (IK) is applied on top of the animated pose: the solvers
+//! run after [`animate_targets`](crate::animate_targets) has written the pose
+//! of the joints, and before transform propagation, so the solved pose is what
+//! gets rendered. Typical uses are placing feet on uneven ground and keeping a
+//! hand on a weapon.
+//!
+//! An IK solver is added to the *end effector*, the last joint of the chain
+//! (e.g. a foot or a hand), usually an entity animated through
+//! [`AnimatedBy`](crate::AnimatedBy). The chain is made of the end effector and
+//! its ancestors in the hierarchy. Two solvers are available:
+//!
+//! * [`TwoBoneIk`] analytically solves chains of exactly two bones, such as
+//!   legs and arms.
+//!
+//! * [`IkChain`] iteratively solves chains of any length, with either the
+//!   [FABRIK] or the [CCD] algorithm.
+//!
+//! Joints of the chain can be given an [`IkAngleLimit`] to restrict how far the
+//! solver may rotate them away from their animated pose.
+//!
+//! Joints don't have to be animated. Each solver remembers the rotations it
+//! last wrote in its [`IkPose`], and a joint whose rotation hasn't changed
+//! since is solved again from the rotation it had before IK was applied, so
+//! partially weighted chains and angle limits don't accumulate over frames.
+//!
+//! [FABRIK]: IkSolver::Fabrik
+//! [CCD]: IkSolver::Ccd
+
+use alloc::vec::Vec;
+
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    hierarchy::ChildOf,
+    reflect::ReflectComponent,
+    system::{Query, SystemParam},
+};
+use bevy_math::{Quat, Vec3};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_transform::components::Transform;
+
+/// Bends the two bones above the end effector this component is attached to,
+/// so that the end effector reaches the [target](Self::target).
+///
+/// The chain consists of the end effector, its parent (the middle joint, such
+/// as a knee or an elbow) and its grandparent (the root joint, such as a hip or
+/// a shoulder). If the target is out of reach, the chain is stretched towards
+/// it.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone)]
+#[require(IkPose)]
+pub struct TwoBoneIk {
+    /// The entity whose position the end effector should reach.
+    pub target: Entity,
+    /// An entity the middle joint should bend towards.
+    ///
+    /// If `None`, the chain keeps bending in the direction of the animated
+    /// pose.
+    pub pole: Option<Entity>,
+    /// How much the solved pose replaces the animated pose, from 0.0 (not at
+    /// all) to 1.0 (completely).
+    pub weight: f32,
+}
+
+impl TwoBoneIk {
+    /// Creates a two-bone IK solver with full weight and no pole target.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            pole: None,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the pole target the middle joint should bend towards.
+    pub fn with_pole(mut self, pole: Entity) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Sets how much the solved pose replaces the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// The algorithm an [`IkChain`] is solved with.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+#[reflect(Clone, Default, PartialEq)]
+pub enum IkSolver {
+    /// Forward And Backward Reaching Inverse Kinematics, which repeatedly
+    /// moves the joints along the chain towards the target and back towards
+    /// the root. This usually converges quickly and spreads the bending evenly
+    /// along the chain.
+    #[default]
+    Fabrik,
+    /// Cyclic Coordinate Descent, which repeatedly rotates each joint, from the
+    /// end effector up to the root, so that the end effector points at the
+    /// target. This tends to bend the joints closest to the end effector the
+    /// most.
+    Ccd,
+}
+
+/// Bends the chain of joints above the end effector this component is attached
+/// to, so that the end effector reaches the [target](Self::target).
+///
+/// The chain consists of the end effector and its
+/// [`bone_count`](Self::bone_count) closest ancestors.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone)]
+#[require(IkPose)]
+pub struct IkChain {
+    /// The entity whose position the end effector should reach.
+    pub target: Entity,
+    /// An entity the joints in the middle of the chain should bend towards.
+    ///
+    /// This is only used by [`IkSolver::Fabrik`].
+    pub pole: Option<Entity>,
+    /// The number of bones in the chain, which is the number of ancestors of
+    /// the end effector that the solver rotates.
+    pub bone_count: usize,
+    /// The algorithm the chain is solved with.
+    pub solver: IkSolver,
+    /// The maximum number of iterations of the solver.
+    pub iterations: u32,
+    /// The distance from the target at which the end effector is considered
+    /// to have reached it.
+    pub tolerance: f32,
+    /// How much the solved pose replaces the animated pose, from 0.0 (not at
+    /// all) to 1.0 (completely).
+    pub weight: f32,
+}
+
+impl IkChain {
+    /// Creates an IK chain with the given number of bones, solved with the
+    /// given solver, with full weight and no pole target.
+    pub fn new(target: Entity, bone_count: usize, solver: IkSolver) -> Self {
+        Self {
+            target,
+            pole: None,
+            bone_count,
+            solver,
+            iterations: 16,
+            tolerance: 1e-3,
+            weight: 1.0,
+        }
+    }
+
+    /// Sets the pole target the joints in the middle of the chain should bend
+    /// towards.
+    pub fn with_pole(mut self, pole: Entity) -> Self {
+        self.pole = Some(pole);
+        self
+    }
+
+    /// Sets how much the solved pose replaces the animated pose.
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the maximum number of iterations of the solver.
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+}
+
+/// Limits how far IK solvers may rotate a joint away from its animated pose.
+///
+/// Attach this to the joints of an IK chain. Joints without this component
+/// can be rotated freely.
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component, Clone, Default)]
+pub struct IkAngleLimit {
+    /// The maximum angle, in radians, between the animated rotation of the
+    /// joint and its solved rotation.
+    pub max_angle: f32,
+}
+
+impl Default for IkAngleLimit {
+    fn default() -> Self {
+        Self {
+            max_angle: core::f32::consts::PI,
+        }
+    }
+}
+
+/// The rotations of the joints of an IK chain before and after the chain was
+/// last solved.
+///
+/// This is added to the end effector along with [`TwoBoneIk`] and [`IkChain`].
+/// It lets the solver tell the animated pose of a joint apart from the pose it
+/// solved in the previous frame, for joints that no animation writes to.
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Clone, Default)]
+pub struct IkPose {
+    joints: Vec<IkJointPose>,
+}
+
+/// The rotation of a joint in an [`IkPose`].
+#[derive(Clone, Copy, Debug, Reflect)]
+#[reflect(Clone)]
+struct IkJointPose {
+    entity: Entity,
+    /// The local rotation of the joint before IK was applied.
+    animated: Quat,
+    /// The local rotation the solver wrote.
+    solved: Quat,
+}
+
+impl IkPose {
+    /// Returns the rotation the joint had before IK was applied, given its
+    /// current rotation.
+    ///
+    /// If the rotation is still the one the solver wrote, nothing has animated
+    /// the joint since, so the previous animated rotation is returned.
+    fn animated_rotation(&self, entity: Entity, rotation: Quat) -> Quat {
+        match self.joints.iter().find(|joint| joint.entity == entity) {
+            Some(joint) if joint.solved == rotation => joint.animated,
+            _ => rotation,
+        }
+    }
+}
+
+/// The joints that IK solvers read and write.
+#[derive(SystemParam)]
+pub struct IkJoints<'w, 's> {
+    joints: Query<
+        'w,
+        's,
+        (
+            &'static mut Transform,
+            Option<&'static ChildOf>,
+            Option<&'static IkAngleLimit>,
+        ),
+    >,
+}
+
+impl IkJoints<'_, '_> {
+    /// Computes the world-space transform of the given entity from the local
+    /// transforms of it and its ancestors.
+    ///
+    /// Unlike [`GlobalTransform`](bevy_transform::components::GlobalTransform),
+    /// this takes the animation that was applied this frame into account.
+    fn world_transform(&self, entity: Entity) -> Option<Transform> {
+        let (transform, child_of, _) = self.joints.get(entity).ok()?;
+        match child_of {
+            Some(child_of) => Some(
+                self.world_transform(child_of.parent())
+                    .unwrap_or_default()
+                    .mul_transform(*transform),
+            ),
+            None => Some(*transform),
+        }
+    }
+
+    /// Collects the joints of the chain ending at `end_effector`, from the
+    /// root of the chain to the end effector, in their animated pose.
+    fn chain(
+        &self,
+        end_effector: Entity,
+        bone_count: usize,
+        previous: &IkPose,
+    ) -> Option<IkChainPose> {
+        let mut entities = Vec::with_capacity(bone_count + 1);
+        entities.push(end_effector);
+        for _ in 0..bone_count {
+            let (_, child_of, _) = self.joints.get(*entities.last()?).ok()?;
+            entities.push(child_of?.parent());
+        }
+        entities.reverse();
+
+        let parent_world = match self.joints.get(entities[0]).ok()?.1 {
+            Some(child_of) => self.world_transform(child_of.parent())?,
+            None => Transform::IDENTITY,
+        };
+        let mut locals = Vec::with_capacity(entities.len());
+        let mut limits = Vec::with_capacity(entities.len());
+        for &entity in &entities {
+            let (transform, _, limit) = self.joints.get(entity).ok()?;
+            locals.push(Transform {
+                rotation: previous.animated_rotation(entity, transform.rotation),
+                ..*transform
+            });
+            limits.push(limit.map_or(core::f32::consts::PI, |limit| limit.max_angle));
+        }
+
+        let mut pose = IkChainPose {
+            entities,
+            parent_world,
+            original_rotations: locals.iter().map(|local| local.rotation).collect(),
+            locals,
+            limits,
+            worlds: Vec::new(),
+        };
+        pose.update_worlds(0);
+        Some(pose)
+    }
+
+    /// Writes the solved rotations of the chain, blended with the animated
+    /// pose by `weight`, and records them in `previous`.
+    fn write(&mut self, pose: &IkChainPose, weight: f32, previous: &mut IkPose) {
+        let weight = weight.clamp(0.0, 1.0);
+        previous.joints.clear();
+        for (index, &entity) in pose.entities.iter().enumerate() {
+            if let Ok((mut transform, _, _)) = self.joints.get_mut(entity) {
+                let animated = pose.original_rotations[index];
+                transform.rotation = animated
+                    .slerp(pose.locals[index].rotation, weight)
+                    .normalize();
+                previous.joints.push(IkJointPose {
+                    entity,
+                    animated,
+                    solved: transform.rotation,
+                });
+            }
+        }
+    }
+}
+
+/// The pose of the joints of an IK chain while it's being solved.
+struct IkChainPose {
+    /// The joints of the chain, from the root to the end effector.
+    entities: Vec<Entity>,
+    /// The world-space transform of the parent of the root of the chain.
+    parent_world: Transform,
+    /// The local transforms of the joints.
+    locals: Vec<Transform>,
+    /// The local rotations of the joints in the animated pose.
+    original_rotations: Vec<Quat>,
+    /// The maximum angle each joint may be rotated away from its animated
+    /// rotation.
+    limits: Vec<f32>,
+    /// The world-space transforms of the joints.
+    worlds: Vec<Transform>,
+}
+
+impl IkChainPose {
+    /// Recomputes the world-space transforms of the joints from `start` to the
+    /// end effector.
+    fn update_worlds(&mut self, start: usize) {
+        self.worlds.truncate(start);
+        for index in start..self.locals.len() {
+            let parent = match index {
+                0 => self.parent_world,
+                _ => self.worlds[index - 1],
+            };
+            self.worlds.push(parent.mul_transform(self.locals[index]));
+        }
+    }
+
+    fn position(&self, index: usize) -> Vec3 {
+        self.worlds[index].translation
+    }
+
+    fn end_effector(&self) -> Vec3 {
+        self.position(self.worlds.len() - 1)
+    }
+
+    /// Applies the world-space rotation `delta` to the joint at `index`,
+    /// respecting its angle limit, and updates the joints below it.
+    fn rotate_joint(&mut self, index: usize, delta: Quat) {
+        let parent_rotation = match index {
+            0 => self.parent_world.rotation,
+            _ => self.worlds[index - 1].rotation,
+        };
+        let world_rotation = delta * self.worlds[index].rotation;
+        let mut local_rotation = (parent_rotation.inverse() * world_rotation).normalize();
+
+        let original_rotation = self.original_rotations[index];
+        let angle = original_rotation.angle_between(local_rotation);
+        let limit = self.limits[index];
+        if angle > limit {
+            local_rotation = original_rotation.slerp(local_rotation, limit / angle);
+        }
+
+        self.locals[index].rotation = local_rotation;
+        self.update_worlds(index);
+    }
+
+    /// Rotates the joints, from the root to the end effector, so that each
+    /// bone points at the given world-space position of the next joint.
+    fn apply_positions(&mut self, positions: &[Vec3]) {
+        for index in 0..self.locals.len() - 1 {
+            let joint = self.position(index);
+            let (Some(from), Some(to)) = (
+                (self.position(index + 1) - joint).try_normalize(),
+                (positions[index + 1] - joint).try_normalize(),
+            ) else {
+                continue;
+            };
+            self.rotate_joint(index, Quat::from_rotation_arc(from, to));
+        }
+    }
+}
+
+/// Returns a unit vector perpendicular to `direction` in the plane containing
+/// `hint`, or any perpendicular unit vector if `hint` is parallel to
+/// `direction`.
+fn perpendicular_towards(direction: Vec3, hint: Vec3) -> Vec3 {
+    (hint - direction * hint.dot(direction))
+        .try_normalize()
+        .unwrap_or_else(|| direction.any_orthonormal_vector())
+}
+
+/// Computes the world-space positions of the joints of a two-bone chain that
+/// reach `target` as closely as possible.
+///
+/// `bend` is the direction the middle joint should bend towards.
+fn solve_two_bone_positions(positions: [Vec3; 3], target: Vec3, bend: Vec3) -> [Vec3; 3] {
+    let [root, middle, end] = positions;
+    let upper_length = root.distance(middle);
+    let lower_length = middle.distance(end);
+
+    let Some(direction) = (target - root).try_normalize() else {
+        return positions;
+    };
+    let min_distance = (upper_length - lower_length).abs();
+    let max_distance = upper_length + lower_length;
+    let distance = root.distance(target).clamp(min_distance, max_distance);
+
+    // Law of cosines, for the angle at the root of the chain.
+    let cos_angle = if upper_length * distance > 0.0 {
+        ((upper_length * upper_length + distance * distance - lower_length * lower_length)
+            / (2.0 * upper_length * distance))
+            .clamp(-1.0, 1.0)
+    } else {
+        1.0
+    };
+    let sin_angle = (1.0 - cos_angle * cos_angle).sqrt();
+    let bend = perpendicular_towards(direction, bend);
+
+    [
+        root,
+        root + upper_length * (cos_angle * direction + sin_angle * bend),
+        root + distance * direction,
+    ]
+}
+
+/// Computes the world-space positions of the joints of a chain that reach
+/// `target` as closely as possible with the FABRIK algorithm.
+fn solve_fabrik_positions(
+    positions: &mut [Vec3],
+    target: Vec3,
+    pole: Option<Vec3>,
+    iterations: u32,
+    tolerance: f32,
+) {
+    let lengths: Vec<f32> = positions
+        .windows(2)
+        .map(|bone| bone[0].distance(bone[1]))
+        .collect();
+    let root = positions[0];
+    let last = positions.len() - 1;
+
+    if root.distance(target) >= lengths.iter().sum::<f32>() {
+        // The target is out of reach, so stretch the chain towards it.
+        let direction = (target - root).normalize_or_zero();
+        for (index, length) in lengths.iter().enumerate() {
+            positions[index + 1] = positions[index] + direction * *length;
+        }
+        return;
+    }
+
+    // Bend the middle of the chain towards the pole, so the solver converges
+    // to a pose that bends in that direction.
+    if let Some(pole) = pole
+        && let Some(axis) = (positions[last] - root).try_normalize()
+    {
+        let towards_pole = perpendicular_towards(axis, pole - root);
+        for position in &mut positions[1..last] {
+            let joint = *position - root;
+            let along = axis * joint.dot(axis);
+            let offset = joint - along;
+            *position = root + along + towards_pole * offset.length();
+        }
+    }
+
+    for _ in 0..iterations {
+        if positions[last].distance(target) <= tolerance {
+            break;
+        }
+
+        // Backward pass: move the end effector to the target.
+        positions[last] = target;
+        for index in (0..last).rev() {
+            let direction = (positions[index] - positions[index + 1]).normalize_or_zero();
+            positions[index] = positions[index + 1] + direction * lengths[index];
+        }
+
+        // Forward pass: move the root back to where it was.
+        positions[0] = root;
+        for index in 0..last {
+            let direction = (positions[index + 1] - positions[index]).normalize_or_zero();
+            positions[index + 1] = positions[index] + direction * lengths[index];
+        }
+    }
+}
+
+/// A system that solves all [`TwoBoneIk`] chains.
+pub fn solve_two_bone_ik(
+    mut solvers: Query<(Entity, &TwoBoneIk, &mut IkPose)>,
+    mut joints: IkJoints,
+) {
+    for (end_effector, solver, mut previous) in &mut solvers {
+        let Some(mut pose) = joints.chain(end_effector, 2, &previous) else {
+            continue;
+        };
+        let Some(target) = joints.world_transform(solver.target) else {
+            continue;
+        };
+
+        let positions = [pose.position(0), pose.position(1), pose.position(2)];
+        let bend = match solver.pole.and_then(|pole| joints.world_transform(pole)) {
+            Some(pole) => pole.translation - positions[0],
+            None => positions[1] - positions[0],
+        };
+        let solved = solve_two_bone_positions(positions, target.translation, bend);
+
+        pose.apply_positions(&solved);
+        joints.write(&pose, solver.weight, &mut previous);
+    }
+}
+
+/// A system that solves all [`IkChain`]s.
+pub fn solve_ik_chains(mut solvers: Query<(Entity, &IkChain, &mut IkPose)>, mut joints: IkJoints) {
+    for (end_effector, solver, mut previous) in &mut solvers {
+        if solver.bone_count == 0 {
+            continue;
+        }
+        let Some(mut pose) = joints.chain(end_effector, solver.bone_count, &previous) else {
+            continue;
+        };
+        let Some(target) = joints.world_transform(solver.target) else {
+            continue;
+        };
+        let target = target.translation;
+
+        match solver.solver {
+            IkSolver::Fabrik => {
+                let pole = solver
+                    .pole
+                    .and_then(|pole| joints.world_transform(pole))
+                    .map(|pole| pole.translation);
+                let mut positions: Vec<Vec3> =
+                    pose.worlds.iter().map(|world| world.translation).collect();
+                solve_fabrik_positions(
+                    &mut positions,
+                    target,
+                    pole,
+                    solver.iterations,
+                    solver.tolerance,
+                );
+                pose.apply_positions(&positions);
+            }
+            IkSolver::Ccd => {
+                let last = pose.locals.len() - 1;
+                for _ in 0..solver.iterations {
+                    if pose.end_effector().distance(target) <= solver.tolerance {
+                        break;
+                    }
+                    for index in (0..last).rev() {
+                        let joint = pose.position(index);
+                        let (Some(from), Some(to)) = (
+                            (pose.end_effector() - joint).try_normalize(),
+                            (target - joint).try_normalize(),
+                        ) else {
+                            continue;
+                        };
+                        pose.rotate_joint(index, Quat::from_rotation_arc(from, to));
+                    }
+                }
+            }
+        }
+
+        joints.write(&pose, solver.weight, &mut previous);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_math::Vec3;
+
+    use super::*;
+
+    /// Spawns a straight chain of joints along the Y axis, one unit apart, and
+    /// returns them from the root to the end effector.
+    fn spawn_chain(world: &mut World, joint_count: usize) -> Vec<Entity> {
+        let mut joints = Vec::new();
+        let mut parent: Option<Entity> = None;
+        for index in 0..joint_count {
+            let translation = if index == 0 { Vec3::ZERO } else { Vec3::Y };
+            let mut joint = world.spawn(Transform::from_translation(translation));
+            if let Some(parent) = parent {
+                joint.insert(ChildOf(parent));
+            }
+            parent = Some(joint.id());
+            joints.push(joint.id());
+        }
+        joints
+    }
+
+    fn world_position(world: &mut World, entity: Entity) -> Vec3 {
+        world
+            .run_system_once(move |joints: IkJoints| {
+                joints.world_transform(entity).unwrap().translation
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn two_bone_ik_reaches_target_and_bends_towards_pole() {
+        let mut world = World::new();
+        let joints = spawn_chain(&mut world, 3);
+        let target = world.spawn(Transform::from_xyz(1.0, 1.0, 0.0)).id();
+        let pole = world.spawn(Transform::from_xyz(0.0, 0.0, 5.0)).id();
+        world
+            .entity_mut(joints[2])
+            .insert(TwoBoneIk::new(target).with_pole(pole));
+
+        world.run_system_once(solve_two_bone_ik).unwrap();
+
+        let end = world_position(&mut world, joints[2]);
+        assert!(end.distance(Vec3::new(1.0, 1.0, 0.0)) < 1e-4, "{end}");
+        let middle = world_position(&mut world, joints[1]);
+        assert!(middle.z > 0.5, "{middle}");
+        assert!((middle.length() - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn two_bone_ik_weight_blends_with_animated_pose() {
+        let mut world = World::new();
+        let joints = spawn_chain(&mut world, 3);
+        let target = world.spawn(Transform::from_xyz(0.0, -2.0, 0.0)).id();
+        world
+            .entity_mut(joints[2])
+            .insert(TwoBoneIk::new(target).with_weight(0.0));
+
+        world.run_system_once(solve_two_bone_ik).unwrap();
+
+        let end = world_position(&mut world, joints[2]);
+        assert!(end.distance(Vec3::new(0.0, 2.0, 0.0)) < 1e-4, "{end}");
+    }
+
+    #[test]
+    fn ik_chains_reach_target() {
+        for solver in [IkSolver::Fabrik, IkSolver::Ccd] {
+            let mut world = World::new();
+            let joints = spawn_chain(&mut world, 4);
+            let target = world.spawn(Transform::from_xyz(1.5, 1.5, 0.0)).id();
+            world
+                .entity_mut(joints[3])
+                .insert(IkChain::new(target, 3, solver).with_iterations(64));
+
+            world.run_system_once(solve_ik_chains).unwrap();
+
+            let end = world_position(&mut world, joints[3]);
+            assert!(
+                end.distance(Vec3::new(1.5, 1.5, 0.0)) < 1e-2,
+                "{solver:?}: {end}"
+            );
+        }
+    }
+
+    #[test]
+    fn ik_chain_respects_angle_limits() {
+        let mut world = World::new();
+        let joints = spawn_chain(&mut world, 3);
+        let target = world.spawn(Transform::from_xyz(2.0, 0.0, 0.0)).id();
+        world
+            .entity_mut(joints[0])
+            .insert(IkAngleLimit { max_angle: 0.5 });
+        world
+            .entity_mut(joints[2])
+            .insert(IkChain::new(target, 2, IkSolver::Ccd));
+
+        world.run_system_once(solve_ik_chains).unwrap();
+
+        let rotation = world.get::<Transform>(joints[0]).unwrap().rotation;
+        assert!(rotation.angle_between(Quat::IDENTITY) <= 0.5 + 1e-4);
+        // The unlimited joint still rotates to get as close as it can.
+        let middle_rotation = world.get::<Transform>(joints[1]).unwrap().rotation;
+        assert!(middle_rotation.angle_between(Quat::IDENTITY) > 0.5);
+    }
+
+    #[test]
+    fn partially_weighted_ik_does_not_accumulate_over_frames() {
+        let mut world = World::new();
+        let joints = spawn_chain(&mut world, 3);
+        let target = world.spawn(Transform::from_xyz(1.0, 1.0, 0.0)).id();
+        world
+            .entity_mut(joints[2])
+            .insert(TwoBoneIk::new(target).with_weight(0.5));
+
+        world.run_system_once(solve_two_bone_ik).unwrap();
+        let first = world_position(&mut world, joints[2]);
+        for _ in 0..8 {
+            world.run_system_once(solve_two_bone_ik).unwrap();
+        }
+
+        let end = world_position(&mut world, joints[2]);
+        assert!(end.distance(first) < 1e-4, "{first} {end}");
+        assert!(end.distance(Vec3::new(1.0, 1.0, 0.0)) > 0.1, "{end}");
+
+        // Once the joints are animated again, that pose is used instead.
+        world.get_mut::<Transform>(joints[0]).unwrap().rotation = Quat::IDENTITY;
+        world.get_mut::<Transform>(joints[1]).unwrap().rotation = Quat::IDENTITY;
+        world.run_system_once(solve_two_bone_ik).unwrap();
+        let end = world_position(&mut world, joints[2]);
+        assert!(end.distance(first) < 1e-4, "{first} {end}");
+    }
+
+    #[test]
+    fn ik_angle_limits_do_not_drift_over_frames() {
+        let mut world = World::new();
+        let joints = spawn_chain(&mut world, 3);
+        let target = world.spawn(Transform::from_xyz(2.0, 0.0, 0.0)).id();
+        world
+            .entity_mut(joints[0])
+            .insert(IkAngleLimit { max_angle: 0.5 });
+        world
+            .entity_mut(joints[2])
+            .insert(IkChain::new(target, 2, IkSolver::Ccd));
+
+        for _ in 0..8 {
+            world.run_system_once(solve_ik_chains).unwrap();
+        }
+
+        let rotation = world.get::<Transform>(joints[0]).unwrap().rotation;
+        assert!(rotation.angle_between(Quat::IDENTITY) <= 0.5 + 1e-4);
+    }
+}
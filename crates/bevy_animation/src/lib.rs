@@ -11,10 +11,15 @@ extern crate alloc;
 
 pub mod animatable;
 pub mod animation_curves;
+pub mod bone_mask;
+pub mod compression;
 pub mod gltf_curves;
 pub mod graph;
+pub mod ik;
 #[cfg(feature = "bevy_mesh")]
 mod morph;
+pub mod root_motion;
+pub mod state_machine;
 pub mod transition;
 
 mod animation_event;
@@ -33,18 +38,18 @@ use graph::AnimationNodeType;
 use prelude::AnimationCurveEvaluator;
 
 use crate::{
-    graph::{AnimationGraphHandle, ThreadedAnimationGraphs},
+    graph::{AnimationGraphHandle, ThreadedAnimationGraph, ThreadedAnimationGraphs},
     prelude::EvaluatorId,
 };
 
 use bevy_app::{AnimationSystems, App, Plugin, PostUpdate};
-use bevy_asset::{Asset, AssetApp, AssetEventSystems, Assets};
+use bevy_asset::{Asset, AssetApp, AssetEventSystems, AssetId, Assets};
 use bevy_ecs::{prelude::*, resource::IsResource, world::EntityMutExcept};
-use bevy_math::FloatOrd;
+use bevy_math::{FloatOrd, Quat, Vec3};
 use bevy_platform::{collections::HashMap, hash::NoOpHash};
 use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
 use bevy_time::Time;
-use bevy_transform::TransformSystems;
+use bevy_transform::{components::Transform, TransformSystems};
 use bevy_utils::{PreHashMap, PreHashMapExt, TypeIdMap};
 use serde::{Deserialize, Serialize};
 use thread_local::ThreadLocal;
@@ -57,14 +62,25 @@ use uuid::Uuid;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        animatable::*, animation_curves::*, graph::*, transition::*, AnimationClip,
-        AnimationPlayer, AnimationPlugin, VariableCurve,
+        animatable::*, animation_curves::*, bone_mask::*, compression::*, graph::*, ik::*,
+        root_motion::*, state_machine::*, transition::*, AnimationClip, AnimationPlayer,
+        AnimationPlugin, VariableCurve,
     };
 }
 
 use crate::{
-    animation_curves::AnimationCurve,
-    graph::{AnimationGraph, AnimationGraphAssetLoader, AnimationNodeIndex},
+    animation_curves::{AnimatableCurveEvaluator, AnimationCurve},
+    bone_mask::{BoneMask, BoneMaskAssetLoader},
+    compression::{
+        AnimationClipLoader, AnimationClipSaver, AnimationCompressionReport,
+        AnimationCompressionSettings, AnimationCompressor,
+    },
+    graph::{AnimationGraph, AnimationGraphAssetLoader, AnimationMask, AnimationNodeIndex},
+    ik::{solve_ik_chains, solve_two_bone_ik},
+    root_motion::{RootField, RootMotion, RootMotionPose},
+    state_machine::{
+        advance_animation_graph_nodes, AnimationParameters, AnimationStateMachineState,
+    },
     transition::{advance_transitions, expire_completed_transitions},
 };
 use alloc::sync::Arc;
@@ -317,6 +333,28 @@ impl AnimationClip {
             .push(variable_curve);
     }
 
+    /// Compresses the curves of this clip by removing redundant keyframes and
+    /// quantizing rotations, within the tolerances of the given `settings`.
+    ///
+    /// Curves that can't be compressed, such as curves with cubic or stepped
+    /// interpolation, are left unchanged. Returns a report of how much the
+    /// curves were compressed.
+    ///
+    /// See the [`compression`] module for details.
+    pub fn compress(
+        &mut self,
+        settings: &AnimationCompressionSettings,
+    ) -> AnimationCompressionReport {
+        let mut report = AnimationCompressionReport::default();
+        for curve in self.curves.values_mut().flatten() {
+            if let Some((compressed, curve_report)) = curve.0.compress(settings) {
+                curve.0 = compressed;
+                report += curve_report;
+            }
+        }
+        report
+    }
+
     /// Add an [`EntityEvent`] with no [`AnimationTargetId`].
     ///
     /// The `event` will be cloned and triggered on the [`AnimationPlayer`] entity once the `time` (in seconds)
@@ -678,6 +716,16 @@ impl ActiveAnimation {
 #[reflect(Component, Default, Clone)]
 pub struct AnimationPlayer {
     active_animations: HashMap<AnimationNodeIndex, ActiveAnimation>,
+    parameters: AnimationParameters,
+    /// The weights that state machine and blend space nodes assign to their
+    /// children, by child.
+    node_weights: HashMap<AnimationNodeIndex, f32>,
+    state_machines: HashMap<AnimationNodeIndex, AnimationStateMachineState>,
+    root_motion: Option<RootMotion>,
+    /// The weights of the named joints of the bone masks used by the graph,
+    /// resolved against the skeleton below this player.
+    #[reflect(ignore)]
+    resolved_bone_masks: HashMap<AssetId<BoneMask>, HashMap<AnimationTargetId, f32>>,
 }
 
 // This is needed since `#[derive(Clone)]` does not generate optimized `clone_from`.
@@ -685,11 +733,22 @@ impl Clone for AnimationPlayer {
     fn clone(&self) -> Self {
         Self {
             active_animations: self.active_animations.clone(),
+            parameters: self.parameters.clone(),
+            node_weights: self.node_weights.clone(),
+            state_machines: self.state_machines.clone(),
+            root_motion: self.root_motion,
+            resolved_bone_masks: self.resolved_bone_masks.clone(),
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.active_animations.clone_from(&source.active_animations);
+        self.parameters.clone_from(&source.parameters);
+        self.node_weights.clone_from(&source.node_weights);
+        self.state_machines.clone_from(&source.state_machines);
+        self.root_motion = source.root_motion;
+        self.resolved_bone_masks
+            .clone_from(&source.resolved_bone_masks);
     }
 }
 
@@ -716,6 +775,12 @@ pub struct AnimationEvaluationState {
     /// This is built up as new curve evaluators are encountered during graph
     /// traversal.
     current_evaluators: CurrentEvaluators,
+
+    /// For each override layer node, the fraction of the weight that remains
+    /// for the children of that node that haven't been evaluated yet.
+    ///
+    /// This is reset for every animation target.
+    override_layer_remaining_weights: HashMap<AnimationNodeIndex, f32>,
 }
 
 #[derive(Default)]
@@ -930,6 +995,46 @@ impl AnimationPlayer {
     pub fn animation_mut(&mut self, animation: AnimationNodeIndex) -> Option<&mut ActiveAnimation> {
         self.active_animations.get_mut(&animation)
     }
+
+    /// Returns the parameters that drive the state machines and blend spaces
+    /// of the animation graph.
+    pub fn parameters(&self) -> &AnimationParameters {
+        &self.parameters
+    }
+
+    /// Returns the parameters that drive the state machines and blend spaces
+    /// of the animation graph, mutably.
+    pub fn parameters_mut(&mut self) -> &mut AnimationParameters {
+        &mut self.parameters
+    }
+
+    /// Returns the weight that the state machine or blend space node above the
+    /// given node currently assigns to it.
+    ///
+    /// This is 1.0 for nodes that aren't children of such a node.
+    pub fn node_weight(&self, node: AnimationNodeIndex) -> f32 {
+        self.node_weights.get(&node).copied().unwrap_or(1.0)
+    }
+
+    /// Returns the runtime state of the state machine node with the given
+    /// index, if it has started.
+    pub fn state_machine(&self, node: AnimationNodeIndex) -> Option<&AnimationStateMachineState> {
+        self.state_machines.get(&node)
+    }
+
+    /// Enables or disables root motion extraction for this player.
+    ///
+    /// See [`RootMotion`] for details.
+    pub fn set_root_motion(&mut self, root_motion: Option<RootMotion>) -> &mut Self {
+        self.root_motion = root_motion;
+        self
+    }
+
+    /// Returns the root motion configuration of this player, if root motion
+    /// is enabled.
+    pub fn root_motion(&self) -> Option<&RootMotion> {
+        self.root_motion.as_ref()
+    }
 }
 
 /// A system that triggers untargeted animation events for the currently-playing animations.
@@ -946,7 +1051,7 @@ fn trigger_untargeted_animation_events(
         };
 
         for (index, active_animation) in player.active_animations.iter() {
-            if active_animation.paused {
+            if active_animation.paused || state_machine::is_node_silenced(player, graph, *index) {
                 continue;
             }
 
@@ -954,7 +1059,7 @@ fn trigger_untargeted_animation_events(
                 .get(*index)
                 .and_then(|node| match &node.node_type {
                     AnimationNodeType::Clip(handle) => Some(handle),
-                    AnimationNodeType::Blend | AnimationNodeType::Add => None,
+                    _ => None,
                 })
                 .and_then(|id| clips.get(id))
             else {
@@ -1030,6 +1135,7 @@ pub fn animate_targets(
     par_commands: ParallelCommands,
     clips: Res<Assets<AnimationClip>>,
     graphs: Res<Assets<AnimationGraph>>,
+    bone_masks: Res<Assets<BoneMask>>,
     threaded_animation_graphs: Res<ThreadedAnimationGraphs>,
     players: Query<(&AnimationPlayer, &AnimationGraphHandle)>,
     mut targets: Query<
@@ -1076,148 +1182,305 @@ pub fn animate_targets(
 
             let mut evaluation_state = animation_evaluation_state.get_or_default().borrow_mut();
             let evaluation_state = &mut *evaluation_state;
+            let mut entity_mut = entity_mut;
+
+            let evaluation = GraphEvaluation {
+                animation_player,
+                animation_graph,
+                threaded_animation_graph,
+                clips: &clips,
+                bone_masks: &bone_masks,
+                target_id,
+                target_mask,
+            };
 
-            // Evaluate the graph.
-            for &animation_graph_node_index in threaded_animation_graph.threaded_graph.iter() {
-                let Some(animation_graph_node) = animation_graph.get(animation_graph_node_index)
-                else {
-                    continue;
+            // If this target is the root of a player in root motion mode,
+            // sample the root at the times needed to measure how far it moved
+            // this frame, before evaluating the actual pose.
+            let root_motion = animation_player
+                .root_motion
+                .filter(|root_motion| root_motion.target == target_id);
+            let root_motion_samples = root_motion.map(|_| {
+                let base = entity_mut.get::<Transform>().copied().unwrap_or_default();
+                let mut sample = |sample_time: &dyn Fn(&ActiveAnimation, &AnimationClip) -> f32| {
+                    evaluation.sample_root(evaluation_state, base, sample_time)
                 };
-
-                match animation_graph_node.node_type {
-                    AnimationNodeType::Blend => {
-                        // This is a blend node.
-                        for edge_index in threaded_animation_graph.sorted_edge_ranges
-                            [animation_graph_node_index.index()]
-                        .clone()
-                        {
-                            if let Err(err) = evaluation_state.blend_all(
-                                threaded_animation_graph.sorted_edges[edge_index as usize],
-                            ) {
-                                warn!("Failed to blend animation: {:?}", err);
+                let start = sample(&root_motion::start_sample_time);
+                let wrap = animation_player
+                    .active_animations
+                    .values()
+                    .any(root_motion::wrapped)
+                    .then(|| {
+                        (
+                            sample(&root_motion::wrap_end_sample_time),
+                            sample(&root_motion::wrap_start_sample_time),
+                        )
+                    });
+                let previous = entity_mut.get::<RootMotionPose>().map(|pose| pose.pose);
+                (previous, start, wrap)
+            });
+
+            evaluation.evaluate(
+                evaluation_state,
+                &|active_animation, _| active_animation.seek_time,
+                &|_| true,
+                &mut |active_animation, clip| {
+                    if active_animation.paused {
+                        return;
+                    }
+                    // Trigger all animation events that occurred this tick, if any.
+                    if let Some(triggered_events) = TriggeredEvents::from_animation(
+                        AnimationEventTarget::Node(target_id),
+                        clip,
+                        active_animation,
+                    ) && !triggered_events.is_empty()
+                    {
+                        par_commands.command_scope(move |mut commands| {
+                            for TimedAnimationEvent { time, event } in triggered_events.iter() {
+                                event.trigger(
+                                    &mut commands,
+                                    entity,
+                                    *time,
+                                    active_animation.weight,
+                                );
                             }
-                        }
+                        });
+                    }
+                },
+            );
 
-                        if let Err(err) = evaluation_state.push_blend_register_all(
-                            animation_graph_node.weight,
-                            animation_graph_node_index,
+            if let Err(err) = evaluation_state.commit_all(entity_mut.reborrow()) {
+                warn!("Animation application failed: {:?}", err);
+            }
+
+            if let (Some(root_motion), Some((previous, start, wrap))) =
+                (root_motion, root_motion_samples)
+                && let Some(mut transform) = entity_mut.get_mut::<Transform>()
+            {
+                let current = *transform;
+                let delta = root_motion.delta(&previous.unwrap_or(current), &current, wrap);
+                root_motion.strip(&mut transform, &start);
+                let pose = RootMotionPose {
+                    pose: current,
+                    delta,
+                };
+                match entity_mut.get_mut::<RootMotionPose>() {
+                    Some(mut root_motion_pose) => *root_motion_pose = pose,
+                    None => par_commands.command_scope(move |mut commands| {
+                        commands.entity(entity).insert(pose);
+                    }),
+                }
+            }
+        });
+}
+
+/// Everything needed to evaluate an animation graph for a single animation
+/// target.
+struct GraphEvaluation<'a> {
+    animation_player: &'a AnimationPlayer,
+    animation_graph: &'a AnimationGraph,
+    threaded_animation_graph: &'a ThreadedAnimationGraph,
+    clips: &'a Assets<AnimationClip>,
+    bone_masks: &'a Assets<BoneMask>,
+    target_id: AnimationTargetId,
+    /// The mask groups the animation target belongs to.
+    target_mask: AnimationMask,
+}
+
+impl GraphEvaluation<'_> {
+    /// Evaluates the graph into the curve evaluators of `evaluation_state`,
+    /// sampling each clip at the time returned by `sample_time`.
+    ///
+    /// Only curves whose evaluator id passes `curve_filter` are sampled.
+    /// `on_clip` is called for every clip that contributes to the pose of the
+    /// target.
+    fn evaluate(
+        &self,
+        evaluation_state: &mut AnimationEvaluationState,
+        sample_time: &dyn Fn(&ActiveAnimation, &AnimationClip) -> f32,
+        curve_filter: &dyn Fn(&EvaluatorId) -> bool,
+        on_clip: &mut dyn FnMut(&ActiveAnimation, &AnimationClip),
+    ) {
+        evaluation_state.override_layer_remaining_weights.clear();
+
+        for &animation_graph_node_index in self.threaded_animation_graph.threaded_graph.iter() {
+            let Some(animation_graph_node) = self.animation_graph.get(animation_graph_node_index)
+            else {
+                continue;
+            };
+
+            // The weight that a state machine or blend space assigns to
+            // this node, if it's the child of one.
+            let node_weight = self
+                .animation_player
+                .node_weight(animation_graph_node_index);
+
+            match animation_graph_node.node_type {
+                AnimationNodeType::Blend
+                | AnimationNodeType::StateMachine(_)
+                | AnimationNodeType::BlendSpace1d(_)
+                | AnimationNodeType::BlendSpace2d(_)
+                | AnimationNodeType::OverrideLayer(_) => {
+                    // This is a blend node.
+                    for edge_index in self.threaded_animation_graph.sorted_edge_ranges
+                        [animation_graph_node_index.index()]
+                    .clone()
+                    {
+                        if let Err(err) = evaluation_state.blend_all(
+                            self.threaded_animation_graph.sorted_edges[edge_index as usize],
                         ) {
-                            warn!("Animation blending failed: {:?}", err);
+                            warn!("Failed to blend animation: {:?}", err);
                         }
                     }
 
-                    AnimationNodeType::Add => {
-                        // This is an additive blend node.
-                        for edge_index in threaded_animation_graph.sorted_edge_ranges
-                            [animation_graph_node_index.index()]
-                        .clone()
-                        {
-                            if let Err(err) = evaluation_state
-                                .add_all(threaded_animation_graph.sorted_edges[edge_index as usize])
-                            {
-                                warn!("Failed to blend animation: {:?}", err);
-                            }
-                        }
+                    let weight = self.layer_weight(
+                        evaluation_state,
+                        animation_graph_node_index,
+                        animation_graph_node.weight * node_weight,
+                    );
+                    if let Err(err) =
+                        evaluation_state.push_blend_register_all(weight, animation_graph_node_index)
+                    {
+                        warn!("Animation blending failed: {:?}", err);
+                    }
+                }
 
-                        if let Err(err) = evaluation_state.push_blend_register_all(
-                            animation_graph_node.weight,
-                            animation_graph_node_index,
+                AnimationNodeType::Add | AnimationNodeType::AdditiveLayer(_) => {
+                    // This is an additive blend node.
+                    for edge_index in self.threaded_animation_graph.sorted_edge_ranges
+                        [animation_graph_node_index.index()]
+                    .clone()
+                    {
+                        if let Err(err) = evaluation_state.add_all(
+                            self.threaded_animation_graph.sorted_edges[edge_index as usize],
                         ) {
-                            warn!("Animation blending failed: {:?}", err);
+                            warn!("Failed to blend animation: {:?}", err);
                         }
                     }
 
-                    AnimationNodeType::Clip(ref animation_clip_handle) => {
-                        // This is a clip node.
-                        let Some(active_animation) = animation_player
-                            .active_animations
-                            .get(&animation_graph_node_index)
-                        else {
-                            continue;
-                        };
-
-                        // If the weight is zero or the current animation target is
-                        // masked out, stop here.
-                        if active_animation.weight == 0.0
-                            || (target_mask
-                                & threaded_animation_graph.computed_masks
-                                    [animation_graph_node_index.index()])
-                                != 0
-                        {
-                            continue;
-                        }
+                    let weight = self.layer_weight(
+                        evaluation_state,
+                        animation_graph_node_index,
+                        animation_graph_node.weight * node_weight,
+                    );
+                    if let Err(err) =
+                        evaluation_state.push_blend_register_all(weight, animation_graph_node_index)
+                    {
+                        warn!("Animation blending failed: {:?}", err);
+                    }
+                }
 
-                        let Some(clip) = clips.get(animation_clip_handle) else {
-                            continue;
-                        };
-
-                        if !active_animation.paused {
-                            // Trigger all animation events that occurred this tick, if any.
-                            if let Some(triggered_events) = TriggeredEvents::from_animation(
-                                AnimationEventTarget::Node(target_id),
-                                clip,
-                                active_animation,
-                            ) && !triggered_events.is_empty()
-                            {
-                                par_commands.command_scope(move |mut commands| {
-                                    for TimedAnimationEvent { time, event } in
-                                        triggered_events.iter()
-                                    {
-                                        event.trigger(
-                                            &mut commands,
-                                            entity,
-                                            *time,
-                                            active_animation.weight,
-                                        );
-                                    }
-                                });
-                            }
-                        }
+                AnimationNodeType::Clip(ref animation_clip_handle) => {
+                    // This is a clip node.
+                    let Some(active_animation) = self
+                        .animation_player
+                        .active_animations
+                        .get(&animation_graph_node_index)
+                    else {
+                        continue;
+                    };
+
+                    // If the weight is zero or the current animation target is
+                    // masked out, stop here.
+                    if active_animation.weight == 0.0
+                        || node_weight == 0.0
+                        || (self.target_mask
+                            & self.threaded_animation_graph.computed_masks
+                                [animation_graph_node_index.index()])
+                            != 0
+                    {
+                        continue;
+                    }
+
+                    let Some(clip) = self.clips.get(animation_clip_handle) else {
+                        continue;
+                    };
 
-                        let Some(curves) = clip.curves_for_target(target_id) else {
+                    on_clip(active_animation, clip);
+
+                    let Some(curves) = clip.curves_for_target(self.target_id) else {
+                        continue;
+                    };
+
+                    let weight = self.layer_weight(
+                        evaluation_state,
+                        animation_graph_node_index,
+                        active_animation.weight * animation_graph_node.weight * node_weight,
+                    );
+                    let seek_time = sample_time(active_animation, clip);
+
+                    for curve in curves {
+                        // Fetch the curve evaluator. Curve evaluator types
+                        // are unique to each property, but shared among all
+                        // curve types. For example, given two curve types A
+                        // and B, `RotationCurve<A>` and `RotationCurve<B>`
+                        // will both yield a `RotationCurveEvaluator` and
+                        // therefore will share the same evaluator in this
+                        // table.
+                        let curve_evaluator_id = (*curve.0).evaluator_id();
+                        if !curve_filter(&curve_evaluator_id) {
                             continue;
-                        };
-
-                        let weight = active_animation.weight * animation_graph_node.weight;
-                        let seek_time = active_animation.seek_time;
-
-                        for curve in curves {
-                            // Fetch the curve evaluator. Curve evaluator types
-                            // are unique to each property, but shared among all
-                            // curve types. For example, given two curve types A
-                            // and B, `RotationCurve<A>` and `RotationCurve<B>`
-                            // will both yield a `RotationCurveEvaluator` and
-                            // therefore will share the same evaluator in this
-                            // table.
-                            let curve_evaluator_id = (*curve.0).evaluator_id();
-                            let curve_evaluator = evaluation_state
-                                .evaluators
-                                .get_or_insert_with(curve_evaluator_id.clone(), || {
-                                    curve.0.create_evaluator()
-                                });
-
-                            evaluation_state
-                                .current_evaluators
-                                .insert(curve_evaluator_id);
-
-                            if let Err(err) = AnimationCurve::apply(
-                                &*curve.0,
-                                curve_evaluator,
-                                seek_time,
-                                weight,
-                                animation_graph_node_index,
-                            ) {
-                                warn!("Animation application failed: {:?}", err);
-                            }
+                        }
+                        let curve_evaluator = evaluation_state
+                            .evaluators
+                            .get_or_insert_with(curve_evaluator_id.clone(), || {
+                                curve.0.create_evaluator()
+                            });
+
+                        evaluation_state
+                            .current_evaluators
+                            .insert(curve_evaluator_id);
+
+                        if let Err(err) = AnimationCurve::apply(
+                            &*curve.0,
+                            curve_evaluator,
+                            seek_time,
+                            weight,
+                            animation_graph_node_index,
+                        ) {
+                            warn!("Animation application failed: {:?}", err);
                         }
                     }
                 }
             }
+        }
+    }
 
-            if let Err(err) = evaluation_state.commit_all(entity_mut) {
-                warn!("Animation application failed: {:?}", err);
-            }
-        });
+    /// Samples the translation and rotation of the target with each clip
+    /// sampled at the time returned by `sample_time`, without writing them to
+    /// the target. Fields that aren't animated are taken from `base`.
+    fn sample_root(
+        &self,
+        evaluation_state: &mut AnimationEvaluationState,
+        base: Transform,
+        sample_time: &dyn Fn(&ActiveAnimation, &AnimationClip) -> f32,
+    ) -> Transform {
+        self.evaluate(
+            evaluation_state,
+            sample_time,
+            &|evaluator_id| RootField::of(evaluator_id).is_some(),
+            &mut |_, _| {},
+        );
+        evaluation_state.take_root(base)
+    }
+
+    /// Calls [`AnimationEvaluationState::layer_weight`] for this target.
+    fn layer_weight(
+        &self,
+        evaluation_state: &mut AnimationEvaluationState,
+        node_index: AnimationNodeIndex,
+        weight: f32,
+    ) -> f32 {
+        evaluation_state.layer_weight(
+            self.animation_graph,
+            self.threaded_animation_graph,
+            self.bone_masks,
+            &self.animation_player.resolved_bone_masks,
+            self.target_id,
+            node_index,
+            weight,
+        )
+    }
 }
 
 /// Adds animation support to an app
@@ -1229,15 +1492,21 @@ impl Plugin for AnimationPlugin {
         app.init_asset::<AnimationClip>()
             .init_asset::<AnimationGraph>()
             .init_asset_loader::<AnimationGraphAssetLoader>()
+            .init_asset::<BoneMask>()
+            .init_asset_loader::<BoneMaskAssetLoader>()
+            .init_asset_loader::<AnimationClipLoader>()
             .register_asset_reflect::<AnimationClip>()
             .register_asset_reflect::<AnimationGraph>()
+            .register_asset_reflect::<BoneMask>()
             .init_resource::<ThreadedAnimationGraphs>()
             .add_systems(
                 PostUpdate,
                 (
                     graph::thread_animation_graphs.before(AssetEventSystems),
                     advance_transitions,
+                    advance_animation_graph_nodes,
                     advance_animations,
+                    bone_mask::resolve_bone_masks,
                     // TODO: `animate_targets` can animate anything, so
                     // ambiguity testing currently considers it ambiguous with
                     // every other system in `PostUpdate`. We may want to move
@@ -1245,13 +1514,35 @@ impl Plugin for AnimationPlugin {
                     // `PostUpdate`. For now, we just disable ambiguity testing
                     // for this system.
                     animate_targets.ambiguous_with_all(),
+                    root_motion::update_root_motion_deltas,
                     trigger_untargeted_animation_events,
                     expire_completed_transitions,
                 )
                     .chain()
                     .in_set(AnimationSystems)
                     .before(TransformSystems::Propagate),
+            )
+            .add_systems(
+                PostUpdate,
+                (solve_two_bone_ik, solve_ik_chains)
+                    .chain()
+                    .after(AnimationSystems)
+                    .before(TransformSystems::Propagate),
             );
+
+        if let Some(processor) = app
+            .world()
+            .get_resource::<bevy_asset::processor::AssetProcessor>()
+        {
+            processor.register_processor::<bevy_asset::processor::LoadTransformAndSave<
+                AnimationClipLoader,
+                AnimationCompressor,
+                AnimationClipSaver,
+            >>(bevy_asset::processor::LoadTransformAndSave::new(
+                AnimationCompressor::new(),
+                AnimationClipSaver,
+            ));
+        }
     }
 }
 
@@ -1296,6 +1587,67 @@ impl From<&Name> for AnimationTargetId {
 }
 
 impl AnimationEvaluationState {
+    /// Returns the weight with which the node with the given index should be
+    /// pushed onto the evaluation stack, given its own `weight`.
+    ///
+    /// This is `weight` unless the parent of the node is a layer node. For
+    /// such nodes, the layers are scaled by the weight of the animation target
+    /// in the bone mask. In addition, the children of override layer nodes
+    /// are weighted so that each layer interpolates from the result of the
+    /// children below it, rather than being averaged with them.
+    fn layer_weight(
+        &mut self,
+        animation_graph: &AnimationGraph,
+        threaded_animation_graph: &ThreadedAnimationGraph,
+        bone_masks: &Assets<BoneMask>,
+        resolved_bone_masks: &HashMap<AssetId<BoneMask>, HashMap<AnimationTargetId, f32>>,
+        target_id: AnimationTargetId,
+        node_index: AnimationNodeIndex,
+        weight: f32,
+    ) -> f32 {
+        let Some(parent_index) = threaded_animation_graph.parents[node_index.index()] else {
+            return weight;
+        };
+        let (bone_mask, is_override) = match animation_graph[parent_index].node_type {
+            AnimationNodeType::OverrideLayer(ref bone_mask) => (bone_mask, true),
+            AnimationNodeType::AdditiveLayer(ref bone_mask) => (bone_mask, false),
+            _ => return weight,
+        };
+
+        // The first child is the base, which isn't masked.
+        let is_base = threaded_animation_graph
+            .sorted_edges
+            .get(threaded_animation_graph.sorted_edge_ranges[parent_index.index()].start as usize)
+            == Some(&node_index);
+        let mask_weight = match bone_mask {
+            _ if is_base => 1.0,
+            None => 1.0,
+            Some(bone_mask) => bone_masks.get(bone_mask).map_or(0.0, |mask| {
+                mask.weight_with_joints(target_id, resolved_bone_masks.get(&bone_mask.id()))
+            }),
+        };
+        if !is_override {
+            return weight * mask_weight;
+        }
+
+        // Children of override layer nodes are evaluated from the last layer
+        // down to the base. Each child takes its share of the weight that the
+        // layers above it left over, so that the blend of all children is
+        // equivalent to interpolating towards each layer in turn.
+        let alpha = if is_base {
+            1.0
+        } else {
+            (weight * mask_weight).clamp(0.0, 1.0)
+        };
+        let remaining_weight = self
+            .override_layer_remaining_weights
+            .entry(parent_index)
+            .or_insert(1.0);
+        let weight = alpha * *remaining_weight;
+        *remaining_weight *= 1.0 - alpha;
+        weight
+    }
+
     /// Calls [`AnimationCurveEvaluator::blend`] on all curve evaluator types
     /// that we've been building up for a single target.
     ///
@@ -1363,6 +1715,36 @@ impl AnimationEvaluationState {
                 .commit(entity_mut.reborrow())
         })
     }
+
+    /// Takes the root translation and rotation evaluated by
+    /// [`GraphEvaluation::sample_root`] out of the current evaluators,
+    /// without committing them.
+    fn take_root(&mut self, mut transform: Transform) -> Transform {
+        let _ = self.current_evaluators.clear(|id| {
+            let evaluator = self.evaluators.get_mut(id.clone()).unwrap();
+            match RootField::of(&id) {
+                Some(RootField::Translation) => {
+                    if let Some(translation) = evaluator
+                        .downcast_mut::<AnimatableCurveEvaluator<Vec3>>()
+                        .and_then(AnimatableCurveEvaluator::take)
+                    {
+                        transform.translation = translation;
+                    }
+                }
+                Some(RootField::Rotation) => {
+                    if let Some(rotation) = evaluator
+                        .downcast_mut::<AnimatableCurveEvaluator<Quat>>()
+                        .and_then(AnimatableCurveEvaluator::take)
+                    {
+                        transform.rotation = rotation;
+                    }
+                }
+                None => {}
+            }
+            Ok(())
+        });
+        transform
+    }
 }
 
 /// All the events from an [`AnimationClip`] that occurred this tick.
@@ -1702,4 +2084,231 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn override_layer_uses_bone_mask() {
+        let mut bone_mask = BoneMask::new();
+        bone_mask.set_weight(AnimationTargetId::from_name(&Name::new("masked")), 1.0);
+        assert_override_layer_masks_target(bone_mask);
+    }
+
+    #[test]
+    fn override_layer_uses_named_bone_mask_joints() {
+        let mut bone_mask = BoneMask::new();
+        bone_mask.add_named_joint("masked", 1.0);
+        assert_override_layer_masks_target(bone_mask);
+    }
+
+    /// Plays two clips on an override layer using `bone_mask` and checks that
+    /// only the target named "masked" is blended with the layer.
+    fn assert_override_layer_masks_target(bone_mask: BoneMask) {
+        use crate::animation_curves::{AnimatableCurve, AnimatedField};
+        use bevy_app::TaskPoolPlugin;
+        use bevy_asset::AssetPlugin;
+        use bevy_math::{
+            curve::{ConstantCurve, Interval},
+            Vec3,
+        };
+        use bevy_time::TimePlugin;
+        use bevy_transform::components::Transform;
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            TimePlugin,
+            AssetPlugin::default(),
+            AnimationPlugin,
+        ));
+
+        let masked = AnimationTargetId::from_name(&Name::new("masked"));
+        let unmasked = AnimationTargetId::from_name(&Name::new("unmasked"));
+        let mut constant_clip = |translation| {
+            let mut clip = AnimationClip::default();
+            for target in [masked, unmasked] {
+                clip.add_curve_to_target(
+                    target,
+                    AnimatableCurve::new(
+                        animated_field!(Transform::translation),
+                        ConstantCurve::new(Interval::UNIT, translation),
+                    ),
+                );
+            }
+            app.world_mut()
+                .resource_mut::<Assets<AnimationClip>>()
+                .add(clip)
+        };
+        let base_clip = constant_clip(Vec3::X);
+        let layer_clip = constant_clip(Vec3::Y);
+
+        let bone_mask = app
+            .world_mut()
+            .resource_mut::<Assets<BoneMask>>()
+            .add(bone_mask);
+
+        let mut graph = AnimationGraph::new();
+        let layer = graph.add_override_layer(Some(bone_mask), 1.0, graph.root);
+        let base = graph.add_clip(base_clip, 1.0, layer);
+        let overlay = graph.add_clip(layer_clip, 0.5, layer);
+        let graph = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationGraph>>()
+            .add(graph);
+
+        let mut player = AnimationPlayer::default();
+        player.play(base).repeat();
+        player.play(overlay).repeat();
+        let player = app
+            .world_mut()
+            .spawn((player, AnimationGraphHandle(graph)))
+            .id();
+        let [masked, unmasked] =
+            [(masked, "masked"), (unmasked, "unmasked")].map(|(target, name)| {
+                app.world_mut()
+                    .spawn((
+                        Name::new(name),
+                        Transform::default(),
+                        target,
+                        AnimatedBy(player),
+                        ChildOf(player),
+                    ))
+                    .id()
+            });
+
+        app.update();
+        app.update();
+
+        let translation = |entity| app.world().get::<Transform>(entity).unwrap().translation;
+        assert_eq!(translation(masked), Vec3::new(0.5, 0.5, 0.0));
+        assert_eq!(translation(unmasked), Vec3::X);
+    }
+
+    /// Sets up a player that extracts the root motion of a clip that walks 2
+    /// units forward and 1 unit up in one second, with updates of 150ms, and
+    /// returns the player and the root target.
+    fn root_motion_app() -> (App, Entity, Entity) {
+        use crate::{
+            animation_curves::{AnimatableCurve, AnimatedField},
+            root_motion::RootMotion,
+        };
+        use bevy_app::TaskPoolPlugin;
+        use bevy_asset::AssetPlugin;
+        use bevy_math::{
+            curve::{EaseFunction, EasingCurve},
+            Vec3,
+        };
+        use bevy_time::{TimePlugin, TimeUpdateStrategy};
+        use bevy_transform::components::Transform;
+        use core::time::Duration;
+
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            TimePlugin,
+            AssetPlugin::default(),
+            AnimationPlugin,
+        ))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            150,
+        )));
+
+        // Walks 2 units forward and 1 unit up in one second from a rest
+        // offset of half a unit to the side, then loops.
+        let root = AnimationTargetId::from_name(&Name::new("root"));
+        let mut clip = AnimationClip::default();
+        clip.add_curve_to_target(
+            root,
+            AnimatableCurve::new(
+                animated_field!(Transform::translation),
+                EasingCurve::new(
+                    Vec3::new(0.5, 0.0, 0.0),
+                    Vec3::new(0.5, 1.0, 2.0),
+                    EaseFunction::Linear,
+                ),
+            ),
+        );
+        let clip = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationClip>>()
+            .add(clip);
+        let (graph, node) = AnimationGraph::from_clip(clip);
+        let graph = app
+            .world_mut()
+            .resource_mut::<Assets<AnimationGraph>>()
+            .add(graph);
+
+        let mut player = AnimationPlayer::default();
+        player.play(node).repeat();
+        player.set_root_motion(Some(RootMotion::new(root)));
+        let player = app
+            .world_mut()
+            .spawn((player, AnimationGraphHandle(graph)))
+            .id();
+        let root = app
+            .world_mut()
+            .spawn((Transform::default(), root, AnimatedBy(player)))
+            .id();
+        (app, player, root)
+    }
+
+    #[test]
+    fn root_motion_is_extracted_across_loops() {
+        use crate::root_motion::RootMotionDelta;
+        use bevy_math::Vec3;
+        use bevy_transform::components::Transform;
+
+        let (mut app, player, root) = root_motion_app();
+
+        // The first update starts the clock.
+        app.update();
+        app.update();
+
+        // The clip wraps around during the seventh of these updates.
+        for _ in 0..8 {
+            app.update();
+            let delta = *app.world().get::<RootMotionDelta>(player).unwrap();
+            assert!(delta
+                .translation
+                .abs_diff_eq(Vec3::new(0.0, 0.0, 0.3), 1e-4));
+            let translation = app.world().get::<Transform>(root).unwrap().translation;
+            assert_eq!(translation.x, 0.5);
+            assert_eq!(translation.z, 0.0);
+        }
+    }
+
+    #[test]
+    fn root_motion_is_updated_in_place_and_removed_when_disabled() {
+        use crate::root_motion::{RootMotionDelta, RootMotionPose};
+        use bevy_ecs::{lifecycle::Insert, observer::On};
+
+        #[derive(Resource, Default)]
+        struct Inserts(usize);
+
+        let (mut app, player, root) = root_motion_app();
+        app.init_resource::<Inserts>()
+            .add_observer(
+                |_: On<Insert, RootMotionDelta>, mut inserts: ResMut<Inserts>| {
+                    inserts.0 += 1;
+                },
+            )
+            .add_observer(
+                |_: On<Insert, RootMotionPose>, mut inserts: ResMut<Inserts>| {
+                    inserts.0 += 1;
+                },
+            );
+
+        for _ in 0..5 {
+            app.update();
+        }
+        assert_eq!(app.world().resource::<Inserts>().0, 2);
+        assert!(app.world().get::<RootMotionDelta>(player).is_some());
+        assert!(app.world().get::<RootMotionPose>(root).is_some());
+
+        app.world_mut()
+            .get_mut::<AnimationPlayer>(player)
+            .unwrap()
+            .set_root_motion(None);
+        app.update();
+        assert!(app.world().get::<RootMotionDelta>(player).is_none());
+        assert!(app.world().get::<RootMotionPose>(root).is_none());
+    }
 }
@@ -1,13 +1,21 @@
 use crate::{
     animatable::Animatable,
     animation_curves::{AnimationCurve, AnimationCurveEvaluator, EvaluatorId},
+    compression::{
+        compress_weights, AnimationCompressionReport, AnimationCompressionSettings,
+        SerializedAnimationCurve,
+    },
+    gltf_curves::WideLinearKeyframeCurve,
     graph::AnimationNodeIndex,
     AnimationEntityMut, AnimationEvaluationError,
 };
 use bevy_math::curve::{iterable::IterableCurve, Interval};
 use bevy_mesh::morph::MorphWeights;
 use bevy_reflect::{FromReflect, Reflect, Reflectable};
-use core::{any::TypeId, fmt::Debug};
+use core::{
+    any::{Any, TypeId},
+    fmt::Debug,
+};
 
 /// This type allows an [`IterableCurve`] valued in `f32` to be used as an [`AnimationCurve`]
 /// that animates [morph weights].
@@ -104,6 +112,20 @@ where
             .push((weight, graph_node));
         Ok(())
     }
+
+    fn compress(
+        &self,
+        settings: &AnimationCompressionSettings,
+    ) -> Option<(Box<dyn AnimationCurve>, AnimationCompressionReport)> {
+        let curve = (&self.0 as &dyn Any).downcast_ref::<WideLinearKeyframeCurve<f32>>()?;
+        let (curve, report) = compress_weights(curve, settings)?;
+        Some((Box::new(WeightsCurve(curve)), report))
+    }
+
+    fn serialize_keyframes(&self) -> Option<SerializedAnimationCurve> {
+        let curve = (&self.0 as &dyn Any).downcast_ref::<WideLinearKeyframeCurve<f32>>()?;
+        Some(SerializedAnimationCurve::from_weights(curve))
+    }
 }
 
 impl WeightsCurveEvaluator {
@@ -0,0 +1,305 @@
+//! Root motion, which turns the movement of a character's root joint into a
+//! per-frame delta that gameplay code applies.
+
+use core::f32::consts::{PI, TAU};
+
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::Component,
+    entity::{hash_set::EntityHashSet, Entity},
+    reflect::ReflectComponent,
+    system::{Commands, Local, Query},
+    world::Ref,
+};
+use bevy_math::{ops, Quat, Vec3};
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_transform::components::Transform;
+
+use crate::{
+    animation_curves::EvaluatorId, ActiveAnimation, AnimatedBy, AnimationClip, AnimationPlayer,
+    AnimationTargetId,
+};
+
+/// Configures an [`AnimationPlayer`] to extract root motion from one of its
+/// animation targets.
+///
+/// When root motion is enabled, the horizontal translation relative to the
+/// start of the animation and the rotation about the Y axis (yaw) are
+/// stripped from the [`Transform`] of the root target every frame, so the
+/// root stays in place. Instead, the movement of
+/// the root during the frame is stored in a [`RootMotionDelta`] component on
+/// the entity with the [`AnimationPlayer`], which gameplay code can apply to
+/// a character controller or physics body.
+///
+/// The delta takes the whole animation graph into account, so it's weighted
+/// by blend nodes, transitions and state machines just like the pose. Clips
+/// that loop during a frame contribute the motion up to their end plus the
+/// motion from their start, rather than jumping back to the start.
+///
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+#[derive(Clone, Copy, Debug, Reflect)]
+#[reflect(Clone, Debug)]
+pub struct RootMotion {
+    /// The animation target whose motion is extracted, usually the root joint
+    /// of the skeleton.
+    pub target: AnimationTargetId,
+    /// Whether vertical translation is extracted as well.
+    ///
+    /// This is off by default, so jumps and crouches in the animation stay
+    /// in the pose.
+    pub extract_vertical: bool,
+}
+
+/// The movement of the root target of an [`AnimationPlayer`] during the last
+/// frame, in the space of the character at the start of the frame.
+///
+/// This component is inserted on entities whose [`AnimationPlayer`] has
+/// [`RootMotion`] enabled, and updated every frame. It's zero in frames where
+/// the root target wasn't animated, and it's removed when root motion is
+/// disabled. To move a character with it, apply it relative to the
+/// character's own transform:
+///
+/// ```
+/// # use bevy_animation::root_motion::RootMotionDelta;
+/// # use bevy_transform::components::Transform;
+/// fn apply_root_motion(transform: &mut Transform, delta: &RootMotionDelta) {
+///     transform.translation += transform.rotation * delta.translation;
+///     transform.rotation *= delta.rotation;
+/// }
+/// ```
+///
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct RootMotionDelta {
+    /// The translation of the root during the frame.
+    pub translation: Vec3,
+    /// The rotation of the root about the Y axis during the frame.
+    pub rotation: Quat,
+}
+
+impl Default for RootMotionDelta {
+    fn default() -> Self {
+        Self {
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+        }
+    }
+}
+
+impl RootMotion {
+    /// Creates a configuration that extracts the horizontal motion of the
+    /// given animation target.
+    pub fn new(target: AnimationTargetId) -> Self {
+        Self {
+            target,
+            extract_vertical: false,
+        }
+    }
+
+    /// Sets whether vertical translation is extracted as well.
+    pub fn with_extract_vertical(mut self, extract_vertical: bool) -> Self {
+        self.extract_vertical = extract_vertical;
+        self
+    }
+
+    /// Returns the components of a translation that are extracted.
+    fn extracted(&self, translation: Vec3) -> Vec3 {
+        if self.extract_vertical {
+            translation
+        } else {
+            Vec3::new(translation.x, 0.0, translation.z)
+        }
+    }
+
+    /// Computes the delta between the pose at the start of the frame,
+    /// `previous`, and the pose at the end of the frame, `current`.
+    ///
+    /// If a clip looped during the frame, `wrap` contains the poses at the
+    /// end of its loop and at the start of the next one.
+    pub(crate) fn delta(
+        &self,
+        previous: &Transform,
+        current: &Transform,
+        wrap: Option<(Transform, Transform)>,
+    ) -> RootMotionDelta {
+        let previous_yaw = yaw(previous.rotation);
+        let current_yaw = yaw(current.rotation);
+
+        let (translation, angle) = match wrap {
+            None => (
+                Quat::from_rotation_y(-previous_yaw)
+                    * self.extracted(current.translation - previous.translation),
+                wrap_angle(current_yaw - previous_yaw),
+            ),
+            Some((end, start)) => {
+                // Move to the end of the loop, then continue from the start
+                // of the loop in the frame the root has turned to.
+                let end_yaw = yaw(end.rotation);
+                let start_yaw = yaw(start.rotation);
+                let first_angle = wrap_angle(end_yaw - previous_yaw);
+                let first = Quat::from_rotation_y(-previous_yaw)
+                    * self.extracted(end.translation - previous.translation);
+                let second = Quat::from_rotation_y(first_angle - start_yaw)
+                    * self.extracted(current.translation - start.translation);
+                (
+                    first + second,
+                    first_angle + wrap_angle(current_yaw - start_yaw),
+                )
+            }
+        };
+
+        RootMotionDelta {
+            translation,
+            rotation: Quat::from_rotation_y(angle),
+        }
+    }
+
+    /// Removes the yaw and the translation extracted since the `start` pose
+    /// from the transform of the root target, so the root keeps the offset it
+    /// has at the start of the animation.
+    pub(crate) fn strip(&self, transform: &mut Transform, start: &Transform) {
+        transform.rotation = Quat::from_rotation_y(-yaw(transform.rotation)) * transform.rotation;
+        transform.translation -= self.extracted(transform.translation - start.translation);
+    }
+}
+
+/// The pose of the root target at the end of the last frame, before root
+/// motion was stripped from it, and the motion measured in that frame.
+///
+/// The next frame measures the motion of the root from this pose, so the
+/// motion of each frame is measured with the weights of the frame it happened
+/// in.
+#[derive(Component, Clone, Copy, Debug)]
+pub(crate) struct RootMotionPose {
+    pub(crate) pose: Transform,
+    pub(crate) delta: RootMotionDelta,
+}
+
+/// A system that copies the root motion measured on each root target this
+/// frame to the [`RootMotionDelta`] of its [`AnimationPlayer`].
+///
+/// It also removes the root motion state of players and targets that no
+/// longer extract root motion, so stale deltas aren't applied.
+///
+/// [`AnimationPlayer`]: crate::AnimationPlayer
+pub(crate) fn update_root_motion_deltas(
+    mut commands: Commands,
+    mut players: Query<(Entity, &AnimationPlayer, Option<&mut RootMotionDelta>)>,
+    roots: Query<(Entity, &AnimationTargetId, &AnimatedBy, Ref<RootMotionPose>)>,
+    mut updated: Local<EntityHashSet>,
+) {
+    updated.clear();
+    for (root, &target_id, &AnimatedBy(player_id), pose) in &roots {
+        let Ok((_, player, delta)) = players.get_mut(player_id) else {
+            commands.entity(root).remove::<RootMotionPose>();
+            continue;
+        };
+        if player
+            .root_motion()
+            .is_none_or(|root_motion| root_motion.target != target_id)
+        {
+            commands.entity(root).remove::<RootMotionPose>();
+            continue;
+        }
+        // The pose is only updated in frames where the root was animated.
+        let new_delta = if pose.is_changed() {
+            pose.delta
+        } else {
+            RootMotionDelta::default()
+        };
+        match delta {
+            Some(mut delta) => {
+                delta.set_if_neq(new_delta);
+            }
+            None => {
+                commands.entity(player_id).insert(new_delta);
+            }
+        }
+        updated.insert(player_id);
+    }
+
+    for (player_id, player, delta) in &mut players {
+        let Some(mut delta) = delta else {
+            continue;
+        };
+        if player.root_motion().is_none() {
+            commands.entity(player_id).remove::<RootMotionDelta>();
+        } else if !updated.contains(&player_id) {
+            delta.set_if_neq(RootMotionDelta::default());
+        }
+    }
+}
+
+/// The fields of the root [`Transform`] that are sampled to measure root
+/// motion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RootField {
+    Translation,
+    Rotation,
+}
+
+impl RootField {
+    /// Returns the root field that curves with the given evaluator animate, if
+    /// any.
+    pub(crate) fn of(evaluator_id: &EvaluatorId) -> Option<Self> {
+        match evaluator_id.transform_field()? {
+            "translation" => Some(Self::Translation),
+            "rotation" => Some(Self::Rotation),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the rotation of `rotation` about the Y axis, in radians.
+fn yaw(rotation: Quat) -> f32 {
+    let forward = rotation * Vec3::Z;
+    ops::atan2(forward.x, forward.z)
+}
+
+/// Wraps an angle into the range [-π, π].
+fn wrap_angle(angle: f32) -> f32 {
+    (angle + PI).rem_euclid(TAU) - PI
+}
+
+/// Returns true if the animation looped back to its start during the last
+/// update.
+pub(crate) fn wrapped(active_animation: &ActiveAnimation) -> bool {
+    !active_animation.paused && active_animation.just_completed && !active_animation.is_finished()
+}
+
+/// Returns the time at the start of a clip, which is its end for clips that
+/// play backwards.
+pub(crate) fn start_sample_time(active_animation: &ActiveAnimation, clip: &AnimationClip) -> f32 {
+    if active_animation.speed < 0.0 {
+        clip.duration()
+    } else {
+        0.0
+    }
+}
+
+/// Returns the time at the end of the loop for clips that looped in the last
+/// update, and the current time for all other clips.
+pub(crate) fn wrap_end_sample_time(
+    active_animation: &ActiveAnimation,
+    clip: &AnimationClip,
+) -> f32 {
+    match (wrapped(active_animation), active_animation.speed < 0.0) {
+        (false, _) => active_animation.seek_time,
+        (true, false) => clip.duration(),
+        (true, true) => 0.0,
+    }
+}
+
+/// Returns the time at the start of the loop for clips that looped in the
+/// last update, and the current time for all other clips.
+pub(crate) fn wrap_start_sample_time(
+    active_animation: &ActiveAnimation,
+    clip: &AnimationClip,
+) -> f32 {
+    match (wrapped(active_animation), active_animation.speed < 0.0) {
+        (false, _) => active_animation.seek_time,
+        (true, false) => 0.0,
+        (true, true) => clip.duration(),
+    }
+}
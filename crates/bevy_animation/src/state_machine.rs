@@ -0,0 +1,937 @@
+//! Animation state machines, blend spaces and the parameters that drive them.
+//!
+//! A [`AnimationNodeType::StateMachine`] node blends between its children,
+//! which are the *states* of the machine, according to which state is
+//! currently active. The machine switches states when the conditions of one of
+//! its [`AnimationStateTransition`]s, expressed in terms of the
+//! [`AnimationParameters`] of the [`AnimationPlayer`], are met, cross-fading
+//! between the two states over the blend duration of the transition.
+//!
+//! [`AnimationNodeType::BlendSpace1d`] and [`AnimationNodeType::BlendSpace2d`]
+//! nodes blend between their children according to where one or two float
+//! parameters lie relative to the positions of the children. See
+//! [`BlendSpace1d`] and [`BlendSpace2d`] for more details.
+//!
+//! The weights that these nodes assign to their children are recomputed every
+//! frame by [`advance_animation_graph_nodes`]. This system also starts the
+//! animation clips below a state when it's entered and stops them once the
+//! state has faded out, and starts the clips below blend spaces, so the clips
+//! they control don't need to be played manually.
+
+use alloc::{string::String, vec, vec::Vec};
+
+use bevy_ecs::system::{Query, Res};
+use bevy_math::Vec2;
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{prelude::ReflectDefault, Reflect};
+use bevy_time::Time;
+use petgraph::Direction;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    graph::{AnimationGraph, AnimationGraphHandle, AnimationNodeIndex, AnimationNodeType},
+    AnimationPlayer,
+};
+use bevy_asset::Assets;
+
+/// The value of a single animation parameter.
+///
+/// See [`AnimationParameters`].
+#[derive(Clone, Copy, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AnimationParameter {
+    /// A float parameter, typically used to drive blend spaces or compared
+    /// against thresholds in transition conditions.
+    Float(f32),
+    /// A boolean parameter.
+    Bool(bool),
+    /// A trigger, which stays set until a transition that checks it fires.
+    Trigger(bool),
+}
+
+/// The named parameters that drive the state machines and blend spaces of the
+/// [`AnimationGraph`] played by an [`AnimationPlayer`].
+///
+/// Parameters that have never been set read as `0.0`, `false`, or not
+/// triggered respectively.
+#[derive(Clone, Debug, Default, Reflect)]
+#[reflect(Clone, Default)]
+pub struct AnimationParameters {
+    values: HashMap<String, AnimationParameter>,
+}
+
+impl AnimationParameters {
+    /// Returns the value of the parameter with the given name, if it has been
+    /// set.
+    pub fn get(&self, name: &str) -> Option<AnimationParameter> {
+        self.values.get(name).copied()
+    }
+
+    /// Sets the parameter with the given name to the given value.
+    pub fn set(&mut self, name: impl Into<String>, value: AnimationParameter) -> &mut Self {
+        self.values.insert(name.into(), value);
+        self
+    }
+
+    /// Sets the float parameter with the given name.
+    pub fn set_float(&mut self, name: impl Into<String>, value: f32) -> &mut Self {
+        self.set(name, AnimationParameter::Float(value))
+    }
+
+    /// Sets the boolean parameter with the given name.
+    pub fn set_bool(&mut self, name: impl Into<String>, value: bool) -> &mut Self {
+        self.set(name, AnimationParameter::Bool(value))
+    }
+
+    /// Sets the trigger with the given name.
+    ///
+    /// The trigger stays set until a transition that checks it fires, or
+    /// until it's [reset](Self::reset_trigger).
+    pub fn set_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.set(name, AnimationParameter::Trigger(true))
+    }
+
+    /// Resets the trigger with the given name.
+    pub fn reset_trigger(&mut self, name: impl Into<String>) -> &mut Self {
+        self.set(name, AnimationParameter::Trigger(false))
+    }
+
+    /// Returns the value of the float parameter with the given name, or `0.0`
+    /// if it isn't a float parameter.
+    pub fn float(&self, name: &str) -> f32 {
+        match self.get(name) {
+            Some(AnimationParameter::Float(value)) => value,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the value of the boolean parameter with the given name, or
+    /// `false` if it isn't a boolean parameter.
+    pub fn bool(&self, name: &str) -> bool {
+        matches!(self.get(name), Some(AnimationParameter::Bool(true)))
+    }
+
+    /// Returns true if the trigger with the given name is set.
+    pub fn is_triggered(&self, name: &str) -> bool {
+        matches!(self.get(name), Some(AnimationParameter::Trigger(true)))
+    }
+
+    /// Removes all parameters.
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+/// The definition of an animation state machine, stored in an
+/// [`AnimationNodeType::StateMachine`] node.
+///
+/// Each state is a child node of the state machine node. Exactly one state is
+/// current at a time; the first state is the one the machine starts in.
+#[derive(Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Default)]
+pub struct AnimationStateMachine {
+    /// The states of this machine.
+    pub states: Vec<AnimationState>,
+    /// The transitions between states, in priority order.
+    ///
+    /// Every frame, the first transition whose requirements are met is taken.
+    /// At most one transition is taken per frame.
+    #[serde(default)]
+    pub transitions: Vec<AnimationStateTransition>,
+}
+
+/// A named state of an [`AnimationStateMachine`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone)]
+pub struct AnimationState {
+    /// The name of the state, used by transitions to refer to it.
+    pub name: String,
+    /// The child node of the state machine node that is played while this
+    /// state is active.
+    pub node: AnimationNodeIndex,
+}
+
+/// A transition between the states of an [`AnimationStateMachine`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone)]
+pub struct AnimationStateTransition {
+    /// The name of the state this transition starts from, or `None` if it can
+    /// be taken from any state other than [`Self::to`].
+    pub from: Option<String>,
+    /// The name of the state this transition leads to.
+    pub to: String,
+    /// The conditions that must all hold for the transition to be taken.
+    #[serde(default)]
+    pub conditions: Vec<AnimationCondition>,
+    /// If set, the transition can only be taken after the source state has
+    /// been active for at least this many seconds.
+    #[serde(default)]
+    pub exit_time: Option<f32>,
+    /// The time, in seconds, over which the source state is faded out and
+    /// the target state is faded in.
+    #[serde(default)]
+    pub blend_duration: f32,
+}
+
+/// A condition on the [`AnimationParameters`] that must hold for an
+/// [`AnimationStateTransition`] to be taken.
+#[derive(Clone, Debug, PartialEq, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AnimationCondition {
+    /// The float parameter is greater than the value.
+    Greater {
+        /// The name of the parameter.
+        parameter: String,
+        /// The value to compare against.
+        value: f32,
+    },
+    /// The float parameter is less than the value.
+    Less {
+        /// The name of the parameter.
+        parameter: String,
+        /// The value to compare against.
+        value: f32,
+    },
+    /// The boolean parameter equals the value.
+    Bool {
+        /// The name of the parameter.
+        parameter: String,
+        /// The value to compare against.
+        value: bool,
+    },
+    /// The trigger is set. Taking the transition resets the trigger.
+    Trigger {
+        /// The name of the trigger.
+        parameter: String,
+    },
+}
+
+impl AnimationStateMachine {
+    /// Creates a new state machine with no states.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a state that plays the given child node of the state machine
+    /// node.
+    ///
+    /// The first state added is the one the machine starts in.
+    pub fn add_state(&mut self, name: impl Into<String>, node: AnimationNodeIndex) -> &mut Self {
+        self.states.push(AnimationState {
+            name: name.into(),
+            node,
+        });
+        self
+    }
+
+    /// Adds a transition, with a lower priority than the existing ones.
+    pub fn add_transition(&mut self, transition: AnimationStateTransition) -> &mut Self {
+        self.transitions.push(transition);
+        self
+    }
+
+    /// Returns the index of the state with the given name.
+    pub fn state_index(&self, name: &str) -> Option<usize> {
+        self.states.iter().position(|state| state.name == name)
+    }
+}
+
+impl AnimationStateTransition {
+    /// Creates a transition from the state named `from` to the state named
+    /// `to`, with no conditions and no blending.
+    pub fn new(from: impl Into<String>, to: impl Into<String>) -> Self {
+        Self {
+            from: Some(from.into()),
+            to: to.into(),
+            conditions: Vec::new(),
+            exit_time: None,
+            blend_duration: 0.0,
+        }
+    }
+
+    /// Creates a transition from any state to the state named `to`, with no
+    /// conditions and no blending.
+    pub fn from_any(to: impl Into<String>) -> Self {
+        Self {
+            from: None,
+            ..Self::new("", to)
+        }
+    }
+
+    /// Adds a condition that must hold for the transition to be taken.
+    pub fn with_condition(mut self, condition: AnimationCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Sets the time the source state must have been active for before the
+    /// transition can be taken.
+    pub fn with_exit_time(mut self, exit_time: f32) -> Self {
+        self.exit_time = Some(exit_time);
+        self
+    }
+
+    /// Sets the time over which the transition cross-fades the two states.
+    pub fn with_blend_duration(mut self, blend_duration: f32) -> Self {
+        self.blend_duration = blend_duration;
+        self
+    }
+}
+
+impl AnimationCondition {
+    /// Returns true if the condition holds for the given parameters.
+    pub fn evaluate(&self, parameters: &AnimationParameters) -> bool {
+        match self {
+            AnimationCondition::Greater { parameter, value } => {
+                parameters.float(parameter) > *value
+            }
+            AnimationCondition::Less { parameter, value } => parameters.float(parameter) < *value,
+            AnimationCondition::Bool { parameter, value } => parameters.bool(parameter) == *value,
+            AnimationCondition::Trigger { parameter } => parameters.is_triggered(parameter),
+        }
+    }
+}
+
+/// The runtime state of an [`AnimationStateMachine`] for a single
+/// [`AnimationPlayer`].
+#[derive(Clone, Debug, Default, Reflect)]
+#[reflect(Clone, Default)]
+pub struct AnimationStateMachineState {
+    current: usize,
+    state_time: f32,
+    weights: Vec<f32>,
+    blend_duration: f32,
+}
+
+impl AnimationStateMachineState {
+    /// Creates the state of a machine that has just started in its first
+    /// state.
+    fn new(machine: &AnimationStateMachine) -> Self {
+        let mut weights = vec![0.0; machine.states.len()];
+        if let Some(weight) = weights.first_mut() {
+            *weight = 1.0;
+        }
+        Self {
+            current: 0,
+            state_time: 0.0,
+            weights,
+            blend_duration: 0.0,
+        }
+    }
+
+    /// Returns the index of the current state in
+    /// [`AnimationStateMachine::states`].
+    pub fn current_state(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the time, in seconds, the current state has been active for.
+    pub fn state_time(&self) -> f32 {
+        self.state_time
+    }
+
+    /// Returns the blend weight of the state with the given index.
+    ///
+    /// The current state has weight 1.0 once any transition into it finished,
+    /// and states that are being faded out have a weight between 0.0 and 1.0.
+    pub fn state_weight(&self, state: usize) -> f32 {
+        self.weights.get(state).copied().unwrap_or_default()
+    }
+
+    /// Advances the machine by `delta` seconds, taking at most one transition,
+    /// and updates the weights of the states.
+    ///
+    /// Returns the index of the state that was entered, if any.
+    fn advance(
+        &mut self,
+        machine: &AnimationStateMachine,
+        parameters: &mut AnimationParameters,
+        delta: f32,
+    ) -> Option<usize> {
+        self.weights.resize(machine.states.len(), 0.0);
+        if self.current >= machine.states.len() {
+            return None;
+        }
+
+        self.state_time += delta;
+
+        let current_name = &machine.states[self.current].name;
+        let mut entered = None;
+        for transition in &machine.transitions {
+            let applies = match transition.from {
+                Some(ref from) => from == current_name,
+                None => transition.to != *current_name,
+            };
+            if !applies
+                || transition
+                    .exit_time
+                    .is_some_and(|exit_time| self.state_time < exit_time)
+                || !transition
+                    .conditions
+                    .iter()
+                    .all(|condition| condition.evaluate(parameters))
+            {
+                continue;
+            }
+            let Some(target) = machine.state_index(&transition.to) else {
+                continue;
+            };
+
+            for condition in &transition.conditions {
+                if let AnimationCondition::Trigger { parameter } = condition {
+                    parameters.reset_trigger(parameter.clone());
+                }
+            }
+
+            self.current = target;
+            self.state_time = 0.0;
+            self.blend_duration = transition.blend_duration;
+            entered = Some(target);
+            break;
+        }
+
+        if self.blend_duration <= 0.0 {
+            for (index, weight) in self.weights.iter_mut().enumerate() {
+                *weight = if index == self.current { 1.0 } else { 0.0 };
+            }
+        } else {
+            let step = delta / self.blend_duration;
+            for (index, weight) in self.weights.iter_mut().enumerate() {
+                *weight = if index == self.current {
+                    (*weight + step).min(1.0)
+                } else {
+                    (*weight - step).max(0.0)
+                };
+            }
+        }
+
+        entered
+    }
+}
+
+/// A blend space that blends its children according to the value of a single
+/// float parameter, stored in an [`AnimationNodeType::BlendSpace1d`] node.
+///
+/// Each point is a child node of the blend space node placed at a position on
+/// a line. The two points surrounding the value of the parameter are linearly
+/// interpolated; outside the range of the points, the nearest point is played
+/// alone.
+#[derive(Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Default)]
+pub struct BlendSpace1d {
+    /// The name of the float parameter that drives this blend space.
+    pub parameter: String,
+    /// The points of this blend space.
+    pub points: Vec<BlendSpace1dPoint>,
+}
+
+/// A point of a [`BlendSpace1d`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone)]
+pub struct BlendSpace1dPoint {
+    /// The value of the parameter at which this point has full weight.
+    pub position: f32,
+    /// The child node of the blend space node played at this point.
+    pub node: AnimationNodeIndex,
+}
+
+/// A blend space that blends its children according to the values of two
+/// float parameters, stored in an [`AnimationNodeType::BlendSpace2d`] node.
+///
+/// Each point is a child node of the blend space node placed at a position on
+/// a plane. The weights of the points are computed with gradient band
+/// interpolation: every point's influence falls off linearly towards each
+/// other point, and the resulting weights are normalized to sum to 1.0.
+#[derive(Clone, Debug, Default, Reflect, Serialize, Deserialize)]
+#[reflect(Clone, Default)]
+pub struct BlendSpace2d {
+    /// The name of the float parameter that drives the X axis.
+    pub x_parameter: String,
+    /// The name of the float parameter that drives the Y axis.
+    pub y_parameter: String,
+    /// The points of this blend space.
+    pub points: Vec<BlendSpace2dPoint>,
+}
+
+/// A point of a [`BlendSpace2d`].
+#[derive(Clone, Debug, Reflect, Serialize, Deserialize)]
+#[reflect(Clone)]
+pub struct BlendSpace2dPoint {
+    /// The values of the parameters at which this point has full weight.
+    pub position: Vec2,
+    /// The child node of the blend space node played at this point.
+    pub node: AnimationNodeIndex,
+}
+
+impl BlendSpace1d {
+    /// Creates a blend space driven by the float parameter with the given
+    /// name, with no points.
+    pub fn new(parameter: impl Into<String>) -> Self {
+        Self {
+            parameter: parameter.into(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Adds a point that plays the given child node of the blend space node.
+    pub fn add_point(&mut self, position: f32, node: AnimationNodeIndex) -> &mut Self {
+        self.points.push(BlendSpace1dPoint { position, node });
+        self
+    }
+
+    /// Returns the weight of every point, in the order of [`Self::points`],
+    /// for the given value of the parameter.
+    pub fn weights(&self, value: f32) -> Vec<f32> {
+        let mut weights = vec![0.0; self.points.len()];
+
+        let mut below: Option<usize> = None;
+        let mut above: Option<usize> = None;
+        for (index, point) in self.points.iter().enumerate() {
+            if point.position <= value
+                && below.is_none_or(|below| point.position > self.points[below].position)
+            {
+                below = Some(index);
+            }
+            if point.position >= value
+                && above.is_none_or(|above| point.position < self.points[above].position)
+            {
+                above = Some(index);
+            }
+        }
+
+        match (below, above) {
+            (Some(below), Some(above)) if below != above => {
+                let start = self.points[below].position;
+                let end = self.points[above].position;
+                let t = (value - start) / (end - start);
+                weights[below] = 1.0 - t;
+                weights[above] = t;
+            }
+            (Some(index), _) | (None, Some(index)) => weights[index] = 1.0,
+            (None, None) => {}
+        }
+        weights
+    }
+}
+
+impl BlendSpace2d {
+    /// Creates a blend space driven by the float parameters with the given
+    /// names, with no points.
+    pub fn new(x_parameter: impl Into<String>, y_parameter: impl Into<String>) -> Self {
+        Self {
+            x_parameter: x_parameter.into(),
+            y_parameter: y_parameter.into(),
+            points: Vec::new(),
+        }
+    }
+
+    /// Adds a point that plays the given child node of the blend space node.
+    pub fn add_point(&mut self, position: Vec2, node: AnimationNodeIndex) -> &mut Self {
+        self.points.push(BlendSpace2dPoint { position, node });
+        self
+    }
+
+    /// Returns the weight of every point, in the order of [`Self::points`],
+    /// for the given values of the parameters.
+    pub fn weights(&self, value: Vec2) -> Vec<f32> {
+        let mut weights: Vec<f32> = self
+            .points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                let to_value = value - point.position;
+                self.points
+                    .iter()
+                    .enumerate()
+                    .filter(|&(other_index, _)| other_index != index)
+                    .map(|(_, other)| {
+                        let to_other = other.position - point.position;
+                        let length_squared = to_other.length_squared();
+                        if length_squared == 0.0 {
+                            return 1.0;
+                        }
+                        (1.0 - to_value.dot(to_other) / length_squared).clamp(0.0, 1.0)
+                    })
+                    .fold(1.0, f32::min)
+            })
+            .collect();
+
+        let total: f32 = weights.iter().sum();
+        if total > 0.0 {
+            for weight in &mut weights {
+                *weight /= total;
+            }
+        }
+        weights
+    }
+}
+
+/// A system that advances the state machines and evaluates the blend spaces
+/// of every [`AnimationPlayer`], updating the weights of their children.
+///
+/// When a state is entered, the animation clips below it are started from the
+/// beginning; once a state has completely faded out, they're stopped. The
+/// animation clips below a blend space are started, repeating forever, if
+/// they aren't playing already, unless the blend space is in a state (or below
+/// another node) that currently has a weight of zero.
+pub fn advance_animation_graph_nodes(
+    time: Res<Time>,
+    animation_graphs: Res<Assets<AnimationGraph>>,
+    mut players: Query<(&mut AnimationPlayer, &AnimationGraphHandle)>,
+) {
+    let delta_seconds = time.delta_secs();
+    players
+        .par_iter_mut()
+        .for_each(|(mut player, graph_handle)| {
+            let Some(animation_graph) = animation_graphs.get(graph_handle) else {
+                return;
+            };
+
+            for node_index in animation_graph.graph.node_indices() {
+                match animation_graph[node_index].node_type {
+                    AnimationNodeType::StateMachine(ref machine) => {
+                        advance_state_machine(
+                            &mut player,
+                            animation_graph,
+                            node_index,
+                            machine,
+                            delta_seconds,
+                        );
+                    }
+                    AnimationNodeType::BlendSpace1d(ref blend_space) => {
+                        let value = player.parameters.float(&blend_space.parameter);
+                        let weights = blend_space.weights(value);
+                        let nodes = blend_space.points.iter().map(|point| point.node);
+                        apply_blend_space_weights(
+                            &mut player,
+                            animation_graph,
+                            node_index,
+                            nodes,
+                            &weights,
+                        );
+                    }
+                    AnimationNodeType::BlendSpace2d(ref blend_space) => {
+                        let value = Vec2::new(
+                            player.parameters.float(&blend_space.x_parameter),
+                            player.parameters.float(&blend_space.y_parameter),
+                        );
+                        let weights = blend_space.weights(value);
+                        let nodes = blend_space.points.iter().map(|point| point.node);
+                        apply_blend_space_weights(
+                            &mut player,
+                            animation_graph,
+                            node_index,
+                            nodes,
+                            &weights,
+                        );
+                    }
+                    AnimationNodeType::Clip(_)
+                    | AnimationNodeType::Blend
+                    | AnimationNodeType::Add
+                    | AnimationNodeType::OverrideLayer(_)
+                    | AnimationNodeType::AdditiveLayer(_) => {}
+                }
+            }
+        });
+}
+
+/// Advances a single state machine of `player` and updates the weights of its
+/// states.
+fn advance_state_machine(
+    player: &mut AnimationPlayer,
+    animation_graph: &AnimationGraph,
+    node_index: AnimationNodeIndex,
+    machine: &AnimationStateMachine,
+    delta_seconds: f32,
+) {
+    let AnimationPlayer {
+        ref mut parameters,
+        ref mut state_machines,
+        ..
+    } = *player;
+
+    let mut entered = None;
+    let state = state_machines.entry(node_index).or_insert_with(|| {
+        entered = Some(0);
+        AnimationStateMachineState::new(machine)
+    });
+    let previous_weights = state.weights.clone();
+    if let Some(target) = state.advance(machine, parameters, delta_seconds) {
+        entered = Some(target);
+    }
+    let weights = state.weights.clone();
+
+    for (index, machine_state) in machine.states.iter().enumerate() {
+        let weight = weights.get(index).copied().unwrap_or_default();
+        player.node_weights.insert(machine_state.node, weight);
+
+        if entered == Some(index) {
+            for_each_clip(animation_graph, machine_state.node, &mut |clip| {
+                player.start(clip);
+            });
+        } else if weight == 0.0 && previous_weights.get(index).is_some_and(|&w| w > 0.0) {
+            for_each_clip(animation_graph, machine_state.node, &mut |clip| {
+                player.stop(clip);
+            });
+        }
+    }
+}
+
+/// Sets the weights of the points of a blend space, and makes sure the clips
+/// below them are playing unless the blend space is silenced.
+fn apply_blend_space_weights(
+    player: &mut AnimationPlayer,
+    animation_graph: &AnimationGraph,
+    blend_space: AnimationNodeIndex,
+    nodes: impl Iterator<Item = AnimationNodeIndex>,
+    weights: &[f32],
+) {
+    let silenced = is_node_silenced(player, animation_graph, blend_space);
+    for (node, &weight) in nodes.zip(weights) {
+        player.node_weights.insert(node, weight);
+        if silenced {
+            continue;
+        }
+        for_each_clip(animation_graph, node, &mut |clip| {
+            if !player.is_playing_animation(clip) {
+                player.play(clip).repeat();
+            }
+        });
+    }
+}
+
+/// Returns `true` if the state machines and blend spaces above `node` (or `node`
+/// itself) currently give it a weight of zero along every path from the root.
+pub(crate) fn is_node_silenced(
+    player: &AnimationPlayer,
+    animation_graph: &AnimationGraph,
+    node: AnimationNodeIndex,
+) -> bool {
+    if player.node_weight(node) == 0.0 {
+        return true;
+    }
+    let mut parents = animation_graph
+        .graph
+        .neighbors_directed(node, Direction::Incoming)
+        .peekable();
+    parents.peek().is_some()
+        && parents.all(|parent| is_node_silenced(player, animation_graph, parent))
+}
+
+/// Calls `f` on every clip node in the subtree rooted at `node`, including
+/// `node` itself.
+fn for_each_clip(
+    animation_graph: &AnimationGraph,
+    node: AnimationNodeIndex,
+    f: &mut impl FnMut(AnimationNodeIndex),
+) {
+    let Some(graph_node) = animation_graph.get(node) else {
+        return;
+    };
+    if let AnimationNodeType::Clip(_) = graph_node.node_type {
+        f(node);
+    }
+    for child in animation_graph
+        .graph
+        .neighbors_directed(node, Direction::Outgoing)
+    {
+        for_each_clip(animation_graph, child, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{
+        AnimationGraph, AnimationGraphHandle, SerializedAnimationGraph, SerializedAnimationNodeType,
+    };
+    use bevy_asset::Handle;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use core::time::Duration;
+
+    fn walk_run_machine() -> AnimationStateMachine {
+        let mut machine = AnimationStateMachine::new();
+        machine
+            .add_state("idle", AnimationNodeIndex::new(1))
+            .add_state("run", AnimationNodeIndex::new(2))
+            .add_state("jump", AnimationNodeIndex::new(3))
+            .add_transition(
+                AnimationStateTransition::new("idle", "run")
+                    .with_condition(AnimationCondition::Greater {
+                        parameter: "speed".into(),
+                        value: 0.5,
+                    })
+                    .with_blend_duration(0.5),
+            )
+            .add_transition(AnimationStateTransition::new("run", "idle").with_condition(
+                AnimationCondition::Less {
+                    parameter: "speed".into(),
+                    value: 0.5,
+                },
+            ))
+            .add_transition(AnimationStateTransition::from_any("jump").with_condition(
+                AnimationCondition::Trigger {
+                    parameter: "jump".into(),
+                },
+            ))
+            .add_transition(AnimationStateTransition::new("jump", "idle").with_exit_time(1.0));
+        machine
+    }
+
+    #[test]
+    fn state_machine_transitions() {
+        let machine = walk_run_machine();
+        let mut parameters = AnimationParameters::default();
+        let mut state = AnimationStateMachineState::new(&machine);
+
+        assert_eq!(state.advance(&machine, &mut parameters, 0.1), None);
+        assert_eq!(state.current_state(), 0);
+
+        // A float condition starts a cross-fade.
+        parameters.set_float("speed", 1.0);
+        assert_eq!(state.advance(&machine, &mut parameters, 0.25), Some(1));
+        assert_eq!(state.state_weight(0), 0.5);
+        assert_eq!(state.state_weight(1), 0.5);
+        assert_eq!(state.advance(&machine, &mut parameters, 0.25), None);
+        assert_eq!(state.state_weight(0), 0.0);
+        assert_eq!(state.state_weight(1), 1.0);
+
+        // A trigger is consumed by the transition it fires.
+        parameters.set_trigger("jump");
+        assert_eq!(state.advance(&machine, &mut parameters, 0.1), Some(2));
+        assert!(!parameters.is_triggered("jump"));
+        assert_eq!(state.state_weight(1), 0.0);
+        assert_eq!(state.state_weight(2), 1.0);
+
+        // The exit time holds the state until it has been active long enough.
+        assert_eq!(state.advance(&machine, &mut parameters, 0.5), None);
+        assert_eq!(state.advance(&machine, &mut parameters, 0.5), Some(0));
+        assert_eq!(state.state_time(), 0.0);
+    }
+
+    #[test]
+    fn blend_space_weights() {
+        let mut blend_space = BlendSpace1d::new("speed");
+        blend_space
+            .add_point(0.0, AnimationNodeIndex::new(1))
+            .add_point(2.0, AnimationNodeIndex::new(2))
+            .add_point(1.0, AnimationNodeIndex::new(3));
+        assert_eq!(blend_space.weights(-1.0), [1.0, 0.0, 0.0]);
+        assert_eq!(blend_space.weights(0.25), [0.75, 0.0, 0.25]);
+        assert_eq!(blend_space.weights(1.5), [0.0, 0.5, 0.5]);
+        assert_eq!(blend_space.weights(3.0), [0.0, 1.0, 0.0]);
+
+        let mut blend_space = BlendSpace2d::new("x", "y");
+        blend_space
+            .add_point(Vec2::ZERO, AnimationNodeIndex::new(1))
+            .add_point(Vec2::X, AnimationNodeIndex::new(2))
+            .add_point(Vec2::Y, AnimationNodeIndex::new(3));
+        assert_eq!(blend_space.weights(Vec2::ZERO), [1.0, 0.0, 0.0]);
+        assert_eq!(blend_space.weights(Vec2::Y), [0.0, 0.0, 1.0]);
+        let weights = blend_space.weights(Vec2::new(0.5, 0.0));
+        assert_eq!(weights, [0.5, 0.5, 0.0]);
+    }
+
+    #[test]
+    fn faded_out_state_keeps_blend_space_clips_stopped() {
+        let mut graph = AnimationGraph::new();
+        let machine = graph.add_state_machine(AnimationStateMachine::new(), 1.0, graph.root);
+        let locomotion = graph.add_blend_space_1d(BlendSpace1d::new("speed"), 1.0, machine);
+        let walk = graph.add_clip(Handle::default(), 1.0, locomotion);
+        let run = graph.add_clip(Handle::default(), 1.0, locomotion);
+        let idle = graph.add_clip(Handle::default(), 1.0, machine);
+        graph
+            .blend_space_1d_mut(locomotion)
+            .unwrap()
+            .add_point(0.0, walk)
+            .add_point(1.0, run);
+        graph
+            .state_machine_mut(machine)
+            .unwrap()
+            .add_state("locomotion", locomotion)
+            .add_state("idle", idle)
+            .add_transition(
+                AnimationStateTransition::new("locomotion", "idle").with_condition(
+                    AnimationCondition::Trigger {
+                        parameter: "stop".into(),
+                    },
+                ),
+            );
+
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        world.init_resource::<Assets<AnimationGraph>>();
+        let graph = world.resource_mut::<Assets<AnimationGraph>>().add(graph);
+        let player = world
+            .spawn((
+                AnimationPlayer::default(),
+                AnimationGraphHandle(graph.clone()),
+            ))
+            .id();
+        let update = |world: &mut World| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(100));
+            world
+                .run_system_once(advance_animation_graph_nodes)
+                .unwrap();
+        };
+
+        update(&mut world);
+        let animation_player = world.get::<AnimationPlayer>(player).unwrap();
+        assert!(animation_player.is_playing_animation(walk));
+        assert!(animation_player.is_playing_animation(run));
+
+        world
+            .get_mut::<AnimationPlayer>(player)
+            .unwrap()
+            .parameters_mut()
+            .set_trigger("stop");
+        update(&mut world);
+        update(&mut world);
+        let animation_player = world.get::<AnimationPlayer>(player).unwrap();
+        assert!(animation_player.is_playing_animation(idle));
+        assert!(!animation_player.is_playing_animation(walk));
+        assert!(!animation_player.is_playing_animation(run));
+
+        let graph = world
+            .resource::<Assets<AnimationGraph>>()
+            .get(&graph)
+            .unwrap();
+        assert!(is_node_silenced(animation_player, graph, walk));
+        assert!(!is_node_silenced(animation_player, graph, idle));
+    }
+
+    #[test]
+    fn serialize_state_machine_and_blend_spaces() {
+        let mut graph = AnimationGraph::new();
+        let machine = graph.add_state_machine(AnimationStateMachine::new(), 1.0, graph.root);
+        let idle = graph.add_blend(1.0, machine);
+        let locomotion = graph.add_blend_space_1d(BlendSpace1d::new("speed"), 1.0, machine);
+        graph
+            .state_machine_mut(machine)
+            .unwrap()
+            .add_state("idle", idle)
+            .add_state("locomotion", locomotion)
+            .add_transition(AnimationStateTransition::new("idle", "locomotion"));
+
+        let mut ron = String::new();
+        graph.save(&mut ron).unwrap();
+        let serialized: SerializedAnimationGraph = ron::de::from_str(&ron).unwrap();
+
+        let SerializedAnimationNodeType::StateMachine(ref machine) =
+            serialized.graph[machine].node_type
+        else {
+            panic!("expected a state machine node");
+        };
+        assert_eq!(machine.state_index("locomotion"), Some(1));
+        assert_eq!(machine.states[1].node, locomotion);
+        assert_eq!(machine.transitions[0].to, "locomotion");
+        let SerializedAnimationNodeType::BlendSpace1d(ref blend_space) =
+            serialized.graph[locomotion].node_type
+        else {
+            panic!("expected a blend space node");
+        };
+        assert_eq!(blend_space.parameter, "speed");
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{
     First, Main, MainSchedulePlugin, PlaceholderPlugin, Plugin, Plugins, PluginsState, SubApp,
-    SubApps,
+    SubApps, Update,
 };
 use alloc::{
     boxed::Box,
@@ -10,6 +10,7 @@ use alloc::{
 pub use bevy_derive::AppLabel;
 use bevy_ecs::{
     component::RequiredComponentsError,
+    coroutine::{run_coroutines, Coroutine, CoroutineSystems},
     error::{ErrorHandler, FallbackErrorHandler},
     intern::Interned,
     message::{message_update_system, MessageCursor},
@@ -130,6 +131,13 @@ impl Default for App {
                 .in_set(bevy_ecs::message::MessageUpdateSystems)
                 .run_if(bevy_ecs::message::message_update_condition),
         );
+        app.add_systems(
+            Update,
+            run_coroutines
+                .in_set(CoroutineSystems)
+                .ambiguous_with_all()
+                .run_if(any_with_component::<Coroutine>),
+        );
         app.add_message::<AppExit>();
 
         app
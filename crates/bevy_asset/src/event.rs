@@ -85,6 +85,11 @@ impl<A: Asset> AssetEvent<A> {
     pub fn is_unused(&self, asset_id: impl Into<AssetId<A>>) -> bool {
         matches!(self, AssetEvent::Unused { id } if *id == asset_id.into())
     }
+
+    /// Converts this to an "untyped" / "generic-less" asset event that stores the type information.
+    pub fn untyped(&self) -> UntypedAssetEvent {
+        self.into()
+    }
 }
 
 impl<A: Asset> Clone for AssetEvent<A> {
@@ -127,3 +132,46 @@ impl<A: Asset> PartialEq for AssetEvent<A> {
 }
 
 impl<A: Asset> Eq for AssetEvent<A> {}
+
+/// An untyped version of [`AssetEvent`].
+#[expect(missing_docs, reason = "Documenting the id fields is unhelpful.")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UntypedAssetEvent {
+    /// Emitted whenever an [`Asset`] is added.
+    Added { id: UntypedAssetId },
+    /// Emitted whenever an [`Asset`] value is modified.
+    Modified { id: UntypedAssetId },
+    /// Emitted whenever an [`Asset`] is removed.
+    Removed { id: UntypedAssetId },
+    /// Emitted when the last [`Handle::Strong`](`super::Handle::Strong`) of an [`Asset`] is dropped.
+    Unused { id: UntypedAssetId },
+    /// Emitted whenever an [`Asset`] has been fully loaded (including its dependencies and all "recursive dependencies").
+    LoadedWithDependencies { id: UntypedAssetId },
+}
+
+impl UntypedAssetEvent {
+    /// Returns the id of the asset this event is about.
+    pub fn id(&self) -> UntypedAssetId {
+        match self {
+            Self::Added { id }
+            | Self::Modified { id }
+            | Self::Removed { id }
+            | Self::Unused { id }
+            | Self::LoadedWithDependencies { id } => *id,
+        }
+    }
+}
+
+impl<A: Asset> From<&AssetEvent<A>> for UntypedAssetEvent {
+    fn from(value: &AssetEvent<A>) -> Self {
+        match *value {
+            AssetEvent::Added { id } => Self::Added { id: id.untyped() },
+            AssetEvent::Modified { id } => Self::Modified { id: id.untyped() },
+            AssetEvent::Removed { id } => Self::Removed { id: id.untyped() },
+            AssetEvent::Unused { id } => Self::Unused { id: id.untyped() },
+            AssetEvent::LoadedWithDependencies { id } => {
+                Self::LoadedWithDependencies { id: id.untyped() }
+            }
+        }
+    }
+}
@@ -33,6 +33,10 @@ pub trait AssetLoader: TypePath + Send + Sync + 'static {
     type Settings: Settings + Default + Serialize + for<'a> Deserialize<'a>;
     /// The type of [error](`std::error::Error`) which could be encountered by this loader.
     type Error: Into<BevyError>;
+    /// The version of this loader. Bump it whenever a change to the loader changes the assets it
+    /// produces, so that the [`AssetProcessor`](crate::processor::AssetProcessor) reprocesses the
+    /// assets that were processed with an older version.
+    const VERSION: u32 = 0;
     /// Asynchronously loads [`AssetLoader::Asset`] (and any other labeled assets) from the bytes provided by [`Reader`].
     fn load(
         &self,
@@ -66,6 +70,8 @@ pub trait ErasedAssetLoader: Send + Sync + 'static {
     fn default_meta(&self) -> Box<dyn AssetMetaDyn>;
     /// Returns the type path of the [`AssetLoader`].
     fn type_path(&self) -> &'static str;
+    /// Returns the [`AssetLoader::VERSION`] of the [`AssetLoader`].
+    fn version(&self) -> u32;
     /// Returns the [`TypeId`] of the [`AssetLoader`].
     fn type_id(&self) -> TypeId;
     /// Returns the type name of the top-level [`Asset`] loaded by the [`AssetLoader`].
@@ -116,6 +122,10 @@ where
         L::type_path()
     }
 
+    fn version(&self) -> u32 {
+        L::VERSION
+    }
+
     fn type_id(&self) -> TypeId {
         TypeId::of::<L>()
     }
@@ -80,12 +80,15 @@ pub enum AssetAction<LoaderSettings, ProcessSettings> {
 /// [`AssetProcessor`]: crate::processor::AssetProcessor
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct ProcessedInfo {
-    /// A hash of the asset bytes and the asset .meta data
+    /// A hash of the asset bytes, the asset .meta data and the version of its loader or processor
     pub hash: AssetHash,
     /// A hash of the asset bytes, the asset .meta data, and the `full_hash` of every `process_dependency`
     pub full_hash: AssetHash,
     /// Information about the "process dependencies" used to process this asset.
     pub process_dependencies: Vec<ProcessDependencyInfo>,
+    /// The type path of the loader or processor configured for this asset when it was processed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processed_with: Option<String>,
 }
 
 /// Information about a dependency used to process an asset. This is used to determine whether an asset's "process dependency"
@@ -260,10 +263,15 @@ pub type AssetHash = [u8; 32];
 /// NOTE: changing the hashing logic here is a _breaking change_ that requires a [`META_FORMAT_VERSION`] bump.
 pub(crate) async fn get_asset_hash(
     meta_bytes: &[u8],
+    version: u32,
     asset_reader: &mut impl Reader,
 ) -> Result<AssetHash, AssetReaderError> {
     let mut hasher = blake3::Hasher::new();
     hasher.update(meta_bytes);
+    // Unversioned loaders and processors keep the hashes they had before versions were hashed.
+    if version != 0 {
+        hasher.update(&version.to_le_bytes());
+    }
     let mut buffer = [0; blake3::CHUNK_LEN];
     loop {
         let bytes_read = asset_reader.read(&mut buffer).await?;
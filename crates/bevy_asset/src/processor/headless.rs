@@ -0,0 +1,77 @@
+use bevy_app::{App, PluginsState};
+use bevy_tasks::{block_on, IoTaskPool};
+use thiserror::Error;
+
+use super::{AssetProcessor, InitializeError, ProcessOnceOutput};
+
+/// Processes every asset of the [`AssetProcessor`] in `app` once, without running the app, and
+/// blocks until processing finished.
+///
+/// This lets a build step process assets without opening a window or running the game loop. The
+/// app must be built with [`AssetPlugin`](crate::AssetPlugin) in
+/// [`AssetMode::Processed`](crate::AssetMode::Processed) with the asset processor enabled, and
+/// with the loaders, processors and sources to use registered. Its plugins are finished if needed,
+/// but its schedules are never run.
+///
+/// ```no_run
+/// # use bevy_app::{App, TaskPoolPlugin};
+/// # use bevy_asset::{processor::process_assets_once, AssetMode, AssetPlugin};
+/// let mut app = App::new();
+/// app.add_plugins((
+///     TaskPoolPlugin::default(),
+///     AssetPlugin {
+///         mode: AssetMode::Processed,
+///         use_asset_processor_override: Some(true),
+///         watch_for_changes_override: Some(false),
+///         ..Default::default()
+///     },
+/// ));
+/// // Register asset loaders and processors here.
+///
+/// let output = process_assets_once(&mut app).unwrap();
+/// for (path, err) in output.errors() {
+///     eprintln!("Failed to process {path}: {err}");
+/// }
+/// ```
+pub fn process_assets_once(app: &mut App) -> Result<ProcessOnceOutput, ProcessAssetsOnceError> {
+    if app.plugins_state() == PluginsState::Ready {
+        app.finish();
+        app.cleanup();
+    }
+
+    let processor = app
+        .world()
+        .get_resource::<AssetProcessor>()
+        .ok_or(ProcessAssetsOnceError::MissingAssetProcessor)?
+        .clone();
+
+    let task = IoTaskPool::get().spawn(async move { processor.process_once().await });
+
+    #[cfg(feature = "multi_threaded")]
+    let output = block_on(task);
+
+    // Without the `multi_threaded` feature, tasks only make progress when the task pools are ticked
+    // on the main thread.
+    #[cfg(not(feature = "multi_threaded"))]
+    let output = {
+        let mut task = task;
+        loop {
+            #[cfg(not(target_arch = "wasm32"))]
+            bevy_tasks::tick_global_task_pools_on_main_thread();
+            if let Some(output) = block_on(bevy_tasks::poll_once(&mut task)) {
+                break output;
+            }
+        }
+    };
+
+    Ok(output?)
+}
+
+/// An error that prevents [`process_assets_once`] from processing assets.
+#[derive(Error, Debug)]
+pub enum ProcessAssetsOnceError {
+    #[error("The app has no `AssetProcessor`. Add `AssetPlugin` in `AssetMode::Processed` with the asset processor enabled")]
+    MissingAssetProcessor,
+    #[error(transparent)]
+    InitializeError(#[from] InitializeError),
+}
@@ -36,13 +36,25 @@
 //! - [`Process`]: a flexible low-level API for processing assets in arbitrary ways.
 //!
 //! In most cases, [`LoadTransformAndSave`] should be sufficient.
+//!
+//! # Inspecting processing
+//!
+//! - [`AssetProcessor::dependency_graph`] returns which assets were used to process which other assets.
+//! - [`AssetProcessor::dry_run`] lists the assets that would be processed, and why, without processing them.
+//! - [`AssetProcessorData::last_run_report`] returns a serializable [`ProcessRunReport`] of the last processing run, with timings.
+//!
+//! To process assets without running an app (for example on a build machine), use [`process_assets_once`].
 
+mod headless;
 mod log;
 mod process;
+mod report;
 
 use async_lock::RwLockReadGuardArc;
+pub use headless::*;
 pub use log::*;
 pub use process::*;
+pub use report::*;
 
 use crate::{
     io::{
@@ -56,11 +68,20 @@ use crate::{
     AssetLoadError, AssetMetaCheck, AssetPath, AssetServer, AssetServerMode, DeserializeMetaError,
     MissingAssetLoaderForExtensionError, UnapprovedPathMode, WriteDefaultMetaError,
 };
-use alloc::{borrow::ToOwned, boxed::Box, string::String, sync::Arc, vec, vec::Vec};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::VecDeque,
+    string::{String, ToString},
+    sync::Arc,
+    vec,
+    vec::Vec,
+};
 use bevy_ecs::prelude::*;
 use bevy_platform::{
     collections::{hash_map::Entry, HashMap, HashSet},
     sync::{PoisonError, RwLock},
+    time::Instant,
 };
 use bevy_tasks::IoTaskPool;
 use futures_io::ErrorKind;
@@ -74,10 +95,7 @@ use thiserror::Error;
 use tracing::{debug, error, trace, warn};
 
 #[cfg(feature = "trace")]
-use {
-    alloc::string::ToString,
-    tracing::{info_span, instrument::Instrument},
-};
+use tracing::{info_span, instrument::Instrument};
 
 /// A "background" asset processor that reads asset values from a source [`AssetSource`] (which corresponds to an [`AssetReader`](crate::io::AssetReader) / [`AssetWriter`](crate::io::AssetWriter) pair),
 /// processes them in some way, and writes them to a destination [`AssetSource`].
@@ -114,6 +132,21 @@ pub struct AssetProcessorData {
     /// The processors that will be used to process assets.
     processors: RwLock<Processors>,
     sources: Arc<AssetSources>,
+    /// The reports of the current and last processing runs.
+    run_reports: Mutex<ProcessRunRecorder>,
+    /// The results of processing each asset, collected while running
+    /// [`AssetProcessor::process_once`].
+    collected_results:
+        Mutex<Option<Vec<(AssetPath<'static>, Result<ProcessResult, ProcessError>)>>>,
+}
+
+/// Collects the [`ProcessRunReport`] of the current processing run.
+#[derive(Default)]
+struct ProcessRunRecorder {
+    /// The start time and the asset reports of the run in progress, if any.
+    current: Option<(Instant, Vec<AssetProcessReport>)>,
+    /// The report of the last finished run.
+    last: Option<ProcessRunReport>,
 }
 
 /// The current state of processing, including the overall state and the state of all assets.
@@ -208,6 +241,153 @@ impl AssetProcessor {
         &self.data.sources
     }
 
+    /// Returns a snapshot of the process dependencies between the assets known to the processor.
+    ///
+    /// The graph is only populated once the processor has been initialized (see
+    /// [`AssetProcessorData::wait_until_initialized`]).
+    pub async fn dependency_graph(&self) -> ProcessDependencyGraph {
+        let infos = self.data.processing_state.asset_infos.read().await;
+        let nodes = infos
+            .infos
+            .iter()
+            .map(|(path, info)| {
+                let node = ProcessDependencyNode {
+                    dependencies: info
+                        .processed_info
+                        .iter()
+                        .flat_map(|processed_info| &processed_info.process_dependencies)
+                        .map(|dependency| dependency.path.clone())
+                        .collect(),
+                    dependents: info.dependents.iter().cloned().collect(),
+                    status: info.status,
+                };
+                (path.clone(), node)
+            })
+            .collect();
+        ProcessDependencyGraph { nodes }
+    }
+
+    /// Returns the assets that would be processed if the processor started now, and why, without
+    /// processing or writing anything.
+    ///
+    /// This compares the source assets with the processed assets in storage, so it is meant to be
+    /// used while the processor isn't running. Assets that have a process dependency that would be
+    /// processed are included with [`ReprocessReason::DependencyChanged`].
+    ///
+    /// Returns an error if an asset or its meta can't be read, or refers to a missing loader or
+    /// processor, since processing it would fail.
+    pub async fn dry_run(&self) -> Result<Vec<DryRunEntry>, ProcessError> {
+        let mut asset_paths = Vec::new();
+        let mut processed_infos = HashMap::<AssetPath<'static>, Option<ProcessedInfo>>::default();
+        let mut reasons = HashMap::<AssetPath<'static>, ReprocessReason>::default();
+        let mut new_hashes = Vec::new();
+
+        for source in self.sources().iter_processed() {
+            let mut paths = Vec::new();
+            get_asset_paths(source.reader(), PathBuf::from(""), &mut paths, None)
+                .await
+                .map_err(|err| ProcessError::AssetReaderError {
+                    path: AssetPath::from(PathBuf::new()).with_source(source.id()),
+                    err,
+                })?;
+
+            for path in paths {
+                let asset_path = AssetPath::from(path).with_source(source.id());
+                let Some(SourceMeta {
+                    meta_bytes,
+                    processed_with,
+                    version,
+                    ..
+                }) = self.read_source_meta(source, &asset_path).await?
+                else {
+                    continue;
+                };
+
+                let reader_err = |err| ProcessError::AssetReaderError {
+                    path: asset_path.clone(),
+                    err,
+                };
+                let new_hash = {
+                    let mut reader = source
+                        .reader()
+                        .read(asset_path.path())
+                        .await
+                        .map_err(reader_err)?;
+                    get_asset_hash(&meta_bytes, version, &mut reader)
+                        .await
+                        .map_err(reader_err)?
+                };
+
+                let processed_info = match source.ungated_processed_reader() {
+                    Some(processed_reader) => processed_reader
+                        .read_meta_bytes(asset_path.path())
+                        .await
+                        .ok()
+                        .and_then(|meta_bytes| {
+                            ron::de::from_bytes::<ProcessedInfoMinimal>(&meta_bytes).ok()
+                        })
+                        .and_then(|minimal| minimal.processed_info),
+                    None => None,
+                };
+
+                processed_infos.insert(asset_path.clone(), processed_info);
+                new_hashes.push((new_hash, processed_with));
+                asset_paths.push(asset_path);
+            }
+        }
+
+        // Determine why each asset would be processed on its own.
+        for (asset_path, (new_hash, processed_with)) in asset_paths.iter().zip(new_hashes) {
+            let reason = reprocess_reason(
+                processed_infos[asset_path].as_ref(),
+                new_hash,
+                processed_with.as_deref(),
+                |path| {
+                    processed_infos
+                        .get(path)
+                        .and_then(Option::as_ref)
+                        .map(|info| info.full_hash)
+                },
+            );
+            if let Some(reason) = reason {
+                reasons.insert(asset_path.clone(), reason);
+            }
+        }
+
+        // Processing an asset also processes its dependents.
+        let mut dependents = HashMap::<&AssetPath<'static>, Vec<&AssetPath<'static>>>::default();
+        for (asset_path, processed_info) in &processed_infos {
+            for dependency in processed_info
+                .iter()
+                .flat_map(|info| &info.process_dependencies)
+            {
+                dependents
+                    .entry(&dependency.path)
+                    .or_default()
+                    .push(asset_path);
+            }
+        }
+        let mut queue = reasons.keys().cloned().collect::<VecDeque<_>>();
+        while let Some(dependency) = queue.pop_front() {
+            for &dependent in dependents.get(&dependency).into_iter().flatten() {
+                if let Entry::Vacant(entry) = reasons.entry(dependent.clone()) {
+                    entry.insert(ReprocessReason::DependencyChanged {
+                        dependency: dependency.clone(),
+                    });
+                    queue.push_back(dependent.clone());
+                }
+            }
+        }
+
+        Ok(asset_paths
+            .into_iter()
+            .filter_map(|path| {
+                let reason = reasons.remove(&path)?;
+                Some(DryRunEntry { path, reason })
+            })
+            .collect())
+    }
+
     /// Logs an unrecoverable error. On the next run of the processor, all assets will be regenerated. This should only be used as a last resort.
     /// Every call to this should be considered with scrutiny and ideally replaced with something more granular.
     async fn log_unrecoverable(&self) {
@@ -254,39 +434,76 @@ impl AssetProcessor {
         let processor = processor.clone();
         IoTaskPool::get()
             .spawn(async move {
-                let start_time = std::time::Instant::now();
-                debug!("Processing Assets");
+                let new_task_sender = processor.process_initial().await.unwrap();
 
-                processor.initialize().await.unwrap();
+                debug!("Listening for changes to source assets");
+                processor.spawn_source_change_event_listeners(&new_task_sender);
+            })
+            .detach();
+    }
 
-                let (new_task_sender, new_task_receiver) = async_channel::unbounded();
-                processor
-                    .queue_initial_processing_tasks(&new_task_sender)
-                    .await;
+    /// Processes every asset in the processed asset sources once, without listening for changes
+    /// afterwards, and returns the result of processing each asset.
+    ///
+    /// This is an alternative to [`AssetProcessor::start`] for processing assets without running
+    /// an app, for example in a build step. See [`process_assets_once`] for a blocking version that
+    /// uses the processor of an [`App`](bevy_app::App).
+    ///
+    /// Like [`AssetProcessor::start`], this can only be called once per processor.
+    pub async fn process_once(&self) -> Result<ProcessOnceOutput, InitializeError> {
+        *self
+            .data
+            .collected_results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Vec::new());
+        // Dropping the task sender stops executing tasks, since there are no listeners.
+        drop(self.process_initial().await?);
+        let results = self
+            .data
+            .collected_results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take()
+            .unwrap_or_default();
+        Ok(ProcessOnceOutput {
+            results,
+            report: self.data.last_run_report().unwrap_or_default(),
+        })
+    }
 
-                // Once all the tasks are queued for the initial processing, start actually
-                // executing the tasks.
-                {
-                    let processor = processor.clone();
-                    let new_task_sender = new_task_sender.clone();
-                    IoTaskPool::get()
-                        .spawn(async move {
-                            processor
-                                .execute_processing_tasks(new_task_sender, new_task_receiver)
-                                .await;
-                        })
-                        .detach();
-                }
+    /// Initializes the processor and processes every asset in the processed asset sources. Returns
+    /// the sender used to queue processing tasks, which keeps executing tasks while it is alive.
+    async fn process_initial(
+        &self,
+    ) -> Result<async_channel::Sender<(AssetSourceId<'static>, PathBuf)>, InitializeError> {
+        let start_time = Instant::now();
+        debug!("Processing Assets");
 
-                processor.data.wait_until_finished().await;
+        self.initialize().await?;
 
-                let end_time = std::time::Instant::now();
-                debug!("Processing finished in {:?}", end_time - start_time);
+        let (new_task_sender, new_task_receiver) = async_channel::unbounded();
+        self.queue_initial_processing_tasks(&new_task_sender).await;
 
-                debug!("Listening for changes to source assets");
-                processor.spawn_source_change_event_listeners(&new_task_sender);
-            })
-            .detach();
+        // Once all the tasks are queued for the initial processing, start actually
+        // executing the tasks.
+        {
+            let processor = self.clone();
+            let new_task_sender = new_task_sender.clone();
+            IoTaskPool::get()
+                .spawn(async move {
+                    processor
+                        .execute_processing_tasks(new_task_sender, new_task_receiver)
+                        .await;
+                })
+                .detach();
+        }
+
+        self.data.wait_until_finished().await;
+
+        let end_time = Instant::now();
+        debug!("Processing finished in {:?}", end_time - start_time);
+
+        Ok(new_task_sender)
     }
 
     /// Sends start task events for all assets in all processed sources into `sender`.
@@ -353,6 +570,8 @@ impl AssetProcessor {
         // to the finished state (otherwise we'd be sitting around stuck in the `Initialized`
         // state).
         if new_task_receiver.is_empty() {
+            self.data.begin_run();
+            self.data.finish_run();
             self.data
                 .processing_state
                 .set_state(ProcessorState::Finished)
@@ -391,6 +610,9 @@ impl AssetProcessor {
                     };
                     let processor = self.clone();
                     let task_finished_sender = task_finished_sender.clone();
+                    if pending_tasks == 0 {
+                        self.data.begin_run();
+                    }
                     pending_tasks += 1;
                     IoTaskPool::get()
                         .spawn(async move {
@@ -412,6 +634,7 @@ impl AssetProcessor {
                     if pending_tasks == 0 {
                         // clean up metadata in asset server
                         self.server.write_infos().consume_handle_drop_events();
+                        self.data.finish_run();
                         self.data
                             .processing_state
                             .set_state(ProcessorState::Finished)
@@ -838,42 +1061,6 @@ impl AssetProcessor {
         self.validate_transaction_log_and_recover().await;
         let mut asset_infos = self.data.processing_state.asset_infos.write().await;
 
-        /// Retrieves asset paths recursively. If `clean_empty_folders_writer` is Some, it will be used to clean up empty
-        /// folders when they are discovered.
-        async fn get_asset_paths(
-            reader: &dyn ErasedAssetReader,
-            path: PathBuf,
-            paths: &mut Vec<PathBuf>,
-            mut empty_dirs: Option<&mut Vec<PathBuf>>,
-        ) -> Result<bool, AssetReaderError> {
-            if reader.is_directory(&path).await? {
-                let mut path_stream = reader.read_directory(&path).await?;
-                let mut contains_files = false;
-
-                while let Some(child_path) = path_stream.next().await {
-                    contains_files |= Box::pin(get_asset_paths(
-                        reader,
-                        child_path,
-                        paths,
-                        empty_dirs.as_deref_mut(),
-                    ))
-                    .await?;
-                }
-                // Add the current directory after all its subdirectories so we delete any empty
-                // subdirectories before the current directory.
-                if !contains_files
-                    && path.parent().is_some()
-                    && let Some(empty_dirs) = empty_dirs
-                {
-                    empty_dirs.push(path);
-                }
-                Ok(contains_files)
-            } else {
-                paths.push(path);
-                Ok(true)
-            }
-        }
-
         for source in self.sources().iter_processed() {
             let Some(processed_reader) = source.ungated_processed_reader() else {
                 continue;
@@ -1022,57 +1209,79 @@ impl AssetProcessor {
         processor_task_event: async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
     ) {
         let asset_path = AssetPath::from(path).with_source(source.id());
-        let result = self.process_asset_internal(source, &asset_path).await;
+        let start_time = Instant::now();
+        let mut reason = None;
+        let result = self
+            .process_asset_internal(source, &asset_path, &mut reason)
+            .await;
+        self.data.record_asset_report(AssetProcessReport {
+            path: asset_path.clone(),
+            outcome: match &result {
+                Ok(ProcessResult::Processed(_)) => ProcessOutcome::Processed,
+                Ok(ProcessResult::SkippedNotChanged) => ProcessOutcome::SkippedNotChanged,
+                Ok(ProcessResult::Ignored) => ProcessOutcome::Ignored,
+                Err(err) if is_unprocessable_asset_error(err) => ProcessOutcome::Ignored,
+                Err(err) => ProcessOutcome::Failed(err.to_string()),
+            },
+            reason,
+            duration: start_time.elapsed(),
+        });
         let mut infos = self.data.processing_state.asset_infos.write().await;
         infos
-            .finish_processing(asset_path, result, processor_task_event)
+            .finish_processing(asset_path.clone(), &result, processor_task_event)
             .await;
+        drop(infos);
+        if let Some(results) = self
+            .data
+            .collected_results
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .as_mut()
+        {
+            results.push((asset_path, result));
+        }
     }
 
-    async fn process_asset_internal(
+    /// Reads the meta of the source asset at `asset_path`, or creates the default meta if there is
+    /// none. Returns [`None`] if the asset is configured to be ignored.
+    async fn read_source_meta(
         &self,
         source: &AssetSource,
         asset_path: &AssetPath<'static>,
-    ) -> Result<ProcessResult, ProcessError> {
-        // TODO: check if already processing to protect against duplicate hot-reload events
-        debug!("Processing {}", asset_path);
+    ) -> Result<Option<SourceMeta>, ProcessError> {
         let server = &self.server;
-        let path = asset_path.path();
-        let reader = source.reader();
-
-        let reader_err = |err| ProcessError::AssetReaderError {
-            path: asset_path.clone(),
-            err,
-        };
-        let writer_err = |err| ProcessError::AssetWriterError {
-            path: asset_path.clone(),
-            err,
-        };
-
-        let (mut source_meta, meta_bytes, processor) = match reader.read_meta_bytes(path).await {
+        match source.reader().read_meta_bytes(asset_path.path()).await {
             Ok(meta_bytes) => {
                 let minimal: AssetMetaMinimal = ron::de::from_bytes(&meta_bytes).map_err(|e| {
                     ProcessError::DeserializeMetaError(DeserializeMetaError::DeserializeMinimal(e))
                 })?;
-                let (meta, processor) = match minimal.asset {
+                let (meta, processor, processed_with, version) = match minimal.asset {
                     AssetActionMinimal::Load { loader } => {
                         let loader = server.get_asset_loader_with_type_name(&loader).await?;
                         let meta = loader.deserialize_meta(&meta_bytes)?;
-                        (meta, None)
+                        (meta, None, loader.type_path(), loader.version())
                     }
                     AssetActionMinimal::Process { processor } => {
                         let processor = self.get_processor(&processor)?;
                         let meta = processor.deserialize_meta(&meta_bytes)?;
-                        (meta, Some(processor))
+                        let processed_with = processor.type_path();
+                        let version = processor.version();
+                        (meta, Some(processor), processed_with, version)
                     }
                     AssetActionMinimal::Ignore => {
-                        return Ok(ProcessResult::Ignored);
+                        return Ok(None);
                     }
                 };
-                (meta, meta_bytes, processor)
+                Ok(Some(SourceMeta {
+                    meta,
+                    meta_bytes,
+                    processor,
+                    processed_with: Some(processed_with.to_owned()),
+                    version,
+                }))
             }
             Err(AssetReaderError::NotFound(_path)) => {
-                let (meta, processor) = if let Some(processor) = asset_path
+                let (meta, processor, processed_with, version) = if let Some(processor) = asset_path
                     .get_full_extension()
                     .and_then(|ext| self.get_default_processor(ext))
                 {
@@ -1080,26 +1289,69 @@ impl AssetProcessor {
                     // returning the processor here anyway, and we're only using this meta to pass
                     // along the processor settings.
                     let meta = processor.default_meta(MetaTypePathKind::Long);
-                    (meta, Some(processor))
+                    let processed_with = processor.type_path();
+                    let version = processor.version();
+                    (meta, Some(processor), Some(processed_with), version)
                 } else {
                     match server.get_path_asset_loader(asset_path.clone()).await {
-                        Ok(loader) => (loader.default_meta(), None),
+                        Ok(loader) => (
+                            loader.default_meta(),
+                            None,
+                            Some(loader.type_path()),
+                            loader.version(),
+                        ),
                         Err(MissingAssetLoaderForExtensionError { .. }) => {
                             let meta: Box<dyn AssetMetaDyn> =
                                 Box::new(AssetMeta::<(), ()>::new(AssetAction::Ignore));
-                            (meta, None)
+                            (meta, None, None, 0)
                         }
                     }
                 };
                 let meta_bytes = meta.serialize();
-                (meta, meta_bytes, processor)
-            }
-            Err(err) => {
-                return Err(ProcessError::ReadAssetMetaError {
-                    path: asset_path.clone(),
-                    err,
-                })
+                Ok(Some(SourceMeta {
+                    meta,
+                    meta_bytes,
+                    processor,
+                    processed_with: processed_with.map(ToOwned::to_owned),
+                    version,
+                }))
             }
+            Err(err) => Err(ProcessError::ReadAssetMetaError {
+                path: asset_path.clone(),
+                err,
+            }),
+        }
+    }
+
+    async fn process_asset_internal(
+        &self,
+        source: &AssetSource,
+        asset_path: &AssetPath<'static>,
+        reason: &mut Option<ReprocessReason>,
+    ) -> Result<ProcessResult, ProcessError> {
+        // TODO: check if already processing to protect against duplicate hot-reload events
+        debug!("Processing {}", asset_path);
+        let path = asset_path.path();
+        let reader = source.reader();
+
+        let reader_err = |err| ProcessError::AssetReaderError {
+            path: asset_path.clone(),
+            err,
+        };
+        let writer_err = |err| ProcessError::AssetWriterError {
+            path: asset_path.clone(),
+            err,
+        };
+
+        let Some(SourceMeta {
+            meta: mut source_meta,
+            meta_bytes,
+            processor,
+            processed_with,
+            version,
+        }) = self.read_source_meta(source, asset_path).await?
+        else {
+            return Ok(ProcessResult::Ignored);
         };
 
         let processed_writer = source.processed_writer()?;
@@ -1109,7 +1361,7 @@ impl AssetProcessor {
             // as soon as the hash is computed.
             let mut reader_for_hash = reader.read(path).await.map_err(reader_err)?;
 
-            get_asset_hash(&meta_bytes, &mut reader_for_hash)
+            get_asset_hash(&meta_bytes, version, &mut reader_for_hash)
                 .await
                 .map_err(reader_err)?
         };
@@ -1117,29 +1369,26 @@ impl AssetProcessor {
             hash: new_hash,
             full_hash: new_hash,
             process_dependencies: Vec::new(),
+            processed_with: processed_with.clone(),
         };
 
         {
             let infos = self.data.processing_state.asset_infos.read().await;
-            if let Some(current_processed_info) = infos
-                .get(asset_path)
-                .and_then(|i| i.processed_info.as_ref())
-                && current_processed_info.hash == new_hash
-            {
-                let mut dependency_changed = false;
-                for current_dep_info in &current_processed_info.process_dependencies {
-                    let live_hash = infos
-                        .get(&current_dep_info.path)
+            *reason = reprocess_reason(
+                infos
+                    .get(asset_path)
+                    .and_then(|i| i.processed_info.as_ref()),
+                new_hash,
+                processed_with.as_deref(),
+                |path| {
+                    infos
+                        .get(path)
                         .and_then(|i| i.processed_info.as_ref())
-                        .map(|i| i.full_hash);
-                    if live_hash != Some(current_dep_info.full_hash) {
-                        dependency_changed = true;
-                        break;
-                    }
-                }
-                if !dependency_changed {
-                    return Ok(ProcessResult::SkippedNotChanged);
-                }
+                        .map(|i| i.full_hash)
+                },
+            );
+            if reason.is_none() {
+                return Ok(ProcessResult::SkippedNotChanged);
             }
         }
 
@@ -1347,6 +1596,8 @@ impl AssetProcessorData {
             log_factory: Mutex::new(Some(Box::new(FileTransactionLogFactory::default()))),
             log: Default::default(),
             processors: Default::default(),
+            run_reports: Default::default(),
+            collected_results: Default::default(),
         }
     }
 
@@ -1386,6 +1637,56 @@ impl AssetProcessorData {
     pub async fn wait_until_finished(&self) {
         self.processing_state.wait_until_finished().await;
     }
+
+    /// Returns the report of the last finished processing run, if any.
+    pub fn last_run_report(&self) -> Option<ProcessRunReport> {
+        self.run_reports
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .last
+            .clone()
+    }
+
+    /// Starts collecting the report of a new processing run.
+    fn begin_run(&self) {
+        let mut run_reports = self
+            .run_reports
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        run_reports.current = Some((Instant::now(), Vec::new()));
+    }
+
+    /// Adds the report of a processed asset to the current processing run.
+    fn record_asset_report(&self, report: AssetProcessReport) {
+        let mut run_reports = self
+            .run_reports
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some((_, assets)) = &mut run_reports.current {
+            assets.push(report);
+        }
+    }
+
+    /// Finishes the current processing run, making its report the last run report.
+    fn finish_run(&self) {
+        let mut run_reports = self
+            .run_reports
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let (start_time, assets) = run_reports
+            .current
+            .take()
+            .unwrap_or_else(|| (Instant::now(), Vec::new()));
+        debug!(
+            "Processing run handled {} assets in {:?}",
+            assets.len(),
+            start_time.elapsed()
+        );
+        run_reports.last = Some(ProcessRunReport {
+            duration: start_time.elapsed(),
+            assets,
+        });
+    }
 }
 
 impl ProcessingState {
@@ -1499,6 +1800,129 @@ impl ProcessingState {
     }
 }
 
+/// Retrieves asset paths recursively. If `clean_empty_folders_writer` is Some, it will be used to clean up empty
+/// folders when they are discovered.
+async fn get_asset_paths(
+    reader: &dyn ErasedAssetReader,
+    path: PathBuf,
+    paths: &mut Vec<PathBuf>,
+    mut empty_dirs: Option<&mut Vec<PathBuf>>,
+) -> Result<bool, AssetReaderError> {
+    if reader.is_directory(&path).await? {
+        let mut path_stream = reader.read_directory(&path).await?;
+        let mut contains_files = false;
+
+        while let Some(child_path) = path_stream.next().await {
+            contains_files |= Box::pin(get_asset_paths(
+                reader,
+                child_path,
+                paths,
+                empty_dirs.as_deref_mut(),
+            ))
+            .await?;
+        }
+        // Add the current directory after all its subdirectories so we delete any empty
+        // subdirectories before the current directory.
+        if !contains_files
+            && path.parent().is_some()
+            && let Some(empty_dirs) = empty_dirs
+        {
+            empty_dirs.push(path);
+        }
+        Ok(contains_files)
+    } else {
+        paths.push(path);
+        Ok(true)
+    }
+}
+
+/// Returns `true` if `err` means that the asset cannot be processed at all, rather than that
+/// processing it failed.
+fn is_unprocessable_asset_error(err: &ProcessError) -> bool {
+    matches!(
+        err,
+        ProcessError::ExtensionRequired
+            | ProcessError::MissingAssetLoaderForExtension(_)
+            | ProcessError::AssetReaderError {
+                err: AssetReaderError::NotFound(_),
+                ..
+            }
+    )
+}
+
+/// The output of [`AssetProcessor::process_once`].
+#[derive(Debug)]
+pub struct ProcessOnceOutput {
+    /// The result of processing each asset, in the order they finished.
+    pub results: Vec<(AssetPath<'static>, Result<ProcessResult, ProcessError>)>,
+    /// The report of the processing run.
+    pub report: ProcessRunReport,
+}
+
+impl ProcessOnceOutput {
+    /// Returns the assets that failed to process, with their errors.
+    ///
+    /// Assets that cannot be processed at all (for example because no loader supports their
+    /// extension) are not considered failures.
+    pub fn errors(&self) -> impl Iterator<Item = (&AssetPath<'static>, &ProcessError)> {
+        self.results
+            .iter()
+            .filter_map(|(path, result)| match result {
+                Err(err) if !is_unprocessable_asset_error(err) => Some((path, err)),
+                _ => None,
+            })
+    }
+
+    /// Returns `true` if no asset failed to process.
+    pub fn is_success(&self) -> bool {
+        self.errors().next().is_none()
+    }
+}
+
+/// The loaded meta of a source asset. See [`AssetProcessor::read_source_meta`].
+struct SourceMeta {
+    meta: Box<dyn AssetMetaDyn>,
+    meta_bytes: Vec<u8>,
+    processor: Option<Arc<dyn ErasedProcessor>>,
+    /// The type path of the configured loader or processor, if any.
+    processed_with: Option<String>,
+    /// The version of the configured loader or processor, which is part of the asset hash.
+    version: u32,
+}
+
+/// Determines why an asset must be processed given its `current` processed info, the hash of its
+/// source and the loader or processor it is now configured with. `dependency_hash` returns the
+/// latest full hash of a process dependency. Returns [`None`] if the asset doesn't need to be
+/// processed.
+fn reprocess_reason(
+    current: Option<&ProcessedInfo>,
+    new_hash: AssetHash,
+    processed_with: Option<&str>,
+    dependency_hash: impl Fn(&AssetPath<'static>) -> Option<AssetHash>,
+) -> Option<ReprocessReason> {
+    let Some(current) = current else {
+        return Some(ReprocessReason::NotProcessed);
+    };
+    if current.hash != new_hash {
+        return Some(match (current.processed_with.as_deref(), processed_with) {
+            (Some(previous), Some(processed_with)) if previous != processed_with => {
+                ReprocessReason::LoaderChanged {
+                    previous: previous.to_owned(),
+                    current: processed_with.to_owned(),
+                }
+            }
+            _ => ReprocessReason::HashChanged,
+        });
+    }
+    current
+        .process_dependencies
+        .iter()
+        .find(|dependency| dependency_hash(&dependency.path) != Some(dependency.full_hash))
+        .map(|dependency| ReprocessReason::DependencyChanged {
+            dependency: dependency.path.clone(),
+        })
+}
+
 /// The (successful) result of processing an asset
 #[derive(Debug, Clone)]
 pub enum ProcessResult {
@@ -1615,7 +2039,7 @@ impl ProcessorAssetInfos {
     async fn finish_processing(
         &mut self,
         asset_path: AssetPath<'static>,
-        result: Result<ProcessResult, ProcessError>,
+        result: &Result<ProcessResult, ProcessError>,
         reprocess_sender: async_channel::Sender<(AssetSourceId<'static>, PathBuf)>,
     ) {
         match result {
@@ -1635,7 +2059,7 @@ impl ProcessorAssetInfos {
                     self.add_dependent(&process_dependency_info.path, asset_path.to_owned());
                 }
                 let info = self.get_or_insert(asset_path);
-                info.processed_info = Some(processed_info);
+                info.processed_info = Some(processed_info.clone());
                 info.update_status(ProcessStatus::Processed).await;
                 let dependents = info.dependents.iter().cloned().collect::<Vec<_>>();
                 for path in dependents {
@@ -1682,6 +2106,7 @@ impl ProcessorAssetInfos {
                         hash: AssetHash::default(),
                         full_hash: AssetHash::default(),
                         process_dependencies: vec![],
+                        processed_with: None,
                     });
                     self.add_dependent(dependency.path(), asset_path.to_owned());
                 }
@@ -0,0 +1,154 @@
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+use bevy_platform::collections::{HashMap, HashSet};
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+
+use crate::AssetPath;
+
+use super::ProcessStatus;
+
+/// The reason an asset is (or would be) processed by the [`AssetProcessor`](super::AssetProcessor).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReprocessReason {
+    /// The asset has no processed version yet.
+    NotProcessed,
+    /// The asset bytes, its meta or the version of its loader or processor changed since the
+    /// asset was last processed.
+    HashChanged,
+    /// The loader or processor configured for the asset changed since the asset was last
+    /// processed.
+    LoaderChanged {
+        /// The type path of the loader or processor that was last used.
+        previous: String,
+        /// The type path of the loader or processor that is now configured.
+        current: String,
+    },
+    /// A process dependency of the asset changed (or will be reprocessed).
+    DependencyChanged {
+        /// The path of the dependency that changed.
+        dependency: AssetPath<'static>,
+    },
+}
+
+/// An asset that would be processed by [`AssetProcessor::dry_run`](super::AssetProcessor::dry_run).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DryRunEntry {
+    /// The path of the asset.
+    pub path: AssetPath<'static>,
+    /// Why the asset would be processed.
+    pub reason: ReprocessReason,
+}
+
+/// What happened to an asset during a processing run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProcessOutcome {
+    /// The asset was processed.
+    Processed,
+    /// The asset was skipped because it did not change.
+    SkippedNotChanged,
+    /// The asset was ignored (because it is configured to be ignored, or cannot be processed).
+    Ignored,
+    /// Processing the asset failed with the given error.
+    Failed(String),
+}
+
+/// A report of how a single asset was handled in a [`ProcessRunReport`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetProcessReport {
+    /// The path of the asset.
+    pub path: AssetPath<'static>,
+    /// What happened to the asset.
+    pub outcome: ProcessOutcome,
+    /// Why the asset was processed, if it was determined.
+    pub reason: Option<ReprocessReason>,
+    /// How long handling the asset took.
+    pub duration: Duration,
+}
+
+/// A machine-readable report of a processing run.
+///
+/// A run starts when the [`AssetProcessor`](super::AssetProcessor) begins processing after being
+/// idle, and ends when it returns to [`ProcessorState::Finished`](super::ProcessorState::Finished).
+/// An asset may appear several times if it was processed again because one of its dependencies
+/// changed during the run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessRunReport {
+    /// The total duration of the run.
+    pub duration: Duration,
+    /// The assets handled during the run, in the order they finished.
+    pub assets: Vec<AssetProcessReport>,
+}
+
+impl ProcessRunReport {
+    /// Returns the assets that failed to process.
+    pub fn failed(&self) -> impl Iterator<Item = &AssetProcessReport> {
+        self.assets
+            .iter()
+            .filter(|asset| matches!(asset.outcome, ProcessOutcome::Failed(_)))
+    }
+}
+
+/// A node of a [`ProcessDependencyGraph`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessDependencyNode {
+    /// The assets that were used to process this asset.
+    pub dependencies: Vec<AssetPath<'static>>,
+    /// The assets whose processing used this asset.
+    pub dependents: Vec<AssetPath<'static>>,
+    /// The latest processing status of the asset, if it has been processed.
+    pub status: Option<ProcessStatus>,
+}
+
+/// A snapshot of the process dependencies between assets, as tracked by the
+/// [`AssetProcessor`](super::AssetProcessor).
+///
+/// See [`AssetProcessor::dependency_graph`](super::AssetProcessor::dependency_graph).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProcessDependencyGraph {
+    pub(crate) nodes: HashMap<AssetPath<'static>, ProcessDependencyNode>,
+}
+
+impl ProcessDependencyGraph {
+    /// Returns the node for the asset at `path`, if it is known to the processor.
+    pub fn get(&self, path: &AssetPath<'static>) -> Option<&ProcessDependencyNode> {
+        self.nodes.get(path)
+    }
+
+    /// Iterates over all the assets known to the processor.
+    pub fn iter(&self) -> impl Iterator<Item = (&AssetPath<'static>, &ProcessDependencyNode)> {
+        self.nodes.iter()
+    }
+
+    /// Returns the assets that were directly used to process the asset at `path`.
+    pub fn dependencies(&self, path: &AssetPath<'static>) -> &[AssetPath<'static>] {
+        self.nodes
+            .get(path)
+            .map(|node| node.dependencies.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns the assets whose processing directly used the asset at `path`.
+    pub fn dependents(&self, path: &AssetPath<'static>) -> &[AssetPath<'static>] {
+        self.nodes
+            .get(path)
+            .map(|node| node.dependents.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns every asset that depends on the asset at `path`, directly or indirectly, in
+    /// breadth-first order. These are the assets that are reprocessed when `path` changes.
+    pub fn transitive_dependents(&self, path: &AssetPath<'static>) -> Vec<AssetPath<'static>> {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::from([path.clone()]);
+        let mut dependents = Vec::new();
+        while let Some(path) = queue.pop_front() {
+            for dependent in self.dependents(&path) {
+                if visited.insert(dependent.clone()) {
+                    dependents.push(dependent.clone());
+                    queue.push_back(dependent.clone());
+                }
+            }
+        }
+        dependents
+    }
+}
@@ -26,11 +26,13 @@ use crate::{
     io::{
         memory::{Dir, MemoryAssetReader, MemoryAssetWriter},
         AssetReader, AssetReaderError, AssetSourceBuilder, AssetSourceBuilders, AssetSourceEvent,
-        AssetSourceId, AssetWatcher, PathStream, Reader,
+        AssetSourceId, AssetWatcher, PathStream, Reader, VecReader,
     },
+    meta::{get_asset_hash, ProcessedInfoMinimal},
     processor::{
-        AssetProcessor, GetProcessorError, LoadTransformAndSave, LogEntry, Process, ProcessContext,
-        ProcessError, ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory,
+        process_assets_once, AssetProcessor, DryRunEntry, GetProcessorError, LoadTransformAndSave,
+        LogEntry, Process, ProcessContext, ProcessError, ProcessOutcome, ProcessResult,
+        ProcessorState, ProcessorTransactionLog, ProcessorTransactionLogFactory, ReprocessReason,
     },
     saver::{tests::CoolTextSaver, AssetSaver},
     tests::{
@@ -492,6 +494,76 @@ fn no_meta_or_default_processor_copies_asset() {
     assert_eq!(processed_asset, source_asset);
 }
 
+#[test]
+fn loader_version_is_part_of_processed_hash() {
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs:
+            ProcessingDirs {
+                source: source_dir,
+                processed: processed_dir,
+                ..
+            },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    #[derive(TypePath)]
+    struct VersionedCoolTextLoader;
+
+    impl AssetLoader for VersionedCoolTextLoader {
+        type Asset = CoolText;
+        type Settings = ();
+        type Error = <CoolTextLoader as AssetLoader>::Error;
+        const VERSION: u32 = 2;
+
+        async fn load(
+            &self,
+            reader: &mut dyn Reader,
+            settings: &Self::Settings,
+            load_context: &mut LoadContext<'_>,
+        ) -> Result<Self::Asset, Self::Error> {
+            CoolTextLoader.load(reader, settings, load_context).await
+        }
+    }
+
+    app.init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(VersionedCoolTextLoader);
+
+    let guard = source_gate.write_blocking();
+
+    let path = Path::new("abc.cool.ron");
+    let source_asset = serialize_as_cool_text("abc");
+    let source_meta = r#"(
+    meta_format_version: "1.0",
+    asset: Load(
+        loader: "bevy_asset::processor::tests::VersionedCoolTextLoader",
+        settings: (),
+    ),
+)"#;
+    source_dir.insert_asset_text(path, &source_asset);
+    source_dir.insert_meta_text(path, source_meta);
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    let processed_info =
+        ron::de::from_str::<ProcessedInfoMinimal>(&read_meta_as_string(&processed_dir, path))
+            .unwrap()
+            .processed_info
+            .unwrap();
+    let hash_with_version = |version| {
+        bevy_tasks::block_on(get_asset_hash(
+            source_meta.as_bytes(),
+            version,
+            &mut VecReader::new(source_asset.as_bytes().to_vec()),
+        ))
+        .unwrap()
+    };
+    assert_eq!(processed_info.hash, hash_with_version(2));
+    assert_ne!(processed_info.hash, hash_with_version(0));
+}
+
 #[test]
 fn asset_processor_transforms_asset_default_processor() {
     let AppWithProcessor {
@@ -1679,6 +1751,175 @@ fn only_reprocesses_wrong_hash_on_startup() {
     );
 }
 
+#[test]
+fn reports_runs_dependency_graph_and_dry_run() {
+    let AppWithProcessor {
+        mut app,
+        source_gate,
+        default_source_dirs: ProcessingDirs {
+            source: source_dir, ..
+        },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    #[derive(TypePath)]
+    struct ClearEmbedded;
+
+    impl MutateAsset<CoolText> for ClearEmbedded {
+        fn mutate(&self, asset: &mut CoolText) {
+            asset.text.push_str(&asset.embedded);
+            asset.embedded.clear();
+        }
+    }
+
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<ClearEmbedded, CoolText>,
+        CoolTextSaver,
+    >;
+
+    app.init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_processor(CoolTextProcessor::new(
+            RootAssetTransformer::new(ClearEmbedded),
+            CoolTextSaver,
+        ))
+        .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+
+    let guard = source_gate.write_blocking();
+
+    let dependency = Path::new("dependency.cool.ron");
+    let dependent = Path::new("dependent.cool.ron");
+    let unrelated = Path::new("unrelated.cool.ron");
+    let cool_text_ron = CoolTextRon {
+        text: "dependent".into(),
+        dependencies: vec![],
+        embedded_dependencies: vec![dependency.to_string_lossy().into_owned()],
+        sub_texts: vec![],
+    };
+    source_dir.insert_asset_text(dependency, &serialize_as_cool_text("dependency"));
+    source_dir.insert_asset_text(
+        dependent,
+        &ron::ser::to_string_pretty(&cool_text_ron, PrettyConfig::new().new_line("\n")).unwrap(),
+    );
+    source_dir.insert_asset_text(unrelated, &serialize_as_cool_text("unrelated"));
+
+    run_app_until_finished_processing(&mut app, guard);
+
+    let processor = app.world().resource::<AssetProcessor>().clone();
+    let report = processor.data().last_run_report().unwrap();
+    assert_eq!(report.failed().count(), 0);
+    for path in [dependency, dependent, unrelated] {
+        let asset_report = report
+            .assets
+            .iter()
+            .find(|asset| asset.path == AssetPath::from_path(path))
+            .unwrap();
+        assert_eq!(asset_report.outcome, ProcessOutcome::Processed);
+        assert_eq!(asset_report.reason, Some(ReprocessReason::NotProcessed));
+    }
+
+    let graph = bevy_tasks::block_on(processor.dependency_graph());
+    let dependency_path = AssetPath::from_path(dependency).into_owned();
+    let dependent_path = AssetPath::from_path(dependent).into_owned();
+    assert_eq!(
+        graph.dependencies(&dependent_path),
+        core::slice::from_ref(&dependency_path)
+    );
+    assert_eq!(
+        graph.dependents(&dependency_path),
+        core::slice::from_ref(&dependent_path)
+    );
+    assert_eq!(
+        graph.transitive_dependents(&dependency_path),
+        core::slice::from_ref(&dependent_path)
+    );
+
+    // Nothing changed since processing.
+    assert_eq!(bevy_tasks::block_on(processor.dry_run()).unwrap(), []);
+
+    // Change the source without notifying the processor, so nothing gets processed.
+    source_dir.insert_asset_text(dependency, &serialize_as_cool_text("changed"));
+    let mut entries = bevy_tasks::block_on(processor.dry_run()).unwrap();
+    entries.sort_by_key(|entry| entry.path.to_string());
+    assert_eq!(
+        entries,
+        [
+            DryRunEntry {
+                path: dependency_path.clone(),
+                reason: ReprocessReason::HashChanged,
+            },
+            DryRunEntry {
+                path: dependent_path,
+                reason: ReprocessReason::DependencyChanged {
+                    dependency: dependency_path,
+                },
+            },
+        ]
+    );
+}
+
+#[test]
+fn process_assets_once_without_running_app() {
+    let AppWithProcessor {
+        mut app,
+        default_source_dirs:
+            ProcessingDirs {
+                source: source_dir,
+                processed: processed_dir,
+                ..
+            },
+        ..
+    } = create_app_with_asset_processor(&[]);
+
+    type CoolTextProcessor = LoadTransformAndSave<
+        CoolTextLoader,
+        RootAssetTransformer<AddText, CoolText>,
+        CoolTextSaver,
+    >;
+
+    app.init_asset::<CoolText>()
+        .init_asset::<SubText>()
+        .register_asset_loader(CoolTextLoader)
+        .register_asset_processor(CoolTextProcessor::new(
+            RootAssetTransformer::new(AddText(" processed".to_string())),
+            CoolTextSaver,
+        ))
+        .set_default_asset_processor::<CoolTextProcessor>("cool.ron");
+
+    let good = Path::new("good.cool.ron");
+    let bad = Path::new("bad.cool.ron");
+    source_dir.insert_asset_text(good, &serialize_as_cool_text("good"));
+    source_dir.insert_asset_text(bad, &serialize_as_cool_text("bad"));
+    source_dir.insert_meta_text(
+        bad,
+        r#"(
+    meta_format_version: "1.0",
+    asset: Process(
+        processor: "Missing",
+        settings: (),
+    ),
+)"#,
+    );
+
+    let output = process_assets_once(&mut app).unwrap();
+
+    assert_eq!(
+        read_asset_as_string(&processed_dir, good),
+        serialize_as_cool_text("good processed")
+    );
+    assert!(output.results.iter().any(|(path, result)| {
+        *path == AssetPath::from_path(good) && matches!(result, Ok(ProcessResult::Processed(_)))
+    }));
+    let errors = output.errors().collect::<Vec<_>>();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(*errors[0].0, AssetPath::from_path(bad));
+    assert!(matches!(errors[0].1, ProcessError::MissingProcessor(_)));
+    assert!(!output.is_success());
+    assert_eq!(output.report.failed().count(), 1);
+}
+
 #[test]
 fn writes_short_default_meta_for_processor() {
     let AppWithProcessor {
@@ -1,19 +1,22 @@
-use alloc::{borrow::Cow, boxed::Box, format};
+use alloc::{borrow::Cow, boxed::Box, format, vec::Vec};
 use core::any::{Any, TypeId};
 use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize};
 use thiserror::Error;
 use tracing::warn;
 use uuid::Uuid;
 
-use bevy_ecs::world::{unsafe_world_cell::UnsafeWorldCell, World};
+use bevy_ecs::{
+    message::Messages,
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
 use bevy_reflect::{
     serde::{ReflectDeserializerProcessor, ReflectSerializerProcessor},
     FromReflect, FromType, PartialReflect, Reflect, TypeRegistry,
 };
 
 use crate::{
-    Asset, AssetId, AssetPath, AssetServer, Assets, Handle, InvalidGenerationError, LoadContext,
-    UntypedAssetId, UntypedHandle,
+    Asset, AssetEvent, AssetId, AssetPath, AssetServer, Assets, Handle, InvalidGenerationError,
+    LoadContext, UntypedAssetEvent, UntypedAssetId, UntypedHandle,
 };
 
 /// Type data for the [`TypeRegistry`] used to operate on reflected [`Asset`]s.
@@ -39,6 +42,7 @@ pub struct ReflectAsset {
     len: fn(&World) -> usize,
     ids: for<'w> fn(&'w World) -> Box<dyn Iterator<Item = UntypedAssetId> + 'w>,
     remove: fn(&mut World, UntypedAssetId) -> Option<Box<dyn Reflect>>,
+    read_events: fn(&World, &mut usize) -> Vec<UntypedAssetEvent>,
 }
 
 impl ReflectAsset {
@@ -154,6 +158,14 @@ impl ReflectAsset {
     pub fn ids<'w>(&self, world: &'w World) -> impl Iterator<Item = UntypedAssetId> + 'w {
         (self.ids)(world)
     }
+
+    /// Reads the buffered [`AssetEvent`]s of this asset type as [`UntypedAssetEvent`]s.
+    ///
+    /// Only the events whose message count is at least `next_event` are returned, and `next_event`
+    /// is advanced past them, so it can be used like a [`MessageCursor`](bevy_ecs::message::MessageCursor).
+    pub fn read_events(&self, world: &World, next_event: &mut usize) -> Vec<UntypedAssetEvent> {
+        (self.read_events)(world, next_event)
+    }
 }
 
 impl<A: Asset + FromReflect> FromType<A> for ReflectAsset {
@@ -199,6 +211,19 @@ impl<A: Asset + FromReflect> FromType<A> for ReflectAsset {
                 let value = assets.remove(asset_id.typed_debug_checked());
                 value.map(|value| Box::new(value) as Box<dyn Reflect>)
             },
+            read_events: |world, next_event| {
+                let Some(messages) = world.get_resource::<Messages<AssetEvent<A>>>() else {
+                    return Vec::new();
+                };
+                let mut count = (*next_event).max(messages.oldest_message_count());
+                let mut events = Vec::new();
+                while let Some((event, _)) = messages.get_message(count) {
+                    events.push(event.untyped());
+                    count += 1;
+                }
+                *next_event = count;
+                events
+            },
         }
     }
 }
@@ -524,7 +549,7 @@ mod tests {
         tests::{create_app, run_app_until, CoolText, CoolTextLoader, CoolTextRon, SubText},
         Asset, AssetApp, AssetServer, Assets, DirectAssetAccessExt, EphemeralHandleBehavior,
         Handle, HandleDeserializeProcessor, HandleSerializeProcessor, LoadedUntypedAsset,
-        ReflectAsset, UntypedHandle,
+        ReflectAsset, UntypedAssetEvent, UntypedHandle,
     };
     use bevy_ecs::reflect::AppTypeRegistry;
     use bevy_reflect::{
@@ -582,6 +607,49 @@ mod tests {
         assert_eq!(reflect_asset.len(app.world()), 0);
     }
 
+    #[test]
+    fn test_reflect_asset_read_events() {
+        let mut app = create_app().0;
+        app.init_asset::<AssetType>()
+            .register_asset_reflect::<AssetType>();
+
+        let reflect_asset = {
+            let type_registry = app.world().resource::<AppTypeRegistry>();
+            let type_registry = type_registry.read();
+
+            type_registry
+                .get_type_data::<ReflectAsset>(TypeId::of::<AssetType>())
+                .unwrap()
+                .clone()
+        };
+
+        let mut next_event = 0;
+        assert!(reflect_asset
+            .read_events(app.world(), &mut next_event)
+            .is_empty());
+
+        let value = AssetType {
+            field: "test".into(),
+        };
+        let handle = reflect_asset.add(app.world_mut(), &value);
+        app.update();
+
+        let events = reflect_asset.read_events(app.world(), &mut next_event);
+        assert!(events.contains(&UntypedAssetEvent::Added { id: handle.id() }));
+        assert!(reflect_asset
+            .read_events(app.world(), &mut next_event)
+            .is_empty());
+
+        reflect_asset.get_mut(app.world_mut(), &handle).unwrap();
+        app.update();
+
+        let events = reflect_asset.read_events(app.world(), &mut next_event);
+        assert_eq!(
+            events,
+            vec![UntypedAssetEvent::Modified { id: handle.id() }]
+        );
+    }
+
     fn serialize_as_cool_text(text: &str) -> String {
         let cool_text_ron = CoolTextRon {
             text: text.into(),
@@ -1,34 +1,140 @@
 use crate::{
-    AudioPlayer, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode, PlaybackSettings,
-    SpatialAudioSink, SpatialListener,
+    AudioBusSink, AudioPlayer, Decodable, DefaultSpatialScale, GlobalVolume, PlaybackMode,
+    PlaybackSettings, RouteToBus, SpatialAudioSink, SpatialListener,
 };
 use bevy_asset::{Asset, Assets};
 use bevy_ecs::{prelude::*, system::SystemParam};
 use bevy_math::Vec3;
 use bevy_transform::prelude::GlobalTransform;
-use rodio::{DeviceSinkBuilder, MixerDeviceSink, Player, Source, SpatialPlayer};
+use rodio::{
+    mixer::{Mixer, MixerSource},
+    ChannelCount, DeviceSinkBuilder, MixerDeviceSink, Player, SampleRate, Source, SpatialPlayer,
+};
+use std::sync::Mutex;
 use tracing::warn;
 
 use crate::{AudioSink, AudioSinkPlayback};
 
+/// Where the [`AudioPlugin`](crate::AudioPlugin) plays audio.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum AudioOutputMode {
+    /// Play audio on the default audio device.
+    #[default]
+    Device,
+    /// Don't open an audio device, and mix all audio into the [`OfflineAudioOutput`] resource
+    /// instead, where it can be rendered into a buffer.
+    ///
+    /// This is useful for testing audio, or for recording it.
+    Offline {
+        /// The number of channels of the output.
+        channels: ChannelCount,
+        /// The sample rate of the output.
+        sample_rate: SampleRate,
+    },
+}
+
 /// Used internally to play audio on the current "audio device"
 #[derive(Resource)]
 pub(crate) struct AudioOutput {
-    stream: Option<MixerDeviceSink>,
+    /// The audio device, which stops playing when dropped.
+    _stream: Option<MixerDeviceSink>,
+    /// The mixer that all audio is played on, if audio output is available.
+    mixer: Option<Mixer>,
+    channels: ChannelCount,
+    sample_rate: SampleRate,
 }
 
-impl Default for AudioOutput {
-    fn default() -> Self {
-        let stream = DeviceSinkBuilder::open_default_sink()
-            .inspect_err(|_err| {
-                warn!("No audio device found.");
-            })
-            .map(|mut s| {
-                s.log_on_drop(false);
-                s
-            })
-            .ok();
-        Self { stream }
+impl AudioOutput {
+    /// Opens the audio output, along with the [`OfflineAudioOutput`] in
+    /// [`AudioOutputMode::Offline`].
+    pub(crate) fn new(mode: AudioOutputMode) -> (Self, Option<OfflineAudioOutput>) {
+        match mode {
+            AudioOutputMode::Device => {
+                let stream = DeviceSinkBuilder::open_default_sink()
+                    .inspect_err(|_err| {
+                        warn!("No audio device found.");
+                    })
+                    .map(|mut s| {
+                        s.log_on_drop(false);
+                        s
+                    })
+                    .ok();
+                let (channels, sample_rate) =
+                    stream
+                        .as_ref()
+                        .map_or((ChannelCount::MIN, SampleRate::MIN), |stream| {
+                            (
+                                stream.config().channel_count(),
+                                stream.config().sample_rate(),
+                            )
+                        });
+                let mixer = stream.as_ref().map(|stream| stream.mixer().clone());
+                let output = Self {
+                    _stream: stream,
+                    mixer,
+                    channels,
+                    sample_rate,
+                };
+                (output, None)
+            }
+            AudioOutputMode::Offline {
+                channels,
+                sample_rate,
+            } => {
+                let (mixer, source) = rodio::mixer::mixer(channels, sample_rate);
+                let output = Self {
+                    _stream: None,
+                    mixer: Some(mixer),
+                    channels,
+                    sample_rate,
+                };
+                let offline = OfflineAudioOutput {
+                    source: Mutex::new(source),
+                };
+                (output, Some(offline))
+            }
+        }
+    }
+
+    pub(crate) fn mixer(&self) -> Option<&Mixer> {
+        self.mixer.as_ref()
+    }
+
+    pub(crate) fn channels(&self) -> ChannelCount {
+        self.channels
+    }
+
+    pub(crate) fn sample_rate(&self) -> SampleRate {
+        self.sample_rate
+    }
+}
+
+/// The mixed output of all audio in [`AudioOutputMode::Offline`].
+///
+/// Audio only advances when it is rendered, so rendering one frame's worth of audio per frame
+/// keeps it in sync with the app.
+#[derive(Resource)]
+pub struct OfflineAudioOutput {
+    source: Mutex<MixerSource>,
+}
+
+impl OfflineAudioOutput {
+    /// Renders the next `frames` frames of audio, as interleaved samples.
+    pub fn render(&self, frames: usize) -> Vec<f32> {
+        let mut source = self.source.lock().unwrap();
+        let samples = frames * source.channels().get() as usize;
+        // The mixer ends while nothing is playing, but picks up new audio afterwards.
+        (0..samples).map(|_| source.next().unwrap_or(0.0)).collect()
+    }
+
+    /// The number of channels of the rendered audio.
+    pub fn channels(&self) -> ChannelCount {
+        self.source.lock().unwrap().channels()
+    }
+
+    /// The sample rate of the rendered audio.
+    pub fn sample_rate(&self) -> SampleRate {
+        self.source.lock().unwrap().sample_rate()
     }
 }
 
@@ -91,22 +197,31 @@ pub(crate) fn play_queued_audio_system<Source: Asset + Decodable>(
             &AudioPlayer<Source>,
             &PlaybackSettings,
             Option<&GlobalTransform>,
+            Option<&RouteToBus>,
         ),
         (Without<AudioSink>, Without<SpatialAudioSink>),
     >,
+    buses: Query<&AudioBusSink>,
     ear_positions: EarPositions,
     default_spatial_scale: Res<DefaultSpatialScale>,
     mut commands: Commands,
 ) where
     f32: rodio::cpal::FromSample<rodio::Sample>,
 {
-    let Some(stream) = audio_output.stream.as_ref() else {
+    let Some(output_mixer) = audio_output.mixer() else {
         // audio output unavailable; cannot play sound
         return;
     };
-    let mixer = stream.mixer();
 
-    for (entity, source_handle, settings, maybe_emitter_transform) in &query_nonplaying {
+    for (entity, source_handle, settings, maybe_emitter_transform, route) in &query_nonplaying {
+        let mixer = match route {
+            Some(route) => match buses.get(route.0) {
+                Ok(bus) => bus.mixer(),
+                // the bus hasn't started yet
+                Err(_) => continue,
+            },
+            None => output_mixer,
+        };
         let Some(audio_source) = audio_sources.get(&source_handle.0) else {
             continue;
         };
@@ -334,7 +449,7 @@ pub(crate) fn cleanup_finished_audio<T: Decodable + Asset>(
 
 /// Run Condition to only play audio if the audio output is available
 pub(crate) fn audio_output_available(audio_output: Res<AudioOutput>) -> bool {
-    audio_output.stream.is_some()
+    audio_output.mixer.is_some()
 }
 
 /// Updates spatial audio sinks when emitter positions change.
@@ -0,0 +1,715 @@
+use crate::{
+    audio_output::AudioOutput,
+    effects::{smoothing_coefficient, EffectChain, EffectChainUpdate},
+    AudioEffect, Volume,
+};
+use alloc::sync::Arc;
+use bevy_ecs::prelude::*;
+use bevy_reflect::prelude::*;
+use core::{
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    time::Duration,
+};
+use rodio::{
+    mixer::{Mixer, MixerSource},
+    source::SeekError,
+    ChannelCount, SampleRate, Source,
+};
+use std::sync::Mutex;
+
+/// A mixer bus, which mixes the audio routed into it and applies a volume and a chain of effects
+/// to the result.
+///
+/// Route an [`AudioPlayer`](crate::AudioPlayer) into a bus by adding a [`RouteToBus`] component
+/// to it. Buses can be routed into other buses the same way, for example to put music, sound
+/// effects, voice and UI buses under a master bus. Audio that isn't routed into a bus plays
+/// directly on the audio output.
+///
+/// When Bevy starts the bus, an [`AudioBusSink`] component is added to the entity. Unlike
+/// [`PlaybackSettings`](crate::PlaybackSettings), changes to this component are applied while
+/// the bus is playing. A bus which is routed into another bus restarts when that bus stops,
+/// either once that bus starts again or on the audio output if it was despawned.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_audio::{AudioBus, AudioEffect, RouteToBus, SidechainDucking, Volume};
+/// fn setup_buses(mut commands: Commands) {
+///     let master = commands.spawn(AudioBus::default()).id();
+///     let voice = commands.spawn((AudioBus::default(), RouteToBus(master))).id();
+///     commands.spawn((
+///         AudioBus::default()
+///             .with_volume(Volume::Decibels(-6.0))
+///             .with_effect(AudioEffect::low_pass(4000.0)),
+///         // Lower the music while someone is talking.
+///         SidechainDucking::new(voice),
+///         RouteToBus(master),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Clone, Default, Debug)]
+pub struct AudioBus {
+    /// The volume applied to the output of the bus, after the effects.
+    pub volume: Volume,
+    /// Whether the bus is muted.
+    pub muted: bool,
+    /// Whether the bus is soloed.
+    ///
+    /// While any bus is soloed, only soloed buses and the buses they are routed into or from
+    /// can be heard. Audio which isn't routed into a bus is unaffected.
+    pub solo: bool,
+    /// The effects applied to the mixed audio, in order.
+    pub effects: Vec<AudioEffect>,
+}
+
+impl Default for AudioBus {
+    fn default() -> Self {
+        Self {
+            volume: Volume::Linear(1.0),
+            muted: false,
+            solo: false,
+            effects: Vec::new(),
+        }
+    }
+}
+
+impl AudioBus {
+    /// Helper to set the volume of the bus.
+    pub fn with_volume(mut self, volume: Volume) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Helper to add an effect at the end of the effect chain.
+    pub fn with_effect(mut self, effect: AudioEffect) -> Self {
+        self.effects.push(effect);
+        self
+    }
+
+    /// Helper to start muted.
+    pub fn muted(mut self) -> Self {
+        self.muted = true;
+        self
+    }
+}
+
+/// Routes the output of an [`AudioPlayer`](crate::AudioPlayer) or an [`AudioBus`] into the
+/// [`AudioBus`] on the given entity.
+///
+/// The route is read when playback starts: changing it afterwards has no effect on audio
+/// which is already playing. An [`AudioPlayer`](crate::AudioPlayer) doesn't start playing until
+/// its bus has started, so routing into an entity without an [`AudioBus`] keeps it silent.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[relationship(relationship_target = AudioBusInputs)]
+#[reflect(Component, Clone, Debug, PartialEq)]
+pub struct RouteToBus(pub Entity);
+
+/// The entities routed into an [`AudioBus`] with a [`RouteToBus`] component.
+#[derive(Component, Debug, Default)]
+#[relationship_target(relationship = RouteToBus)]
+pub struct AudioBusInputs(Vec<Entity>);
+
+/// Lowers the volume of an [`AudioBus`] while another bus is playing, for example to lower the
+/// music while a character is talking.
+///
+/// Insert this component on the bus which should be lowered.
+#[derive(Component, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component, Clone, Debug, PartialEq)]
+pub struct SidechainDucking {
+    /// The bus whose output triggers the ducking.
+    pub trigger: Entity,
+    /// The level of the trigger bus above which the ducking starts.
+    pub threshold: Volume,
+    /// The volume applied to the ducked bus while the trigger bus is above the threshold.
+    pub reduction: Volume,
+    /// How quickly the volume is lowered once the trigger starts.
+    pub attack: Duration,
+    /// How quickly the volume recovers once the trigger stops.
+    pub release: Duration,
+}
+
+impl SidechainDucking {
+    /// Creates a [`SidechainDucking`] that lowers the volume by 12 dB while `trigger` is above
+    /// -40 dB, with a 50 ms attack and a 500 ms release.
+    pub const fn new(trigger: Entity) -> Self {
+        Self {
+            trigger,
+            threshold: Volume::Decibels(-40.0),
+            reduction: Volume::Decibels(-12.0),
+            attack: Duration::from_millis(50),
+            release: Duration::from_millis(500),
+        }
+    }
+
+    /// Helper to set the volume applied while ducking.
+    pub const fn with_reduction(mut self, reduction: Volume) -> Self {
+        self.reduction = reduction;
+        self
+    }
+}
+
+/// Used to control a playing [`AudioBus`].
+///
+/// This component is inserted by Bevy when the bus starts, and stops the bus when it is
+/// removed. Audio which is routed into a stopped bus can no longer be heard.
+#[derive(Component)]
+pub struct AudioBusSink {
+    mixer: Mixer,
+    controls: Arc<BusControls>,
+    /// The effects last sent to the audio thread.
+    effects: Vec<AudioEffect>,
+    /// The ducking last sent to the audio thread.
+    ducking: Option<SidechainDucking>,
+    /// The controls of the bus this bus is routed into, if any.
+    parent: Option<Arc<BusControls>>,
+    channels: usize,
+    sample_rate: f32,
+}
+
+impl AudioBusSink {
+    /// Plays a [`Source`] on this bus, in addition to the audio routed into it.
+    pub fn add<S: Source + Send + 'static>(&self, source: S) {
+        self.mixer.add(source);
+    }
+
+    /// Returns the peak level of the output of the bus, which decays over a few hundred
+    /// milliseconds. This is the level which triggers [`SidechainDucking`].
+    pub fn level(&self) -> Volume {
+        Volume::Linear(self.controls.level.load())
+    }
+
+    /// Returns the volume currently applied by [`SidechainDucking`].
+    pub fn ducking_volume(&self) -> Volume {
+        Volume::Linear(self.controls.ducking.load())
+    }
+
+    pub(crate) fn mixer(&self) -> &Mixer {
+        &self.mixer
+    }
+}
+
+impl Drop for AudioBusSink {
+    fn drop(&mut self) {
+        self.controls.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// An `f32` that can be shared with the audio thread.
+struct AtomicF32(AtomicU32);
+
+impl AtomicF32 {
+    fn new(value: f32) -> Self {
+        Self(AtomicU32::new(value.to_bits()))
+    }
+
+    fn load(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    fn store(&self, value: f32) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// The state shared between an [`AudioBusSink`] and the [`BusSource`] on the audio thread.
+struct BusControls {
+    /// The volume of the bus, including mute and solo.
+    gain: AtomicF32,
+    /// The peak level of the output, read by the buses which duck to this one.
+    level: Arc<AtomicF32>,
+    /// The volume currently applied by the ducking.
+    ducking: AtomicF32,
+    stopped: AtomicBool,
+    /// A new configuration for the audio thread to pick up.
+    pending: Mutex<Option<BusConfig>>,
+}
+
+struct BusConfig {
+    effects: EffectChainUpdate,
+    ducking: Option<DuckingConfig>,
+}
+
+struct DuckingConfig {
+    trigger_level: Arc<AtomicF32>,
+    threshold: f32,
+    reduction: f32,
+    attack: Duration,
+    release: Duration,
+}
+
+/// How many frames the audio thread processes between checks for a new configuration.
+const CONFIG_INTERVAL: usize = 256;
+
+/// How long the level of a bus takes to decay.
+const LEVEL_RELEASE: Duration = Duration::from_millis(300);
+
+/// The [`Source`] which plays the output of a bus.
+struct BusSource {
+    input: MixerSource,
+    controls: Arc<BusControls>,
+    effects: EffectChain,
+    ducker: Option<Ducker>,
+    /// The frame being played, with one sample per channel.
+    frame: Vec<f32>,
+    position: usize,
+    frames_until_config: usize,
+    level: f32,
+    level_release: f32,
+}
+
+struct Ducker {
+    trigger_level: Arc<AtomicF32>,
+    threshold: f32,
+    reduction: f32,
+    attack: f32,
+    release: f32,
+    gain: f32,
+}
+
+impl Ducker {
+    fn process(&mut self) -> f32 {
+        let target = if self.trigger_level.load() > self.threshold {
+            self.reduction
+        } else {
+            1.0
+        };
+        let coefficient = if target < self.gain {
+            self.attack
+        } else {
+            self.release
+        };
+        self.gain = target + coefficient * (self.gain - target);
+        self.gain
+    }
+}
+
+impl BusSource {
+    fn new(input: MixerSource, controls: Arc<BusControls>) -> Self {
+        let channels = input.channels().get() as usize;
+        let sample_rate = input.sample_rate().get() as f32;
+        Self {
+            input,
+            controls,
+            effects: EffectChain::new(sample_rate),
+            ducker: None,
+            frame: vec![0.0; channels],
+            position: channels,
+            frames_until_config: 0,
+            level: 0.0,
+            level_release: smoothing_coefficient(LEVEL_RELEASE, sample_rate),
+        }
+    }
+
+    fn configure(&mut self, config: BusConfig) {
+        let sample_rate = self.input.sample_rate().get() as f32;
+        self.effects.configure(config.effects);
+        let gain = self.ducker.as_ref().map_or(1.0, |ducker| ducker.gain);
+        self.ducker = config.ducking.map(|ducking| Ducker {
+            trigger_level: ducking.trigger_level,
+            threshold: ducking.threshold,
+            reduction: ducking.reduction,
+            attack: smoothing_coefficient(ducking.attack, sample_rate),
+            release: smoothing_coefficient(ducking.release, sample_rate),
+            gain,
+        });
+    }
+
+    fn process_frame(&mut self) {
+        if self.frames_until_config == 0 {
+            self.frames_until_config = CONFIG_INTERVAL;
+            // Never block the audio thread, the configuration is picked up next time instead.
+            let config = self
+                .controls
+                .pending
+                .try_lock()
+                .ok()
+                .and_then(|mut pending| pending.take());
+            if let Some(config) = config {
+                self.configure(config);
+            }
+        }
+        self.frames_until_config -= 1;
+
+        for sample in &mut self.frame {
+            // An empty mixer ends, but the bus keeps playing silence until it is stopped.
+            *sample = self.input.next().unwrap_or(0.0);
+        }
+        self.effects.process(&mut self.frame);
+
+        let mut gain = self.controls.gain.load();
+        if let Some(ducker) = &mut self.ducker {
+            let ducking = ducker.process();
+            self.controls.ducking.store(ducking);
+            gain *= ducking;
+        }
+        let mut peak = 0.0f32;
+        for sample in &mut self.frame {
+            *sample *= gain;
+            peak = peak.max(sample.abs());
+        }
+        self.level = peak.max(self.level * self.level_release);
+        self.controls.level.store(self.level);
+    }
+}
+
+impl Iterator for BusSource {
+    type Item = rodio::Sample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position == self.frame.len() {
+            // Only stop at the end of a frame, to keep the channels of the output aligned.
+            if self.controls.stopped.load(Ordering::Relaxed) {
+                return None;
+            }
+            self.process_frame();
+            self.position = 0;
+        }
+        let sample = self.frame[self.position];
+        self.position += 1;
+        Some(sample)
+    }
+}
+
+impl Source for BusSource {
+    fn current_span_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> ChannelCount {
+        self.input.channels()
+    }
+
+    fn sample_rate(&self) -> SampleRate {
+        self.input.sample_rate()
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        None
+    }
+
+    fn try_seek(&mut self, _: Duration) -> Result<(), SeekError> {
+        Err(SeekError::NotSupported {
+            underlying_source: core::any::type_name::<Self>(),
+        })
+    }
+}
+
+/// Starts the [`AudioBus`]es which don't have an [`AudioBusSink`] yet.
+///
+/// A bus which is routed into another bus waits for that bus to start first. Buses whose parent
+/// bus stopped are stopped as well, so they restart.
+pub(crate) fn start_audio_buses(
+    audio_output: Res<AudioOutput>,
+    buses: Query<(Entity, Option<&RouteToBus>), (With<AudioBus>, Without<AudioBusSink>)>,
+    sinks: Query<(Entity, &AudioBusSink)>,
+    mut commands: Commands,
+) {
+    let Some(output) = audio_output.mixer() else {
+        return;
+    };
+
+    for (entity, sink) in &sinks {
+        if sink
+            .parent
+            .as_ref()
+            .is_some_and(|parent| parent.stopped.load(Ordering::Relaxed))
+        {
+            commands.entity(entity).remove::<AudioBusSink>();
+        }
+    }
+
+    for (entity, route) in &buses {
+        let (mixer, parent) = match route {
+            Some(route) => match sinks.get(route.0) {
+                Ok((_, sink)) => (sink.mixer(), Some(sink.controls.clone())),
+                Err(_) => continue,
+            },
+            None => (output, None),
+        };
+
+        let (bus_mixer, source) =
+            rodio::mixer::mixer(audio_output.channels(), audio_output.sample_rate());
+        let controls = Arc::new(BusControls {
+            gain: AtomicF32::new(0.0),
+            level: Arc::new(AtomicF32::new(0.0)),
+            ducking: AtomicF32::new(1.0),
+            stopped: AtomicBool::new(false),
+            pending: Mutex::new(None),
+        });
+        mixer.add(BusSource::new(source, controls.clone()));
+
+        commands.entity(entity).insert(AudioBusSink {
+            mixer: bus_mixer,
+            controls,
+            effects: Vec::new(),
+            ducking: None,
+            parent,
+            channels: audio_output.channels().get() as usize,
+            sample_rate: audio_output.sample_rate().get() as f32,
+        });
+    }
+}
+
+/// Sends the changes to [`AudioBus`]es and their [`SidechainDucking`] to the audio thread.
+pub(crate) fn update_audio_buses(
+    buses: Query<(
+        Entity,
+        &AudioBus,
+        Option<&RouteToBus>,
+        Option<&SidechainDucking>,
+    )>,
+    mut sinks: Query<&mut AudioBusSink>,
+) {
+    // Soloing a bus keeps the buses it is routed into, and the buses routed into it, audible.
+    let route = |entity: Entity| {
+        buses
+            .get(entity)
+            .ok()
+            .and_then(|(_, _, route, _)| route.map(|route| route.0))
+    };
+    let mut soloed = Vec::new();
+    for (entity, bus, ..) in &buses {
+        if bus.solo {
+            let mut current = Some(entity);
+            while let Some(entity) = current {
+                soloed.push(entity);
+                current = route(entity);
+            }
+        }
+    }
+    let audible = |entity: Entity| {
+        let mut current = Some(entity);
+        while let Some(entity) = current {
+            if buses.get(entity).is_ok_and(|(_, bus, ..)| bus.solo) {
+                return true;
+            }
+            current = route(entity);
+        }
+        soloed.contains(&entity)
+    };
+
+    for (entity, bus, _, ducking) in &buses {
+        let trigger_level = ducking.and_then(|ducking| {
+            sinks
+                .get(ducking.trigger)
+                .ok()
+                .map(|trigger| trigger.controls.level.clone())
+        });
+        let Ok(mut sink) = sinks.get_mut(entity) else {
+            continue;
+        };
+
+        let gain = if bus.muted || (!soloed.is_empty() && !audible(entity)) {
+            0.0
+        } else {
+            bus.volume.to_linear()
+        };
+        sink.controls.gain.store(gain);
+
+        // Wait for the trigger bus to start before sending the ducking.
+        let ducking = ducking.filter(|_| trigger_level.is_some());
+        if sink.effects == bus.effects && sink.ducking.as_ref() == ducking {
+            continue;
+        }
+        let mut pending = sink.controls.pending.lock().unwrap();
+        let effects = EffectChainUpdate::new(
+            &bus.effects,
+            &sink.effects,
+            pending.take().map(|config| config.effects),
+            sink.channels,
+            sink.sample_rate,
+        );
+        let config = BusConfig {
+            effects,
+            ducking: ducking
+                .zip(trigger_level)
+                .map(|(ducking, trigger_level)| DuckingConfig {
+                    trigger_level,
+                    threshold: ducking.threshold.to_linear(),
+                    reduction: ducking.reduction.to_linear(),
+                    attack: ducking.attack,
+                    release: ducking.release,
+                }),
+        };
+        *pending = Some(config);
+        drop(pending);
+        sink.effects = bus.effects.clone();
+        sink.ducking = ducking.cloned();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{audio_output::*, AudioPlayer, AudioSink, GlobalVolume, Pitch};
+    use bevy_asset::Assets;
+    use bevy_ecs::system::RunSystemOnce;
+    use rodio::buffer::SamplesBuffer;
+
+    const CHANNELS: ChannelCount = ChannelCount::new(1).unwrap();
+    const SAMPLE_RATE: SampleRate = SampleRate::new(48000).unwrap();
+
+    fn world() -> World {
+        let mut world = World::new();
+        let (output, offline) = AudioOutput::new(AudioOutputMode::Offline {
+            channels: CHANNELS,
+            sample_rate: SAMPLE_RATE,
+        });
+        world.insert_resource(output);
+        world.insert_resource(offline.unwrap());
+        world
+    }
+
+    fn update(world: &mut World) {
+        world.run_system_once(start_audio_buses).unwrap();
+        world.run_system_once(update_audio_buses).unwrap();
+    }
+
+    /// A source which plays `value` for a second.
+    fn constant(value: f32) -> SamplesBuffer {
+        SamplesBuffer::new(CHANNELS, SAMPLE_RATE, vec![value; 48000])
+    }
+
+    /// Renders a few frames and returns the last sample.
+    fn render(world: &World) -> f32 {
+        let samples = world.resource::<OfflineAudioOutput>().render(64);
+        *samples.last().unwrap()
+    }
+
+    fn play(world: &World, bus: Entity, value: f32) {
+        world.get::<AudioBusSink>(bus).unwrap().add(constant(value));
+    }
+
+    #[test]
+    fn buses_apply_volume_mute_and_solo() {
+        let mut world = world();
+        let master = world
+            .spawn(AudioBus::default().with_volume(Volume::Linear(0.5)))
+            .id();
+        let music = world.spawn((AudioBus::default(), RouteToBus(master))).id();
+        let sfx = world.spawn(AudioBus::default()).id();
+        // The music bus starts once the master bus has started.
+        update(&mut world);
+        assert!(world.get::<AudioBusSink>(music).is_none());
+        update(&mut world);
+
+        play(&world, music, 1.0);
+        play(&world, sfx, 0.25);
+        assert_eq!(render(&world), 0.75);
+
+        world.get_mut::<AudioBus>(sfx).unwrap().muted = true;
+        update(&mut world);
+        assert_eq!(render(&world), 0.5);
+
+        // Soloing the music keeps the master bus audible.
+        world.get_mut::<AudioBus>(sfx).unwrap().muted = false;
+        world.get_mut::<AudioBus>(music).unwrap().solo = true;
+        update(&mut world);
+        assert_eq!(render(&world), 0.5);
+
+        world.get_mut::<AudioBus>(music).unwrap().solo = false;
+        world.get_mut::<AudioBus>(sfx).unwrap().solo = true;
+        update(&mut world);
+        assert_eq!(render(&world), 0.25);
+
+        // Despawning a bus stops it.
+        world.despawn(sfx);
+        update(&mut world);
+        assert_eq!(render(&world), 0.5);
+    }
+
+    #[test]
+    fn buses_restart_when_their_parent_stops() {
+        let mut world = world();
+        let master = world
+            .spawn(AudioBus::default().with_volume(Volume::Linear(0.5)))
+            .id();
+        let music = world.spawn((AudioBus::default(), RouteToBus(master))).id();
+        update(&mut world);
+        update(&mut world);
+        play(&world, music, 1.0);
+        assert_eq!(render(&world), 0.5);
+
+        // The music restarts once the master bus restarts.
+        world.entity_mut(master).remove::<AudioBusSink>();
+        update(&mut world);
+        assert!(world.get::<AudioBusSink>(music).is_none());
+        update(&mut world);
+        play(&world, music, 1.0);
+        assert_eq!(render(&world), 0.5);
+
+        // Without the master bus, the music restarts on the audio output.
+        world.despawn(master);
+        update(&mut world);
+        update(&mut world);
+        play(&world, music, 1.0);
+        assert_eq!(render(&world), 1.0);
+    }
+
+    #[test]
+    fn voice_ducks_music() {
+        let mut world = world();
+        let voice = world.spawn(AudioBus::default()).id();
+        let music = world
+            .spawn((
+                AudioBus::default(),
+                SidechainDucking {
+                    attack: Duration::ZERO,
+                    release: Duration::ZERO,
+                    ..SidechainDucking::new(voice).with_reduction(Volume::Linear(0.1))
+                },
+            ))
+            .id();
+        update(&mut world);
+        update(&mut world);
+
+        play(&world, music, 1.0);
+        assert_eq!(render(&world), 1.0);
+
+        play(&world, voice, 0.5);
+        assert!((render(&world) - 0.6).abs() < 1e-6);
+        let sink = world.get::<AudioBusSink>(music).unwrap();
+        assert_eq!(sink.ducking_volume(), Volume::Linear(0.1));
+        assert_eq!(
+            world.get::<AudioBusSink>(voice).unwrap().level(),
+            Volume::Linear(0.5)
+        );
+    }
+
+    #[test]
+    fn players_are_routed_into_buses() {
+        let mut world = world();
+        world.init_resource::<GlobalVolume>();
+        world.init_resource::<crate::DefaultSpatialScale>();
+        world.init_resource::<Assets<Pitch>>();
+        let handle = world
+            .resource_mut::<Assets<Pitch>>()
+            .add(Pitch::new(440.0, Duration::from_secs(1)));
+
+        let bus = world.spawn(AudioBus::default().muted()).id();
+        let player = world.spawn((AudioPlayer(handle), RouteToBus(bus))).id();
+
+        // The player waits for its bus to start.
+        world
+            .run_system_once(play_queued_audio_system::<Pitch>)
+            .unwrap();
+        assert!(world.get::<AudioSink>(player).is_none());
+
+        update(&mut world);
+        world
+            .run_system_once(play_queued_audio_system::<Pitch>)
+            .unwrap();
+        assert!(world.get::<AudioSink>(player).is_some());
+        let samples = world.resource::<OfflineAudioOutput>().render(4800);
+        assert!(samples.iter().all(|sample| *sample == 0.0));
+
+        world.get_mut::<AudioBus>(bus).unwrap().muted = false;
+        update(&mut world);
+        let samples = world.resource::<OfflineAudioOutput>().render(4800);
+        assert!(samples.iter().any(|sample| *sample != 0.0));
+        assert_eq!(
+            world.get::<AudioBusInputs>(bus).unwrap().collection(),
+            &vec![player]
+        );
+    }
+}
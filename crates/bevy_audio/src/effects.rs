@@ -0,0 +1,543 @@
+use crate::Volume;
+use bevy_math::ops;
+use bevy_reflect::prelude::*;
+use core::{f32::consts::PI, time::Duration};
+
+/// An effect applied to the output of an [`AudioBus`](crate::AudioBus).
+///
+/// Effects are applied in the order they appear in [`AudioBus::effects`](crate::AudioBus::effects).
+/// Changing the parameters of an effect keeps its internal state, so filters and reverb tails
+/// don't click when they are adjusted during playback.
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Clone, Debug, PartialEq)]
+pub enum AudioEffect {
+    /// A second-order filter that removes frequencies above the cutoff frequency.
+    LowPass {
+        /// The cutoff frequency in hertz.
+        cutoff_frequency: f32,
+        /// The resonance of the filter. `0.707` gives a flat response.
+        q: f32,
+    },
+    /// A second-order filter that removes frequencies below the cutoff frequency.
+    HighPass {
+        /// The cutoff frequency in hertz.
+        cutoff_frequency: f32,
+        /// The resonance of the filter. `0.707` gives a flat response.
+        q: f32,
+    },
+    /// A reverb based on the Freeverb algorithm.
+    Reverb {
+        /// The size of the simulated room, between `0.0` and `1.0`. Larger rooms have longer tails.
+        room_size: f32,
+        /// How quickly high frequencies die out, between `0.0` and `1.0`.
+        damping: f32,
+        /// The amount of reverberated signal in the output, between `0.0` (dry) and `1.0` (wet).
+        wet: f32,
+    },
+    /// A compressor that reduces the volume of the bus while it is louder than a threshold.
+    Compressor {
+        /// The level above which the volume is reduced.
+        threshold: Volume,
+        /// How much the signal above the threshold is reduced. A ratio of `4.0` turns an
+        /// excess of 4 dB into 1 dB.
+        ratio: f32,
+        /// How quickly the compressor reacts to the signal getting louder.
+        attack: Duration,
+        /// How quickly the compressor recovers once the signal gets quieter.
+        release: Duration,
+        /// The gain applied after compression, to make up for the reduced volume.
+        makeup_gain: Volume,
+    },
+}
+
+impl AudioEffect {
+    /// Creates a [`LowPass`](Self::LowPass) filter with a flat response.
+    pub const fn low_pass(cutoff_frequency: f32) -> Self {
+        Self::LowPass {
+            cutoff_frequency,
+            q: core::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    /// Creates a [`HighPass`](Self::HighPass) filter with a flat response.
+    pub const fn high_pass(cutoff_frequency: f32) -> Self {
+        Self::HighPass {
+            cutoff_frequency,
+            q: core::f32::consts::FRAC_1_SQRT_2,
+        }
+    }
+
+    /// Creates a [`Reverb`](Self::Reverb) with moderate damping.
+    pub const fn reverb(room_size: f32, wet: f32) -> Self {
+        Self::Reverb {
+            room_size,
+            damping: 0.5,
+            wet,
+        }
+    }
+
+    /// Creates a [`Compressor`](Self::Compressor) with a 5 ms attack, a 100 ms release and no
+    /// makeup gain.
+    pub const fn compressor(threshold: Volume, ratio: f32) -> Self {
+        Self::Compressor {
+            threshold,
+            ratio,
+            attack: Duration::from_millis(5),
+            release: Duration::from_millis(100),
+            makeup_gain: Volume::Linear(1.0),
+        }
+    }
+}
+
+/// Returns the coefficient of a one-pole smoothing filter which covers most of the distance to
+/// its target in `time`.
+pub(crate) fn smoothing_coefficient(time: Duration, sample_rate: f32) -> f32 {
+    let samples = time.as_secs_f32() * sample_rate;
+    if samples <= 0.0 {
+        0.0
+    } else {
+        ops::exp(-1.0 / samples)
+    }
+}
+
+/// Processes interleaved frames with a chain of [`AudioEffect`]s on the audio thread.
+pub(crate) struct EffectChain {
+    effects: Vec<(AudioEffect, EffectProcessor)>,
+    sample_rate: f32,
+}
+
+impl EffectChain {
+    pub(crate) fn new(sample_rate: f32) -> Self {
+        Self {
+            effects: Vec::new(),
+            sample_rate,
+        }
+    }
+
+    /// Replaces the effects of the chain. Effects which keep their kind and position are
+    /// updated in place, so they keep their state.
+    pub(crate) fn configure(&mut self, update: EffectChainUpdate) {
+        self.effects.truncate(update.effects.len());
+        for (index, (effect, processor)) in update.effects.into_iter().enumerate() {
+            match (self.effects.get_mut(index), processor) {
+                (Some(slot), Some(processor)) => *slot = (effect, processor),
+                (None, Some(processor)) => self.effects.push((effect, processor)),
+                (Some((current, processor)), None) => {
+                    if *current != effect {
+                        processor.update(&effect, self.sample_rate);
+                        *current = effect;
+                    }
+                }
+                // The update creates a processor for every effect the chain doesn't have yet.
+                (None, None) => {}
+            }
+        }
+    }
+
+    /// Applies the effects to a single frame, which holds one sample per channel.
+    pub(crate) fn process(&mut self, frame: &mut [f32]) {
+        for (_, processor) in &mut self.effects {
+            processor.process(frame);
+        }
+    }
+}
+
+/// New effects for an [`EffectChain`], sent to the audio thread.
+///
+/// The processors of effects which can't be updated in place are created here, so the audio
+/// thread doesn't have to allocate them.
+pub(crate) struct EffectChainUpdate {
+    effects: Vec<(AudioEffect, Option<EffectProcessor>)>,
+}
+
+impl EffectChainUpdate {
+    /// Creates the update which replaces the `current` effects of a chain with `effects`.
+    ///
+    /// If the last update sent to the chain hasn't been applied yet, it is passed as
+    /// `unapplied`: the chain still holds the effects from before it, and the processors it
+    /// created are reused.
+    pub(crate) fn new(
+        effects: &[AudioEffect],
+        current: &[AudioEffect],
+        unapplied: Option<EffectChainUpdate>,
+        channels: usize,
+        sample_rate: f32,
+    ) -> Self {
+        let mut unapplied = unapplied.map(|update| update.effects);
+        let effects = effects
+            .iter()
+            .enumerate()
+            .map(|(index, effect)| {
+                let same_kind = |other: &AudioEffect| {
+                    core::mem::discriminant(other) == core::mem::discriminant(effect)
+                };
+                let new_processor = || Some(EffectProcessor::new(effect, channels, sample_rate));
+                let processor = match &mut unapplied {
+                    Some(unapplied) => match unapplied.get_mut(index) {
+                        // The chain holds an effect of the same kind as the unapplied one.
+                        Some((previous, None)) if same_kind(previous) => None,
+                        Some((previous, processor)) if same_kind(previous) => {
+                            processor.take().map(|mut processor| {
+                                processor.update(effect, sample_rate);
+                                processor
+                            })
+                        }
+                        _ => new_processor(),
+                    },
+                    None => match current.get(index) {
+                        Some(current) if same_kind(current) => None,
+                        _ => new_processor(),
+                    },
+                };
+                (effect.clone(), processor)
+            })
+            .collect();
+        Self { effects }
+    }
+}
+
+enum EffectProcessor {
+    Biquad(Biquad),
+    Reverb(Reverb),
+    Compressor(Compressor),
+}
+
+impl EffectProcessor {
+    fn new(effect: &AudioEffect, channels: usize, sample_rate: f32) -> Self {
+        let mut processor = match effect {
+            AudioEffect::LowPass { .. } | AudioEffect::HighPass { .. } => {
+                Self::Biquad(Biquad::new(channels))
+            }
+            AudioEffect::Reverb { .. } => Self::Reverb(Reverb::new(channels, sample_rate)),
+            AudioEffect::Compressor { .. } => Self::Compressor(Compressor::default()),
+        };
+        processor.update(effect, sample_rate);
+        processor
+    }
+
+    fn update(&mut self, effect: &AudioEffect, sample_rate: f32) {
+        match (self, effect) {
+            (
+                Self::Biquad(biquad),
+                AudioEffect::LowPass {
+                    cutoff_frequency,
+                    q,
+                },
+            ) => biquad.set_low_pass(*cutoff_frequency, *q, sample_rate),
+            (
+                Self::Biquad(biquad),
+                AudioEffect::HighPass {
+                    cutoff_frequency,
+                    q,
+                },
+            ) => biquad.set_high_pass(*cutoff_frequency, *q, sample_rate),
+            (
+                Self::Reverb(reverb),
+                AudioEffect::Reverb {
+                    room_size,
+                    damping,
+                    wet,
+                },
+            ) => reverb.set_parameters(*room_size, *damping, *wet),
+            (
+                Self::Compressor(compressor),
+                AudioEffect::Compressor {
+                    threshold,
+                    ratio,
+                    attack,
+                    release,
+                    makeup_gain,
+                },
+            ) => {
+                compressor.threshold = threshold.to_decibels();
+                compressor.ratio = ratio.max(1.0);
+                compressor.attack = smoothing_coefficient(*attack, sample_rate);
+                compressor.release = smoothing_coefficient(*release, sample_rate);
+                compressor.makeup_gain = makeup_gain.to_linear();
+            }
+            _ => {}
+        }
+    }
+
+    fn process(&mut self, frame: &mut [f32]) {
+        match self {
+            Self::Biquad(biquad) => biquad.process(frame),
+            Self::Reverb(reverb) => reverb.process(frame),
+            Self::Compressor(compressor) => compressor.process(frame),
+        }
+    }
+}
+
+/// A second-order IIR filter, using the coefficients from the Audio EQ Cookbook.
+struct Biquad {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    /// The transposed direct form II state of each channel.
+    state: Vec<[f32; 2]>,
+}
+
+impl Biquad {
+    fn new(channels: usize) -> Self {
+        Self {
+            b0: 1.0,
+            b1: 0.0,
+            b2: 0.0,
+            a1: 0.0,
+            a2: 0.0,
+            state: vec![[0.0; 2]; channels],
+        }
+    }
+
+    /// Returns the cosine of the normalized cutoff frequency and the `alpha` term.
+    fn prepare(cutoff_frequency: f32, q: f32, sample_rate: f32) -> (f32, f32) {
+        let cutoff_frequency = cutoff_frequency.clamp(1.0, sample_rate * 0.49);
+        let omega = 2.0 * PI * cutoff_frequency / sample_rate;
+        let (sin, cos) = ops::sin_cos(omega);
+        (cos, sin / (2.0 * q.max(0.01)))
+    }
+
+    fn set_coefficients(&mut self, b: [f32; 3], a: [f32; 3]) {
+        self.b0 = b[0] / a[0];
+        self.b1 = b[1] / a[0];
+        self.b2 = b[2] / a[0];
+        self.a1 = a[1] / a[0];
+        self.a2 = a[2] / a[0];
+    }
+
+    fn set_low_pass(&mut self, cutoff_frequency: f32, q: f32, sample_rate: f32) {
+        let (cos, alpha) = Self::prepare(cutoff_frequency, q, sample_rate);
+        self.set_coefficients(
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        );
+    }
+
+    fn set_high_pass(&mut self, cutoff_frequency: f32, q: f32, sample_rate: f32) {
+        let (cos, alpha) = Self::prepare(cutoff_frequency, q, sample_rate);
+        self.set_coefficients(
+            [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        );
+    }
+
+    fn process(&mut self, frame: &mut [f32]) {
+        for (sample, state) in frame.iter_mut().zip(&mut self.state) {
+            let input = *sample;
+            let output = self.b0 * input + state[0];
+            state[0] = self.b1 * input - self.a1 * output + state[1];
+            state[1] = self.b2 * input - self.a2 * output;
+            *sample = output;
+        }
+    }
+}
+
+/// The delay lengths of the Freeverb comb filters at 44.1 kHz.
+const COMB_TUNING: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+/// The delay lengths of the Freeverb allpass filters at 44.1 kHz.
+const ALLPASS_TUNING: [usize; 4] = [556, 441, 341, 225];
+/// The offset added to the delay lengths of each channel, to decorrelate the channels.
+const STEREO_SPREAD: usize = 23;
+
+struct Reverb {
+    channels: Vec<ReverbChannel>,
+    feedback: f32,
+    damping: f32,
+    wet: f32,
+}
+
+struct ReverbChannel {
+    combs: Vec<Comb>,
+    allpasses: Vec<Allpass>,
+}
+
+struct Comb {
+    buffer: Vec<f32>,
+    index: usize,
+    filter_store: f32,
+}
+
+struct Allpass {
+    buffer: Vec<f32>,
+    index: usize,
+}
+
+impl Reverb {
+    fn new(channels: usize, sample_rate: f32) -> Self {
+        let scale = sample_rate / 44100.0;
+        let length = |tuning: usize, channel: usize| {
+            (((tuning + STEREO_SPREAD * channel) as f32 * scale) as usize).max(1)
+        };
+        Self {
+            channels: (0..channels)
+                .map(|channel| ReverbChannel {
+                    combs: COMB_TUNING
+                        .iter()
+                        .map(|tuning| Comb {
+                            buffer: vec![0.0; length(*tuning, channel)],
+                            index: 0,
+                            filter_store: 0.0,
+                        })
+                        .collect(),
+                    allpasses: ALLPASS_TUNING
+                        .iter()
+                        .map(|tuning| Allpass {
+                            buffer: vec![0.0; length(*tuning, channel)],
+                            index: 0,
+                        })
+                        .collect(),
+                })
+                .collect(),
+            feedback: 0.0,
+            damping: 0.0,
+            wet: 0.0,
+        }
+    }
+
+    fn set_parameters(&mut self, room_size: f32, damping: f32, wet: f32) {
+        self.feedback = room_size.clamp(0.0, 1.0) * 0.28 + 0.7;
+        self.damping = damping.clamp(0.0, 1.0) * 0.4;
+        self.wet = wet.clamp(0.0, 1.0);
+    }
+
+    fn process(&mut self, frame: &mut [f32]) {
+        for (sample, channel) in frame.iter_mut().zip(&mut self.channels) {
+            let input = *sample * 0.015;
+            let mut output = 0.0;
+            for comb in &mut channel.combs {
+                let delayed = comb.buffer[comb.index];
+                comb.filter_store =
+                    delayed * (1.0 - self.damping) + comb.filter_store * self.damping;
+                comb.buffer[comb.index] = input + comb.filter_store * self.feedback;
+                comb.index = (comb.index + 1) % comb.buffer.len();
+                output += delayed;
+            }
+            for allpass in &mut channel.allpasses {
+                let delayed = allpass.buffer[allpass.index];
+                allpass.buffer[allpass.index] = output + delayed * 0.5;
+                allpass.index = (allpass.index + 1) % allpass.buffer.len();
+                output = delayed - output;
+            }
+            *sample = *sample * (1.0 - self.wet) + output * self.wet * 3.0;
+        }
+    }
+}
+
+#[derive(Default)]
+struct Compressor {
+    /// The threshold in decibels.
+    threshold: f32,
+    ratio: f32,
+    attack: f32,
+    release: f32,
+    makeup_gain: f32,
+    /// The detected level of the signal.
+    envelope: f32,
+}
+
+impl Compressor {
+    fn process(&mut self, frame: &mut [f32]) {
+        // The channels are linked, so the stereo image doesn't shift while compressing.
+        let peak = frame
+            .iter()
+            .fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+        let coefficient = if peak > self.envelope {
+            self.attack
+        } else {
+            self.release
+        };
+        self.envelope = peak + coefficient * (self.envelope - peak);
+
+        let excess = Volume::Linear(self.envelope).to_decibels() - self.threshold;
+        let gain = if excess > 0.0 {
+            Volume::Decibels(-excess * (1.0 - 1.0 / self.ratio)).to_linear()
+        } else {
+            1.0
+        } * self.makeup_gain;
+        for sample in frame {
+            *sample *= gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: f32 = 48000.0;
+
+    /// Runs a mono sine wave through `effects` and returns the peak of the second half of the
+    /// output, once the effects have settled.
+    fn sine_peak(frequency: f32, amplitude: f32, effects: &[AudioEffect]) -> f32 {
+        let mut chain = EffectChain::new(SAMPLE_RATE);
+        chain.configure(EffectChainUpdate::new(effects, &[], None, 1, SAMPLE_RATE));
+        let samples = SAMPLE_RATE as usize / 5;
+        let mut peak = 0.0f32;
+        for n in 0..samples {
+            let mut frame = [amplitude * ops::sin(2.0 * PI * frequency * n as f32 / SAMPLE_RATE)];
+            chain.process(&mut frame);
+            if n > samples / 2 {
+                peak = peak.max(frame[0].abs());
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn filters_remove_frequencies() {
+        let low_pass = [AudioEffect::low_pass(500.0)];
+        assert!(sine_peak(100.0, 1.0, &low_pass) > 0.9);
+        assert!(sine_peak(8000.0, 1.0, &low_pass) < 0.02);
+
+        let high_pass = [AudioEffect::high_pass(2000.0)];
+        assert!(sine_peak(100.0, 1.0, &high_pass) < 0.01);
+        assert!(sine_peak(8000.0, 1.0, &high_pass) > 0.9);
+    }
+
+    #[test]
+    fn compressor_reduces_loud_signals() {
+        let compressor = [AudioEffect::compressor(Volume::Decibels(-12.0), 4.0)];
+        // A full scale signal is 12 dB above the threshold, which is reduced to 3 dB.
+        let peak = Volume::Linear(sine_peak(440.0, 1.0, &compressor)).to_decibels();
+        assert!((peak - -9.0).abs() < 1.0, "{peak}");
+        // A signal below the threshold is untouched.
+        assert!((sine_peak(440.0, 0.1, &compressor) - 0.1).abs() < 0.001);
+    }
+
+    #[test]
+    fn reverb_has_a_tail() {
+        let mut chain = EffectChain::new(SAMPLE_RATE);
+        let reverb = [AudioEffect::reverb(0.8, 1.0)];
+        chain.configure(EffectChainUpdate::new(&reverb, &[], None, 2, SAMPLE_RATE));
+        let mut frame = [1.0, 1.0];
+        chain.process(&mut frame);
+        let mut tail = 0.0f32;
+        for _ in 0..SAMPLE_RATE as usize / 10 {
+            let mut frame = [0.0, 0.0];
+            chain.process(&mut frame);
+            tail = tail.max(frame[0].abs());
+        }
+        assert!(tail > 0.0);
+
+        // Changing the parameters keeps the tail going.
+        let update = EffectChainUpdate::new(
+            &[AudioEffect::reverb(0.5, 1.0)],
+            &reverb,
+            None,
+            2,
+            SAMPLE_RATE,
+        );
+        assert!(update.effects[0].1.is_none());
+        chain.configure(update);
+        let mut frame = [0.0, 0.0];
+        let mut tail = 0.0f32;
+        for _ in 0..SAMPLE_RATE as usize / 10 {
+            chain.process(&mut frame);
+            tail = tail.max(frame[0].abs());
+            frame = [0.0, 0.0];
+        }
+        assert!(tail > 0.0);
+    }
+}
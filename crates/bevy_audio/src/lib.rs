@@ -32,6 +32,8 @@ extern crate alloc;
 mod audio;
 mod audio_output;
 mod audio_source;
+mod bus;
+mod effects;
 mod pitch;
 mod sinks;
 mod volume;
@@ -42,13 +44,17 @@ mod volume;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        AudioPlayer, AudioSink, AudioSinkPlayback, AudioSource, Decodable, GlobalVolume, Pitch,
-        PlaybackSettings, SpatialAudioSink, SpatialListener,
+        AudioBus, AudioBusSink, AudioEffect, AudioPlayer, AudioSink, AudioSinkPlayback,
+        AudioSource, Decodable, GlobalVolume, Pitch, PlaybackSettings, RouteToBus,
+        SidechainDucking, SpatialAudioSink, SpatialListener,
     };
 }
 
 pub use audio::*;
+pub use audio_output::{AudioOutputMode, OfflineAudioOutput};
 pub use audio_source::*;
+pub use bus::*;
+pub use effects::*;
 pub use pitch::*;
 pub use volume::*;
 
@@ -61,6 +67,7 @@ use bevy_ecs::prelude::*;
 use bevy_transform::TransformSystems;
 
 use audio_output::*;
+use bus::{start_audio_buses, update_audio_buses};
 
 /// Set for the audio playback systems, so they can share a run condition
 #[derive(SystemSet, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
@@ -68,7 +75,9 @@ struct AudioPlaybackSystems;
 
 /// Adds support for audio playback to a Bevy Application
 ///
-/// Insert an [`AudioPlayer`] onto your entities to play audio.
+/// Insert an [`AudioPlayer`] onto your entities to play audio. To mix audio through buses with
+/// their own volume and effects, spawn [`AudioBus`] entities and route players into them with
+/// [`RouteToBus`].
 #[derive(Default)]
 pub struct AudioPlugin {
     /// The global volume for all audio entities.
@@ -76,10 +85,17 @@ pub struct AudioPlugin {
     /// The scale factor applied to the positions of audio sources and listeners for
     /// spatial audio.
     pub default_spatial_scale: SpatialScale,
+    /// Where audio is played.
+    pub output: AudioOutputMode,
 }
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut App) {
+        let (output, offline) = AudioOutput::new(self.output);
+        if let Some(offline) = offline {
+            app.insert_resource(offline);
+        }
+
         app.insert_resource(self.global_volume)
             .insert_resource(DefaultSpatialScale(self.default_spatial_scale))
             .configure_sets(
@@ -90,9 +106,14 @@ impl Plugin for AudioPlugin {
             )
             .add_systems(
                 PostUpdate,
-                (update_emitter_positions, update_listener_positions).in_set(AudioPlaybackSystems),
+                (
+                    update_emitter_positions,
+                    update_listener_positions,
+                    (start_audio_buses, update_audio_buses).chain(),
+                )
+                    .in_set(AudioPlaybackSystems),
             )
-            .init_resource::<AudioOutput>();
+            .insert_resource(output);
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
         {
@@ -112,7 +133,10 @@ impl AddAudioSource for App {
     {
         self.init_asset::<T>().add_systems(
             PostUpdate,
-            (play_queued_audio_system::<T>, cleanup_finished_audio::<T>)
+            (
+                play_queued_audio_system::<T>.after(update_audio_buses),
+                cleanup_finished_audio::<T>,
+            )
                 .in_set(AudioPlaybackSystems),
         );
         self
@@ -26,6 +26,14 @@ pub struct CiTestingSetup {
     ///
     /// [`TimeUpdateStrategy::ManualDuration`]: bevy_time::TimeUpdateStrategy::ManualDuration
     pub fixed_frame_time: Option<f32>,
+    /// Records all input from the first frame, and saves it to the given path when the app exits.
+    ///
+    /// This requires the `bevy_ci_input_recording` feature.
+    pub record_input: Option<String>,
+    /// Replays the input recording at the given path, starting at the first frame.
+    ///
+    /// This requires the `bevy_ci_input_recording` feature.
+    pub replay_input: Option<String>,
 }
 
 /// An event to send at a given frame, used for CI testing.
@@ -59,6 +67,18 @@ pub enum CiTestingEvent {
         /// Rotation to move the camera to.
         rotation: Quat,
     },
+    /// Starts recording input, using the next frame as frame `0` of the recording.
+    ///
+    /// This requires the `bevy_ci_input_recording` feature.
+    StartInputRecording,
+    /// Stops recording input, and saves the recording to the given path.
+    ///
+    /// This requires the `bevy_ci_input_recording` feature.
+    StopInputRecording(String),
+    /// Replays the input recording at the given path, starting on the next frame.
+    ///
+    /// This requires the `bevy_ci_input_recording` feature.
+    ReplayInput(String),
     /// Sends a [`CiTestingCustomEvent`] using the given [`String`].
     Custom(String),
 }
@@ -77,10 +97,13 @@ mod tests {
 (
     setup: (
         fixed_frame_time: Some(0.03),
+        replay_input: Some("bug_report.ron"),
     ),
     events: [
         (100, Custom("Hello, world!")),
+        (150, StartInputRecording),
         (200, Screenshot),
+        (250, StopInputRecording("input.ron")),
         (300, AppExit),
     ],
 )"#;
@@ -88,10 +111,14 @@ mod tests {
         let expected = CiTestingConfig {
             setup: CiTestingSetup {
                 fixed_frame_time: Some(0.03),
+                record_input: None,
+                replay_input: Some("bug_report.ron".into()),
             },
             events: vec![
                 CiTestingEventOnFrame(100, CiTestingEvent::Custom("Hello, world!".into())),
+                CiTestingEventOnFrame(150, CiTestingEvent::StartInputRecording),
                 CiTestingEventOnFrame(200, CiTestingEvent::Screenshot),
+                CiTestingEventOnFrame(250, CiTestingEvent::StopInputRecording("input.ron".into())),
                 CiTestingEventOnFrame(300, CiTestingEvent::AppExit),
             ],
         };
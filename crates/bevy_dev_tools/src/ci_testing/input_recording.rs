@@ -0,0 +1,594 @@
+//! Recording and replaying input, used to reproduce bug reports and to drive regression tests.
+
+use super::config::CiTestingConfig;
+use bevy_app::AppExit;
+use bevy_ecs::{entity::EntityHashMap, prelude::*};
+use bevy_input::{
+    gamepad::{GamepadConnectionEvent, RawGamepadEvent},
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+};
+use bevy_time::{Real, Time};
+use bevy_window::WindowEvent;
+use core::time::Duration;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
+
+/// A log of input messages, recorded with an [`InputRecorder`] and replayed with an [`InputReplayer`].
+///
+/// Messages are stored in the order they were read. Messages of the same type keep their relative order
+/// within a frame, but the order between different message types is not preserved.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Default, Clone)]
+pub struct InputRecording {
+    /// The recorded messages.
+    pub messages: Vec<RecordedMessage>,
+}
+
+impl InputRecording {
+    /// Serializes this recording to a [`ron`] string.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+    }
+
+    /// Deserializes a recording from a [`ron`] string.
+    pub fn from_ron(ron: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(ron)
+    }
+
+    /// Writes this recording to the file at `path`, logging any errors.
+    pub fn save(&self, path: &str) {
+        let result = self
+            .to_ron()
+            .map_err(|error| error.to_string())
+            .and_then(|ron| std::fs::write(path, ron).map_err(|error| error.to_string()));
+        match result {
+            Ok(()) => info!("Saved input recording to {path}."),
+            Err(error) => error!("Failed to save input recording to {path}: {error}"),
+        }
+    }
+
+    /// Reads a recording from the file at `path`, logging any errors.
+    pub fn load(path: &str) -> Option<Self> {
+        let result = std::fs::read_to_string(path)
+            .map_err(|error| error.to_string())
+            .and_then(|ron| Self::from_ron(&ron).map_err(|error| error.to_string()));
+        match result {
+            Ok(recording) => Some(recording),
+            Err(error) => {
+                error!("Failed to load input recording from {path}: {error}");
+                None
+            }
+        }
+    }
+}
+
+/// A single message in an [`InputRecording`].
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct RecordedMessage {
+    /// The frame the message was read on, relative to the start of the recording.
+    pub frame: u32,
+    /// The [`Time<Real>`] elapsed since the start of the recording.
+    pub time: Duration,
+    /// The message itself.
+    pub message: RecordedInput,
+}
+
+/// An input message stored in an [`InputRecording`].
+///
+/// Gamepad input is stored as [`RawGamepadEvent`]s, which are the messages `InputPlugin` processes
+/// into `GamepadEvent`s. This way replayed gamepad input is filtered by the current `GamepadSettings`,
+/// just like the original input.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub enum RecordedInput {
+    /// A [`KeyboardInput`] message.
+    Keyboard(KeyboardInput),
+    /// A [`MouseButtonInput`] message.
+    MouseButton(MouseButtonInput),
+    /// A [`MouseMotion`] message.
+    MouseMotion(MouseMotion),
+    /// A [`MouseWheel`] message.
+    MouseWheel(MouseWheel),
+    /// A [`TouchInput`] message.
+    Touch(TouchInput),
+    /// A [`RawGamepadEvent`] message.
+    Gamepad(RawGamepadEvent),
+    /// A [`WindowEvent`] message. Input that is recorded by one of the other variants is not
+    /// duplicated here.
+    Window(WindowEvent),
+}
+
+/// Records input messages into an [`InputRecording`].
+///
+/// Recording can be started from a [`CiTestingConfig`] using [`CiTestingSetup::record_input`] or
+/// [`CiTestingEvent::StartInputRecording`].
+///
+/// [`CiTestingSetup::record_input`]: super::CiTestingSetup::record_input
+/// [`CiTestingEvent::StartInputRecording`]: super::CiTestingEvent::StartInputRecording
+#[derive(Resource, Default)]
+pub struct InputRecorder {
+    active: Option<ActiveRecording>,
+}
+
+struct ActiveRecording {
+    recording: InputRecording,
+    frame: u32,
+    start: Option<Duration>,
+}
+
+impl InputRecorder {
+    /// Starts a new recording, discarding the current one (if any). The next frame is recorded as frame `0`.
+    pub fn start(&mut self) {
+        self.active = Some(ActiveRecording {
+            recording: InputRecording::default(),
+            frame: 0,
+            start: None,
+        });
+    }
+
+    /// Stops the current recording and returns it.
+    pub fn stop(&mut self) -> Option<InputRecording> {
+        self.active.take().map(|active| active.recording)
+    }
+
+    /// Returns `true` if input is currently being recorded.
+    pub fn is_recording(&self) -> bool {
+        self.active.is_some()
+    }
+}
+
+/// Replays an [`InputRecording`] by writing its messages on the matching frames.
+///
+/// Replaying can be started from a [`CiTestingConfig`] using [`CiTestingSetup::replay_input`] or
+/// [`CiTestingEvent::ReplayInput`].
+///
+/// [`CiTestingSetup::replay_input`]: super::CiTestingSetup::replay_input
+/// [`CiTestingEvent::ReplayInput`]: super::CiTestingEvent::ReplayInput
+#[derive(Resource, Default)]
+pub struct InputReplayer {
+    active: Option<ActiveReplay>,
+}
+
+struct ActiveReplay {
+    messages: alloc::vec::IntoIter<RecordedMessage>,
+    frame: u32,
+    /// Maps the gamepad entities of the recording to the gamepad entities spawned by the replay.
+    gamepads: EntityHashMap<Entity>,
+}
+
+impl InputReplayer {
+    /// Starts replaying the `recording`, replacing the current replay (if any). Frame `0` of the recording
+    /// is replayed on the next frame.
+    pub fn start(&mut self, recording: InputRecording) {
+        let mut messages = recording.messages;
+        messages.sort_by_key(|message| message.frame);
+        self.active = Some(ActiveReplay {
+            messages: messages.into_iter(),
+            frame: 0,
+            gamepads: EntityHashMap::default(),
+        });
+    }
+
+    /// Stops the current replay.
+    pub fn stop(&mut self) {
+        self.active = None;
+    }
+
+    /// Returns `true` if a recording is currently being replayed.
+    pub fn is_replaying(&self) -> bool {
+        self.active.is_some()
+    }
+}
+
+pub(crate) fn record_input(
+    mut recorder: ResMut<InputRecorder>,
+    real_time: Res<Time<Real>>,
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut mouse_button: MessageReader<MouseButtonInput>,
+    mut mouse_motion: MessageReader<MouseMotion>,
+    mut mouse_wheel: MessageReader<MouseWheel>,
+    mut touch: MessageReader<TouchInput>,
+    mut gamepad: MessageReader<RawGamepadEvent>,
+    mut window: MessageReader<WindowEvent>,
+) {
+    let Some(active) = &mut recorder.active else {
+        keyboard.clear();
+        mouse_button.clear();
+        mouse_motion.clear();
+        mouse_wheel.clear();
+        touch.clear();
+        gamepad.clear();
+        window.clear();
+        return;
+    };
+
+    let frame = active.frame;
+    let start = *active.start.get_or_insert(real_time.elapsed());
+    let time = real_time.elapsed().saturating_sub(start);
+    let messages = keyboard
+        .read()
+        .cloned()
+        .map(RecordedInput::Keyboard)
+        .chain(mouse_button.read().cloned().map(RecordedInput::MouseButton))
+        .chain(mouse_motion.read().cloned().map(RecordedInput::MouseMotion))
+        .chain(mouse_wheel.read().cloned().map(RecordedInput::MouseWheel))
+        .chain(touch.read().cloned().map(RecordedInput::Touch))
+        .chain(gamepad.read().cloned().map(RecordedInput::Gamepad))
+        .chain(
+            window
+                .read()
+                .filter(|event| !is_recorded_input(event))
+                .cloned()
+                .map(RecordedInput::Window),
+        )
+        .map(|message| RecordedMessage {
+            frame,
+            time,
+            message,
+        });
+    active.recording.messages.extend(messages);
+    active.frame += 1;
+}
+
+/// Returns `true` if the [`WindowEvent`] wraps a message that is recorded on its own.
+fn is_recorded_input(event: &WindowEvent) -> bool {
+    matches!(
+        event,
+        WindowEvent::KeyboardInput(_)
+            | WindowEvent::MouseButtonInput(_)
+            | WindowEvent::MouseMotion(_)
+            | WindowEvent::MouseWheel(_)
+            | WindowEvent::TouchInput(_)
+    )
+}
+
+/// Returns `true` if every message recorded or replayed by an [`InputRecording`] is registered,
+/// which requires the `InputPlugin` and the `WindowPlugin`.
+pub(crate) fn input_messages_exist(
+    keyboard: Option<Res<Messages<KeyboardInput>>>,
+    mouse_button: Option<Res<Messages<MouseButtonInput>>>,
+    mouse_motion: Option<Res<Messages<MouseMotion>>>,
+    mouse_wheel: Option<Res<Messages<MouseWheel>>>,
+    touch: Option<Res<Messages<TouchInput>>>,
+    gamepad: Option<Res<Messages<RawGamepadEvent>>>,
+    gamepad_connection: Option<Res<Messages<GamepadConnectionEvent>>>,
+    window: Option<Res<Messages<WindowEvent>>>,
+) -> bool {
+    keyboard.is_some()
+        && mouse_button.is_some()
+        && mouse_motion.is_some()
+        && mouse_wheel.is_some()
+        && touch.is_some()
+        && gamepad.is_some()
+        && gamepad_connection.is_some()
+        && window.is_some()
+}
+
+/// Returns `true` if the [`InputReplayer`] is replaying a recording.
+pub(crate) fn is_replaying(replayer: Res<InputReplayer>) -> bool {
+    replayer.is_replaying()
+}
+
+pub(crate) fn replay_input(world: &mut World) {
+    world.resource_scope(|world, mut replayer: Mut<InputReplayer>| {
+        let Some(active) = &mut replayer.active else {
+            return;
+        };
+
+        let frame = active.frame;
+        while active
+            .messages
+            .as_slice()
+            .first()
+            .is_some_and(|message| message.frame <= frame)
+        {
+            let Some(RecordedMessage { message, .. }) = active.messages.next() else {
+                break;
+            };
+            match message {
+                RecordedInput::Keyboard(message) => {
+                    world.write_message(message);
+                }
+                RecordedInput::MouseButton(message) => {
+                    world.write_message(message);
+                }
+                RecordedInput::MouseMotion(message) => {
+                    world.write_message(message);
+                }
+                RecordedInput::MouseWheel(message) => {
+                    world.write_message(message);
+                }
+                RecordedInput::Touch(message) => {
+                    world.write_message(message);
+                }
+                RecordedInput::Gamepad(message) => {
+                    write_gamepad_event(world, &mut active.gamepads, message);
+                }
+                RecordedInput::Window(message) => write_window_event(world, message),
+            }
+        }
+        active.frame += 1;
+        if active.messages.as_slice().is_empty() {
+            replayer.active = None;
+            info!("Finished replaying input after {} frames.", frame + 1);
+        }
+    });
+}
+
+/// Writes the [`RawGamepadEvent`], using the gamepad entity spawned by the replay in place of the
+/// recorded one, since the gamepad entities of the recording don't exist anymore.
+fn write_gamepad_event(
+    world: &mut World,
+    gamepads: &mut EntityHashMap<Entity>,
+    mut event: RawGamepadEvent,
+) {
+    let recorded_gamepad = match &mut event {
+        RawGamepadEvent::Connection(event) => &mut event.gamepad,
+        RawGamepadEvent::Button(event) => &mut event.gamepad,
+        RawGamepadEvent::Axis(event) => &mut event.gamepad,
+    };
+    *recorded_gamepad = *gamepads
+        .entry(*recorded_gamepad)
+        .or_insert_with(|| world.spawn_empty().id());
+
+    // Connections are handled by a separate system, which reads `GamepadConnectionEvent`s.
+    if let RawGamepadEvent::Connection(connection) = &event {
+        world.write_message(connection.clone());
+    }
+    world.write_message(event);
+}
+
+/// Writes the [`WindowEvent`] and the message it wraps, like the windowing backend does.
+fn write_window_event(world: &mut World, event: WindowEvent) {
+    world.write_message(event.clone());
+    match event {
+        WindowEvent::AppLifecycle(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::CursorEntered(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::CursorLeft(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::CursorMoved(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::FileDragAndDrop(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::Ime(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::RequestRedraw(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowBackendScaleFactorChanged(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowCloseRequested(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowCreated(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowDestroyed(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowFocused(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowMoved(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowOccluded(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowResized(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowScaleFactorChanged(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::WindowThemeChanged(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::MouseButtonInput(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::MouseMotion(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::MouseWheel(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::PinchGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::RotationGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::DoubleTapGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::PanGesture(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::TouchInput(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::KeyboardInput(e) => {
+            world.write_message(e);
+        }
+        WindowEvent::KeyboardFocusLost(e) => {
+            world.write_message(e);
+        }
+    }
+}
+
+/// Saves the recording started by [`CiTestingSetup::record_input`] when the app exits.
+///
+/// [`CiTestingSetup::record_input`]: super::CiTestingSetup::record_input
+pub(crate) fn save_recording_on_exit(
+    mut app_exit: MessageReader<AppExit>,
+    mut recorder: ResMut<InputRecorder>,
+    config: Res<CiTestingConfig>,
+) {
+    if app_exit.read().next().is_none() {
+        return;
+    }
+    if let Some(path) = &config.setup.record_input
+        && let Some(recording) = recorder.stop()
+    {
+        recording.save(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::{App, PreUpdate};
+    use bevy_input::{
+        gamepad::{
+            Gamepad, GamepadButton, GamepadConnection, GamepadConnectionEvent,
+            RawGamepadButtonChangedEvent,
+        },
+        keyboard::KeyCode,
+        ButtonInput, ButtonState, InputPlugin, InputSystems,
+    };
+    use bevy_time::TimePlugin;
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((TimePlugin, InputPlugin))
+            .add_message::<WindowEvent>()
+            .init_resource::<InputRecorder>()
+            .init_resource::<InputReplayer>()
+            .add_systems(
+                PreUpdate,
+                (replay_input.run_if(is_replaying), record_input)
+                    .chain()
+                    .run_if(input_messages_exist)
+                    .before(InputSystems),
+            );
+        app
+    }
+
+    fn key_input(key_code: KeyCode, state: ButtonState) -> KeyboardInput {
+        KeyboardInput {
+            key_code,
+            logical_key: bevy_input::keyboard::Key::Space,
+            state,
+            text: None,
+            repeat: false,
+            window: Entity::PLACEHOLDER,
+        }
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let mut app = test_app();
+        app.world_mut().resource_mut::<InputRecorder>().start();
+        app.update();
+        app.world_mut()
+            .write_message(key_input(KeyCode::Space, ButtonState::Pressed));
+        app.update();
+        app.update();
+        app.world_mut()
+            .write_message(key_input(KeyCode::Space, ButtonState::Released));
+        app.update();
+        let recording = app
+            .world_mut()
+            .resource_mut::<InputRecorder>()
+            .stop()
+            .unwrap();
+
+        let frames = recording
+            .messages
+            .iter()
+            .map(|message| message.frame)
+            .collect::<Vec<_>>();
+        assert_eq!(frames, [1, 3]);
+
+        let recording = InputRecording::from_ron(&recording.to_ron().unwrap()).unwrap();
+        let mut app = test_app();
+        app.world_mut()
+            .resource_mut::<InputReplayer>()
+            .start(recording);
+
+        let mut pressed = Vec::new();
+        for _ in 0..5 {
+            app.update();
+            pressed.push(
+                app.world()
+                    .resource::<ButtonInput<KeyCode>>()
+                    .pressed(KeyCode::Space),
+            );
+        }
+        assert_eq!(pressed, [false, true, true, false, false]);
+        assert!(!app.world().resource::<InputReplayer>().is_replaying());
+    }
+
+    #[test]
+    fn replayed_gamepads_are_spawned() {
+        let recorded_gamepad = Entity::from_raw_u32(1000).unwrap();
+        let message = |frame, message| RecordedMessage {
+            frame,
+            time: Duration::ZERO,
+            message: RecordedInput::Gamepad(message),
+        };
+        let recording = InputRecording {
+            messages: vec![
+                message(
+                    0,
+                    RawGamepadEvent::Connection(GamepadConnectionEvent::new(
+                        recorded_gamepad,
+                        GamepadConnection::Connected {
+                            name: "Recorded gamepad".into(),
+                            vendor_id: None,
+                            product_id: None,
+                        },
+                    )),
+                ),
+                message(
+                    1,
+                    RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                        recorded_gamepad,
+                        GamepadButton::South,
+                        1.0,
+                    )),
+                ),
+            ],
+        };
+
+        let mut app = test_app();
+        app.world_mut()
+            .resource_mut::<InputReplayer>()
+            .start(recording);
+        app.update();
+        app.update();
+
+        let gamepad = app
+            .world_mut()
+            .query::<&Gamepad>()
+            .single(app.world())
+            .unwrap();
+        assert!(gamepad.pressed(GamepadButton::South));
+    }
+
+    #[test]
+    fn systems_skip_missing_messages() {
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .init_resource::<InputRecorder>()
+            .init_resource::<InputReplayer>()
+            .add_systems(
+                PreUpdate,
+                (replay_input.run_if(is_replaying), record_input)
+                    .chain()
+                    .run_if(input_messages_exist),
+            );
+        app.world_mut().resource_mut::<InputRecorder>().start();
+        app.update();
+    }
+}
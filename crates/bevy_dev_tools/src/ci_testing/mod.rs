@@ -1,6 +1,8 @@
 //! Utilities for testing in CI environments.
 
 mod config;
+#[cfg(feature = "bevy_ci_input_recording")]
+mod input_recording;
 mod systems;
 
 use crate::EasyCameraMovementPlugin;
@@ -8,6 +10,8 @@ use crate::EasyCameraMovementPlugin;
 use crate::EasyScreenRecordPlugin;
 
 pub use self::config::*;
+#[cfg(feature = "bevy_ci_input_recording")]
+pub use self::input_recording::*;
 
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
@@ -21,6 +25,9 @@ use core::time::Duration;
 /// (`ci_testing_config.ron` by default) and executes its specified actions. For a reference of the
 /// allowed configuration, see [`CiTestingConfig`].
 ///
+/// It can also record input into an [`InputRecording`] and replay it on matching frames, which
+/// is useful to reproduce bug reports and to drive headless regression tests.
+///
 /// This plugin is included within `DefaultPlugins` and `MinimalPlugins`
 /// when the `bevy_ci_testing` feature is enabled.
 /// It is recommended to only used this plugin during testing (manual or
@@ -78,6 +85,41 @@ impl Plugin for CiTestingPlugin {
                 fixed_frame_time,
             )));
         }
+        #[cfg(feature = "bevy_ci_input_recording")]
+        {
+            let mut recorder = InputRecorder::default();
+            if config.setup.record_input.is_some() {
+                recorder.start();
+            }
+            let mut replayer = InputReplayer::default();
+            if let Some(recording) = config
+                .setup
+                .replay_input
+                .as_deref()
+                .and_then(InputRecording::load)
+            {
+                replayer.start(recording);
+            }
+            // The systems only run when input is recorded or replayed in an app with the input
+            // and window messages, so this doesn't get in the way of headless apps.
+            app.insert_resource(recorder)
+                .insert_resource(replayer)
+                .add_systems(
+                    PreUpdate,
+                    (replay_input.run_if(is_replaying), record_input)
+                        .chain()
+                        .run_if(input_messages_exist)
+                        .before(bevy_input::InputSystems),
+                )
+                .add_systems(Last, save_recording_on_exit);
+        }
+        #[cfg(not(feature = "bevy_ci_input_recording"))]
+        if config.setup.record_input.is_some() || config.setup.replay_input.is_some() {
+            tracing::warn!(
+                "Recording and replaying input requires the `bevy_ci_input_recording` feature."
+            );
+        }
+
         app.add_message::<CiTestingCustomEvent>()
             .insert_resource(config)
             .add_systems(
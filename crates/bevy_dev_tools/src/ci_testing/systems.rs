@@ -1,6 +1,8 @@
 use crate::CameraMovement;
 
 use super::config::*;
+#[cfg(feature = "bevy_ci_input_recording")]
+use super::input_recording::*;
 use bevy_app::AppExit;
 use bevy_camera::Camera;
 use bevy_ecs::prelude::*;
@@ -76,6 +78,33 @@ pub(crate) fn send_events(world: &mut World, mut current_frame: Local<u32>) {
                     });
                 }
             }
+            #[cfg(feature = "bevy_ci_input_recording")]
+            CiTestingEvent::StartInputRecording => {
+                info!("Started recording input at frame {}.", *current_frame);
+                world.resource_mut::<InputRecorder>().start();
+            }
+            #[cfg(feature = "bevy_ci_input_recording")]
+            CiTestingEvent::StopInputRecording(path) => {
+                info!("Stopped recording input at frame {}.", *current_frame);
+                if let Some(recording) = world.resource_mut::<InputRecorder>().stop() {
+                    recording.save(&path);
+                }
+            }
+            #[cfg(feature = "bevy_ci_input_recording")]
+            CiTestingEvent::ReplayInput(path) => {
+                if let Some(recording) = InputRecording::load(&path) {
+                    info!("Replaying input from {} at frame {}.", path, *current_frame);
+                    world.resource_mut::<InputReplayer>().start(recording);
+                }
+            }
+            #[cfg(not(feature = "bevy_ci_input_recording"))]
+            CiTestingEvent::StartInputRecording
+            | CiTestingEvent::StopInputRecording(_)
+            | CiTestingEvent::ReplayInput(_) => {
+                tracing::warn!(
+                    "Recording and replaying input requires the `bevy_ci_input_recording` feature."
+                );
+            }
             // Custom events are forwarded to the world.
             CiTestingEvent::Custom(event_string) => {
                 world.write_message(CiTestingCustomEvent(event_string));
@@ -1,7 +1,7 @@
 //! Tools for debugging states.
 
 use bevy_ecs::message::MessageReader;
-use bevy_state::state::{StateTransitionEvent, States};
+use bevy_state::state::{StateStackOperation, StateTransitionEvent, States};
 use tracing::info;
 
 /// Logs state transitions into console.
@@ -17,11 +17,17 @@ pub fn log_transitions<S: States>(mut transitions: MessageReader<StateTransition
         exited,
         entered,
         allow_same_state_transitions,
+        stack_operation,
     } = transition;
     let skip_text = if exited == entered && !*allow_same_state_transitions {
         " (disallowing same-state transitions)"
     } else {
         ""
     };
-    info!("{name} transition: {exited:?} => {entered:?}{skip_text}");
+    let stack_text = match stack_operation {
+        Some(StateStackOperation::Push) => " (push)",
+        Some(StateStackOperation::Pop) => " (pop)",
+        None => "",
+    };
+    info!("{name} transition: {exited:?} => {entered:?}{skip_text}{stack_text}");
 }
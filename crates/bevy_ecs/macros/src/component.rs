@@ -169,19 +169,37 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .map(|path| path.to_token_stream(&bevy_ecs_path)),
     );
 
+    let many_relationship = attrs
+        .relationship
+        .as_ref()
+        .is_some_and(|relationship| relationship.many);
+    let many_relationship_target = attrs
+        .relationship_target
+        .as_ref()
+        .is_some_and(|target| target.many);
+
     if relationship.is_some() {
+        let relationship_trait = if many_relationship {
+            quote!(ManyRelationship)
+        } else {
+            quote!(Relationship)
+        };
         on_insert_path
-            .push(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert));
+            .push(quote!(<Self as #bevy_ecs_path::relationship::#relationship_trait>::on_insert));
         on_discard_path
-            .push(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_discard));
+            .push(quote!(<Self as #bevy_ecs_path::relationship::#relationship_trait>::on_discard));
     }
-    if let Some(target) = attrs.relationship_target {
+    if let Some(target) = &attrs.relationship_target {
+        let target_trait = if target.many {
+            quote!(ManyRelationshipTarget)
+        } else {
+            quote!(RelationshipTarget)
+        };
         on_discard_path
-            .push(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_discard));
+            .push(quote!(<Self as #bevy_ecs_path::relationship::#target_trait>::on_discard));
         if target.linked_spawn {
-            on_despawn_path.push(
-                quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_despawn),
-            );
+            on_despawn_path
+                .push(quote!(<Self as #bevy_ecs_path::relationship::#target_trait>::on_despawn));
         }
     }
 
@@ -233,7 +251,15 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         .then_some(quote! { #bevy_ecs_path::component::Immutable })
         .unwrap_or(quote! { #bevy_ecs_path::component::Mutable });
 
-    let clone_behavior = if relationship_target.is_some() || relationship.is_some() {
+    let observe_mutations = attrs
+        .observe_mutations
+        .then_some(quote! { const OBSERVE_MUTATIONS: bool = true; });
+
+    let clone_behavior = if many_relationship_target {
+        quote!(#bevy_ecs_path::component::ComponentCloneBehavior::Custom(
+            #bevy_ecs_path::relationship::clone_many_relationship_target::<Self>
+        ))
+    } else if (relationship_target.is_some() || relationship.is_some()) && !many_relationship {
         quote!(
             use #bevy_ecs_path::relationship::{
                 RelationshipCloneBehaviorBase, RelationshipCloneBehaviorViaClone, RelationshipCloneBehaviorViaReflect,
@@ -250,7 +276,10 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         )
     };
 
+    // Many-to-many relationships aren't supported by dynamic relationship accessors.
     let relationship_accessor = if (relationship.is_some() || relationship_target.is_some())
+        && !many_relationship
+        && !many_relationship_target
         && let Data::Struct(DataStruct {
             fields,
             struct_token,
@@ -286,6 +315,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         impl #impl_generics #bevy_ecs_path::component::Component for #struct_name #type_generics #where_clause {
             const STORAGE_TYPE: #bevy_ecs_path::component::StorageType = #storage;
             type Mutability = #mutable_type;
+            #observe_mutations
             fn register_required_components(
                 _requiree: #bevy_ecs_path::component::ComponentId,
                 required_components: &mut #bevy_ecs_path::component::RequiredComponentsRegistrator,
@@ -423,6 +453,7 @@ pub const ON_DESPAWN: &str = "on_despawn";
 pub const MAP_ENTITIES: &str = "map_entities";
 
 pub const IMMUTABLE: &str = "immutable";
+pub const OBSERVE_MUTATIONS: &str = "observe_mutations";
 pub const CLONE_BEHAVIOR: &str = "clone_behavior";
 
 /// All allowed attribute value expression kinds for component hooks.
@@ -546,6 +577,7 @@ struct Attrs {
     relationship: Option<Relationship>,
     relationship_target: Option<RelationshipTarget>,
     immutable: bool,
+    observe_mutations: bool,
     clone_behavior: Option<Expr>,
     map_entities: Option<MapEntitiesAttributeKind>,
 }
@@ -564,11 +596,13 @@ struct Require {
 struct Relationship {
     relationship_target: Type,
     allow_self_referential: bool,
+    many: bool,
 }
 
 struct RelationshipTarget {
     relationship: Type,
     linked_spawn: bool,
+    many: bool,
 }
 
 // values for `storage` attribute
@@ -587,6 +621,7 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
         relationship: None,
         relationship_target: None,
         immutable: false,
+        observe_mutations: false,
         clone_behavior: None,
         map_entities: None,
     };
@@ -634,6 +669,9 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                 } else if nested.path.is_ident(IMMUTABLE) {
                     attrs.immutable = true;
                     Ok(())
+                } else if nested.path.is_ident(OBSERVE_MUTATIONS) {
+                    attrs.observe_mutations = true;
+                    Ok(())
                 } else if nested.path.is_ident(CLONE_BEHAVIOR) {
                     attrs.clone_behavior = Some(nested.value()?.parse()?);
                     Ok(())
@@ -676,6 +714,13 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
             ));
     }
 
+    if attrs.immutable && attrs.observe_mutations {
+        return Err(syn::Error::new(
+            ast.ident.span(),
+            "Immutable components can't be mutated, please remove `observe_mutations`",
+        ));
+    }
+
     Ok(attrs)
 }
 
@@ -783,18 +828,23 @@ mod kw {
     syn::custom_keyword!(relationship);
     syn::custom_keyword!(linked_spawn);
     syn::custom_keyword!(allow_self_referential);
+    syn::custom_keyword!(many);
 }
 
 impl Parse for Relationship {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship_target: Option<Type> = None;
         let mut allow_self_referential: bool = false;
+        let mut many: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::allow_self_referential) {
                 input.parse::<kw::allow_self_referential>()?;
                 allow_self_referential = true;
+            } else if lookahead.peek(kw::many) {
+                input.parse::<kw::many>()?;
+                many = true;
             } else if lookahead.peek(kw::relationship_target) {
                 input.parse::<kw::relationship_target>()?;
                 input.parse::<Token![=]>()?;
@@ -811,6 +861,7 @@ impl Parse for Relationship {
                 syn::Error::new(input.span(), "Missing `relationship_target = X` attribute")
             })?,
             allow_self_referential,
+            many,
         })
     }
 }
@@ -819,12 +870,16 @@ impl Parse for RelationshipTarget {
     fn parse(input: syn::parse::ParseStream) -> Result<Self> {
         let mut relationship: Option<Type> = None;
         let mut linked_spawn: bool = false;
+        let mut many: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
             if lookahead.peek(kw::linked_spawn) {
                 input.parse::<kw::linked_spawn>()?;
                 linked_spawn = true;
+            } else if lookahead.peek(kw::many) {
+                input.parse::<kw::many>()?;
+                many = true;
             } else if lookahead.peek(kw::relationship) {
                 input.parse::<kw::relationship>()?;
                 input.parse::<Token![=]>()?;
@@ -841,6 +896,7 @@ impl Parse for RelationshipTarget {
                 syn::Error::new(input.span(), "Missing `relationship = X` attribute")
             })?,
             linked_spawn,
+            many,
         })
     }
 }
@@ -877,6 +933,35 @@ fn derive_relationship(
     let relationship_target = &relationship.relationship_target;
     let allow_self_referential = relationship.allow_self_referential;
 
+    if relationship.many {
+        let edges = &field.ty;
+        return Ok(Some(quote! {
+            impl #impl_generics #bevy_ecs_path::relationship::ManyRelationship for #struct_name #type_generics #where_clause {
+                type RelationshipTarget = #relationship_target;
+                type Data = <#edges as #bevy_ecs_path::relationship::EdgeData>::Data;
+                const ALLOW_SELF_REFERENTIAL: bool = #allow_self_referential;
+
+                #[inline(always)]
+                fn edges(&self) -> &#bevy_ecs_path::relationship::RelationshipEdges<Self::Data> {
+                    &self.#relationship_member
+                }
+
+                #[inline]
+                fn edges_mut_risky(&mut self) -> &mut #bevy_ecs_path::relationship::RelationshipEdges<Self::Data> {
+                    &mut self.#relationship_member
+                }
+
+                #[inline]
+                fn from_edges(edges: #bevy_ecs_path::relationship::RelationshipEdges<Self::Data>) -> Self {
+                    Self {
+                        #(#members: ::core::default::Default::default(),)*
+                        #relationship_member: edges
+                    }
+                }
+            }
+        }));
+    }
+
     Ok(Some(quote! {
         impl #impl_generics #bevy_ecs_path::relationship::Relationship for #struct_name #type_generics #where_clause {
             type RelationshipTarget = #relationship_target;
@@ -939,8 +1024,13 @@ fn derive_relationship_target(
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
     let linked_spawn = relationship_target.linked_spawn;
+    let target_trait = if relationship_target.many {
+        quote!(ManyRelationshipTarget)
+    } else {
+        quote!(RelationshipTarget)
+    };
     Ok(Some(quote! {
-        impl #impl_generics #bevy_ecs_path::relationship::RelationshipTarget for #struct_name #type_generics #where_clause {
+        impl #impl_generics #bevy_ecs_path::relationship::#target_trait for #struct_name #type_generics #where_clause {
             const LINKED_SPAWN: bool = #linked_spawn;
             type Relationship = #relationship;
             type Collection = #collection;
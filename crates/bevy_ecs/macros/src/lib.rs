@@ -590,6 +590,22 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
 /// ```
 /// results in a different file being used as the source of the settings.
 ///
+/// ## Version
+/// ```ignore
+/// #[derive(SettingsGroup)]
+/// #[settings_group(version = 2)]
+/// struct MySettings {
+///     test: true
+/// }
+/// ```
+/// results in:
+/// ```ignore
+/// [my_settings]
+/// _version = 2
+/// test = true
+/// ```
+/// Settings files with older versions are upgraded by the registered `SettingsMigrations`.
+///
 /// ## Key Override
 /// Only valid for enums, as struct keys are always derived from the field name.
 /// ```ignore
@@ -605,6 +621,22 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
 /// [my_settings_enum]
 /// my_key = "variant1"
 /// ```
+///
+/// ## Field Validation
+/// Only valid for structs with named fields. Values that fail validation are rejected when
+/// loading settings, and the field keeps its previous value.
+/// ```ignore
+/// #[derive(SettingsGroup)]
+/// struct MySettings {
+///     #[settings_group(range = 0.0..=1.0)]
+///     volume: f32,
+///     #[settings_group(one_of = ["low", "medium", "high"])]
+///     quality: String,
+///     /// The function takes a reference to the field, and returns `Result<(), impl Display>`.
+///     #[settings_group(validate = validate_name)]
+///     name: String,
+/// }
+/// ```
 #[proc_macro_derive(SettingsGroup, attributes(settings_group))]
 pub fn derive_settings_group(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -613,10 +645,11 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 
     let path = bevy_settings_path();
 
-    let (override_group_name, override_key_name, override_file) = {
+    let (override_group_name, override_key_name, override_file, version) = {
         let mut override_group_name: Option<String> = None;
         let mut override_key_name: Option<String> = None;
         let mut override_file: Option<String> = None;
+        let mut version: Option<u32> = None;
 
         input
             .attrs
@@ -639,6 +672,11 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
                         let s: syn::LitStr = value.parse()?;
                         override_file = Some(s.value());
                         Ok(())
+                    } else if meta.path.is_ident("version") {
+                        let value = meta.value()?;
+                        let n: syn::LitInt = value.parse()?;
+                        version = Some(n.base10_parse()?);
+                        Ok(())
                     } else {
                         Err(meta.error("unsupported attribute"))
                     }
@@ -646,7 +684,12 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
                 .ok()
             });
 
-        (override_group_name, override_key_name, override_file)
+        (
+            override_group_name,
+            override_key_name,
+            override_file,
+            version,
+        )
     };
 
     let key_name = match &input.data {
@@ -675,6 +718,118 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
         }
     };
 
+    let mut field_validations = Vec::new();
+    if let Data::Struct(data) = &input.data {
+        for field in data.fields.iter() {
+            let mut checks = Vec::new();
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("settings_group"))
+            {
+                let Some(field_name) = &field.ident else {
+                    return syn::Error::new_spanned(
+                        attr,
+                        "Field validation is only supported for structs with named fields",
+                    )
+                    .into_compile_error()
+                    .into();
+                };
+                let field_name = field_name.to_string();
+                let result = attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("range") {
+                        let range: syn::Expr = meta.value()?.parse()?;
+                        let range_text = range.to_token_stream().to_string();
+                        checks.push(quote! {
+                            if !(#range).contains(typed) {
+                                return ::core::result::Result::Err(
+                                    #path::SettingsValidationError::OutOfRange {
+                                        field: #field_name,
+                                        value: ::std::format!("{value:?}"),
+                                        range: #range_text,
+                                    },
+                                );
+                            }
+                        });
+                        Ok(())
+                    } else if meta.path.is_ident("one_of") {
+                        let allowed: syn::ExprArray = meta.value()?.parse()?;
+                        let allowed_text = allowed.to_token_stream().to_string();
+                        checks.push(quote! {
+                            if !#allowed.iter().any(|allowed| typed == allowed) {
+                                return ::core::result::Result::Err(
+                                    #path::SettingsValidationError::NotAllowed {
+                                        field: #field_name,
+                                        value: ::std::format!("{value:?}"),
+                                        allowed: #allowed_text,
+                                    },
+                                );
+                            }
+                        });
+                        Ok(())
+                    } else if meta.path.is_ident("validate") {
+                        let validate: syn::Expr = meta.value()?.parse()?;
+                        checks.push(quote! {
+                            if let ::core::result::Result::Err(message) = (#validate)(typed) {
+                                return ::core::result::Result::Err(
+                                    #path::SettingsValidationError::Invalid {
+                                        field: #field_name,
+                                        value: ::std::format!("{value:?}"),
+                                        message: ::std::string::ToString::to_string(&message),
+                                    },
+                                );
+                            }
+                        });
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported attribute"))
+                    }
+                });
+                if let Err(error) = result {
+                    return error.into_compile_error().into();
+                }
+            }
+            if let (Some(field_name), false) = (&field.ident, checks.is_empty()) {
+                let field_name = field_name.to_string();
+                let field_type = &field.ty;
+                field_validations.push(quote! {
+                    #field_name => {
+                        if let ::core::option::Option::Some(typed) =
+                            value.try_downcast_ref::<#field_type>()
+                        {
+                            #(#checks)*
+                        }
+                    }
+                });
+            }
+        }
+    }
+
+    let validate_field = if field_validations.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn validate_field(
+                field: &str,
+                value: &dyn #path::__macro_exports::PartialReflect,
+            ) -> ::core::result::Result<(), #path::SettingsValidationError> {
+                match field {
+                    #(#field_validations)*
+                    _ => {}
+                }
+                ::core::result::Result::Ok(())
+            }
+        }
+    };
+
+    let settings_version = version.map(|version| {
+        quote! {
+            fn settings_version() -> u32 {
+                #version
+            }
+        }
+    });
+
     let group_name = override_group_name.unwrap_or(pascal_to_snake_case(&name.to_string()));
     let key_name = key_name
         .map(|f| quote! { ::core::option::Option::Some(#f) })
@@ -696,6 +851,10 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
             fn settings_source() -> ::core::option::Option<&'static str> {
                 #file_name
             }
+
+            #settings_version
+
+            #validate_field
         }
     };
 
@@ -774,6 +933,16 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 /// #[relationship(relationship_target = PeopleILike, allow_self_referential)]
 /// pub struct LikedBy(pub Entity);
 /// ```
+/// Many-to-many relationships, where each edge carries data:
+/// ```ignore
+/// #[derive(Component)]
+/// #[relationship(relationship_target = WiredFrom, many)]
+/// pub struct WiredTo(RelationshipEdges<f32>);
+///
+/// #[derive(Component)]
+/// #[relationship_target(relationship = WiredTo, many)]
+/// pub struct WiredFrom(Vec<Entity>);
+/// ```
 /// ## Warning
 ///
 /// When `allow_self_referential` is enabled, be careful when using recursive traversal methods
@@ -797,6 +966,13 @@ pub fn derive_settings_group(input: TokenStream) -> TokenStream {
 /// #[component(clone_behavior = Ignore)]
 /// struct MyComponent;
 /// ```
+///
+/// ## Trigger `Mutate` when the component is changed in place
+/// ```ignore
+/// #[derive(Component)]
+/// #[component(observe_mutations)]
+/// struct MyComponent;
+/// ```
 #[proc_macro_derive(
     Component,
     attributes(component, require, relationship, relationship_target, entities)
@@ -28,6 +28,8 @@ pub(crate) struct BundleInserter<'w> {
     archetype: NonNull<Archetype>,
     archetype_move_type: ArchetypeMoveType,
     change_tick: Tick,
+    /// If false, no hooks or observers are triggered for the inserted components.
+    pub(crate) trigger_lifecycle: bool,
 }
 
 impl<'w> BundleInserter<'w> {
@@ -107,6 +109,7 @@ impl<'w> BundleInserter<'w> {
             archetype_move_type,
             change_tick,
             world: world.as_unsafe_world_cell(),
+            trigger_lifecycle: true,
         };
 
         if is_new_created {
@@ -128,6 +131,7 @@ impl<'w> BundleInserter<'w> {
         insert_mode: InsertMode,
         caller: MaybeLocation,
         relationship_hook_mode: RelationshipHookMode,
+        trigger_lifecycle: bool,
         mut archetype: NonNull<Archetype>,
         archetype_after_insert: &ArchetypeAfterBundleInsert,
         world: &'a UnsafeWorldCell<'w>,
@@ -145,7 +149,7 @@ impl<'w> BundleInserter<'w> {
             // SAFETY: Mutable references do not alias and will be dropped after this block
             let mut deferred_world = world.into_deferred();
 
-            if insert_mode == InsertMode::Replace {
+            if trigger_lifecycle && insert_mode == InsertMode::Replace {
                 let archetype = archetype.as_ref();
                 let new_archetype = match archetype_move_type {
                     ArchetypeMoveType::SameArchetype => archetype,
@@ -359,6 +363,7 @@ impl<'w> BundleInserter<'w> {
                 insert_mode,
                 caller,
                 relationship_hook_mode,
+                self.trigger_lifecycle,
                 self.archetype,
                 archetype_after_insert,
                 &self.world,
@@ -381,6 +386,10 @@ impl<'w> BundleInserter<'w> {
             (new_archetype, new_location)
         };
 
+        if !self.trigger_lifecycle {
+            return new_location;
+        }
+
         // SAFETY: We have no outstanding mutable references to world as they were dropped
         let deferred_world = unsafe { self.world.into_deferred() };
 
@@ -437,6 +446,10 @@ impl<'w> BundleInserter<'w> {
             }
             match insert_mode {
                 InsertMode::Replace => {
+                    deferred_world.record_inserted_components(
+                        entity,
+                        archetype_after_insert.inserted().iter().copied(),
+                    );
                     // Insert triggers for both new and existing components if we're replacing them.
                     deferred_world.trigger_on_insert(
                         new_archetype,
@@ -460,6 +473,10 @@ impl<'w> BundleInserter<'w> {
                     }
                 }
                 InsertMode::Keep => {
+                    deferred_world.record_inserted_components(
+                        entity,
+                        archetype_after_insert.added().iter().copied(),
+                    );
                     // Insert triggers only for new components if we're not replacing them (since
                     // nothing is actually inserted).
                     deferred_world.trigger_on_insert(
@@ -24,6 +24,8 @@ pub(crate) struct BundleRemover<'w> {
     old_archetype: NonNull<Archetype>,
     new_archetype: NonNull<Archetype>,
     pub(crate) relationship_hook_mode: RelationshipHookMode,
+    /// If false, no hooks or observers are triggered and no removals are recorded.
+    pub(crate) trigger_lifecycle: bool,
 }
 
 impl<'w> BundleRemover<'w> {
@@ -94,6 +96,7 @@ impl<'w> BundleRemover<'w> {
             old_and_new_table: tables,
             world: world.as_unsafe_world_cell(),
             relationship_hook_mode: RelationshipHookMode::Run,
+            trigger_lifecycle: true,
         };
         if is_new_created {
             remover
@@ -135,57 +138,59 @@ impl<'w> BundleRemover<'w> {
         ) -> (bool, T),
     ) -> (EntityLocation, T) {
         // Hooks
-        // SAFETY: all bundle components exist in World
-        unsafe {
-            // SAFETY: We only keep access to archetype/bundle data.
-            let mut deferred_world = self.world.into_deferred();
-            let bundle_components_in_archetype = || {
-                self.bundle_info
-                    .as_ref()
-                    .iter_explicit_components()
-                    .filter(|component_id| self.old_archetype.as_ref().contains(*component_id))
-            };
-            if self.old_archetype.as_ref().has_discard_observer() {
-                let components = bundle_components_in_archetype().collect::<Vec<_>>();
-                // SAFETY: the DISCARD event_key corresponds to the Discard event's type
-                deferred_world.trigger_raw(
-                    DISCARD,
-                    &mut Discard { entity },
-                    &mut EntityComponentsTrigger {
-                        components: &components,
-                        old_archetype: Some(self.old_archetype.as_ref()),
-                        new_archetype: Some(self.new_archetype.as_ref()),
-                    },
+        if self.trigger_lifecycle {
+            // SAFETY: all bundle components exist in World
+            unsafe {
+                // SAFETY: We only keep access to archetype/bundle data.
+                let mut deferred_world = self.world.into_deferred();
+                let bundle_components_in_archetype = || {
+                    self.bundle_info
+                        .as_ref()
+                        .iter_explicit_components()
+                        .filter(|component_id| self.old_archetype.as_ref().contains(*component_id))
+                };
+                if self.old_archetype.as_ref().has_discard_observer() {
+                    let components = bundle_components_in_archetype().collect::<Vec<_>>();
+                    // SAFETY: the DISCARD event_key corresponds to the Discard event's type
+                    deferred_world.trigger_raw(
+                        DISCARD,
+                        &mut Discard { entity },
+                        &mut EntityComponentsTrigger {
+                            components: &components,
+                            old_archetype: Some(self.old_archetype.as_ref()),
+                            new_archetype: Some(self.new_archetype.as_ref()),
+                        },
+                        caller,
+                    );
+                }
+                deferred_world.trigger_on_discard(
+                    self.old_archetype.as_ref(),
+                    entity,
+                    bundle_components_in_archetype(),
                     caller,
+                    self.relationship_hook_mode,
                 );
-            }
-            deferred_world.trigger_on_discard(
-                self.old_archetype.as_ref(),
-                entity,
-                bundle_components_in_archetype(),
-                caller,
-                self.relationship_hook_mode,
-            );
-            if self.old_archetype.as_ref().has_remove_observer() {
-                let components = bundle_components_in_archetype().collect::<Vec<_>>();
-                // SAFETY: the REMOVE event_key corresponds to the Remove event's type
-                deferred_world.trigger_raw(
-                    REMOVE,
-                    &mut Remove { entity },
-                    &mut EntityComponentsTrigger {
-                        components: &components,
-                        old_archetype: Some(self.old_archetype.as_ref()),
-                        new_archetype: Some(self.new_archetype.as_ref()),
-                    },
+                if self.old_archetype.as_ref().has_remove_observer() {
+                    let components = bundle_components_in_archetype().collect::<Vec<_>>();
+                    // SAFETY: the REMOVE event_key corresponds to the Remove event's type
+                    deferred_world.trigger_raw(
+                        REMOVE,
+                        &mut Remove { entity },
+                        &mut EntityComponentsTrigger {
+                            components: &components,
+                            old_archetype: Some(self.old_archetype.as_ref()),
+                            new_archetype: Some(self.new_archetype.as_ref()),
+                        },
+                        caller,
+                    );
+                }
+                deferred_world.trigger_on_remove(
+                    self.old_archetype.as_ref(),
+                    entity,
+                    bundle_components_in_archetype(),
                     caller,
                 );
             }
-            deferred_world.trigger_on_remove(
-                self.old_archetype.as_ref(),
-                entity,
-                bundle_components_in_archetype(),
-                caller,
-            );
         }
 
         // SAFETY: We still have the cell, so this is unique, it doesn't conflict with other references, and we drop it shortly.
@@ -205,7 +210,9 @@ impl<'w> BundleRemover<'w> {
         // Handle sparse set removes
         for component_id in self.bundle_info.as_ref().iter_explicit_components() {
             if self.old_archetype.as_ref().contains(component_id) {
-                world.removed_components.write(component_id, entity);
+                if self.trigger_lifecycle {
+                    world.removed_components.write(component_id, entity);
+                }
 
                 // Make sure to drop components stored in sparse sets.
                 // Dense components are dropped later in `move_to_and_drop_missing_unchecked`.
@@ -150,6 +150,8 @@ impl<'w> BundleSpawner<'w> {
                     caller,
                 );
             }
+            deferred_world
+                .record_inserted_components(entity, bundle_info.iter_contributed_components());
             deferred_world.trigger_on_insert(
                 archetype,
                 entity,
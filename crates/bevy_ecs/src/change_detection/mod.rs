@@ -161,6 +161,7 @@ mod tests {
             changed_by: caller.as_mut(),
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            mutation: None,
         };
         let mut res = R {};
 
@@ -211,6 +212,7 @@ mod tests {
             changed_by: caller.as_mut(),
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            mutation: None,
         };
         let mut res = R {};
 
@@ -244,6 +246,7 @@ mod tests {
             changed_by: caller.as_mut(),
             last_run,
             this_run,
+            mutation: None,
         };
 
         let mut outer = Outer(0);
@@ -332,6 +335,7 @@ mod tests {
             changed_by: caller.as_mut(),
             last_run,
             this_run,
+            mutation: None,
         };
 
         let mut value: i32 = 5;
@@ -368,6 +372,7 @@ mod tests {
             changed_by: caller.as_mut(),
             last_run: Tick::new(3),
             this_run: Tick::new(4),
+            mutation: None,
         };
         let mut c = C {};
 
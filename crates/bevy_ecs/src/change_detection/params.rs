@@ -1,5 +1,6 @@
 use crate::{
     change_detection::{traits::*, ComponentTickCells, MaybeLocation, Tick},
+    lifecycle::{ContiguousMutationRecord, MutationRecord},
     ptr::PtrMut,
     resource::Resource,
 };
@@ -205,6 +206,8 @@ pub(crate) struct ComponentTicksMut<'w> {
     pub(crate) changed_by: MaybeLocation<&'w mut &'static Location<'static>>,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
+    /// Taken by the first mutable access, for components which trigger [`Mutate`](crate::lifecycle::Mutate).
+    pub(crate) mutation: Option<MutationRecord<'w>>,
 }
 
 impl<'w> ComponentTicksMut<'w> {
@@ -225,6 +228,16 @@ impl<'w> ComponentTicksMut<'w> {
             changed_by: unsafe { cells.changed_by.map(|changed_by| changed_by.deref_mut()) },
             last_run,
             this_run,
+            mutation: None,
+        }
+    }
+
+    /// Reports the mutation of the component through [`Mutate`](crate::lifecycle::Mutate),
+    /// if it opted in and this is the first mutable access.
+    #[inline]
+    pub(crate) fn record_mutation(&mut self) {
+        if let Some(mutation) = self.mutation.take() {
+            mutation.record();
         }
     }
 }
@@ -253,6 +266,8 @@ pub struct ContiguousComponentTicksMut<'w> {
     pub(crate) changed_by: MaybeLocation<&'w mut [&'static Location<'static>]>,
     pub(crate) last_run: Tick,
     pub(crate) this_run: Tick,
+    /// Taken by the first mutable access, for components which trigger [`Mutate`](crate::lifecycle::Mutate).
+    pub(crate) mutation: Option<ContiguousMutationRecord<'w>>,
 }
 
 impl<'w> ContiguousComponentTicksMut<'w> {
@@ -278,6 +293,7 @@ impl<'w> ContiguousComponentTicksMut<'w> {
             changed_by: changed_by.map(|v| unsafe { v.as_mut_slice_unchecked(len) }),
             last_run,
             this_run,
+            mutation: None,
         }
     }
 
@@ -312,6 +328,7 @@ impl<'w> ContiguousComponentTicksMut<'w> {
             changed_by: caller,
             last_run,
             this_run,
+            mutation: None,
         })
     }
 
@@ -422,6 +439,10 @@ impl<'w> ContiguousComponentTicksMut<'w> {
         for t in self.changed.iter_mut() {
             *t = this_run;
         }
+
+        if let Some(mutation) = self.mutation.take() {
+            mutation.record();
+        }
     }
 
     /// Returns a `ContiguousComponentTicksMut` with a smaller lifetime.
@@ -432,6 +453,7 @@ impl<'w> ContiguousComponentTicksMut<'w> {
             changed_by: self.changed_by.as_deref_mut(),
             last_run: self.last_run,
             this_run: self.this_run,
+            mutation: self.mutation,
         }
     }
 }
@@ -941,6 +963,7 @@ impl<'w, T: ?Sized> Mut<'w, T> {
                 changed_by: caller,
                 last_run,
                 this_run,
+                mutation: None,
             },
         }
     }
@@ -1252,6 +1275,7 @@ impl<'w> MutUntyped<'w> {
                 changed_by: self.ticks.changed_by.as_deref_mut(),
                 last_run: self.ticks.last_run,
                 this_run: self.ticks.this_run,
+                mutation: self.ticks.mutation,
             },
         }
     }
@@ -1364,6 +1388,7 @@ impl<'w> DetectChangesMut for MutUntyped<'w> {
     fn set_changed(&mut self) {
         *self.ticks.changed = self.ticks.this_run;
         self.ticks.changed_by.assign(MaybeLocation::caller());
+        self.ticks.record_mutation();
     }
 
     #[inline]
@@ -431,6 +431,7 @@ macro_rules! change_detection_mut_impl {
             fn set_changed(&mut self) {
                 *self.ticks.changed = self.ticks.this_run;
                 self.ticks.changed_by.assign(MaybeLocation::caller());
+                self.ticks.record_mutation();
             }
 
             #[inline]
@@ -507,6 +508,7 @@ macro_rules! impl_methods {
                         changed_by: self.ticks.changed_by.as_deref_mut(),
                         last_run: self.ticks.last_run,
                         this_run: self.ticks.this_run,
+                        mutation: self.ticks.mutation,
                     },
                 }
             }
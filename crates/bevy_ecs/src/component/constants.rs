@@ -12,3 +12,5 @@ pub const REMOVE: usize = 3;
 pub const DESPAWN: usize = 4;
 /// `usize` of the [`IsResource`](crate::resource::IsResource) component used to mark entities with resources.
 pub const IS_RESOURCE: usize = 5;
+/// `usize` for the [`Mutate`](crate::lifecycle::Mutate) component used in lifecycle observers.
+pub const MUTATE: usize = 6;
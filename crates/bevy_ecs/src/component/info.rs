@@ -58,6 +58,12 @@ impl ComponentInfo {
         self.descriptor.mutable
     }
 
+    /// Returns `true` if mutations of the current component trigger [`Mutate`](crate::lifecycle::Mutate).
+    #[inline]
+    pub fn observes_mutations(&self) -> bool {
+        self.descriptor.observe_mutations
+    }
+
     /// Returns [`ComponentCloneBehavior`] of the current component.
     #[inline]
     pub fn clone_behavior(&self) -> &ComponentCloneBehavior {
@@ -228,6 +234,7 @@ pub struct ComponentDescriptor {
     // None if the underlying type doesn't need to be dropped
     drop: Option<for<'a> unsafe fn(OwningPtr<'a>)>,
     mutable: bool,
+    observe_mutations: bool,
     clone_behavior: ComponentCloneBehavior,
     relationship_accessor: MaybeRelationshipAccessor,
 }
@@ -242,6 +249,7 @@ impl Debug for ComponentDescriptor {
             .field("type_id", &self.type_id)
             .field("layout", &self.layout)
             .field("mutable", &self.mutable)
+            .field("observe_mutations", &self.observe_mutations)
             .field("clone_behavior", &self.clone_behavior)
             .field("relationship_accessor", &self.relationship_accessor)
             .finish()
@@ -270,6 +278,7 @@ impl ComponentDescriptor {
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
             mutable: T::Mutability::MUTABLE,
+            observe_mutations: T::Mutability::MUTABLE && T::OBSERVE_MUTATIONS,
             clone_behavior: T::clone_behavior(),
             relationship_accessor: T::relationship_accessor().map(|v| v.initializer).into(),
         }
@@ -307,6 +316,7 @@ impl ComponentDescriptor {
             layout,
             drop,
             mutable,
+            observe_mutations: false,
             clone_behavior,
             relationship_accessor: relationship_accessor.into(),
         }
@@ -330,6 +340,7 @@ impl ComponentDescriptor {
             layout: Layout::new::<T>(),
             drop: needs_drop::<T>().then_some(Self::drop_ptr::<T> as _),
             mutable: true,
+            observe_mutations: false,
             clone_behavior: ComponentCloneBehavior::Default,
             relationship_accessor: None.into(),
         }
@@ -360,6 +371,20 @@ impl ComponentDescriptor {
         self.mutable
     }
 
+    /// Returns whether mutations of this component trigger [`Mutate`](crate::lifecycle::Mutate).
+    #[inline]
+    pub fn observes_mutations(&self) -> bool {
+        self.observe_mutations
+    }
+
+    /// Makes mutations of this component trigger [`Mutate`](crate::lifecycle::Mutate).
+    /// This has no effect on immutable components.
+    #[inline]
+    pub fn with_observed_mutations(mut self) -> Self {
+        self.observe_mutations = self.mutable;
+        self
+    }
+
     fn initialize(&mut self, id: ComponentId, components: &mut Components) {
         self.relationship_accessor.initialize(id, components);
     }
@@ -370,6 +395,7 @@ impl ComponentDescriptor {
 pub struct Components {
     pub(super) components: Vec<Option<ComponentInfo>>,
     pub(super) indices: TypeIdMap<ComponentId>,
+    pub(super) observed_mutations: Vec<ComponentId>,
     // This is kept internal and local to verify that no deadlocks can occur.
     pub(super) queued: bevy_platform::sync::RwLock<QueuedComponents>,
 }
@@ -387,6 +413,9 @@ impl Components {
         mut descriptor: ComponentDescriptor,
     ) {
         descriptor.initialize(id, self);
+        if descriptor.observe_mutations {
+            self.observed_mutations.push(id);
+        }
         let info = ComponentInfo::new(id, descriptor);
         let least_len = id.0 + 1;
         if self.components.len() < least_len {
@@ -440,6 +469,12 @@ impl Components {
         self.num_queued_mut() > 0
     }
 
+    /// Returns the components whose mutations trigger [`Mutate`](crate::lifecycle::Mutate).
+    #[inline]
+    pub fn observed_mutations(&self) -> &[ComponentId] {
+        &self.observed_mutations
+    }
+
     /// Returns the number of components registered with this instance.
     #[inline]
     pub fn num_registered(&self) -> usize {
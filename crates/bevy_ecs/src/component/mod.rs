@@ -71,6 +71,25 @@ use core::{fmt::Debug, marker::PhantomData, ops::Deref};
 /// See the documentation for [`ComponentMutability`] for more details around this
 /// feature.
 ///
+/// Mutable components can opt into the [`Mutate`] lifecycle event, which is triggered after they are changed
+/// in place, by adding the `#[component(observe_mutations)]` attribute.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// #[component(observe_mutations)]
+/// struct Health(f32);
+///
+/// let mut world = World::new();
+/// world.add_observer(|mutate: On<Mutate, Health>, health: Query<&Health>| {
+///     println!("Health changed to {}", health.get(mutate.entity).unwrap().0);
+/// });
+/// let player = world.spawn(Health(100.0)).id();
+///
+/// world.get_mut::<Health>(player).unwrap().0 -= 10.0;
+/// world.trigger_mutations();
+/// ```
+///
 /// See the [`entity`] module level documentation to learn how to add or remove components from an entity.
 ///
 /// See the documentation for [`Query`] to learn how to access component data from a system.
@@ -78,6 +97,7 @@ use core::{fmt::Debug, marker::PhantomData, ops::Deref};
 /// [`entity`]: crate::entity#usage
 /// [`Query`]: crate::system::Query
 /// [`ComponentMutability`]: crate::component::ComponentMutability
+/// [`Mutate`]: crate::lifecycle::Mutate
 ///
 /// # Choosing a storage type
 ///
@@ -520,6 +540,12 @@ pub trait Component: Send + Sync + 'static {
     /// * For a component to be immutable, this type must be [`Immutable`].
     type Mutability: ComponentMutability;
 
+    /// If `true`, changing this component in place triggers [`Mutate`](crate::lifecycle::Mutate).
+    /// This has no effect on immutable components.
+    ///
+    /// This defaults to false, and is set to true with the `#[component(observe_mutations)]` attribute.
+    const OBSERVE_MUTATIONS: bool = false;
+
     /// Gets the `on_add` [`ComponentHook`] for this [`Component`] if one is defined.
     fn on_add() -> Option<ComponentHook> {
         None
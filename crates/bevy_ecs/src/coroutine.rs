@@ -0,0 +1,493 @@
+//! Coroutines, for gameplay logic that spans several frames.
+//!
+//! A [`Coroutine`] is a component holding an `async` block, which is polled by the [`run_coroutines`] system.
+//! Instead of being written as a state machine spread over several systems, multi-frame logic such as a cutscene
+//! or a tutorial flow can then be written as straight-line code, waiting for the next frame, a [`Message`]
+//! or an [`Event`] with `.await`.
+//!
+//! The future is given an [`AsyncWorld`], a handle that gives it access to the [`World`] between awaits.
+//! Like [`Commands`], the closures passed to it are queued, and run by [`run_coroutines`] the next time the
+//! coroutine yields.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::coroutine::{AsyncWorld, Coroutine};
+//! #[derive(Message, Clone)]
+//! struct DialogueClosed;
+//!
+//! #[derive(Component)]
+//! struct Dialogue(&'static str);
+//!
+//! fn start_tutorial(mut commands: Commands) {
+//!     commands.spawn_coroutine(|world: AsyncWorld| async move {
+//!         let dialogue = world
+//!             .run(|world| world.spawn(Dialogue("Welcome!")).id())
+//!             .await;
+//!         world.message::<DialogueClosed>().await;
+//!         world.run(move |world| world.despawn(dialogue)).await;
+//!     });
+//! }
+//! # bevy_ecs::system::assert_is_system(start_tutorial);
+//! ```
+//!
+//! Other futures, such as a `Task` spawned on one of the `bevy_tasks` task pools, can be awaited as well.
+//! Coroutines aren't woken up by their wakers, but polled again each time [`run_coroutines`] runs.
+//!
+//! A coroutine lives on its own entity, which is despawned when the coroutine completes.
+//! Despawning this entity, or the entity owning the coroutine through the [`CoroutineOf`] relationship,
+//! cancels the coroutine by dropping its future.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    future::Future,
+    mem,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+use bevy_platform::{
+    cell::SyncCell,
+    sync::{Arc, Mutex, PoisonError},
+};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::std_traits::ReflectDefault;
+use log::warn;
+
+#[cfg(feature = "bevy_reflect")]
+use crate::reflect::{ReflectComponent, ReflectFromWorld};
+use crate::{
+    change_detection::DetectChangesMut,
+    component::Component,
+    entity::Entity,
+    event::Event,
+    hierarchy::ChildOf,
+    message::{Message, Messages},
+    observer::{Observer, On},
+    query::{QueryState, With},
+    schedule::SystemSet,
+    system::{Commands, EntityCommands, Local},
+    world::World,
+};
+
+type BoxedFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+type WorldCommand = Box<dyn FnOnce(&mut World) + Send>;
+
+enum CoroutineState {
+    /// The coroutine hasn't been polled yet.
+    Start(Box<dyn FnOnce(AsyncWorld) -> BoxedFuture + Send>),
+    /// The coroutine is waiting to be polled again.
+    Running {
+        future: BoxedFuture,
+        queue: Arc<Mutex<Vec<WorldCommand>>>,
+    },
+    /// The coroutine is currently being polled.
+    /// A coroutine found in this state when it's polled again panicked during its last poll.
+    Polling,
+}
+
+/// A component holding a future, which is polled by the [`run_coroutines`] system until it completes.
+///
+/// The entity holding the coroutine is despawned once it completes, and despawning it cancels the coroutine.
+/// Use [`Commands::spawn_coroutine`] or [`EntityCommands::spawn_coroutine`] to spawn one.
+///
+/// See the [module docs](self) for more information.
+#[derive(Component)]
+pub struct Coroutine {
+    state: SyncCell<CoroutineState>,
+}
+
+impl Coroutine {
+    /// Creates a new coroutine, with the future returned by `coroutine`.
+    ///
+    /// `coroutine` is called the first time the coroutine is polled, with the [`AsyncWorld`] handle
+    /// used by the future to access the world.
+    pub fn new<F, Fut>(coroutine: F) -> Self
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        Self {
+            state: SyncCell::new(CoroutineState::Start(Box::new(move |world| {
+                Box::pin(coroutine(world))
+            }))),
+        }
+    }
+}
+
+/// A [`Relationship`](crate::relationship::Relationship) linking a [`Coroutine`] to the entity owning it.
+///
+/// The coroutine is cancelled when the owning entity is despawned.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+#[cfg_attr(feature = "bevy_reflect", reflect(Component, PartialEq, Debug, Clone))]
+#[relationship(relationship_target = Coroutines)]
+pub struct CoroutineOf(#[entities] pub Entity);
+
+/// The [`Coroutine`] entities owned by an entity, through the [`CoroutineOf`] relationship.
+#[derive(Component, Default, Debug, PartialEq, Eq)]
+#[relationship_target(relationship = CoroutineOf, linked_spawn)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+#[cfg_attr(feature = "bevy_reflect", reflect(Component, FromWorld, Default))]
+pub struct Coroutines(Vec<Entity>);
+
+/// A handle given to the future of a [`Coroutine`], used to access the [`World`] and wait for things to happen.
+#[derive(Clone)]
+pub struct AsyncWorld {
+    entity: Entity,
+    queue: Arc<Mutex<Vec<WorldCommand>>>,
+}
+
+impl AsyncWorld {
+    /// Returns the entity holding the [`Coroutine`].
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// Waits until the next time [`run_coroutines`] runs, usually the next frame.
+    pub fn next_frame(&self) -> impl Future<Output = ()> + Send + 'static {
+        Yield(false)
+    }
+
+    /// Runs `f` with exclusive access to the [`World`], and returns its result.
+    ///
+    /// The closure is queued, and run by [`run_coroutines`] when the coroutine yields,
+    /// so this doesn't wait for the next frame.
+    pub async fn run<R, F>(&self, f: F) -> R
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut World) -> R + Send + 'static,
+    {
+        let result = Arc::new(Mutex::new(None));
+        let slot = result.clone();
+        self.queue
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(Box::new(move |world| {
+                *slot.lock().unwrap_or_else(PoisonError::into_inner) = Some(f(world));
+            }));
+        loop {
+            Yield(false).await;
+            if let Some(result) = result.lock().unwrap_or_else(PoisonError::into_inner).take() {
+                return result;
+            }
+        }
+    }
+
+    /// Waits until `condition` returns true, checking it once per frame starting with the current one.
+    pub async fn wait_until<F>(&self, mut condition: F)
+    where
+        F: FnMut(&mut World) -> bool + Send + 'static,
+    {
+        loop {
+            let done;
+            (done, condition) = self.run(move |world| (condition(world), condition)).await;
+            if done {
+                return;
+            }
+            self.next_frame().await;
+        }
+    }
+
+    /// Waits for the next message of type `M` written after this is called, and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the [`Messages`] resource of `M` doesn't exist.
+    pub async fn message<M: Message + Clone>(&self) -> M {
+        let mut cursor = self
+            .run(|world| world.resource::<Messages<M>>().get_cursor_current())
+            .await;
+        loop {
+            let message;
+            (message, cursor) = self
+                .run(move |world| {
+                    let message = cursor.read(world.resource::<Messages<M>>()).next().cloned();
+                    (message, cursor)
+                })
+                .await;
+            if let Some(message) = message {
+                return message;
+            }
+            self.next_frame().await;
+        }
+    }
+
+    /// Waits for the next trigger of the event `E`, and returns it.
+    ///
+    /// The event is caught by an [`Observer`] spawned as a child of the coroutine entity,
+    /// and the coroutine resumes the next time [`run_coroutines`] runs.
+    pub async fn trigger<E: Event + Clone>(&self) -> E {
+        let event = Arc::new(Mutex::new(None));
+        let slot = event.clone();
+        let coroutine = self.entity;
+        let observer = self
+            .run(move |world| {
+                let observer = Observer::new(move |event: On<E>| {
+                    slot.lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .get_or_insert_with(|| event.event().clone());
+                });
+                world.spawn((observer, ChildOf(coroutine))).id()
+            })
+            .await;
+        loop {
+            self.next_frame().await;
+            let received = event.lock().unwrap_or_else(PoisonError::into_inner).take();
+            if let Some(event) = received {
+                self.run(move |world| world.despawn(observer)).await;
+                return event;
+            }
+        }
+    }
+}
+
+/// A future that yields once.
+struct Yield(bool);
+
+impl Future for Yield {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            Poll::Pending
+        }
+    }
+}
+
+/// [`SystemSet`] for [`run_coroutines`].
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CoroutineSystems;
+
+/// Polls every [`Coroutine`] until it yields without queuing work for the [`World`], or completes.
+///
+/// This is added to the `Update` schedule by `bevy_app`, running while any [`Coroutine`] exists, where it can be
+/// ordered relative to other systems with [`CoroutineSystems`].
+/// Coroutines spawned while this runs are first polled the next time it runs.
+///
+/// A coroutine which panics while being polled is cancelled the next time this runs.
+pub fn run_coroutines(
+    world: &mut World,
+    coroutines: &mut QueryState<Entity, With<Coroutine>>,
+    mut entities: Local<Vec<Entity>>,
+) {
+    entities.extend(coroutines.iter(world));
+    for entity in entities.drain(..) {
+        poll_coroutine(world, entity);
+    }
+}
+
+fn poll_coroutine(world: &mut World, entity: Entity) {
+    let Some(mut coroutine) = world.get_mut::<Coroutine>(entity) else {
+        return;
+    };
+    let state = coroutine.bypass_change_detection().state.get();
+    let (mut future, queue) = match mem::replace(state, CoroutineState::Polling) {
+        CoroutineState::Start(start) => {
+            let queue = Arc::new(Mutex::new(Vec::new()));
+            let future = start(AsyncWorld {
+                entity,
+                queue: queue.clone(),
+            });
+            (future, queue)
+        }
+        CoroutineState::Running { future, queue } => (future, queue),
+        CoroutineState::Polling => {
+            // The future panicked and was dropped, so the coroutine can't be resumed
+            warn!("Coroutine {entity} panicked while being polled, and was cancelled");
+            world.despawn(entity);
+            return;
+        }
+    };
+
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if future.as_mut().poll(&mut cx).is_ready() {
+            if let Ok(entity) = world.get_entity_mut(entity) {
+                entity.despawn();
+            }
+            return;
+        }
+        let commands = mem::take(&mut *queue.lock().unwrap_or_else(PoisonError::into_inner));
+        if commands.is_empty() {
+            break;
+        }
+        for command in commands {
+            command(world);
+        }
+        world.flush();
+    }
+
+    // The coroutine is cancelled if its entity was despawned or its component replaced while polling
+    if let Some(mut coroutine) = world.get_mut::<Coroutine>(entity) {
+        let state = coroutine.bypass_change_detection().state.get();
+        if matches!(state, CoroutineState::Polling) {
+            *state = CoroutineState::Running { future, queue };
+        }
+    }
+}
+
+impl<'w, 's> Commands<'w, 's> {
+    /// Spawns a new entity with a [`Coroutine`] running the future returned by `coroutine`.
+    ///
+    /// See the [`coroutine`](crate::coroutine) module docs for more information.
+    pub fn spawn_coroutine<F, Fut>(&mut self, coroutine: F) -> EntityCommands<'_>
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        self.spawn(Coroutine::new(coroutine))
+    }
+}
+
+impl<'a> EntityCommands<'a> {
+    /// Spawns a new entity with a [`Coroutine`] running the future returned by `coroutine`,
+    /// owned by this entity through the [`CoroutineOf`] relationship.
+    ///
+    /// The coroutine is cancelled when this entity is despawned.
+    pub fn spawn_coroutine<F, Fut>(&mut self, coroutine: F) -> &mut Self
+    where
+        F: FnOnce(AsyncWorld) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let owner = self.id();
+        self.commands()
+            .spawn((Coroutine::new(coroutine), CoroutineOf(owner)));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, system::RunSystemOnce};
+    use core::panic::AssertUnwindSafe;
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    #[derive(Message, Clone)]
+    struct Ping(u32);
+
+    #[derive(Event, Clone)]
+    struct Go(u32);
+
+    fn update(world: &mut World) {
+        world.run_system_once(run_coroutines).unwrap();
+    }
+
+    #[test]
+    fn coroutine_runs_across_frames() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let coroutine = world
+            .spawn(Coroutine::new(|world: AsyncWorld| async move {
+                for _ in 0..3 {
+                    world
+                        .run(|world| world.resource_mut::<Counter>().0 += 1)
+                        .await;
+                    world.next_frame().await;
+                }
+            }))
+            .id();
+
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        update(&mut world);
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 3);
+        assert!(world.get_entity(coroutine).is_ok());
+
+        // The entity is despawned once the coroutine completes
+        update(&mut world);
+        assert!(world.get_entity(coroutine).is_err());
+    }
+
+    #[test]
+    fn coroutine_waits_for_messages_and_triggers() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        world.init_resource::<Messages<Ping>>();
+        world.spawn(Coroutine::new(|world: AsyncWorld| async move {
+            let Ping(ping) = world.message::<Ping>().await;
+            let Go(go) = world.trigger::<Go>().await;
+            world
+                .run(move |world| world.resource_mut::<Counter>().0 = ping + go)
+                .await;
+        }));
+
+        update(&mut world);
+        world.trigger(Go(100));
+        world.write_message(Ping(1));
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 0);
+
+        world.trigger(Go(10));
+        world.trigger(Go(20));
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 11);
+        // The observer is despawned once the event is received
+        assert_eq!(world.query::<&Observer>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn coroutine_is_cancelled_with_its_owner() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let owner = world.spawn_empty().id();
+        world
+            .run_system_once(move |mut commands: Commands| {
+                commands.entity(owner).spawn_coroutine(|world| async move {
+                    world
+                        .wait_until(|world| world.resource::<Counter>().0 > 0)
+                        .await;
+                    world
+                        .run(|world| world.resource_mut::<Counter>().0 = 100)
+                        .await;
+                });
+            })
+            .unwrap();
+
+        update(&mut world);
+        world.despawn(owner);
+        world.resource_mut::<Counter>().0 = 1;
+        update(&mut world);
+        assert_eq!(world.resource::<Counter>().0, 1);
+        assert_eq!(world.query::<&Coroutine>().iter(&world).count(), 0);
+    }
+
+    #[test]
+    fn panicked_coroutine_is_cancelled() {
+        let mut world = World::new();
+        let coroutine = world
+            .spawn(Coroutine::new(|world: AsyncWorld| async move {
+                world.next_frame().await;
+                panic!("coroutine panicked");
+            }))
+            .id();
+
+        update(&mut world);
+        let result = std::panic::catch_unwind(AssertUnwindSafe(|| update(&mut world)));
+        assert!(result.is_err());
+        assert!(world.get_entity(coroutine).is_ok());
+
+        update(&mut world);
+        assert!(world.get_entity(coroutine).is_err());
+    }
+
+    #[test]
+    fn polling_doesnt_trigger_change_detection() {
+        let mut world = World::new();
+        world.spawn(Coroutine::new(|world: AsyncWorld| async move {
+            world.next_frame().await;
+        }));
+        world.clear_trackers();
+
+        update(&mut world);
+        let mut changed = world.query_filtered::<(), Changed<Coroutine>>();
+        assert_eq!(changed.iter(&world).count(), 0);
+    }
+}
@@ -0,0 +1,426 @@
+//! Indexes that look up entities by the value of one of their components.
+//!
+//! Finding the entities with a given component value usually requires iterating over a whole [`Query`].
+//! A [`ComponentIndex`] instead keeps track of the entities holding each value of an immutable component,
+//! so they can be retrieved directly. Register one with [`World::register_component_index`], then use the
+//! [`QueryByIndex`] system parameter to fetch query items by value:
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::index::QueryByIndex;
+//! #[derive(Component, Clone, PartialEq, Eq, Hash)]
+//! #[component(immutable)]
+//! struct Team(u8);
+//!
+//! #[derive(Component)]
+//! struct Health(f32);
+//!
+//! let mut world = World::new();
+//! world.register_component_index::<Team>();
+//! world.spawn((Team(1), Health(10.0)));
+//! world.spawn((Team(2), Health(10.0)));
+//!
+//! fn heal_team_two(mut query: QueryByIndex<Team, &mut Health>) {
+//!     for mut health in query.at_mut(&Team(2)) {
+//!         health.0 += 5.0;
+//!     }
+//! }
+//! # world.run_system_cached(heal_team_two).unwrap();
+//! ```
+//!
+//! Since the component is immutable, its value can only change through insertion and removal,
+//! and the index is kept up to date by the component's `on_insert` and `on_discard` hooks.
+//! The index can be registered at any time: entities that already hold the component are indexed
+//! when it's registered.
+
+use core::{hash::Hash, marker::PhantomData};
+
+use bevy_platform::collections::HashMap;
+use bevy_utils::prelude::DebugName;
+
+use crate::{
+    archetype::ArchetypeFlags,
+    component::{Component, Immutable},
+    entity::{hash_set, Entity, EntityHashSet},
+    lifecycle::HookContext,
+    query::{
+        IterQueryData, QueryData, QueryFilter, QueryManyUniqueIter, QuerySingleError, ROQueryItem,
+    },
+    resource::Resource,
+    system::{Query, Res, SystemParam},
+    world::{DeferredWorld, World},
+};
+
+/// A [`Component`] that can be indexed with a [`ComponentIndex`].
+///
+/// This is implemented for all immutable components that can be used as [`HashMap`] keys.
+pub trait IndexableComponent: Component<Mutability = Immutable> + Eq + Hash + Clone {}
+
+impl<C: Component<Mutability = Immutable> + Eq + Hash + Clone> IndexableComponent for C {}
+
+/// A [`Resource`] mapping each value of the component `C` to the entities holding it.
+///
+/// This is created with [`World::register_component_index`], and is usually accessed through [`QueryByIndex`].
+#[derive(Resource)]
+pub struct ComponentIndex<C: IndexableComponent> {
+    index: HashMap<C, EntityHashSet>,
+}
+
+static EMPTY: EntityHashSet = EntityHashSet::new();
+
+/// Marks that the hooks of `C` maintain its [`ComponentIndex`], so the index can be registered again
+/// after it was removed.
+#[derive(Resource)]
+struct ComponentIndexHooks<C: IndexableComponent>(PhantomData<fn() -> C>);
+
+impl<C: IndexableComponent> ComponentIndex<C> {
+    /// Returns the entities holding the given `value`.
+    pub fn get(&self, value: &C) -> &EntityHashSet {
+        self.index.get(value).unwrap_or(&EMPTY)
+    }
+
+    /// Returns true if an entity holds the given `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.index.contains_key(value)
+    }
+
+    /// Iterates over the values held by entities, along with the entities holding them.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &EntityHashSet)> {
+        self.index.iter()
+    }
+
+    /// Iterates over the values held by entities.
+    pub fn values(&self) -> impl Iterator<Item = &C> {
+        self.index.keys()
+    }
+
+    /// Returns the number of distinct values held by entities.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns true if no entity holds a value of `C`.
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    fn on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let value = world.get::<C>(entity).unwrap().clone();
+        // The index may have been removed before the component, e.g. when the world is cleared
+        let Some(mut index) = world.get_resource_mut::<Self>() else {
+            return;
+        };
+        index.index.entry(value).or_default().insert(entity);
+    }
+
+    fn on_discard(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let value = world.get::<C>(entity).unwrap().clone();
+        let Some(mut index) = world.get_resource_mut::<Self>() else {
+            return;
+        };
+        if let Some(entities) = index.index.get_mut(&value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                index.index.remove(&value);
+            }
+        }
+    }
+}
+
+impl World {
+    /// Starts indexing the entities holding each value of the component `C`, in a [`ComponentIndex`] resource.
+    /// This is used by the [`QueryByIndex`] system parameter.
+    ///
+    /// Entities that already hold `C` are added to the index. Registering an index that already exists
+    /// does nothing, and an index whose resource was removed can be registered again.
+    ///
+    /// # Panics
+    ///
+    /// The index is maintained with the `on_insert` and `on_discard` [component hooks](crate::lifecycle::ComponentHooks)
+    /// of `C`, so this panics if `C` already has one of those hooks for another purpose.
+    pub fn register_component_index<C: IndexableComponent>(&mut self) -> &mut Self {
+        if self.contains_resource::<ComponentIndex<C>>() {
+            return self;
+        }
+        if !self.contains_resource::<ComponentIndexHooks<C>>() {
+            let id = self.register_component::<C>();
+            let hooks = self
+                .components
+                .get_hooks_mut(id)
+                .expect("the component was just registered");
+            if hooks.on_insert.is_some() || hooks.on_discard.is_some() {
+                panic!(
+                    "{} can't be indexed, since it already has an on_insert or on_discard hook",
+                    DebugName::type_name::<C>()
+                );
+            }
+            hooks
+                .on_insert(ComponentIndex::<C>::on_insert)
+                .on_discard(ComponentIndex::<C>::on_discard);
+            // Archetypes that already contain `C` cache whether its hooks need to run.
+            self.archetypes.update_flags(
+                id,
+                ArchetypeFlags::ON_INSERT_HOOK | ArchetypeFlags::ON_DISCARD_HOOK,
+                true,
+            );
+            self.insert_resource(ComponentIndexHooks::<C>(PhantomData));
+        }
+
+        let mut index = HashMap::<C, EntityHashSet>::default();
+        for (entity, value) in self.query::<(Entity, &C)>().iter(self) {
+            index.entry(value.clone()).or_default().insert(entity);
+        }
+        self.insert_resource(ComponentIndex::<C> { index });
+        self
+    }
+}
+
+/// A [`SystemParam`] that fetches the items of a [`Query`] for the entities holding a given value of the
+/// component `C`, using its [`ComponentIndex`].
+///
+/// The [`ComponentIndex`] must have been registered with [`World::register_component_index`],
+/// otherwise systems using this parameter fail validation.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::index::QueryByIndex;
+/// #[derive(Component, Clone, PartialEq, Eq, Hash)]
+/// #[component(immutable)]
+/// struct GridPosition(i32, i32);
+///
+/// #[derive(Component)]
+/// struct Name(&'static str);
+///
+/// fn who_is_at(query: QueryByIndex<GridPosition, &Name>) {
+///     if let Ok(name) = query.single_at(&GridPosition(3, 4)) {
+///         println!("{} is at (3, 4)", name.0);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(who_is_at);
+/// ```
+#[derive(SystemParam)]
+pub struct QueryByIndex<'w, 's, C, D, F = ()>
+where
+    C: IndexableComponent,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static,
+{
+    index: Res<'w, ComponentIndex<C>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C, D, F> QueryByIndex<'w, 's, C, D, F>
+where
+    C: IndexableComponent,
+    D: QueryData + 'static,
+    F: QueryFilter + 'static,
+{
+    /// Returns the [`ComponentIndex`] of `C`.
+    pub fn index(&self) -> &ComponentIndex<C> {
+        &self.index
+    }
+
+    /// Returns the underlying [`Query`], to access entities regardless of their value of `C`.
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+
+    /// Returns the underlying [`Query`] mutably, to access entities regardless of their value of `C`.
+    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, F> {
+        &mut self.query
+    }
+
+    /// Returns an iterator over the read-only query items of the entities holding the given `value`.
+    ///
+    /// Entities that don't match the query are skipped.
+    pub fn at(&self, value: &C) -> QueryManyUniqueIter<'_, 's, D::ReadOnly, F, hash_set::Iter<'_>> {
+        self.query.iter_many_unique(self.index.get(value))
+    }
+
+    /// Returns an iterator over the query items of the entities holding the given `value`.
+    ///
+    /// Entities that don't match the query are skipped.
+    pub fn at_mut(&mut self, value: &C) -> QueryManyUniqueIter<'_, 's, D, F, hash_set::Iter<'_>>
+    where
+        D: IterQueryData,
+    {
+        self.query.iter_many_unique_mut(self.index.get(value))
+    }
+
+    /// Returns the read-only query item of the only entity holding the given `value`.
+    ///
+    /// Returns an error if no entity or more than one entity holding `value` matches the query.
+    pub fn single_at(&self, value: &C) -> Result<ROQueryItem<'_, 's, D>, QuerySingleError> {
+        let mut items = self.at(value);
+        let first = items.next();
+        let extra = items.next().is_some();
+
+        match (first, extra) {
+            (Some(item), false) => Ok(item),
+            (None, _) => Err(QuerySingleError::NoEntities(DebugName::type_name::<Self>())),
+            (Some(_), _) => Err(QuerySingleError::MultipleEntities(DebugName::type_name::<
+                Self,
+            >())),
+        }
+    }
+
+    /// Returns the entities holding the given `value`, whether they match the query or not.
+    pub fn entities(&self, value: &C) -> impl Iterator<Item = Entity> + '_ {
+        self.index.get(value).iter().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, system::RunSystemOnce};
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
+    #[component(immutable)]
+    struct Team(u8);
+
+    #[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    #[component(immutable)]
+    struct GridPosition(i32, i32);
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Health(u32);
+
+    fn sorted(entities: impl Iterator<Item = Entity>) -> Vec<Entity> {
+        let mut entities: Vec<_> = entities.collect();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn index_follows_insertions_and_removals() {
+        let mut world = World::new();
+        world.register_component_index::<Team>();
+        let a = world.spawn(Team(1)).id();
+        let b = world.spawn(Team(1)).id();
+        let c = world.spawn(Team(2)).id();
+
+        let index = world.resource::<ComponentIndex<Team>>();
+        assert_eq!(
+            sorted(index.get(&Team(1)).iter().copied()),
+            sorted([a, b].into_iter())
+        );
+        assert_eq!(sorted(index.get(&Team(2)).iter().copied()), vec![c]);
+        assert!(index.get(&Team(3)).is_empty());
+
+        // Replacing the value moves the entity to the new value
+        world.entity_mut(a).insert(Team(2));
+        world.entity_mut(b).remove::<Team>();
+        world.despawn(c);
+
+        let index = world.resource::<ComponentIndex<Team>>();
+        assert!(!index.contains(&Team(1)));
+        assert_eq!(sorted(index.get(&Team(2)).iter().copied()), vec![a]);
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn index_hooks_skip_a_removed_index() {
+        let mut world = World::new();
+        world.register_component_index::<Team>();
+        let a = world.spawn(Team(1)).id();
+
+        world.remove_resource::<ComponentIndex<Team>>();
+        world.entity_mut(a).insert(Team(2));
+        world.spawn(Team(1));
+        world.despawn(a);
+        world.clear_all();
+    }
+
+    #[test]
+    fn index_includes_existing_entities() {
+        let mut world = World::new();
+        let a = world.spawn(Team(1)).id();
+        let b = world.spawn((Team(2), Health(10))).id();
+        world.register_component_index::<Team>();
+
+        let index = world.resource::<ComponentIndex<Team>>();
+        assert_eq!(sorted(index.get(&Team(1)).iter().copied()), vec![a]);
+        assert_eq!(sorted(index.get(&Team(2)).iter().copied()), vec![b]);
+
+        // Entities in archetypes that existed before the index was registered are still tracked.
+        world.entity_mut(a).insert(Team(2));
+        world.entity_mut(b).remove::<Team>();
+        let c = world.spawn(Team(1)).id();
+        let index = world.resource::<ComponentIndex<Team>>();
+        assert_eq!(sorted(index.get(&Team(1)).iter().copied()), vec![c]);
+        assert_eq!(sorted(index.get(&Team(2)).iter().copied()), vec![a]);
+    }
+
+    #[test]
+    fn index_can_be_registered_again() {
+        let mut world = World::new();
+        world.register_component_index::<Team>();
+        let a = world.spawn(Team(1)).id();
+
+        world.remove_resource::<ComponentIndex<Team>>();
+        let b = world.spawn(Team(1)).id();
+        world.register_component_index::<Team>();
+
+        let index = world.resource::<ComponentIndex<Team>>();
+        assert_eq!(
+            sorted(index.get(&Team(1)).iter().copied()),
+            sorted([a, b].into_iter())
+        );
+        world.despawn(a);
+        let index = world.resource::<ComponentIndex<Team>>();
+        assert_eq!(sorted(index.get(&Team(1)).iter().copied()), vec![b]);
+    }
+
+    #[test]
+    fn query_by_index() {
+        let mut world = World::new();
+        world.register_component_index::<GridPosition>();
+        world.register_component_index::<Team>();
+        let a = world.spawn((GridPosition(3, 4), Team(1), Health(10))).id();
+        let b = world.spawn((GridPosition(0, 0), Team(1), Health(10))).id();
+        world.spawn((GridPosition(1, 1), Team(2), Health(10)));
+        // Doesn't match the query
+        world.spawn(Team(1));
+
+        world
+            .run_system_once(move |query: QueryByIndex<GridPosition, Entity>| {
+                assert_eq!(query.single_at(&GridPosition(3, 4)).ok(), Some(a));
+                assert!(matches!(
+                    query.single_at(&GridPosition(5, 5)),
+                    Err(QuerySingleError::NoEntities(_))
+                ));
+            })
+            .unwrap();
+
+        world
+            .run_system_once(
+                |mut query: QueryByIndex<Team, &mut Health, With<GridPosition>>| {
+                    for mut health in query.at_mut(&Team(1)) {
+                        health.0 += 5;
+                    }
+                    assert_eq!(query.at(&Team(1)).count(), 2);
+                    assert_eq!(query.entities(&Team(1)).count(), 3);
+                },
+            )
+            .unwrap();
+
+        assert_eq!(world.get::<Health>(a), Some(&Health(15)));
+        assert_eq!(world.get::<Health>(b), Some(&Health(15)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_requires_free_hooks() {
+        #[derive(Component, Clone, PartialEq, Eq, Hash)]
+        #[component(immutable, on_insert = noop)]
+        struct Hooked;
+
+        fn noop(_world: DeferredWorld, _context: HookContext) {}
+
+        World::new().register_component_index::<Hooked>();
+    }
+}
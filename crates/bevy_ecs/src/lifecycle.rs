@@ -13,7 +13,7 @@
 //!
 //! # Types of lifecycle events
 //!
-//! There are five types of lifecycle events, split into two categories. First, we have lifecycle events that are triggered
+//! There are five types of structural lifecycle events, split into two categories. First, we have lifecycle events that are triggered
 //! when a component is added to an entity:
 //!
 //! - [`Add`]: Triggered when a component is added to an entity that did not already have it.
@@ -29,6 +29,11 @@
 //!
 //! [`Discard`] hooks are evaluated before [`Remove`], then finally [`Despawn`] hooks are evaluated.
 //!
+//! Finally, components that opt in with `#[component(observe_mutations)]` trigger a [`Mutate`] event when they are
+//! changed in place through [`Mut`](crate::change_detection::Mut). Unlike the other lifecycle events, [`Mutate`]
+//! is not triggered immediately: mutations are batched and observers run when commands are applied at the end of
+//! a schedule or at a sync point, or when [`World::trigger_mutations`] is called. [`Mutate`] has no hooks.
+//!
 //! [`Add`] and [`Remove`] are counterparts: they are only triggered when a component is added or removed
 //! from an entity in such a way as to cause a change in the component's presence on that entity.
 //! Similarly, [`Insert`] and [`Discard`] are counterparts: they are triggered when a component is added or overwritten
@@ -64,6 +69,8 @@ use crate::{
     world::{unsafe_world_cell::UnsafeWorldCell, DeferredWorld, World},
 };
 
+use bevy_platform::collections::HashSet;
+use concurrent_queue::ConcurrentQueue;
 use derive_more::derive::Into;
 
 #[cfg(feature = "bevy_reflect")]
@@ -324,6 +331,8 @@ pub const DISCARD: EventKey = EventKey(ComponentId::new(crate::component::DISCAR
 pub const REMOVE: EventKey = EventKey(ComponentId::new(crate::component::REMOVE));
 /// [`EventKey`] for [`Despawn`]
 pub const DESPAWN: EventKey = EventKey(ComponentId::new(crate::component::DESPAWN));
+/// [`EventKey`] for [`Mutate`]
+pub const MUTATE: EventKey = EventKey(ComponentId::new(crate::component::MUTATE));
 
 /// Trigger emitted when a component is inserted onto an entity that does not already have that
 /// component. Runs before `Insert`.
@@ -393,6 +402,112 @@ pub struct Despawn {
     pub entity: Entity,
 }
 
+/// Trigger emitted when a component which opted in with `#[component(observe_mutations)]` has been changed in place
+/// through [`Mut`](crate::change_detection::Mut).
+///
+/// Mutations are batched: this is triggered once for each mutated entity when commands are applied at the end of
+/// a schedule or at a sync point, or when [`World::trigger_mutations`] is called, no matter how many times its
+/// components were written in between. Components that were added or replaced with an insert since the last
+/// batch trigger [`Add`] and [`Insert`] instead.
+///
+/// Like [`Changed`](crate::query::Changed), this detects calls to
+/// [`DerefMut`] and [`set_changed`](crate::change_detection::DetectChangesMut::set_changed),
+/// even if the value didn't actually change.
+#[derive(Debug, Clone, EntityEvent)]
+#[entity_event(trigger = EntityComponentsTrigger<'a>)]
+#[cfg_attr(feature = "bevy_reflect", derive(Reflect))]
+#[cfg_attr(feature = "bevy_reflect", reflect(Debug))]
+#[doc(alias = "OnMutate")]
+#[doc(alias = "OnChange")]
+pub struct Mutate {
+    /// The entity whose components were mutated.
+    pub entity: Entity,
+}
+
+/// Keeps track of the components to report through [`Mutate`] in the next batch.
+#[derive(Debug)]
+pub(crate) struct MutationTracker {
+    /// Observed components which were mutably dereferenced since the last batch.
+    /// Each [`Mut`](crate::change_detection::Mut) pushes its component at most once.
+    pub(crate) mutated: ConcurrentQueue<(Entity, ComponentId)>,
+    /// Observed components added or replaced with an insert since the last batch, which are not reported as mutations.
+    pub(crate) inserted: HashSet<(Entity, ComponentId)>,
+}
+
+impl Default for MutationTracker {
+    fn default() -> Self {
+        Self {
+            mutated: ConcurrentQueue::unbounded(),
+            inserted: HashSet::default(),
+        }
+    }
+}
+
+/// Records the first mutable access through a [`Mut`](crate::change_detection::Mut) of a component which opted
+/// in with `#[component(observe_mutations)]`, so that it is reported through [`Mutate`].
+#[derive(Clone, Copy)]
+pub(crate) struct MutationRecord<'w> {
+    mutated: &'w ConcurrentQueue<(Entity, ComponentId)>,
+    entity: Entity,
+    component_id: ComponentId,
+}
+
+impl<'w> MutationRecord<'w> {
+    /// Creates a record of a mutation of `component_id` on `entity`.
+    #[inline]
+    pub(crate) fn new(
+        mutated: &'w ConcurrentQueue<(Entity, ComponentId)>,
+        entity: Entity,
+        component_id: ComponentId,
+    ) -> Self {
+        Self {
+            mutated,
+            entity,
+            component_id,
+        }
+    }
+
+    /// Queues the mutated component for the next batch of [`Mutate`] events.
+    #[inline]
+    pub(crate) fn record(self) {
+        // The queue is unbounded and never closed, so this can't fail.
+        let _ = self.mutated.push((self.entity, self.component_id));
+    }
+}
+
+/// Like [`MutationRecord`], for the components of a whole table accessed through
+/// [`ContiguousMut`](crate::change_detection::ContiguousMut).
+#[derive(Clone, Copy)]
+pub(crate) struct ContiguousMutationRecord<'w> {
+    mutated: &'w ConcurrentQueue<(Entity, ComponentId)>,
+    entities: &'w [Entity],
+    component_id: ComponentId,
+}
+
+impl<'w> ContiguousMutationRecord<'w> {
+    /// Creates a record of a mutation of `component_id` on all of `entities`.
+    #[inline]
+    pub(crate) fn new(
+        mutated: &'w ConcurrentQueue<(Entity, ComponentId)>,
+        entities: &'w [Entity],
+        component_id: ComponentId,
+    ) -> Self {
+        Self {
+            mutated,
+            entities,
+            component_id,
+        }
+    }
+
+    /// Queues the mutated components for the next batch of [`Mutate`] events.
+    pub(crate) fn record(self) {
+        for &entity in self.entities {
+            // The queue is unbounded and never closed, so this can't fail.
+            let _ = self.mutated.push((entity, self.component_id));
+        }
+    }
+}
+
 /// Wrapper around [`Entity`] for [`RemovedComponents`].
 /// Internally, `RemovedComponents` uses these as an [`Messages<RemovedComponentEntity>`].
 #[derive(Message, Debug, Clone, Into)]
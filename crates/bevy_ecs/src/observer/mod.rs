@@ -587,6 +587,64 @@ mod tests {
         assert_eq!(vec!["discard", "insert"], world.resource::<Order>().0);
     }
 
+    #[test]
+    fn observer_mutate() {
+        #[derive(Component)]
+        #[component(observe_mutations)]
+        struct M(u32);
+
+        #[derive(Component)]
+        #[component(observe_mutations, storage = "SparseSet")]
+        struct N(u32);
+
+        #[derive(Resource, Default)]
+        struct Mutated(Vec<(Entity, usize)>);
+
+        let mut world = World::new();
+        world.init_resource::<Mutated>();
+        world.add_observer(|mutate: On<Mutate, M>, mut mutated: ResMut<Mutated>| {
+            mutated
+                .0
+                .push((mutate.entity, mutate.trigger().components.len()));
+        });
+
+        let a = world.spawn((M(0), N(0), A)).id();
+        let b = world.spawn(M(0)).id();
+        let c = world.spawn(M(0)).id();
+        world.trigger_mutations();
+        assert!(world.resource::<Mutated>().0.is_empty());
+
+        // Writes are batched, and components are reported together.
+        world.get_mut::<M>(a).unwrap().0 += 1;
+        world.get_mut::<M>(a).unwrap().0 += 1;
+        world.get_mut::<N>(a).unwrap().0 += 1;
+        // Replacing the component triggers `Insert` instead.
+        world.get_mut::<M>(b).unwrap().0 += 1;
+        world.entity_mut(b).insert(M(1));
+        // So does adding it, even if it's mutated afterwards.
+        let d = world.spawn(M(0)).id();
+        world.get_mut::<M>(d).unwrap().0 += 1;
+        // Reading or bypassing change detection doesn't trigger anything.
+        let _ = world.get::<M>(c).unwrap().0;
+        world.get_mut::<M>(c).unwrap().bypass_change_detection().0 += 1;
+        world.trigger_mutations();
+        assert_eq!(world.resource::<Mutated>().0, vec![(a, 2)]);
+        world.despawn(d);
+
+        world.trigger_mutations();
+        assert_eq!(world.resource::<Mutated>().0.len(), 1);
+
+        // Mutations are reported when a schedule applies commands.
+        let mut schedule = Schedule::default();
+        schedule.add_systems(|mut query: Query<&mut M>| {
+            for mut m in &mut query {
+                m.0 += 1;
+            }
+        });
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Mutated>().0[1..], [(a, 1), (b, 1), (c, 1)]);
+    }
+
     #[test]
     fn observer_order_recursive() {
         let mut world = World::new();
@@ -7,6 +7,7 @@ use crate::{
     },
     component::{Component, ComponentId, Components, Mutable, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    lifecycle::{ContiguousMutationRecord, MutationRecord},
     query::{
         access_iter::{EcsAccessLevel, EcsAccessType},
         Access, DebugCheckedUnwrap, FilteredAccess, FilteredAccessSet, QueryFilter, QueryState,
@@ -21,6 +22,7 @@ use crate::{
 };
 use bevy_ptr::{ThinSlicePtr, UnsafeCellDeref};
 use bevy_utils::prelude::DebugName;
+use concurrent_queue::ConcurrentQueue;
 use core::{cell::UnsafeCell, iter, marker::PhantomData, panic::Location};
 use variadics_please::all_tuples;
 
@@ -2242,6 +2244,7 @@ pub struct WriteFetch<'w, T: Component> {
     >,
     last_run: Tick,
     this_run: Tick,
+    mutated: &'w ConcurrentQueue<(Entity, ComponentId)>,
 }
 
 impl<T: Component> Clone for WriteFetch<'_, T> {
@@ -2285,6 +2288,7 @@ unsafe impl<'__w, T: Component> WorldQuery for &'__w mut T {
             ),
             last_run,
             this_run,
+            mutated: world.mutated_components(),
         }
     }
 
@@ -2373,11 +2377,13 @@ unsafe impl<'__w, T: Component<Mutability = Mutable>> QueryData for &'__w mut T
 
     #[inline(always)]
     unsafe fn fetch<'w, 's>(
-        _state: &'s Self::State,
+        &component_id: &'s Self::State,
         fetch: &mut Self::Fetch<'w>,
         entity: Entity,
         table_row: TableRow,
     ) -> Option<Self::Item<'w, 's>> {
+        let mutation =
+            T::OBSERVE_MUTATIONS.then(|| MutationRecord::new(fetch.mutated, entity, component_id));
         Some(fetch.components.extract(
             |table| {
                 // SAFETY: set_table was previously called
@@ -2402,6 +2408,7 @@ unsafe impl<'__w, T: Component<Mutability = Mutable>> QueryData for &'__w mut T
                         changed_by: caller.map(|caller| caller.deref_mut()),
                         this_run: fetch.this_run,
                         last_run: fetch.last_run,
+                        mutation,
                     },
                 }
             },
@@ -2416,11 +2423,10 @@ unsafe impl<'__w, T: Component<Mutability = Mutable>> QueryData for &'__w mut T
 
                 Mut {
                     value: component.assert_unique().deref_mut(),
-                    ticks: ComponentTicksMut::from_tick_cells(
-                        ticks,
-                        fetch.last_run,
-                        fetch.this_run,
-                    ),
+                    ticks: ComponentTicksMut {
+                        mutation,
+                        ..ComponentTicksMut::from_tick_cells(ticks, fetch.last_run, fetch.this_run)
+                    },
                 }
             },
         ))
@@ -2449,7 +2455,7 @@ impl<T: Component<Mutability = Mutable>> ContiguousQueryData for &mut T {
     type Contiguous<'w, 's> = ContiguousMut<'w, T>;
 
     unsafe fn fetch_contiguous<'w, 's>(
-        _state: &'s Self::State,
+        &component_id: &'s Self::State,
         fetch: &mut Self::Fetch<'w>,
         entities: &'w [Entity],
     ) -> Self::Contiguous<'w, 's> {
@@ -2462,19 +2468,24 @@ impl<T: Component<Mutability = Mutable>> ContiguousQueryData for &mut T {
                 ContiguousMut {
                     // SAFETY: `entities` has the same length as the rows in the set table.
                     value: unsafe { table_components.as_mut_slice_unchecked(entities.len()) },
-                    // SAFETY:
-                    // - The caller ensures the permission to access ticks.
-                    // - `entities` has the same length as the rows in the set table hence the
-                    // ticks.
-                    ticks: unsafe {
-                        ContiguousComponentTicksMut::from_slice_ptrs(
-                            added_ticks,
-                            changed_ticks,
-                            callers,
-                            entities.len(),
-                            fetch.this_run,
-                            fetch.last_run,
-                        )
+                    ticks: ContiguousComponentTicksMut {
+                        mutation: T::OBSERVE_MUTATIONS.then(|| {
+                            ContiguousMutationRecord::new(fetch.mutated, entities, component_id)
+                        }),
+                        // SAFETY:
+                        // - The caller ensures the permission to access ticks.
+                        // - `entities` has the same length as the rows in the set table hence the
+                        // ticks.
+                        ..unsafe {
+                            ContiguousComponentTicksMut::from_slice_ptrs(
+                                added_ticks,
+                                changed_ticks,
+                                callers,
+                                entities.len(),
+                                fetch.this_run,
+                                fetch.last_run,
+                            )
+                        }
                     },
                 }
             },
@@ -0,0 +1,878 @@
+use crate::{
+    bundle::Bundle,
+    change_detection::{Mut, Tick},
+    component::ComponentInfo,
+    entity::{Entity, EntityHashMap, EntityMapper},
+    reflect::{AppTypeRegistry, ReflectComponent},
+    relationship::RelationshipAccessor,
+    resource::Resource,
+    system::{Commands, EntityCommands},
+    world::{EntityWorldMut, World},
+};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
+use bevy_reflect::{PartialReflect, TypeRegistry};
+use core::any::TypeId;
+
+/// A [`Resource`] storing a history of reversible changes to entities, used to implement undo and redo.
+///
+/// Changes are recorded with [`UndoStack::record`] or one of the undoable commands of
+/// [`UndoCommandsExt`] and [`UndoEntityCommandsExt`]. Nothing is recorded while this resource
+/// is not present in the [`World`].
+///
+/// Every recorded change stores the reflected values of the affected components before and after
+/// the change, so it can be reverted with [`UndoStack::undo`] and reapplied with [`UndoStack::redo`].
+/// Only components registered with [`ReflectComponent`] in the [`AppTypeRegistry`] are recorded.
+/// [`RelationshipTarget`](crate::relationship::RelationshipTarget) components are not recorded either,
+/// since they are kept up to date by their relationships.
+///
+/// Changes are grouped into transactions, which are undone and redone as a whole. By default every
+/// recorded change is its own transaction, while [`begin_transaction`](UndoStack::begin_transaction)
+/// and [`commit_transaction`](UndoStack::commit_transaction) group all changes in between.
+///
+/// Undoing the despawn of an entity spawns it again with a new id. The stack keeps track of these ids,
+/// so that later changes to the entity can still be undone and redone, and entity references in the
+/// restored components are mapped with [`ReflectComponent::map_entities`].
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::reflect::{AppTypeRegistry, ReflectComponent, UndoEntityCommandsExt, UndoStack};
+/// # use bevy_reflect::Reflect;
+/// #[derive(Component, Reflect, PartialEq, Debug)]
+/// #[reflect(Component)]
+/// struct Health(u32);
+///
+/// let mut world = World::new();
+/// world.init_resource::<AppTypeRegistry>();
+/// world.resource::<AppTypeRegistry>().write().register::<Health>();
+/// world.init_resource::<UndoStack>();
+///
+/// let entity = world.spawn(Health(10)).id();
+/// world.commands().entity(entity).insert_undoable(Health(5));
+/// world.flush();
+/// assert_eq!(world.get::<Health>(entity), Some(&Health(5)));
+///
+/// UndoStack::undo(&mut world);
+/// assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+///
+/// UndoStack::redo(&mut world);
+/// assert_eq!(world.get::<Health>(entity), Some(&Health(5)));
+/// ```
+#[derive(Resource, Default)]
+pub struct UndoStack {
+    undo: Vec<UndoTransaction>,
+    redo: Vec<UndoTransaction>,
+    open: Option<UndoTransaction>,
+    depth: usize,
+    limit: Option<usize>,
+    /// Maps the ids changes were recorded with to the ids of the entities which were respawned since.
+    current_ids: EntityHashMap<Entity>,
+    /// The inverse of `current_ids`.
+    recorded_ids: EntityHashMap<Entity>,
+}
+
+impl UndoStack {
+    /// Creates an empty [`UndoStack`] without a limit on the number of transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty [`UndoStack`] which keeps at most `limit` transactions to undo.
+    pub fn with_limit(limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the maximum number of transactions to keep, if any.
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Sets the maximum number of transactions to keep, dropping the oldest ones if necessary.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        self.apply_limit();
+    }
+
+    /// Returns `true` if there is a transaction to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+            || self
+                .open
+                .as_ref()
+                .is_some_and(|open| !open.changes.is_empty())
+    }
+
+    /// Returns `true` if there is a transaction to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Returns the labels of the transactions which can be undone, starting with the oldest.
+    pub fn undo_labels(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.undo.iter().map(|transaction| &*transaction.label)
+    }
+
+    /// Returns the labels of the transactions which can be redone, starting with the last undone.
+    pub fn redo_labels(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.redo
+            .iter()
+            .rev()
+            .map(|transaction| &*transaction.label)
+    }
+
+    /// Removes all recorded transactions.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.open = None;
+        self.depth = 0;
+        self.current_ids.clear();
+        self.recorded_ids.clear();
+    }
+
+    /// Starts a transaction, grouping all changes recorded until the matching
+    /// [`commit_transaction`](Self::commit_transaction) into a single undo step.
+    ///
+    /// Transactions can be nested, in which case the label of the outermost one is used.
+    pub fn begin_transaction(&mut self, label: impl Into<Cow<'static, str>>) {
+        if self.depth == 0 {
+            self.open = Some(UndoTransaction::new(label.into()));
+        }
+        self.depth += 1;
+    }
+
+    /// Ends a transaction started with [`begin_transaction`](Self::begin_transaction).
+    ///
+    /// Does nothing if no transaction is in progress.
+    pub fn commit_transaction(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            self.close_transaction();
+        }
+    }
+
+    /// Runs `f` and records the changes it makes to `entities` as an undoable change.
+    ///
+    /// Entities that do not exist before `f` runs but do afterwards are recorded as spawned,
+    /// while entities that exist before but not afterwards are recorded as despawned.
+    /// Nothing is recorded if the [`UndoStack`] or [`AppTypeRegistry`] resources are not present.
+    pub fn record<R>(
+        world: &mut World,
+        label: impl Into<Cow<'static, str>>,
+        entities: &[Entity],
+        f: impl FnOnce(&mut World) -> R,
+    ) -> R {
+        Self::record_if(world, label, entities, f, |_| true)
+    }
+
+    /// Like [`record`](Self::record), but for changes which can fail part-way.
+    ///
+    /// If `f` returns an error, the changes it made to `entities` are reverted and nothing is recorded.
+    pub fn try_record<T, E>(
+        world: &mut World,
+        label: impl Into<Cow<'static, str>>,
+        entities: &[Entity],
+        f: impl FnOnce(&mut World) -> Result<T, E>,
+    ) -> Result<T, E> {
+        Self::record_if(world, label, entities, f, Result::is_ok)
+    }
+
+    /// Records the changes made by `f` if `keep` returns `true` for its result, and reverts them otherwise.
+    fn record_if<R>(
+        world: &mut World,
+        label: impl Into<Cow<'static, str>>,
+        entities: &[Entity],
+        f: impl FnOnce(&mut World) -> R,
+        keep: impl FnOnce(&R) -> bool,
+    ) -> R {
+        let Some(registry) = world
+            .get_resource::<AppTypeRegistry>()
+            .filter(|_| world.contains_resource::<Self>())
+            .cloned()
+        else {
+            return f(world);
+        };
+        let registry = registry.read();
+
+        let before = {
+            let stack = world.resource::<Self>();
+            entities
+                .iter()
+                .map(|&entity| capture_entity(world, entity, &registry, &stack.recorded_ids))
+                .collect::<Vec<_>>()
+        };
+        // Make sure every change made by `f` has a newer change tick than the captured values.
+        world.increment_change_tick();
+        let result = f(world);
+        let keep = keep(&result);
+
+        world.resource_scope(|world, mut stack: Mut<Self>| {
+            let changes = entities
+                .iter()
+                .zip(before)
+                .filter_map(|(&entity, before)| {
+                    let after = capture_entity(world, entity, &registry, &stack.recorded_ids);
+                    EntityChange::diff(stack.recorded_id(entity), before, after)
+                })
+                .collect::<Vec<_>>();
+            if keep {
+                stack.push(label.into(), changes);
+            } else {
+                let mut transaction = UndoTransaction::new(label.into());
+                transaction.changes = changes;
+                transaction.apply(world, &registry, &mut stack, false);
+            }
+        });
+        result
+    }
+
+    /// Despawns `entity` and records the despawn as an undoable change, returning `true` if it existed.
+    ///
+    /// Entities despawned along with `entity`, like its [`Children`](crate::hierarchy::Children),
+    /// are recorded as well, so undoing the despawn restores them too.
+    pub fn record_despawn(
+        world: &mut World,
+        label: impl Into<Cow<'static, str>>,
+        entity: Entity,
+    ) -> bool {
+        let mut entities = Vec::new();
+        collect_linked_entities(world, entity, &mut entities);
+        Self::record(world, label, &entities, |world| world.despawn(entity))
+    }
+
+    /// Reverts the most recent transaction, returning its label.
+    ///
+    /// Commits any transaction in progress first.
+    /// Returns `None` if there is nothing to undo or the [`UndoStack`] resource is not present.
+    pub fn undo(world: &mut World) -> Option<Cow<'static, str>> {
+        Self::step(world, true)
+    }
+
+    /// Reapplies the most recently undone transaction, returning its label.
+    ///
+    /// Returns `None` if there is nothing to redo or the [`UndoStack`] resource is not present.
+    pub fn redo(world: &mut World) -> Option<Cow<'static, str>> {
+        Self::step(world, false)
+    }
+
+    fn step(world: &mut World, undo: bool) -> Option<Cow<'static, str>> {
+        let registry = world.get_resource::<AppTypeRegistry>()?.clone();
+        let registry = registry.read();
+        let transaction = {
+            let mut stack = world.get_resource_mut::<Self>()?;
+            stack.depth = 0;
+            stack.close_transaction();
+            if undo {
+                stack.undo.pop()
+            } else {
+                stack.redo.pop()
+            }
+        }?;
+
+        world.resource_scope(|world, mut stack: Mut<Self>| {
+            transaction.apply(world, &registry, &mut stack, !undo);
+            let label = transaction.label.clone();
+            if undo {
+                stack.redo.push(transaction);
+            } else {
+                stack.undo.push(transaction);
+            }
+            Some(label)
+        })
+    }
+
+    fn push(&mut self, label: Cow<'static, str>, changes: Vec<EntityChange>) {
+        if changes.is_empty() {
+            return;
+        }
+        if let Some(open) = &mut self.open {
+            open.changes.extend(changes);
+        } else {
+            let mut transaction = UndoTransaction::new(label);
+            transaction.changes = changes;
+            self.redo.clear();
+            self.undo.push(transaction);
+            self.apply_limit();
+        }
+    }
+
+    fn close_transaction(&mut self) {
+        if let Some(open) = self.open.take()
+            && !open.changes.is_empty()
+        {
+            self.redo.clear();
+            self.undo.push(open);
+            self.apply_limit();
+        }
+    }
+
+    fn apply_limit(&mut self) {
+        if let Some(limit) = self.limit
+            && self.undo.len() > limit
+        {
+            self.undo.drain(..self.undo.len() - limit);
+        }
+    }
+
+    fn current_id(&self, recorded: Entity) -> Entity {
+        self.current_ids.get(&recorded).copied().unwrap_or(recorded)
+    }
+
+    fn recorded_id(&self, current: Entity) -> Entity {
+        self.recorded_ids.get(&current).copied().unwrap_or(current)
+    }
+
+    fn remap(&mut self, recorded: Entity, current: Entity) {
+        if let Some(previous) = self.current_ids.insert(recorded, current) {
+            self.recorded_ids.remove(&previous);
+        }
+        self.recorded_ids.insert(current, recorded);
+    }
+}
+
+struct UndoTransaction {
+    label: Cow<'static, str>,
+    changes: Vec<EntityChange>,
+}
+
+impl UndoTransaction {
+    fn new(label: Cow<'static, str>) -> Self {
+        Self {
+            label,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Applies the changes of this transaction in the given direction.
+    ///
+    /// Entities are spawned first and despawned last, so that restored components
+    /// can refer to any entity of the transaction.
+    fn apply(&self, world: &mut World, registry: &TypeRegistry, stack: &mut UndoStack, redo: bool) {
+        let changes = || {
+            let changes = self.changes.iter();
+            let ordered: Box<dyn Iterator<Item = &EntityChange>> = if redo {
+                Box::new(changes)
+            } else {
+                Box::new(changes.rev())
+            };
+            ordered.map(move |change| (change, change.states(redo)))
+        };
+
+        for (change, (exists_from, exists_to)) in changes() {
+            if !exists_from && exists_to {
+                let entity = world.spawn_empty().id();
+                stack.remap(change.entity, entity);
+            }
+        }
+        for (change, (_, exists_to)) in changes() {
+            if exists_to {
+                change.apply_components(world, registry, stack, redo);
+            }
+        }
+        for (change, (exists_from, exists_to)) in changes() {
+            if exists_from
+                && !exists_to
+                && let Ok(entity) = world.get_entity_mut(stack.current_id(change.entity))
+            {
+                entity.despawn();
+            }
+        }
+    }
+}
+
+struct EntityChange {
+    entity: Entity,
+    existed_before: bool,
+    exists_after: bool,
+    components: Vec<ComponentChange>,
+}
+
+struct ComponentChange {
+    type_id: TypeId,
+    before: Option<Box<dyn PartialReflect>>,
+    after: Option<Box<dyn PartialReflect>>,
+}
+
+struct CapturedComponent {
+    type_id: TypeId,
+    changed: Tick,
+    value: Box<dyn PartialReflect>,
+}
+
+impl EntityChange {
+    fn diff(
+        entity: Entity,
+        before: Option<Vec<CapturedComponent>>,
+        after: Option<Vec<CapturedComponent>>,
+    ) -> Option<Self> {
+        let existed_before = before.is_some();
+        let exists_after = after.is_some();
+        let mut before = before.unwrap_or_default();
+
+        let mut components = Vec::new();
+        for after in after.unwrap_or_default() {
+            match before
+                .iter()
+                .position(|before| before.type_id == after.type_id)
+            {
+                Some(index) => {
+                    let before = before.swap_remove(index);
+                    if before.changed != after.changed {
+                        components.push(ComponentChange {
+                            type_id: after.type_id,
+                            before: Some(before.value),
+                            after: Some(after.value),
+                        });
+                    }
+                }
+                None => components.push(ComponentChange {
+                    type_id: after.type_id,
+                    before: None,
+                    after: Some(after.value),
+                }),
+            }
+        }
+        components.extend(before.into_iter().map(|before| ComponentChange {
+            type_id: before.type_id,
+            before: Some(before.value),
+            after: None,
+        }));
+
+        let unchanged = existed_before == exists_after && components.is_empty();
+        (!unchanged && (existed_before || exists_after)).then_some(Self {
+            entity,
+            existed_before,
+            exists_after,
+            components,
+        })
+    }
+
+    /// Returns whether the entity exists before and after applying this change in the given direction.
+    fn states(&self, redo: bool) -> (bool, bool) {
+        if redo {
+            (self.existed_before, self.exists_after)
+        } else {
+            (self.exists_after, self.existed_before)
+        }
+    }
+
+    fn apply_components(
+        &self,
+        world: &mut World,
+        registry: &TypeRegistry,
+        stack: &UndoStack,
+        redo: bool,
+    ) {
+        let Ok(mut entity) = world.get_entity_mut(stack.current_id(self.entity)) else {
+            return;
+        };
+        let mut current_ids = BorrowedEntityMap(&stack.current_ids);
+        for change in &self.components {
+            let Some(reflect_component) = registry
+                .get(change.type_id)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+            else {
+                continue;
+            };
+            let value = if redo { &change.after } else { &change.before };
+            match value {
+                Some(value) => match value.reflect_clone() {
+                    Ok(mut value) => {
+                        if !stack.current_ids.is_empty() {
+                            reflect_component.map_entities(&mut *value, &mut current_ids);
+                        }
+                        reflect_component.insert(&mut entity, value.as_partial_reflect(), registry);
+                    }
+                    Err(_) => reflect_component.insert(&mut entity, &**value, registry),
+                },
+                None => reflect_component.remove(&mut entity),
+            }
+        }
+    }
+}
+
+/// Captures the reflected values of all recordable components of `entity`,
+/// or `None` if it is not spawned.
+fn capture_entity(
+    world: &World,
+    entity: Entity,
+    registry: &TypeRegistry,
+    recorded_ids: &EntityHashMap<Entity>,
+) -> Option<Vec<CapturedComponent>> {
+    let entity = world.get_entity(entity).ok()?;
+    let mapper = &mut BorrowedEntityMap(recorded_ids);
+    let components = entity
+        .archetype()
+        .components()
+        .iter()
+        .filter_map(|&component_id| {
+            let info = world.components().get_info(component_id)?;
+            if matches!(
+                info.relationship_accessor(),
+                Some(RelationshipAccessor::RelationshipTarget { .. })
+            ) {
+                return None;
+            }
+            let type_id = info.type_id()?;
+            let reflect_component = registry.get(type_id)?.data::<ReflectComponent>()?;
+            let reflected = reflect_component.reflect(entity)?;
+            let value = match reflected.reflect_clone() {
+                Ok(mut value) => {
+                    if !recorded_ids.is_empty() {
+                        reflect_component.map_entities(&mut *value, mapper);
+                    }
+                    value.into_partial_reflect()
+                }
+                Err(_) => reflected.to_dynamic(),
+            };
+            Some(CapturedComponent {
+                type_id,
+                changed: entity.get_change_ticks_by_id(component_id)?.changed,
+                value,
+            })
+        })
+        .collect();
+    Some(components)
+}
+
+/// An [`EntityMapper`] which maps entities with a borrowed map and leaves all others unchanged.
+struct BorrowedEntityMap<'a>(&'a EntityHashMap<Entity>);
+
+impl EntityMapper for BorrowedEntityMap<'_> {
+    fn get_mapped(&mut self, source: Entity) -> Entity {
+        self.0.get(&source).copied().unwrap_or(source)
+    }
+
+    fn set_mapped(&mut self, _source: Entity, _target: Entity) {}
+}
+
+/// Collects `entity` and all entities which are despawned along with it through
+/// relationships with [`LINKED_SPAWN`](crate::relationship::RelationshipTarget::LINKED_SPAWN).
+fn collect_linked_entities(world: &World, entity: Entity, entities: &mut Vec<Entity>) {
+    if entities.contains(&entity) {
+        return;
+    }
+    entities.push(entity);
+    let Ok(entity) = world.get_entity(entity) else {
+        return;
+    };
+    for &component_id in entity.archetype().components() {
+        let Some(RelationshipAccessor::RelationshipTarget {
+            iter,
+            linked_spawn: true,
+            ..
+        }) = world
+            .components()
+            .get_info(component_id)
+            .and_then(ComponentInfo::relationship_accessor)
+        else {
+            continue;
+        };
+        let Ok(ptr) = entity.get_by_id(component_id) else {
+            continue;
+        };
+        // SAFETY: `ptr` points to the component the accessor was registered for.
+        let related = unsafe { iter(ptr) }.collect::<Vec<_>>();
+        for related in related {
+            collect_linked_entities(world, related, entities);
+        }
+    }
+}
+
+/// An extension trait for [`Commands`] to record undoable changes in the [`UndoStack`].
+pub trait UndoCommandsExt {
+    /// Spawns a new entity with the given components, recording the spawn in the [`UndoStack`].
+    fn spawn_undoable(&mut self, bundle: impl Bundle) -> EntityCommands<'_>;
+
+    /// Starts a transaction in the [`UndoStack`]. See [`UndoStack::begin_transaction`].
+    fn begin_undo_transaction(&mut self, label: impl Into<Cow<'static, str>>);
+
+    /// Ends a transaction in the [`UndoStack`]. See [`UndoStack::commit_transaction`].
+    fn commit_undo_transaction(&mut self);
+
+    /// Reverts the most recent transaction in the [`UndoStack`]. See [`UndoStack::undo`].
+    fn undo(&mut self);
+
+    /// Reapplies the most recently undone transaction in the [`UndoStack`]. See [`UndoStack::redo`].
+    fn redo(&mut self);
+}
+
+impl UndoCommandsExt for Commands<'_, '_> {
+    fn spawn_undoable(&mut self, bundle: impl Bundle) -> EntityCommands<'_> {
+        let entity = self.allocator.alloc();
+        self.queue(move |world: &mut World| {
+            UndoStack::try_record(world, "Spawn", &[entity], |world| {
+                world.spawn_at(entity, bundle).map(|_| ())
+            })
+        });
+        self.entity(entity)
+    }
+
+    fn begin_undo_transaction(&mut self, label: impl Into<Cow<'static, str>>) {
+        let label = label.into();
+        self.queue(move |world: &mut World| {
+            if let Some(mut stack) = world.get_resource_mut::<UndoStack>() {
+                stack.begin_transaction(label);
+            }
+        });
+    }
+
+    fn commit_undo_transaction(&mut self) {
+        self.queue(|world: &mut World| {
+            if let Some(mut stack) = world.get_resource_mut::<UndoStack>() {
+                stack.commit_transaction();
+            }
+        });
+    }
+
+    fn undo(&mut self) {
+        self.queue(|world: &mut World| {
+            UndoStack::undo(world);
+        });
+    }
+
+    fn redo(&mut self) {
+        self.queue(|world: &mut World| {
+            UndoStack::redo(world);
+        });
+    }
+}
+
+/// An extension trait for [`EntityCommands`] to record undoable changes in the [`UndoStack`].
+pub trait UndoEntityCommandsExt {
+    /// Adds the given components to the entity, recording the change in the [`UndoStack`].
+    fn insert_undoable(&mut self, bundle: impl Bundle) -> &mut Self;
+
+    /// Removes the components of `B` from the entity, recording the change in the [`UndoStack`].
+    fn remove_undoable<B: Bundle>(&mut self) -> &mut Self;
+
+    /// Runs `f` on the entity, recording the changes it makes in the [`UndoStack`].
+    fn modify_undoable(
+        &mut self,
+        f: impl FnOnce(EntityWorldMut) + Send + Sync + 'static,
+    ) -> &mut Self;
+
+    /// Despawns the entity, recording the despawn in the [`UndoStack`].
+    /// See [`UndoStack::record_despawn`].
+    fn despawn_undoable(&mut self);
+}
+
+impl UndoEntityCommandsExt for EntityCommands<'_> {
+    fn insert_undoable(&mut self, bundle: impl Bundle) -> &mut Self {
+        self.modify_undoable(move |mut entity| {
+            entity.insert(bundle);
+        })
+    }
+
+    fn remove_undoable<B: Bundle>(&mut self) -> &mut Self {
+        self.modify_undoable(|mut entity| {
+            entity.remove::<B>();
+        })
+    }
+
+    fn modify_undoable(
+        &mut self,
+        f: impl FnOnce(EntityWorldMut) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.queue(move |entity: EntityWorldMut| {
+            let id = entity.id();
+            let world = entity.into_world_mut();
+            UndoStack::record(world, "Modify", &[id], |world| f(world.entity_mut(id)));
+        })
+    }
+
+    fn despawn_undoable(&mut self) {
+        self.queue(|entity: EntityWorldMut| {
+            let id = entity.id();
+            UndoStack::record_despawn(entity.into_world_mut(), "Despawn", id);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UndoCommandsExt, UndoEntityCommandsExt, UndoStack};
+    use crate::{
+        component::Component,
+        entity::Entity,
+        hierarchy::{ChildOf, Children},
+        reflect::{AppTypeRegistry, ReflectComponent},
+        world::World,
+    };
+    use bevy_reflect::Reflect;
+
+    #[derive(Component, Reflect, Clone, Copy, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    #[derive(Component, Reflect, Clone, Copy, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Mana(u32);
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        {
+            let registry = world.resource::<AppTypeRegistry>();
+            let mut registry = registry.write();
+            registry.register::<Health>();
+            registry.register::<Mana>();
+            registry.register::<ChildOf>();
+            registry.register::<Children>();
+        }
+        world.init_resource::<UndoStack>();
+        world
+    }
+
+    #[test]
+    fn undo_redo_component_changes() {
+        let mut world = world();
+        let entity = world.spawn(Health(10)).id();
+
+        world.commands().entity(entity).insert_undoable(Mana(1));
+        world
+            .commands()
+            .entity(entity)
+            .modify_undoable(|mut entity| entity.get_mut::<Health>().unwrap().0 = 5);
+        world.commands().entity(entity).remove_undoable::<Health>();
+        world.flush();
+        assert_eq!(world.get::<Health>(entity), None);
+
+        assert_eq!(UndoStack::undo(&mut world).as_deref(), Some("Modify"));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(5)));
+        UndoStack::undo(&mut world);
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        UndoStack::undo(&mut world);
+        assert_eq!(world.get::<Mana>(entity), None);
+        assert!(UndoStack::undo(&mut world).is_none());
+
+        UndoStack::redo(&mut world);
+        UndoStack::redo(&mut world);
+        assert_eq!(world.get::<Mana>(entity), Some(&Mana(1)));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(5)));
+        UndoStack::redo(&mut world);
+        assert_eq!(world.get::<Health>(entity), None);
+        assert!(UndoStack::redo(&mut world).is_none());
+    }
+
+    #[test]
+    fn transactions() {
+        let mut world = world();
+        let entity = world.spawn(Health(10)).id();
+
+        let mut commands = world.commands();
+        commands.begin_undo_transaction("Edit");
+        commands.entity(entity).insert_undoable(Health(1));
+        let spawned = commands.spawn_undoable(Mana(2)).id();
+        commands.commit_undo_transaction();
+        world.flush();
+
+        let stack = world.resource::<UndoStack>();
+        assert_eq!(
+            stack.undo_labels().collect::<alloc::vec::Vec<_>>(),
+            ["Edit"]
+        );
+
+        assert_eq!(UndoStack::undo(&mut world).as_deref(), Some("Edit"));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        assert!(!world.entities().contains_spawned(spawned));
+
+        world.commands().redo();
+        world.flush();
+        assert_eq!(world.get::<Health>(entity), Some(&Health(1)));
+        assert_eq!(world.query::<&Mana>().iter(&world).count(), 1);
+    }
+
+    #[test]
+    fn new_changes_clear_redo() {
+        let mut world = world();
+        let entity = world.spawn(Health(10)).id();
+
+        world.commands().entity(entity).insert_undoable(Health(1));
+        world.flush();
+        UndoStack::undo(&mut world);
+        assert!(world.resource::<UndoStack>().can_redo());
+
+        world.commands().entity(entity).insert_undoable(Health(2));
+        world.flush();
+        assert!(!world.resource::<UndoStack>().can_redo());
+    }
+
+    #[test]
+    fn undo_despawn_with_children() {
+        let mut world = world();
+        let parent = world.spawn(Health(10)).id();
+        world.spawn((Mana(1), ChildOf(parent)));
+        let other = world.spawn(Health(0)).id();
+
+        world.commands().entity(parent).insert_undoable(Health(3));
+        world.commands().entity(parent).despawn_undoable();
+        world.flush();
+        assert_eq!(world.query::<&Mana>().iter(&world).count(), 0);
+
+        UndoStack::undo(&mut world);
+        let (parent, children) = world
+            .query::<(Entity, &Children)>()
+            .single(&world)
+            .map(|(entity, children)| (entity, children.to_vec()))
+            .unwrap();
+        assert_eq!(world.get::<Health>(parent), Some(&Health(3)));
+        assert_eq!(children.len(), 1);
+        assert_eq!(world.get::<Mana>(children[0]), Some(&Mana(1)));
+
+        // Changes recorded before the despawn apply to the respawned entity.
+        UndoStack::undo(&mut world);
+        assert_eq!(world.get::<Health>(parent), Some(&Health(10)));
+
+        UndoStack::redo(&mut world);
+        assert_eq!(world.get::<Health>(parent), Some(&Health(3)));
+        UndoStack::redo(&mut world);
+        assert!(!world.entities().contains_spawned(parent));
+        assert_eq!(world.query::<&Mana>().iter(&world).count(), 0);
+        assert_eq!(world.get::<Health>(other), Some(&Health(0)));
+    }
+
+    #[test]
+    fn limit() {
+        let mut world = world();
+        world.insert_resource(UndoStack::with_limit(2));
+        let entity = world.spawn(Health(0)).id();
+
+        for i in 1..=3 {
+            world.commands().entity(entity).insert_undoable(Health(i));
+            world.flush();
+        }
+        UndoStack::undo(&mut world);
+        UndoStack::undo(&mut world);
+        assert!(UndoStack::undo(&mut world).is_none());
+        assert_eq!(world.get::<Health>(entity), Some(&Health(1)));
+    }
+
+    #[test]
+    fn failed_changes_are_reverted() {
+        let mut world = world();
+        let entity = world.spawn(Health(10)).id();
+
+        let result = UndoStack::try_record(&mut world, "Fail", &[entity], |world| {
+            world.entity_mut(entity).insert((Health(1), Mana(1)));
+            Err::<(), _>("failed")
+        });
+        assert_eq!(result, Err("failed"));
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        assert_eq!(world.get::<Mana>(entity), None);
+        assert!(!world.resource::<UndoStack>().can_undo());
+
+        let spawned = world.entity_allocator().alloc();
+        let result = UndoStack::try_record(&mut world, "Fail", &[spawned], |world| {
+            world.spawn_at(spawned, Health(2)).unwrap();
+            Err::<(), _>("failed")
+        });
+        assert!(result.is_err());
+        assert!(!world.entities().contains_spawned(spawned));
+        assert!(!world.resource::<UndoStack>().can_undo());
+    }
+}
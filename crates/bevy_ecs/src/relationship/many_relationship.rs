@@ -0,0 +1,818 @@
+use alloc::{format, vec, vec::Vec};
+use core::slice;
+
+use bevy_utils::prelude::DebugName;
+use log::warn;
+
+use crate::{
+    change_detection::MaybeLocation,
+    component::{Component, Mutable},
+    entity::{ComponentCloneCtx, Entity, EntityMapper, MapEntities, SourceComponent},
+    lifecycle::HookContext,
+    relationship::{RelationshipHookMode, RelationshipSourceCollection},
+    system::{EntityCommand, EntityCommands},
+    world::{DeferredWorld, EntityWorldMut},
+};
+
+/// A [`Component`] on a "source" [`Entity`] that references any number of target entities, where each link (or "edge")
+/// carries a value of type [`ManyRelationship::Data`]. This creates a many-to-many relationship between entities.
+///
+/// Every [`ManyRelationship`] has a corresponding [`ManyRelationshipTarget`] type (and vice-versa), which exists on each
+/// "target" entity and contains the list of all "source" entities that have an edge to the given "target". Like for
+/// [`Relationship`](crate::relationship::Relationship), the source component is the "source of truth", and component hooks
+/// keep the [`ManyRelationshipTarget`] collections in sync with it.
+///
+/// [`ManyRelationship`] and [`ManyRelationshipTarget`] should always be derived via the [`Component`] trait, using the
+/// `many` flag of the `relationship` and `relationship_target` attributes. The relationship field must be a
+/// [`RelationshipEdges`], and the target collection can be any [`RelationshipSourceCollection`]:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::RelationshipEdges;
+/// /// Items stored in an inventory, along with the slot they occupy.
+/// #[derive(Component)]
+/// #[relationship(relationship_target = StoredIn, many)]
+/// struct Stores(RelationshipEdges<usize>);
+///
+/// /// The inventories an item is stored in.
+/// #[derive(Component)]
+/// #[relationship_target(relationship = Stores, many)]
+/// struct StoredIn(Vec<Entity>);
+///
+/// let mut world = World::new();
+/// let sword = world.spawn_empty().id();
+/// let shield = world.spawn_empty().id();
+/// let chest = world
+///     .spawn(Stores(RelationshipEdges::from_iter([(sword, 0), (shield, 3)])))
+///     .id();
+/// let backpack = world.spawn_empty().insert_edge::<Stores>(sword, 1).id();
+///
+/// assert_eq!(world.get::<StoredIn>(sword).unwrap().0, [chest, backpack]);
+/// assert_eq!(world.get::<Stores>(backpack).unwrap().0.get(sword), Some(&1));
+/// ```
+///
+/// Since the source component is immutable, edges are added and removed with
+/// [`EntityWorldMut::insert_edge`] and [`EntityWorldMut::remove_edge`] (or their [`EntityCommands`] equivalents), or by
+/// inserting a new source component. An entity has at most one edge to any given target.
+///
+/// When the [`ManyRelationshipTarget`] is removed from an entity, or the entity is despawned, the edges pointing to it are
+/// removed from the source entities. A source component which is left without edges is removed. With the
+/// `#[relationship_target(many, linked_spawn)]` attribute, the source entities are despawned along with the target instead.
+///
+/// By default, edges cannot point to their own entity: such edges are removed with a warning. Use
+/// `#[relationship(many, allow_self_referential)]` to allow them.
+pub trait ManyRelationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`ManyRelationship`], which contains the list of all
+    /// "source" entities that have an edge to the "target".
+    type RelationshipTarget: ManyRelationshipTarget<Relationship = Self>;
+
+    /// The data carried by each edge.
+    type Data: Send + Sync + 'static;
+
+    /// If `true`, an edge is allowed to point to its own entity.
+    const ALLOW_SELF_REFERENTIAL: bool = false;
+
+    /// Returns the edges of this relationship.
+    fn edges(&self) -> &RelationshipEdges<Self::Data>;
+
+    /// Returns a mutable reference to the edges of this relationship.
+    ///
+    /// # Warning
+    ///
+    /// This should generally not be called by user code, as modifying the edges could invalidate the relationship.
+    /// Use [`EntityWorldMut::insert_edge`] and [`EntityWorldMut::remove_edge`] instead.
+    fn edges_mut_risky(&mut self) -> &mut RelationshipEdges<Self::Data>;
+
+    /// Creates this [`ManyRelationship`] from the given `edges`.
+    fn from_edges(edges: RelationshipEdges<Self::Data>) -> Self;
+
+    /// The `on_insert` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    fn on_insert(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            caller,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        // Unlike one-to-many relationships, the hook still runs during linked spawns and clones: the source may have
+        // edges to entities that aren't part of the linked hierarchy.
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let targets: Vec<Entity> = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .edges()
+            .targets()
+            .collect();
+
+        let invalid: Vec<Entity> = targets
+            .into_iter()
+            .filter(|&target| !attach_edge::<Self>(&mut world, entity, target, caller))
+            .collect();
+
+        if !invalid.is_empty() {
+            world
+                .commands()
+                .queue_silenced(remove_edges::<Self>(invalid).with_entity(entity));
+        }
+    }
+
+    /// The `on_discard` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`] connection.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        if let RelationshipHookMode::Skip = relationship_hook_mode {
+            return;
+        }
+        let targets: Vec<Entity> = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .edges()
+            .targets()
+            .collect();
+
+        for target in targets {
+            detach_edge::<Self>(&mut world, entity, target);
+        }
+    }
+}
+
+/// Adds `source` to the [`ManyRelationshipTarget`] of `target`, for an edge from `source` to `target`. Returns `false`
+/// if the edge is invalid and has to be removed.
+fn attach_edge<R: ManyRelationship>(
+    world: &mut DeferredWorld,
+    source: Entity,
+    target: Entity,
+    caller: MaybeLocation,
+) -> bool {
+    if !R::ALLOW_SELF_REFERENTIAL && target == source {
+        warn!(
+            "{}The {} edge on entity {source:?} points to itself. The invalid edge has been removed.\nIf this is intended behavior self-referential relations can be enabled with the allow_self_referential attribute: #[relationship(many, allow_self_referential)]",
+            caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+            DebugName::type_name::<R>(),
+        );
+        return false;
+    }
+    let Ok(target_ref) = world.get_entity(target) else {
+        warn!(
+            "{}The {} edge on entity {source:?} relates to an entity that does not exist ({target:?}). The invalid edge has been removed.",
+            caller.map(|location| format!("{location}: ")).unwrap_or_default(),
+            DebugName::type_name::<R>(),
+        );
+        return false;
+    };
+
+    // For target collections which only hold one source, remove the edge of the previous source
+    let current_source_to_remove = target_ref
+        .get::<R::RelationshipTarget>()
+        .and_then(|relationship_target| {
+            relationship_target
+                .collection()
+                .source_to_remove_before_add()
+        })
+        .filter(|current| *current != source);
+    if let Some(current_source) = current_source_to_remove {
+        world
+            .commands()
+            .queue_silenced(remove_edges::<R>(vec![target]).with_entity(current_source));
+    }
+
+    // Deferring is necessary for batch mode
+    world
+        .commands()
+        .entity(target)
+        .entry::<R::RelationshipTarget>()
+        .and_modify(move |mut relationship_target| {
+            let collection = relationship_target.collection_mut_risky();
+            // Linked clones may have added this entity already
+            if !collection.iter().any(|entity| entity == source) {
+                collection.add(source);
+            }
+        })
+        .or_insert_with(move || {
+            let mut relationship_target = R::RelationshipTarget::with_capacity(1);
+            relationship_target.collection_mut_risky().add(source);
+            relationship_target
+        });
+    true
+}
+
+/// Removes `source` from the [`ManyRelationshipTarget`] of `target`, for a removed edge from `source` to `target`.
+fn detach_edge<R: ManyRelationship>(world: &mut DeferredWorld, source: Entity, target: Entity) {
+    if let Ok(mut target_entity_mut) = world.get_entity_mut(target)
+        && let Some(mut relationship_target) = target_entity_mut.get_mut::<R::RelationshipTarget>()
+    {
+        relationship_target.collection_mut_risky().remove(source);
+        if relationship_target.len() == 0 {
+            let command = |mut entity: EntityWorldMut| {
+                // this "remove" operation must check emptiness because in the event that an identical
+                // relationship is inserted on top, this despawn would result in the removal of that identical
+                // relationship ... not what we want!
+                if entity
+                    .get::<R::RelationshipTarget>()
+                    .is_some_and(ManyRelationshipTarget::is_empty)
+                {
+                    entity.remove::<R::RelationshipTarget>();
+                }
+            };
+
+            world.commands().queue_silenced(command.with_entity(target));
+        }
+    }
+}
+
+/// A [`Component`] containing the collection of entities that have an edge to this [`Entity`] via the associated
+/// [`ManyRelationship`] type. See the [`ManyRelationship`] documentation for more information.
+pub trait ManyRelationshipTarget: Component<Mutability = Mutable> + Sized {
+    /// If this is true, when despawning or cloning (when [linked cloning is enabled](crate::entity::EntityClonerBuilder::linked_cloning)),
+    /// the source entities with an edge to this entity will also be despawned or cloned.
+    ///
+    /// This defaults to false when derived.
+    const LINKED_SPAWN: bool;
+    /// The [`ManyRelationship`] that populates this [`ManyRelationshipTarget`] collection.
+    type Relationship: ManyRelationship<RelationshipTarget = Self>;
+    /// The collection type that stores the "source" entities for this [`ManyRelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+    /// Returns a mutable reference to the stored [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationshipTarget`] from the given [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    /// The collection should not contain duplicates.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_discard` component hook that removes the edges pointing to this entity from the source entities.
+    // note: think of this as "on_drop"
+    fn on_discard(
+        mut world: DeferredWorld,
+        HookContext {
+            entity,
+            relationship_hook_mode,
+            ..
+        }: HookContext,
+    ) {
+        match relationship_hook_mode {
+            RelationshipHookMode::Run => {}
+            RelationshipHookMode::Skip | RelationshipHookMode::RunIfNotLinked => return,
+        }
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            commands.queue_silenced(
+                remove_edges::<Self::Relationship>(vec![entity]).with_entity(source_entity),
+            );
+        }
+    }
+
+    /// The `on_despawn` component hook that despawns the source entities with an edge to this entity when it is
+    /// despawned.
+    // note: think of this as "on_drop"
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let (entities, mut commands) = world.entities_and_commands();
+        let relationship_target = entities.get(entity).unwrap().get::<Self>().unwrap();
+        for source_entity in relationship_target.iter() {
+            commands.entity(source_entity).try_despawn();
+        }
+    }
+
+    /// Creates this [`ManyRelationshipTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the source entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> <Self::Collection as RelationshipSourceCollection>::SourceIter<'_> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of source entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this entity collection is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// The "clone behavior" for [`ManyRelationshipTarget`]. The collection is rebuilt by the hooks of the
+/// [`ManyRelationship`] sources of truth, so the original collection is not cloned.
+///
+/// This will also queue up clones of the source entities if the [`EntityCloner`](crate::entity::EntityCloner) is
+/// configured to spawn recursively, and retarget the edges of the source entities when moving the component.
+pub fn clone_many_relationship_target<T: ManyRelationshipTarget>(
+    source: &SourceComponent,
+    context: &mut ComponentCloneCtx,
+) {
+    let Some(component) = source.read::<T>() else {
+        return;
+    };
+    let mut cloned = T::with_capacity(component.len());
+    if context.linked_cloning() && T::LINKED_SPAWN {
+        // The cloned sources add themselves to the cloned collection when their edges are inserted
+        for entity in component.iter() {
+            context.queue_entity_clone(entity);
+        }
+    } else if context.moving() {
+        let (old_target, new_target) = (context.source(), context.target());
+        let collection = cloned.collection_mut_risky();
+        for entity in component.iter() {
+            collection.add(entity);
+            context.queue_deferred(move |world, _mapper| {
+                // We don't want relationships hooks to run because we are manually constructing the collection here
+                _ = DeferredWorld::from(world)
+                    .modify_component_with_relationship_hook_mode::<T::Relationship, ()>(
+                        entity,
+                        RelationshipHookMode::Skip,
+                        |relationship| {
+                            relationship
+                                .edges_mut_risky()
+                                .retarget(old_target, new_target);
+                        },
+                    );
+            });
+        }
+    }
+    context.write_target_component(cloned);
+}
+
+/// Removes the edges to `targets` from a [`ManyRelationship`] without running the relationship hooks, removing the
+/// component if no edges are left.
+fn remove_edges<R: ManyRelationship>(targets: Vec<Entity>) -> impl FnOnce(EntityWorldMut) {
+    move |mut entity: EntityWorldMut| {
+        let source = entity.id();
+        let is_empty = entity.world_scope(|world| {
+            DeferredWorld::from(world).modify_component_with_relationship_hook_mode::<R, bool>(
+                source,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    let edges = relationship.edges_mut_risky();
+                    for target in &targets {
+                        edges.remove(*target);
+                    }
+                    edges.is_empty()
+                },
+            )
+        });
+        if let Ok(Some(true)) = is_empty {
+            entity.update_location();
+            entity.remove::<R>();
+        }
+    }
+}
+
+/// The edges of a [`ManyRelationship`]: a list of target entities, each with a value of type `D`.
+///
+/// The edges keep the order in which they were inserted, and there is at most one edge to any given target: inserting
+/// an edge to an existing target replaces its data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy_reflect", derive(bevy_reflect::Reflect))]
+pub struct RelationshipEdges<D = ()> {
+    edges: Vec<(Entity, D)>,
+}
+
+impl<D> Default for RelationshipEdges<D> {
+    fn default() -> Self {
+        Self { edges: Vec::new() }
+    }
+}
+
+impl<D> RelationshipEdges<D> {
+    /// Creates an empty list of edges.
+    pub const fn new() -> Self {
+        Self { edges: Vec::new() }
+    }
+
+    /// Creates an empty list of edges with space for at least `capacity` edges.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            edges: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Inserts an edge to `target`, returning the data of the edge it replaced, if any.
+    pub fn insert(&mut self, target: Entity, data: D) -> Option<D> {
+        match self.edges.iter_mut().find(|(entity, _)| *entity == target) {
+            Some((_, existing)) => Some(core::mem::replace(existing, data)),
+            None => {
+                self.edges.push((target, data));
+                None
+            }
+        }
+    }
+
+    /// Removes the edge to `target`, returning its data, if any.
+    pub fn remove(&mut self, target: Entity) -> Option<D> {
+        let index = self
+            .edges
+            .iter()
+            .position(|(entity, _)| *entity == target)?;
+        Some(self.edges.remove(index).1)
+    }
+
+    /// Returns the data of the edge to `target`.
+    pub fn get(&self, target: Entity) -> Option<&D> {
+        self.edges
+            .iter()
+            .find(|(entity, _)| *entity == target)
+            .map(|(_, data)| data)
+    }
+
+    /// Returns true if there is an edge to `target`.
+    pub fn contains(&self, target: Entity) -> bool {
+        self.edges.iter().any(|(entity, _)| *entity == target)
+    }
+
+    /// Iterates the edges, as pairs of target entity and data.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Entity, &D)> + ExactSizeIterator {
+        self.edges.iter().map(|(entity, data)| (*entity, data))
+    }
+
+    /// Iterates the target entities of the edges.
+    pub fn targets(&self) -> impl DoubleEndedIterator<Item = Entity> + ExactSizeIterator + '_ {
+        self.edges.iter().map(|(entity, _)| *entity)
+    }
+
+    /// Returns the number of edges.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns true if there are no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Points the edge to `old_target` at `new_target` instead, keeping its data and position.
+    fn retarget(&mut self, old_target: Entity, new_target: Entity) {
+        if let Some((entity, _)) = self
+            .edges
+            .iter_mut()
+            .find(|(entity, _)| *entity == old_target)
+        {
+            *entity = new_target;
+        }
+    }
+}
+
+impl<D> FromIterator<(Entity, D)> for RelationshipEdges<D> {
+    fn from_iter<I: IntoIterator<Item = (Entity, D)>>(iter: I) -> Self {
+        let mut edges = Self::new();
+        for (target, data) in iter {
+            edges.insert(target, data);
+        }
+        edges
+    }
+}
+
+impl FromIterator<Entity> for RelationshipEdges {
+    fn from_iter<I: IntoIterator<Item = Entity>>(iter: I) -> Self {
+        iter.into_iter().map(|target| (target, ())).collect()
+    }
+}
+
+impl<'a, D> IntoIterator for &'a RelationshipEdges<D> {
+    type Item = &'a (Entity, D);
+    type IntoIter = slice::Iter<'a, (Entity, D)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.edges.iter()
+    }
+}
+
+impl<D> MapEntities for RelationshipEdges<D> {
+    fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+        for (entity, _) in &mut self.edges {
+            *entity = entity_mapper.get_mapped(*entity);
+        }
+    }
+}
+
+/// Provides the type of the data carried by the edges of a [`ManyRelationship`], for the derive macro.
+#[doc(hidden)]
+pub trait EdgeData {
+    type Data: Send + Sync + 'static;
+}
+
+impl<D: Send + Sync + 'static> EdgeData for RelationshipEdges<D> {
+    type Data = D;
+}
+
+impl<'w> EntityWorldMut<'w> {
+    /// Inserts an edge from this entity to `target` with the [`ManyRelationship`] `R`, replacing the data of an
+    /// existing edge to `target`.
+    ///
+    /// The other edges are left untouched, so the order of the sources of their targets is kept.
+    #[track_caller]
+    pub fn insert_edge<R: ManyRelationship>(&mut self, target: Entity, data: R::Data) -> &mut Self {
+        let Some(relationship) = self.get::<R>() else {
+            let mut edges = RelationshipEdges::with_capacity(1);
+            edges.insert(target, data);
+            return self.insert(R::from_edges(edges));
+        };
+        let is_new = !relationship.edges().contains(target);
+        let source = self.id();
+        let caller = MaybeLocation::caller();
+        self.world_scope(|world| {
+            let mut deferred = DeferredWorld::from(&mut *world);
+            // Only the edge to `target` changes, so the hooks which sync every edge are skipped
+            _ = deferred.modify_component_with_relationship_hook_mode::<R, _>(
+                source,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    relationship.edges_mut_risky().insert(target, data);
+                },
+            );
+            if is_new && !attach_edge::<R>(&mut deferred, source, target, caller) {
+                deferred
+                    .commands()
+                    .queue_silenced(remove_edges::<R>(vec![target]).with_entity(source));
+            }
+            world.flush();
+        });
+        self
+    }
+
+    /// Removes the edge from this entity to `target` with the [`ManyRelationship`] `R`, removing `R` if no edges
+    /// are left.
+    ///
+    /// The other edges are left untouched, so the order of the sources of their targets is kept.
+    pub fn remove_edge<R: ManyRelationship>(&mut self, target: Entity) -> &mut Self {
+        let Some(relationship) = self.get::<R>() else {
+            return self;
+        };
+        if !relationship.edges().contains(target) {
+            return self;
+        }
+        if relationship.edges().len() == 1 {
+            return self.remove::<R>();
+        }
+        let source = self.id();
+        self.world_scope(|world| {
+            let mut deferred = DeferredWorld::from(&mut *world);
+            // Only the edge to `target` changes, so the hooks which sync every edge are skipped
+            _ = deferred.modify_component_with_relationship_hook_mode::<R, _>(
+                source,
+                RelationshipHookMode::Skip,
+                |relationship| {
+                    relationship.edges_mut_risky().remove(target);
+                },
+            );
+            detach_edge::<R>(&mut deferred, source, target);
+            world.flush();
+        });
+        self
+    }
+
+    /// Removes all edges pointing to this entity with the [`ManyRelationship`] of the target `S`.
+    pub fn detach_all_edges<S: ManyRelationshipTarget>(&mut self) -> &mut Self {
+        self.remove::<S>()
+    }
+}
+
+impl<'a> EntityCommands<'a> {
+    /// Inserts an edge from this entity to `target` with the [`ManyRelationship`] `R`, replacing the data of an
+    /// existing edge to `target`.
+    pub fn insert_edge<R: ManyRelationship>(&mut self, target: Entity, data: R::Data) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.insert_edge::<R>(target, data);
+        })
+    }
+
+    /// Removes the edge from this entity to `target` with the [`ManyRelationship`] `R`, removing `R` if no edges
+    /// are left.
+    pub fn remove_edge<R: ManyRelationship>(&mut self, target: Entity) -> &mut Self {
+        self.queue(move |mut entity: EntityWorldMut| {
+            entity.remove_edge::<R>(target);
+        })
+    }
+
+    /// Removes all edges pointing to this entity with the [`ManyRelationship`] of the target `S`.
+    pub fn detach_all_edges<S: ManyRelationshipTarget>(&mut self) -> &mut Self {
+        self.remove::<S>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{entity::EntityCloner, world::World};
+
+    #[derive(Component, Clone, Debug, PartialEq)]
+    #[relationship(relationship_target = WiredFrom, many)]
+    struct WiredTo(RelationshipEdges<f32>);
+
+    #[derive(Component)]
+    #[relationship_target(relationship = WiredTo, many)]
+    struct WiredFrom(Vec<Entity>);
+
+    fn sources(world: &World, target: Entity) -> Vec<Entity> {
+        world
+            .get::<WiredFrom>(target)
+            .map(|wired| wired.iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn edges_are_mirrored_on_targets() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world
+            .spawn(WiredTo([(a, 0.5), (b, 1.0)].into_iter().collect()))
+            .id();
+        let d = world.spawn_empty().insert_edge::<WiredTo>(a, 2.0).id();
+
+        assert_eq!(sources(&world, a), vec![c, d]);
+        assert_eq!(sources(&world, b), vec![c]);
+        assert_eq!(world.get::<WiredTo>(c).unwrap().0.get(b), Some(&1.0));
+
+        // Replacing the data of an edge doesn't duplicate or reorder the source.
+        world.entity_mut(c).insert_edge::<WiredTo>(a, 0.25);
+        assert_eq!(sources(&world, a), vec![c, d]);
+        assert_eq!(world.get::<WiredTo>(c).unwrap().0.get(a), Some(&0.25));
+
+        world.entity_mut(c).remove_edge::<WiredTo>(b);
+        assert!(!world.entity(b).contains::<WiredFrom>());
+        assert_eq!(sources(&world, a), vec![c, d]);
+
+        world.entity_mut(d).remove_edge::<WiredTo>(a);
+        assert!(!world.entity(d).contains::<WiredTo>());
+        assert_eq!(sources(&world, a), vec![c]);
+
+        world.entity_mut(c).remove::<WiredTo>();
+        assert!(!world.entity(a).contains::<WiredFrom>());
+    }
+
+    #[test]
+    fn despawning_targets_removes_edges() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world
+            .spawn(WiredTo([(a, 0.5), (b, 1.0)].into_iter().collect()))
+            .id();
+        let d = world.spawn(WiredTo([(a, 0.5)].into_iter().collect())).id();
+
+        world.despawn(a);
+        assert_eq!(
+            world.get::<WiredTo>(c).unwrap().0,
+            [(b, 1.0)].into_iter().collect()
+        );
+        assert!(!world.entity(d).contains::<WiredTo>());
+
+        world.despawn(c);
+        assert!(!world.entity(b).contains::<WiredFrom>());
+    }
+
+    #[test]
+    fn invalid_edges_are_removed() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        world.despawn(b);
+        let c = world.spawn_empty().id();
+        world.entity_mut(c).insert(WiredTo(
+            [(a, 1.0), (b, 1.0), (c, 1.0)].into_iter().collect(),
+        ));
+        world.flush();
+
+        assert_eq!(
+            world.get::<WiredTo>(c).unwrap().0,
+            [(a, 1.0)].into_iter().collect()
+        );
+        assert_eq!(sources(&world, a), vec![c]);
+    }
+
+    #[test]
+    fn one_source_per_target() {
+        #[derive(Component)]
+        #[relationship(relationship_target = HeldBy, many)]
+        struct Holds(RelationshipEdges<u8>);
+
+        #[derive(Component)]
+        #[relationship_target(relationship = Holds, many)]
+        struct HeldBy(Entity);
+
+        let mut world = World::new();
+        let item = world.spawn_empty().id();
+        let other = world.spawn_empty().id();
+        let a = world
+            .spawn(Holds([(item, 0), (other, 1)].into_iter().collect()))
+            .id();
+        let b = world.spawn_empty().insert_edge::<Holds>(item, 4).id();
+        world.flush();
+
+        assert_eq!(world.get::<HeldBy>(item).unwrap().0, b);
+        assert_eq!(
+            world
+                .get::<Holds>(a)
+                .unwrap()
+                .0
+                .targets()
+                .collect::<Vec<_>>(),
+            vec![other]
+        );
+    }
+
+    #[test]
+    fn cloning_keeps_edges_consistent() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world
+            .spawn(WiredTo([(a, 0.5), (b, 1.0)].into_iter().collect()))
+            .id();
+
+        // Cloning a source adds the clone to the targets.
+        let clone = world.spawn_empty().id();
+        EntityCloner::build_opt_out(&mut world).clone_entity(c, clone);
+        assert_eq!(sources(&world, a), vec![c, clone]);
+        assert_eq!(world.get::<WiredTo>(clone), world.get::<WiredTo>(c));
+
+        // Cloning a target doesn't steal its sources.
+        let a_clone = world.spawn_empty().id();
+        EntityCloner::build_opt_out(&mut world).clone_entity(a, a_clone);
+        assert!(sources(&world, a_clone).is_empty());
+
+        // Moving a target retargets the edges of its sources.
+        let moved = world.spawn_empty().id();
+        EntityCloner::build_opt_out(&mut world)
+            .move_components(true)
+            .clone_entity(b, moved);
+        assert_eq!(sources(&world, moved), vec![c, clone]);
+        assert_eq!(world.get::<WiredTo>(c).unwrap().0.get(moved), Some(&1.0));
+        assert!(!world.get::<WiredTo>(c).unwrap().0.contains(b));
+    }
+
+    #[test]
+    fn linked_spawn_despawns_and_clones_sources() {
+        #[derive(Component, Clone)]
+        #[relationship(relationship_target = Members, many)]
+        struct MemberOf(RelationshipEdges<u32>);
+
+        #[derive(Component)]
+        #[relationship_target(relationship = MemberOf, many, linked_spawn)]
+        struct Members(Vec<Entity>);
+
+        let mut world = World::new();
+        let guild = world.spawn_empty().id();
+        let other_guild = world.spawn_empty().id();
+        let member = world
+            .spawn(MemberOf(
+                [(guild, 1), (other_guild, 2)].into_iter().collect(),
+            ))
+            .id();
+
+        let guild_clone = world.spawn_empty().id();
+        EntityCloner::build_opt_out(&mut world)
+            .linked_cloning(true)
+            .clone_entity(guild, guild_clone);
+        let cloned_members = world.get::<Members>(guild_clone).unwrap().0.clone();
+        assert_eq!(cloned_members.len(), 1);
+        let member_clone = cloned_members[0];
+        assert_ne!(member_clone, member);
+        // The clone's edge to the guild points at the guild's clone, and its other edges are kept.
+        assert_eq!(
+            world.get::<MemberOf>(member_clone).unwrap().0,
+            [(guild_clone, 1), (other_guild, 2)].into_iter().collect()
+        );
+        assert_eq!(
+            world.get::<Members>(other_guild).unwrap().0,
+            vec![member, member_clone]
+        );
+
+        world.despawn(guild);
+        assert!(world.get_entity(member).is_err());
+        assert_eq!(
+            world.get::<Members>(other_guild).unwrap().0,
+            vec![member_clone]
+        );
+    }
+}
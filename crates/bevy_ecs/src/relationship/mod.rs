@@ -1,6 +1,8 @@
 //! This module provides functionality to link entities to each other using specialized components called "relationships". See the [`Relationship`] trait for more info.
 
+mod many_relationship;
 mod related_methods;
+mod relationship_join;
 mod relationship_query;
 mod relationship_source_collection;
 
@@ -12,7 +14,9 @@ use core::{any::TypeId, marker::PhantomData};
 use alloc::format;
 
 use bevy_utils::prelude::DebugName;
+pub use many_relationship::*;
 pub use related_methods::*;
+pub use relationship_join::*;
 pub use relationship_query::*;
 pub use relationship_source_collection::*;
 
@@ -0,0 +1,385 @@
+use core::marker::PhantomData;
+
+use crate::{
+    archetype::Archetype,
+    change_detection::Tick,
+    component::{ComponentId, Components},
+    entity::Entity,
+    query::{
+        EcsAccessType, FilteredAccess, FilteredAccessSet, IterQueryData, NestedQuery, QueryData,
+        QueryFilter, ReadOnlyQueryData, ReleaseStateQueryData, WorldQuery,
+    },
+    relationship::Relationship,
+    storage::{Table, TableRow},
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+
+/// A [`QueryData`] that fetches `D` from the entity targeted by the `R` [`Relationship`] of the queried entity,
+/// such as its parent for [`ChildOf`](crate::hierarchy::ChildOf).
+///
+/// Entities without `R`, or whose related entity doesn't match `D` and `F`, are skipped by the query.
+/// Wrap this in an [`Option`] to keep them instead.
+///
+/// The data of the related entity is read through a [`NestedQuery`], so `D` must be read-only.
+/// Its access is checked against the other parameters of the system like any other query access,
+/// so it can't be used alongside a query that mutably accesses the same components.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::Related;
+/// #[derive(Component)]
+/// struct Scale(f32);
+///
+/// fn print_parent_scale(query: Query<(Entity, Related<ChildOf, &Scale>)>) {
+///     for (entity, parent_scale) in &query {
+///         println!("The parent of {entity} has a scale of {}", parent_scale.0);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_parent_scale);
+/// ```
+pub struct Related<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static = ()>(
+    PhantomData<(R, D, F)>,
+);
+
+/// A [`QueryData`] that fetches `D` from the nearest ancestor of the queried entity matching `D` and `F`,
+/// following the `R` [`Relationship`], such as the nearest ancestor with a given component for
+/// [`ChildOf`](crate::hierarchy::ChildOf).
+///
+/// The search starts at the entity targeted by `R` on the queried entity, not at the queried entity itself.
+/// Entities without a matching ancestor are skipped by the query.
+/// Wrap this in an [`Option`] to keep them instead.
+///
+/// Like [`Related`], `D` must be read-only.
+///
+/// # Warning
+///
+/// If `R` allows self-referential relationships, this will loop forever on entities in a relationship cycle.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::relationship::Up;
+/// #[derive(Component)]
+/// struct Layer(u8);
+///
+/// fn print_inherited_layer(query: Query<(Entity, Option<Up<ChildOf, &Layer>>), Without<Layer>>) {
+///     for (entity, layer) in &query {
+///         let layer = layer.map_or(0, |layer| layer.0);
+///         println!("{entity} inherits layer {layer}");
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(print_inherited_layer);
+/// ```
+pub struct Up<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static = ()>(
+    PhantomData<(R, D, F)>,
+);
+
+type RelatedInner<R, D, F> = (&'static R, NestedQuery<D, F>);
+
+type UpInner<R, D, F> = (&'static R, NestedQuery<D, F>, NestedQuery<&'static R>);
+
+/// Delegates the implementation of [`WorldQuery`] to the inner query data of a join.
+macro_rules! impl_join_world_query {
+    ($name:ident, $inner:ident) => {
+        // SAFETY: delegates to the sound implementation of the inner query data
+        unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>
+            WorldQuery for $name<R, D, F>
+        {
+            type Fetch<'w> = <$inner<R, D, F> as WorldQuery>::Fetch<'w>;
+            type State = <$inner<R, D, F> as WorldQuery>::State;
+
+            fn shrink_fetch<'wlong: 'wshort, 'wshort>(
+                fetch: Self::Fetch<'wlong>,
+            ) -> Self::Fetch<'wshort> {
+                <$inner<R, D, F> as WorldQuery>::shrink_fetch(fetch)
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w, 's>(
+                world: UnsafeWorldCell<'w>,
+                state: &'s Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe {
+                    <$inner<R, D, F> as WorldQuery>::init_fetch(world, state, last_run, this_run)
+                }
+            }
+
+            const IS_DENSE: bool = <$inner<R, D, F> as WorldQuery>::IS_DENSE;
+
+            #[inline]
+            unsafe fn set_archetype<'w, 's>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &'s Self::State,
+                archetype: &'w Archetype,
+                table: &'w Table,
+            ) {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe {
+                    <$inner<R, D, F> as WorldQuery>::set_archetype(fetch, state, archetype, table);
+                }
+            }
+
+            #[inline]
+            unsafe fn set_table<'w, 's>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &'s Self::State,
+                table: &'w Table,
+            ) {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe { <$inner<R, D, F> as WorldQuery>::set_table(fetch, state, table) }
+            }
+
+            fn update_component_access(state: &Self::State, access: &mut FilteredAccess) {
+                <$inner<R, D, F> as WorldQuery>::update_component_access(state, access);
+            }
+
+            fn init_nested_access(
+                state: &Self::State,
+                system_name: Option<&str>,
+                component_access_set: &mut FilteredAccessSet,
+                world: UnsafeWorldCell,
+            ) {
+                <$inner<R, D, F> as WorldQuery>::init_nested_access(
+                    state,
+                    system_name,
+                    component_access_set,
+                    world,
+                );
+            }
+
+            fn init_state(world: &mut World) -> Self::State {
+                <$inner<R, D, F> as WorldQuery>::init_state(world)
+            }
+
+            fn get_state(components: &Components) -> Option<Self::State> {
+                <$inner<R, D, F> as WorldQuery>::get_state(components)
+            }
+
+            fn matches_component_set(
+                state: &Self::State,
+                set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                <$inner<R, D, F> as WorldQuery>::matches_component_set(state, set_contains_id)
+            }
+
+            fn update_archetypes(state: &mut Self::State, world: UnsafeWorldCell) {
+                <$inner<R, D, F> as WorldQuery>::update_archetypes(state, world);
+            }
+        }
+
+        // SAFETY: the inner query data is read-only
+        unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>
+            ReadOnlyQueryData for $name<R, D, F>
+        {
+        }
+
+        // SAFETY: the inner query data is read-only, so items for different entities can alias
+        unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static>
+            IterQueryData for $name<R, D, F>
+        {
+        }
+
+        impl<
+                R: Relationship,
+                D: ReadOnlyQueryData + ReleaseStateQueryData + 'static,
+                F: QueryFilter + 'static,
+            > ReleaseStateQueryData for $name<R, D, F>
+        {
+            fn release_state<'w>(item: Self::Item<'w, '_>) -> Self::Item<'w, 'static> {
+                D::release_state(item)
+            }
+        }
+    };
+}
+
+impl_join_world_query!(Related, RelatedInner);
+impl_join_world_query!(Up, UpInner);
+
+// SAFETY:
+// - `Self::ReadOnly` is `Self`
+// - all access is delegated to the read-only inner query data
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> QueryData
+    for Related<R, D, F>
+{
+    const IS_READ_ONLY: bool = true;
+    // The related entity may not match `D` and `F`
+    const IS_ARCHETYPAL: bool = false;
+    type ReadOnly = Self;
+    type Item<'w, 's> = D::Item<'w, 's>;
+
+    fn shrink<'wlong: 'wshort, 'wshort, 's>(
+        item: Self::Item<'wlong, 's>,
+    ) -> Self::Item<'wshort, 's> {
+        D::shrink(item)
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w, 's>(
+        state: &'s Self::State,
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Option<Self::Item<'w, 's>> {
+        // SAFETY: The invariants are upheld by the caller.
+        let (relationship, data) = unsafe {
+            <RelatedInner<R, D, F> as QueryData>::fetch(state, fetch, entity, table_row)
+        }?;
+        data.get_inner(relationship.get()).ok()
+    }
+
+    fn iter_access(state: &Self::State) -> impl Iterator<Item = EcsAccessType<'_>> {
+        <RelatedInner<R, D, F> as QueryData>::iter_access(state)
+    }
+}
+
+// SAFETY:
+// - `Self::ReadOnly` is `Self`
+// - all access is delegated to the read-only inner query data
+unsafe impl<R: Relationship, D: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> QueryData
+    for Up<R, D, F>
+{
+    const IS_READ_ONLY: bool = true;
+    // There may be no ancestor matching `D` and `F`
+    const IS_ARCHETYPAL: bool = false;
+    type ReadOnly = Self;
+    type Item<'w, 's> = D::Item<'w, 's>;
+
+    fn shrink<'wlong: 'wshort, 'wshort, 's>(
+        item: Self::Item<'wlong, 's>,
+    ) -> Self::Item<'wshort, 's> {
+        D::shrink(item)
+    }
+
+    #[inline(always)]
+    unsafe fn fetch<'w, 's>(
+        state: &'s Self::State,
+        fetch: &mut Self::Fetch<'w>,
+        entity: Entity,
+        table_row: TableRow,
+    ) -> Option<Self::Item<'w, 's>> {
+        // SAFETY: The invariants are upheld by the caller.
+        let (relationship, data, relationships) =
+            unsafe { <UpInner<R, D, F> as QueryData>::fetch(state, fetch, entity, table_row) }?;
+        let mut ancestor = relationship.get();
+        loop {
+            if let Ok(item) = data.get_inner(ancestor) {
+                return Some(item);
+            }
+            ancestor = relationships.get(ancestor).ok()?.get();
+        }
+    }
+
+    fn iter_access(state: &Self::State) -> impl Iterator<Item = EcsAccessType<'_>> {
+        <UpInner<R, D, F> as QueryData>::iter_access(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prelude::*, system::RunSystemOnce};
+    use alloc::vec::Vec;
+
+    #[derive(Component, Clone, Copy, Debug, PartialEq)]
+    struct Scale(f32);
+
+    #[derive(Component)]
+    struct Marker;
+
+    #[test]
+    fn related_fetches_data_from_the_target() {
+        let mut world = World::new();
+        let root = world.spawn(Scale(2.0)).id();
+        let child = world.spawn((Scale(1.0), ChildOf(root))).id();
+        let grandchild = world.spawn(ChildOf(child)).id();
+        let orphan = world.spawn(Scale(3.0)).id();
+
+        let mut query = world.query::<Related<ChildOf, &Scale>>();
+        assert_eq!(query.iter(&world).count(), 2);
+        assert_eq!(query.get(&world, child).unwrap(), &Scale(2.0));
+        assert_eq!(query.get(&world, grandchild).unwrap(), &Scale(1.0));
+        assert!(query.get(&world, root).is_err());
+
+        // Related entities which don't match the filter are skipped
+        let mut query = world.query::<Related<ChildOf, &Scale, With<Marker>>>();
+        assert_eq!(query.iter(&world).count(), 0);
+
+        let mut query = world.query::<Option<Related<ChildOf, &Scale>>>();
+        assert_eq!(query.get(&world, orphan).unwrap(), None);
+        assert_eq!(query.get(&world, child).unwrap(), Some(&Scale(2.0)));
+    }
+
+    #[test]
+    fn up_fetches_data_from_the_nearest_ancestor() {
+        let mut world = World::new();
+        let root = world.spawn((Scale(2.0), Marker)).id();
+        let child = world.spawn((Scale(1.0), ChildOf(root))).id();
+        let grandchild = world.spawn((Scale(0.5), ChildOf(child))).id();
+        let great_grandchild = world.spawn(ChildOf(grandchild)).id();
+
+        let mut query = world.query::<Up<ChildOf, &Scale, With<Marker>>>();
+        assert_eq!(query.get(&world, great_grandchild).unwrap(), &Scale(2.0));
+        assert_eq!(query.get(&world, child).unwrap(), &Scale(2.0));
+        // The search doesn't include the entity itself
+        assert!(query.get(&world, root).is_err());
+
+        let mut query = world.query::<Up<ChildOf, &Scale>>();
+        assert_eq!(query.get(&world, great_grandchild).unwrap(), &Scale(0.5));
+    }
+
+    #[test]
+    fn joins_work_with_par_iter() {
+        #[derive(Component)]
+        struct Total(f32);
+
+        let mut world = World::new();
+        let root = world.spawn(Scale(2.0)).id();
+        let mut leaves = Vec::new();
+        for _ in 0..100 {
+            let child = world.spawn(ChildOf(root)).id();
+            leaves.push(world.spawn((Total(0.0), ChildOf(child))).id());
+        }
+
+        world
+            .run_system_once(
+                |mut query: Query<(
+                    &mut Total,
+                    Option<Related<ChildOf, &Scale>>,
+                    Up<ChildOf, &Scale>,
+                )>| {
+                    query
+                        .par_iter_mut()
+                        .for_each(|(mut total, parent, ancestor)| {
+                            total.0 = parent.map_or(0.0, |scale| scale.0) + ancestor.0;
+                        });
+                },
+            )
+            .unwrap();
+
+        for leaf in leaves {
+            assert_eq!(world.get::<Total>(leaf).unwrap().0, 2.0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn join_access_conflicts_are_detected() {
+        let mut world = World::new();
+        world
+            .run_system_once(|_: Query<&mut Scale>, _: Query<Related<ChildOf, &Scale>>| {})
+            .unwrap();
+    }
+
+    #[test]
+    fn disjoint_join_access_is_allowed() {
+        let mut world = World::new();
+        world
+            .run_system_once(
+                |_: Query<&mut Scale, With<Marker>>,
+                 _: Query<Related<ChildOf, &Scale, Without<Marker>>>| {},
+            )
+            .unwrap();
+    }
+}
@@ -104,6 +104,8 @@ pub struct SystemSchedule {
     ///
     /// If a set doesn't run because of its conditions, this is used to skip all systems in it.
     pub(super) systems_in_sets_with_conditions: Vec<FixedBitSet>,
+    /// Whether the run times of systems are measured in their [`SystemRunStats`](super::SystemRunStats).
+    pub(super) measure_run_times: bool,
 }
 
 impl SystemSchedule {
@@ -119,6 +121,7 @@ impl SystemSchedule {
             system_dependents: Vec::new(),
             sets_with_conditions_of_systems: Vec::new(),
             systems_in_sets_with_conditions: Vec::new(),
+            measure_run_times: false,
         }
     }
 
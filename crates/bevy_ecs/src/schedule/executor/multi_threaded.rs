@@ -32,6 +32,7 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<SystemWithAccess>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    measure_run_times: bool,
 }
 
 struct Conditions<'a> {
@@ -57,6 +58,7 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            measure_run_times: schedule.measure_run_times,
         }
     }
 }
@@ -649,14 +651,15 @@ impl ExecutorState {
     ///   used by the specified system.
     unsafe fn spawn_system_task(&mut self, context: &Context, system_index: usize) {
         // SAFETY: this system is not running, no other reference exists
-        let system = &mut unsafe { &mut *context.environment.systems[system_index].get() }.system;
+        let SystemWithAccess { system, stats, .. } =
+            unsafe { &mut *context.environment.systems[system_index].get() };
         // Move the full context object into the new future.
         let context = *context;
 
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
-            let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let run = AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
                 // access the world data used by the system.
@@ -677,7 +680,10 @@ impl ExecutorState {
                         );
                     }
                 };
-            }));
+            });
+            let res = stats.record(context.environment.measure_run_times, || {
+                std::panic::catch_unwind(run)
+            });
             context.system_completed(system_index, res, system);
         };
 
@@ -693,7 +699,8 @@ impl ExecutorState {
     /// Caller must ensure no systems are currently borrowed.
     unsafe fn spawn_exclusive_system_task(&mut self, context: &Context, system_index: usize) {
         // SAFETY: this system is not running, no other reference exists
-        let system = &mut unsafe { &mut *context.environment.systems[system_index].get() }.system;
+        let SystemWithAccess { system, stats, .. } =
+            unsafe { &mut *context.environment.systems[system_index].get() };
         // Move the full context object into the new future.
         let context = *context;
 
@@ -715,7 +722,7 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
-                let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
+                let run = AssertUnwindSafe(|| {
                     if let Err(RunSystemError::Failed(err)) =
                         __rust_begin_short_backtrace::run(system, world)
                     {
@@ -727,7 +734,10 @@ impl ExecutorState {
                             },
                         );
                     }
-                }));
+                });
+                let res = stats.record(context.environment.measure_run_times, || {
+                    std::panic::catch_unwind(run)
+                });
                 context.system_completed(system_index, res, system);
             };
 
@@ -798,6 +808,7 @@ fn apply_deferred(
             return Err(payload);
         }
     }
+    world.trigger_mutations();
     Ok(())
 }
 
@@ -11,7 +11,9 @@ use std::eprintln;
 
 use crate::{
     error::{ErrorContext, ErrorHandler},
-    schedule::{is_apply_deferred, ConditionWithAccess, SystemExecutor, SystemSchedule},
+    schedule::{
+        is_apply_deferred, ConditionWithAccess, SystemExecutor, SystemSchedule, SystemWithAccess,
+    },
     system::{RunSystemError, ScheduleSystem},
     world::World,
 };
@@ -69,7 +71,7 @@ impl SystemExecutor for SingleThreadedExecutor {
             .unwrap_or_default();
 
         for system_index in 0..schedule.systems.len() {
-            let system = &mut schedule.systems[system_index].system;
+            let SystemWithAccess { system, stats, .. } = &mut schedule.systems[system_index];
 
             #[cfg(feature = "trace")]
             let name = system.name();
@@ -148,7 +150,9 @@ impl SystemExecutor for SingleThreadedExecutor {
             #[cfg(feature = "std")]
             #[expect(clippy::print_stderr, reason = "Allowed behind `std` feature gate.")]
             {
-                if let Err(payload) = std::panic::catch_unwind(f) {
+                if let Err(payload) =
+                    stats.record(schedule.measure_run_times, || std::panic::catch_unwind(f))
+                {
                     eprintln!("Encountered a panic in system `{}`!", system.name());
                     std::panic::resume_unwind(payload);
                 }
@@ -156,7 +160,7 @@ impl SystemExecutor for SingleThreadedExecutor {
 
             #[cfg(not(feature = "std"))]
             {
-                (f)();
+                stats.record(schedule.measure_run_times, f);
             }
 
             self.unapplied_systems.insert(system_index);
@@ -192,6 +196,7 @@ impl SingleThreadedExecutor {
             let system = &mut schedule.systems[system_index].system;
             system.apply_deferred(world);
         }
+        world.trigger_mutations();
 
         self.unapplied_systems.clear();
     }
@@ -31,7 +31,10 @@ mod tests {
     #[cfg(feature = "trace")]
     use alloc::string::ToString;
     use alloc::{vec, vec::Vec};
-    use core::sync::atomic::{AtomicU32, Ordering};
+    use core::{
+        sync::atomic::{AtomicU32, Ordering},
+        time::Duration,
+    };
 
     pub use crate::{
         prelude::World,
@@ -130,6 +133,40 @@ mod tests {
 
             schedule.run(&mut world);
         }
+
+        #[test]
+        fn system_run_stats() {
+            let mut world = World::default();
+            world.init_resource::<SystemOrder>();
+
+            for single_threaded in [false, true] {
+                let mut schedule = Schedule::default();
+                if single_threaded {
+                    schedule.set_executor(SingleThreadedExecutor::new());
+                }
+                schedule.add_systems((make_function_system(0), make_exclusive_system(1)));
+                assert!(schedule.systems_with_stats().is_err());
+
+                schedule.run(&mut world);
+                for (_, _, stats) in schedule.systems_with_stats().unwrap() {
+                    assert_eq!(stats.run_count, 1);
+                    assert_eq!(stats.measured_run_count, 0);
+                    assert_eq!(stats.total_run_time, Duration::ZERO);
+                }
+
+                schedule.set_measure_run_times(true);
+                schedule.run(&mut world);
+
+                let stats: Vec<_> = schedule.systems_with_stats().unwrap().collect();
+                assert_eq!(stats.len(), 2);
+                for (_, _, stats) in stats {
+                    assert_eq!(stats.run_count, 2);
+                    #[cfg(feature = "std")]
+                    assert_eq!(stats.measured_run_count, 1);
+                    assert!(stats.total_run_time >= stats.last_run_time);
+                }
+            }
+        }
     }
 
     mod system_ordering {
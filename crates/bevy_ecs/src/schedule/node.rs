@@ -3,6 +3,7 @@ use core::{
     any::TypeId,
     fmt::{self, Debug},
     ops::{Deref, Index, IndexMut, Range},
+    time::Duration,
 };
 
 use bevy_platform::collections::{HashMap, HashSet};
@@ -39,6 +40,8 @@ pub struct SystemWithAccess {
     /// The access returned by [`System::initialize`].
     /// This will be empty if the system has not been initialized yet.
     pub(crate) access: FilteredAccessSet,
+    /// Statistics about the runs of the system.
+    pub(crate) stats: SystemRunStats,
 }
 
 impl SystemWithAccess {
@@ -48,8 +51,60 @@ impl SystemWithAccess {
         Self {
             system,
             access: FilteredAccessSet::new(),
+            stats: SystemRunStats::default(),
         }
     }
+
+    /// Returns the statistics about the runs of the system.
+    pub fn stats(&self) -> &SystemRunStats {
+        &self.stats
+    }
+}
+
+/// Statistics about the runs of a system in a [`Schedule`](super::Schedule),
+/// collected by the [`SystemExecutor`](super::SystemExecutor).
+///
+/// Run times are only measured for schedules which opted in with
+/// [`Schedule::set_measure_run_times`](super::Schedule::set_measure_run_times),
+/// and only when the `std` feature is enabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SystemRunStats {
+    /// The number of times the system has run.
+    pub run_count: u64,
+    /// The number of runs whose duration was measured.
+    pub measured_run_count: u64,
+    /// The duration of the most recent run.
+    pub last_run_time: Duration,
+    /// The total duration of all measured runs.
+    pub total_run_time: Duration,
+}
+
+impl SystemRunStats {
+    /// Returns the average duration of a measured run, or zero if no run was measured yet.
+    pub fn average_run_time(&self) -> Duration {
+        if self.measured_run_count == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.total_run_time.as_secs_f64() / self.measured_run_count as f64)
+    }
+
+    /// Runs `f`, recording it as a run of the system and measuring its run time if `measure` is `true`.
+    #[inline]
+    pub(crate) fn record<T>(&mut self, measure: bool, f: impl FnOnce() -> T) -> T {
+        self.run_count += 1;
+        #[cfg(feature = "std")]
+        if measure {
+            let start = bevy_platform::time::Instant::now();
+            let out = f();
+            self.last_run_time = start.elapsed();
+            self.total_run_time += self.last_run_time;
+            self.measured_run_count += 1;
+            return out;
+        }
+        #[cfg(not(feature = "std"))]
+        let _ = measure;
+        f()
+    }
 }
 
 impl System for SystemWithAccess {
@@ -540,6 +540,20 @@ impl Schedule {
         self.graph.settings.clone()
     }
 
+    /// Sets whether the run times of systems in this schedule are measured in their [`SystemRunStats`].
+    ///
+    /// This is disabled by default, since measuring adds two clock reads around every system run.
+    pub fn set_measure_run_times(&mut self, enabled: bool) -> &mut Self {
+        self.executable.measure_run_times = enabled;
+        self
+    }
+
+    /// Returns whether the run times of systems in this schedule are measured.
+    /// See [`Schedule::set_measure_run_times`].
+    pub fn measures_run_times(&self) -> bool {
+        self.executable.measure_run_times
+    }
+
     /// Replaces the schedule's executor.
     pub fn set_executor(&mut self, executor: impl SystemExecutor + 'static) -> &mut Self {
         self.executor = Box::new(executor);
@@ -708,6 +722,30 @@ impl Schedule {
         Ok(iter)
     }
 
+    /// Returns an iterator over all systems in this schedule along with their [`SystemRunStats`].
+    ///
+    /// Note: this method will return [`ScheduleNotInitialized`] if the
+    /// schedule has never been initialized or run.
+    pub fn systems_with_stats(
+        &self,
+    ) -> Result<
+        impl Iterator<Item = (SystemKey, &ScheduleSystem, &SystemRunStats)> + Sized,
+        ScheduleNotInitialized,
+    > {
+        if !self.executor_initialized {
+            return Err(ScheduleNotInitialized);
+        }
+
+        let iter = self
+            .executable
+            .system_ids
+            .iter()
+            .zip(&self.executable.systems)
+            .map(|(&node_id, system)| (node_id, &system.system, &system.stats));
+
+        Ok(iter)
+    }
+
     /// Returns the number of systems in this schedule.
     pub fn systems_len(&self) -> usize {
         if !self.executor_initialized {
@@ -1362,6 +1400,7 @@ impl ScheduleGraph {
             system_dependents,
             sets_with_conditions_of_systems,
             systems_in_sets_with_conditions,
+            measure_run_times: false,
         }
     }
 
@@ -1403,7 +1442,8 @@ impl ScheduleGraph {
             }
         }
 
-        let (new_schedule, build_metadata) = self.build_schedule(world, ignored_ambiguities)?;
+        let (mut new_schedule, build_metadata) = self.build_schedule(world, ignored_ambiguities)?;
+        new_schedule.measure_run_times = schedule.measure_run_times;
         *schedule = new_schedule;
 
         for warning in &build_metadata.warnings {
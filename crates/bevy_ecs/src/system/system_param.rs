@@ -775,6 +775,7 @@ unsafe impl<'a, T: Resource> SystemParam for ResMut<'a, T> {
                 changed_by: value.ticks.changed_by,
                 last_run: system_meta.last_run,
                 this_run: change_tick,
+                mutation: value.ticks.mutation,
             },
         })
     }
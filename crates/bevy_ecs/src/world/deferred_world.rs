@@ -210,6 +210,7 @@ impl<'w> DeferredWorld<'w> {
         // - DISCARD is able to accept ZST events
         unsafe {
             let archetype = &*archetype;
+            self.record_inserted_components(entity, [component_id].into_iter());
             self.trigger_on_insert(
                 archetype,
                 entity,
@@ -679,6 +680,30 @@ impl<'w> DeferredWorld<'w> {
         }
     }
 
+    /// Records the components in `targets` that observe mutations as inserted, so they trigger
+    /// [`Add`](crate::lifecycle::Add) and [`Insert`] rather than [`Mutate`](crate::lifecycle::Mutate) until the next batch.
+    ///
+    /// # Safety
+    /// Caller must ensure [`ComponentId`] in target exist in self.
+    #[inline]
+    pub(crate) unsafe fn record_inserted_components(
+        &mut self,
+        entity: Entity,
+        targets: impl Iterator<Item = ComponentId>,
+    ) {
+        let components = self.world.components();
+        if components.observed_mutations().is_empty() {
+            return;
+        }
+        for component_id in targets {
+            // SAFETY: Caller ensures that these components exist
+            if unsafe { components.get_info_unchecked(component_id) }.observes_mutations() {
+                // SAFETY: No references to the inserted components are held outside of `World::trigger_mutations`
+                unsafe { self.world.inserted_components_mut() }.insert((entity, component_id));
+            }
+        }
+    }
+
     /// Triggers all `on_discard` hooks for [`ComponentId`] in target.
     ///
     /// # Safety
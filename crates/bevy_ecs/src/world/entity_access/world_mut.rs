@@ -1472,6 +1472,73 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Inserts a component into the entity without triggering any hooks or observers.
+    ///
+    /// This is only meant for restoring state captured from this world,
+    /// like [`WorldSnapshot`](crate::world::WorldSnapshot) does.
+    pub(crate) fn insert_without_lifecycle<C: Component>(&mut self, component: C) -> &mut Self {
+        let location = self.location();
+        let change_tick = self.world.change_tick();
+        // SAFETY:
+        // - `location.archetype_id` is part of a valid `EntityLocation`.
+        let mut bundle_inserter =
+            unsafe { BundleInserter::new::<C>(self.world, location.archetype_id, change_tick) };
+        bundle_inserter.trigger_lifecycle = false;
+        move_as_ptr!(component);
+        // SAFETY:
+        // - `location` matches current entity and thus must currently exist in the source
+        //   archetype for this inserter and its location within the archetype.
+        // - `C` matches the type used to create the `BundleInserter`.
+        // - Components have no bundle effect, so `apply_effect` does not need to be called.
+        let location = unsafe {
+            bundle_inserter.insert(
+                self.entity,
+                location,
+                component,
+                InsertMode::Replace,
+                MaybeLocation::caller(),
+                RelationshipHookMode::Skip,
+            )
+        };
+        self.location = Some(location);
+        self.world.flush();
+        self.update_location();
+        self
+    }
+
+    /// Removes a component from the entity without triggering any hooks or observers.
+    ///
+    /// This is only meant for restoring state captured from this world,
+    /// like [`WorldSnapshot`](crate::world::WorldSnapshot) does.
+    pub(crate) fn remove_without_lifecycle<C: Component>(&mut self) -> &mut Self {
+        let location = self.location();
+
+        let Some(mut remover) =
+            // SAFETY: The archetype id must be valid since this entity is in it.
+            (unsafe { BundleRemover::new::<C>(self.world, location.archetype_id, false) })
+        else {
+            return self;
+        };
+        remover.trigger_lifecycle = false;
+        // SAFETY:
+        // - The remover archetype came from the passed location and the removal can not fail.
+        // - `location` was obtained from a valid `Self`.
+        let new_location = unsafe {
+            remover.remove(
+                self.entity,
+                location,
+                MaybeLocation::caller(),
+                BundleRemover::empty_pre_remove,
+            )
+        }
+        .0;
+
+        self.location = Some(new_location);
+        self.world.flush();
+        self.update_location();
+        self
+    }
+
     /// Removes a dynamic bundle from the entity if it exists.
     ///
     /// You should prefer to use the typed API [`EntityWorldMut::remove`] where possible.
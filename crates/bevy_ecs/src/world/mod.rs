@@ -11,6 +11,7 @@ mod entity_access;
 mod entity_fetch;
 mod filtered_resource;
 mod identifier;
+mod snapshot;
 mod spawn_batch;
 
 pub mod error;
@@ -32,6 +33,7 @@ pub use entity_access::{
 pub use entity_fetch::{EntityFetcher, WorldEntityFetch};
 pub use filtered_resource::*;
 pub use identifier::WorldId;
+pub use snapshot::{SnapshotConfig, WorldSnapshot};
 pub use spawn_batch::*;
 
 use crate::{
@@ -48,13 +50,19 @@ use crate::{
         ComponentsQueuedRegistrator, ComponentsRegistrator, Mutable, RequiredComponents,
         RequiredComponentsError,
     },
-    entity::{Entities, Entity, EntityAllocator, EntityNotSpawnedError, SpawnError},
+    entity::{
+        Entities, Entity, EntityAllocator, EntityIndexMap, EntityNotSpawnedError, SpawnError,
+    },
     entity_disabling::DefaultQueryFilters,
     error::{ErrorHandler, FallbackErrorHandler},
-    lifecycle::{ComponentHooks, RemovedComponentMessages, ADD, DESPAWN, DISCARD, INSERT, REMOVE},
+    event::EntityComponentsTrigger,
+    lifecycle::{
+        ComponentHooks, MutationTracker, RemovedComponentMessages, ADD, DESPAWN, DISCARD, INSERT,
+        MUTATE, REMOVE,
+    },
     message::{Message, MessageId, Messages, WriteBatchIds},
     observer::Observers,
-    prelude::{Add, Despawn, DetectChangesMut, Discard, Insert, Remove},
+    prelude::{Add, Despawn, DetectChangesMut, Discard, Insert, Mutate, Remove},
     query::{DebugCheckedUnwrap, QueryData, QueryFilter, QueryState},
     relationship::RelationshipHookMode,
     resource::{IsResource, Resource, ResourceEntities, IS_RESOURCE},
@@ -112,6 +120,7 @@ pub struct World {
     pub(crate) last_check_tick: Tick,
     pub(crate) last_trigger_id: u32,
     pub(crate) command_queue: RawCommandQueue,
+    pub(crate) mutations: MutationTracker,
 }
 
 impl Default for World {
@@ -135,6 +144,7 @@ impl Default for World {
             last_trigger_id: 0,
             command_queue: RawCommandQueue::new(),
             component_ids: ComponentIds::default(),
+            mutations: MutationTracker::default(),
         };
         world.bootstrap();
         world
@@ -178,6 +188,9 @@ impl World {
         let is_resource = self.register_component::<IsResource>();
         assert_eq!(IS_RESOURCE, is_resource);
 
+        let on_mutate = self.register_event_key::<Mutate>();
+        assert_eq!(MUTATE, on_mutate);
+
         // This sets up `Disabled` as a disabling component, via the FromWorld impl
         self.init_resource::<DefaultQueryFilters>();
     }
@@ -2915,6 +2928,8 @@ impl World {
                 changed_by: changed_by.as_mut(),
                 last_run: last_change_tick,
                 this_run: change_tick,
+                // The component is reinserted, which triggers `Insert` instead.
+                mutation: None,
             },
         };
 
@@ -3067,6 +3082,70 @@ impl World {
         self.flush_commands();
     }
 
+    /// Triggers [`Mutate`] for every component that opted in with `#[component(observe_mutations)]` and was
+    /// changed in place since the last call, then applies the commands queued by the observers.
+    ///
+    /// Each mutated entity receives a single [`Mutate`] event listing all of its mutated components.
+    /// This is called automatically when commands are applied by a [`Schedule`], so mutations made during a
+    /// schedule are reported at its sync points and at its end.
+    /// Mutations made by [`Mutate`] observers are reported by the next call.
+    pub fn trigger_mutations(&mut self) {
+        if self.mutations.mutated.is_empty() {
+            self.mutations.inserted.clear();
+            return;
+        }
+        self.flush();
+        let inserted = core::mem::take(&mut self.mutations.inserted);
+        // The mutated components of each entity are grouped, in the order the entities were first mutated
+        let mut mutated = EntityIndexMap::<Vec<ComponentId>>::default();
+        for (entity, component_id) in self.mutations.mutated.try_iter() {
+            if inserted.contains(&(entity, component_id)) {
+                continue;
+            }
+            let components = mutated.entry(entity).or_default();
+            if !components.contains(&component_id) {
+                components.push(component_id);
+            }
+        }
+        if self.observers.try_get_observers(MUTATE).is_none() {
+            return;
+        }
+
+        for (entity, components) in mutated.iter() {
+            let world = self.as_unsafe_world_cell();
+            // Observers can't change the archetype of entities, since their commands are applied after the loop.
+            let Ok(location) = world.entities().get_spawned(*entity) else {
+                continue;
+            };
+            let archetype = &world.archetypes()[location.archetype_id];
+            // The component may have been removed since it was mutated
+            let components = components
+                .iter()
+                .copied()
+                .filter(|&component_id| archetype.contains(component_id))
+                .collect::<Vec<_>>();
+            if components.is_empty() {
+                continue;
+            }
+            // SAFETY:
+            // - the MUTATE event_key corresponds to the Mutate event's type
+            // - the observers only have access to the world through `DeferredWorld`
+            unsafe {
+                world.into_deferred().trigger_raw(
+                    MUTATE,
+                    &mut Mutate { entity: *entity },
+                    &mut EntityComponentsTrigger {
+                        components: &components,
+                        old_archetype: Some(archetype),
+                        new_archetype: Some(archetype),
+                    },
+                    MaybeLocation::caller(),
+                );
+            }
+        }
+        self.flush();
+    }
+
     /// Increments the world's current change tick and returns the old value.
     ///
     /// If you need to call this method, but do not have `&mut` access to the world,
@@ -0,0 +1,737 @@
+//! Capturing and restoring the state of selected components and resources in place.
+//!
+//! A [`WorldSnapshot`] is a cheap copy of the values of the components and resources
+//! registered in a [`SnapshotConfig`]. Unlike serialization based approaches, values are
+//! cloned straight out of their [`Table`] columns and [`ComponentSparseSet`]s, and [`WorldSnapshot::restore`]
+//! writes them back into the same [`World`] rather than producing a new one, without
+//! triggering any hooks or observers.
+//! This makes snapshots suitable for use cases like rollback networking, where the
+//! world is captured and restored every frame.
+//!
+//! [`Table`]: crate::storage::Table
+//! [`ComponentSparseSet`]: crate::storage::ComponentSparseSet
+
+use alloc::{boxed::Box, vec::Vec};
+
+use crate::{
+    archetype::ArchetypeEntity,
+    change_detection::{CheckChangeTicks, DetectChanges, DetectChangesMut, Tick},
+    component::{Component, ComponentId, Mutable, StorageType},
+    entity::{Entity, EntityHashMap, EntityHashSet},
+    resource::{IsResource, Resource},
+    world::{World, WorldId},
+};
+use bevy_ptr::UnsafeCellDeref;
+
+/// Describes which components and resources are captured by a [`WorldSnapshot`].
+///
+/// Only types implementing [`Clone`] can be registered. Immutable components can't be, since
+/// restoring them in place would bypass their hooks.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::SnapshotConfig};
+/// #[derive(Component, Clone, PartialEq, Debug)]
+/// struct Position(f32);
+///
+/// #[derive(Resource, Clone)]
+/// struct Frame(u32);
+///
+/// let mut world = World::new();
+/// let entity = world.spawn(Position(0.0)).id();
+/// world.insert_resource(Frame(0));
+///
+/// let config = SnapshotConfig::new()
+///     .with_component::<Position>()
+///     .with_resource::<Frame>();
+/// let snapshot = config.capture(&mut world);
+///
+/// world.get_mut::<Position>(entity).unwrap().0 = 10.0;
+/// world.spawn(Position(5.0));
+///
+/// snapshot.restore(&mut world);
+/// assert_eq!(world.get::<Position>(entity), Some(&Position(0.0)));
+/// assert_eq!(world.query::<&Position>().iter(&world).count(), 1);
+/// ```
+#[derive(Default)]
+pub struct SnapshotConfig {
+    targets: Vec<Box<dyn SnapshotTarget>>,
+}
+
+impl SnapshotConfig {
+    /// Creates an empty config that captures nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `C` of every entity that has it.
+    pub fn with_component<C: Component<Mutability = Mutable> + Clone>(mut self) -> Self {
+        self.add_component::<C>();
+        self
+    }
+
+    /// Captures the component `C` of every entity that has it.
+    pub fn add_component<C: Component<Mutability = Mutable> + Clone>(&mut self) -> &mut Self {
+        self.targets
+            .push(Box::new(ComponentTarget::<C>(core::marker::PhantomData)));
+        self
+    }
+
+    /// Captures the resource `R`, including whether it exists at all.
+    pub fn with_resource<R: Resource + Clone>(mut self) -> Self {
+        self.add_resource::<R>();
+        self
+    }
+
+    /// Captures the resource `R`, including whether it exists at all.
+    pub fn add_resource<R: Resource + Clone>(&mut self) -> &mut Self {
+        self.targets
+            .push(Box::new(ResourceTarget::<R>(core::marker::PhantomData)));
+        self
+    }
+
+    /// Copies the current values of all registered components and resources out of `world`.
+    ///
+    /// This increments the world's change tick, so that everything happening after the capture
+    /// can be told apart from the captured state.
+    pub fn capture(&self, world: &mut World) -> WorldSnapshot {
+        let states = self
+            .targets
+            .iter()
+            .map(|target| target.capture(world))
+            .collect();
+        let tick = world.increment_change_tick();
+        WorldSnapshot {
+            world_id: world.id(),
+            tick,
+            states,
+        }
+    }
+}
+
+/// The captured values of the components and resources selected by a [`SnapshotConfig`].
+///
+/// Created by [`SnapshotConfig::capture`]. See [`WorldSnapshot::restore`] for how the captured
+/// state is written back.
+pub struct WorldSnapshot {
+    world_id: WorldId,
+    tick: Tick,
+    states: Vec<Box<dyn CapturedState>>,
+}
+
+impl WorldSnapshot {
+    /// Returns the id of the [`World`] this snapshot was captured from.
+    pub fn world_id(&self) -> WorldId {
+        self.world_id
+    }
+
+    /// Returns the change tick at which this snapshot was captured.
+    pub fn tick(&self) -> Tick {
+        self.tick
+    }
+
+    /// Returns `true` if `entity` had any of the captured components.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.states.iter().any(|state| state.contains(entity))
+    }
+
+    /// Restores the captured state in place by diffing it against the current state of `world`.
+    ///
+    /// - Captured values which have not been changed since the capture are left untouched,
+    ///   keeping their change ticks. All other values are written back and marked as changed.
+    /// - Captured components and resources which have been added since the capture are removed.
+    /// - Entities with captured components which have been spawned since the capture are despawned.
+    /// - Entities with captured components which have been despawned since the capture are spawned again.
+    ///
+    /// Components and resources are written straight into their storage: no hooks or observers are
+    /// triggered for restored, added or removed values, and no removals are recorded for
+    /// [`RemovedComponents`](crate::lifecycle::RemovedComponents). Restoring a component that
+    /// requires other components still inserts the missing ones. Entities spawned since the
+    /// capture are despawned as usual, so that anything outside the snapshot referring to them
+    /// is cleaned up.
+    ///
+    /// All other entities keep their ids. Despawned entities can not, since their ids may already
+    /// have been reused. They are spawned with a new id instead, and entity references in the
+    /// restored values are mapped with [`Component::map_entities`].
+    /// The returned map contains the new id of every respawned entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `world` is not the world this snapshot was captured from.
+    pub fn restore(&self, world: &mut World) -> EntityHashMap<Entity> {
+        assert_eq!(
+            self.world_id,
+            world.id(),
+            "a `WorldSnapshot` can only be restored into the world it was captured from"
+        );
+
+        let mut entities = EntityHashSet::default();
+        for state in &self.states {
+            state.collect_entities(&mut entities);
+        }
+
+        let mut spawned = Vec::new();
+        for state in &self.states {
+            state.collect_spawned_since(world, self.tick, &entities, &mut spawned);
+        }
+        for entity in spawned {
+            if let Ok(entity) = world.get_entity_mut(entity) {
+                entity.despawn();
+            }
+        }
+
+        let mut entity_map = EntityHashMap::default();
+        for &entity in &entities {
+            if world.entities().contains_spawned(entity) {
+                continue;
+            }
+            entity_map.insert(entity, world.spawn_empty().id());
+        }
+
+        for state in &self.states {
+            state.restore(world, &mut entity_map);
+        }
+        entity_map
+    }
+
+    /// Iterates the captured change ticks and clamps any older than
+    /// [`MAX_CHANGE_AGE`](crate::change_detection::MAX_CHANGE_AGE).
+    ///
+    /// Snapshots which are kept around for a long time should be passed every
+    /// [`CheckChangeTicks`] event, like the world's own ticks, so they keep matching the
+    /// ticks of unchanged values and of newly spawned entities.
+    pub fn check_change_ticks(&mut self, check: CheckChangeTicks) {
+        self.tick.check_tick(check);
+        for state in &mut self.states {
+            state.check_change_ticks(check);
+        }
+    }
+}
+
+trait SnapshotTarget: Send + Sync {
+    fn capture(&self, world: &mut World) -> Box<dyn CapturedState>;
+}
+
+trait CapturedState: Send + Sync {
+    fn contains(&self, entity: Entity) -> bool;
+
+    fn collect_entities(&self, entities: &mut EntityHashSet);
+
+    /// Collects entities with the captured type which were spawned after `tick` and not captured.
+    fn collect_spawned_since(
+        &self,
+        world: &World,
+        tick: Tick,
+        captured: &EntityHashSet,
+        spawned: &mut Vec<Entity>,
+    );
+
+    /// Writes the captured values back. If `entity_map` is not empty, every value is written,
+    /// since unchanged values may still reference entities which have been respawned.
+    fn restore(&self, world: &mut World, entity_map: &mut EntityHashMap<Entity>);
+
+    fn check_change_ticks(&mut self, check: CheckChangeTicks);
+}
+
+/// Iterates every entity which has the component, straight from the archetypes.
+fn entities_with(world: &World, component_id: ComponentId) -> impl Iterator<Item = Entity> + '_ {
+    let archetypes = world.archetypes();
+    archetypes
+        .component_index()
+        .get(&component_id)
+        .into_iter()
+        .flat_map(|records| records.keys())
+        .flat_map(move |&archetype_id| archetypes[archetype_id].entities())
+        .map(ArchetypeEntity::id)
+}
+
+struct ComponentTarget<C: Component>(core::marker::PhantomData<fn() -> C>);
+
+impl<C: Component<Mutability = Mutable> + Clone> SnapshotTarget for ComponentTarget<C> {
+    fn capture(&self, world: &mut World) -> Box<dyn CapturedState> {
+        let component_id = world.register_component::<C>();
+        let values = match C::STORAGE_TYPE {
+            StorageType::Table => capture_tables::<C>(world, component_id),
+            StorageType::SparseSet => capture_sparse_set::<C>(world, component_id),
+        };
+        Box::new(CapturedComponents {
+            component_id,
+            values,
+        })
+    }
+}
+
+/// Clones the values of `C` and their change ticks straight out of every table with a column for it.
+fn capture_tables<C: Component + Clone>(
+    world: &World,
+    component_id: ComponentId,
+) -> Vec<CapturedValue<C>> {
+    let mut values = Vec::new();
+    for table in world.storages().tables.iter() {
+        let Some(column) = table.get_column(component_id) else {
+            continue;
+        };
+        let len = table.entity_count() as usize;
+        // SAFETY: `len` is the length of the table and `component_id` was registered for `C`.
+        let (data, changed) = unsafe {
+            (
+                column.get_data_slice::<C>(len),
+                column.get_changed_ticks_slice(len),
+            )
+        };
+        values.extend(table.entities().iter().zip(data).zip(changed).map(
+            // SAFETY: `world` is borrowed immutably, so nothing can be writing to the column.
+            |((&entity, value), changed)| unsafe {
+                CapturedValue {
+                    entity,
+                    changed: changed.read(),
+                    value: value.deref().clone(),
+                }
+            },
+        ));
+    }
+    values
+}
+
+/// Clones the values of `C` and their change ticks straight out of its sparse set.
+fn capture_sparse_set<C: Component + Clone>(
+    world: &World,
+    component_id: ComponentId,
+) -> Vec<CapturedValue<C>> {
+    let Some(sparse_set) = world.storages().sparse_sets.get(component_id) else {
+        return Vec::new();
+    };
+    entities_with(world, component_id)
+        .filter_map(|entity| {
+            let (value, ticks) = sparse_set.get_with_ticks(entity)?;
+            // SAFETY: `component_id` was registered for `C`, and `world` is borrowed immutably,
+            // so nothing can be writing to the sparse set.
+            unsafe {
+                Some(CapturedValue {
+                    entity,
+                    changed: ticks.changed.read(),
+                    value: value.deref::<C>().clone(),
+                })
+            }
+        })
+        .collect()
+}
+
+struct CapturedValue<T> {
+    entity: Entity,
+    changed: Tick,
+    value: T,
+}
+
+struct CapturedComponents<C: Component> {
+    component_id: ComponentId,
+    values: Vec<CapturedValue<C>>,
+}
+
+impl<C: Component<Mutability = Mutable> + Clone> CapturedState for CapturedComponents<C> {
+    fn contains(&self, entity: Entity) -> bool {
+        self.values.iter().any(|captured| captured.entity == entity)
+    }
+
+    fn collect_entities(&self, entities: &mut EntityHashSet) {
+        entities.extend(self.values.iter().map(|captured| captured.entity));
+    }
+
+    fn collect_spawned_since(
+        &self,
+        world: &World,
+        tick: Tick,
+        captured: &EntityHashSet,
+        spawned: &mut Vec<Entity>,
+    ) {
+        let this_run = world.read_change_tick();
+        spawned.extend(entities_with(world, self.component_id).filter(|&entity| {
+            !captured.contains(&entity)
+                && world
+                    .entity(entity)
+                    .spawn_tick()
+                    .is_newer_than(tick, this_run)
+        }));
+    }
+
+    fn restore(&self, world: &mut World, entity_map: &mut EntityHashMap<Entity>) {
+        let remap = !entity_map.is_empty();
+        let change_tick = world.change_tick();
+        let mut targets = EntityHashSet::default();
+        for captured in &self.values {
+            let target = entity_map
+                .get(&captured.entity)
+                .copied()
+                .unwrap_or(captured.entity);
+            targets.insert(target);
+            let Ok(mut entity) = world.get_entity_mut(target) else {
+                continue;
+            };
+            let mut value = || {
+                let mut value = captured.value.clone();
+                if remap {
+                    C::map_entities(&mut value, entity_map);
+                }
+                value
+            };
+            if let Some(mut current) = entity.get_mut::<C>() {
+                if current.last_changed() == captured.changed && !remap {
+                    continue;
+                }
+                *current.bypass_change_detection() = value();
+                current.set_last_changed(change_tick);
+            } else {
+                entity.insert_without_lifecycle(value());
+            }
+        }
+
+        let added = entities_with(world, self.component_id)
+            .filter(|entity| !targets.contains(entity))
+            .collect::<Vec<_>>();
+        for entity in added {
+            world.entity_mut(entity).remove_without_lifecycle::<C>();
+        }
+    }
+
+    fn check_change_ticks(&mut self, check: CheckChangeTicks) {
+        for captured in &mut self.values {
+            captured.changed.check_tick(check);
+        }
+    }
+}
+
+struct ResourceTarget<R: Resource>(core::marker::PhantomData<fn() -> R>);
+
+impl<R: Resource + Clone> SnapshotTarget for ResourceTarget<R> {
+    fn capture(&self, world: &mut World) -> Box<dyn CapturedState> {
+        let value = world.get_resource_ref::<R>().map(|value| CapturedValue {
+            entity: Entity::PLACEHOLDER,
+            changed: value.last_changed(),
+            value: R::clone(&value),
+        });
+        Box::new(CapturedResource { value })
+    }
+}
+
+struct CapturedResource<R: Resource> {
+    value: Option<CapturedValue<R>>,
+}
+
+impl<R: Resource + Clone> CapturedState for CapturedResource<R> {
+    fn contains(&self, _entity: Entity) -> bool {
+        false
+    }
+
+    fn collect_entities(&self, _entities: &mut EntityHashSet) {}
+
+    fn collect_spawned_since(
+        &self,
+        _world: &World,
+        _tick: Tick,
+        _captured: &EntityHashSet,
+        _spawned: &mut Vec<Entity>,
+    ) {
+    }
+
+    fn restore(&self, world: &mut World, entity_map: &mut EntityHashMap<Entity>) {
+        // Resources are stored on their own entity, which is kept around with its `IsResource`
+        // marker when the resource is removed.
+        let component_id = world.register_component::<R>();
+        let resource_entity = world
+            .resource_entities()
+            .get(component_id)
+            .filter(|&entity| world.entities().contains_spawned(entity));
+        let Some(captured) = &self.value else {
+            if let Some(entity) = resource_entity {
+                world.entity_mut(entity).remove_without_lifecycle::<R>();
+            }
+            return;
+        };
+        let remap = !entity_map.is_empty();
+        let change_tick = world.change_tick();
+        let mut value = || {
+            let mut value = captured.value.clone();
+            if remap {
+                R::map_entities(&mut value, entity_map);
+            }
+            value
+        };
+        if let Some(mut current) = world.get_resource_mut::<R>() {
+            if current.last_changed() == captured.changed && !remap {
+                return;
+            }
+            *current.bypass_change_detection() = value();
+            current.set_last_changed(change_tick);
+        } else {
+            let entity = match resource_entity {
+                Some(entity) => entity,
+                None => world.spawn(IsResource::new(component_id)).id(),
+            };
+            world.entity_mut(entity).insert_without_lifecycle(value());
+        }
+    }
+
+    fn check_change_ticks(&mut self, check: CheckChangeTicks) {
+        if let Some(captured) = &mut self.value {
+            captured.changed.check_tick(check);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnapshotConfig;
+    use crate::{
+        change_detection::{DetectChanges, CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
+        component::Component,
+        entity::{Entity, EntityMapper, MapEntities},
+        lifecycle::{Add, Insert, Remove},
+        observer::On,
+        resource::Resource,
+        system::ResMut,
+        world::World,
+    };
+    use alloc::vec::Vec;
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Health(u32);
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Poisoned;
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    #[component(map_entities)]
+    struct Target(Entity);
+
+    impl MapEntities for Target {
+        fn map_entities<E: EntityMapper>(&mut self, entity_mapper: &mut E) {
+            self.0 = entity_mapper.get_mapped(self.0);
+        }
+    }
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    #[component(storage = "SparseSet")]
+    struct Stunned(u32);
+
+    #[derive(Resource, Clone, Copy, PartialEq, Debug)]
+    struct Score(u32);
+
+    #[derive(Resource, Default)]
+    struct Lifecycle(usize);
+
+    fn config() -> SnapshotConfig {
+        SnapshotConfig::new()
+            .with_component::<Health>()
+            .with_component::<Poisoned>()
+            .with_component::<Target>()
+            .with_component::<Stunned>()
+            .with_resource::<Score>()
+    }
+
+    #[test]
+    fn restore_values_in_place() {
+        let mut world = World::new();
+        let a = world.spawn(Health(10)).id();
+        let b = world.spawn((Health(20), Poisoned)).id();
+        world.insert_resource(Score(1));
+
+        let config = config();
+        let snapshot = config.capture(&mut world);
+        assert!(snapshot.contains(a));
+        assert!(snapshot.contains(b));
+
+        world.get_mut::<Health>(a).unwrap().0 = 0;
+        world.entity_mut(a).insert(Poisoned);
+        world.entity_mut(b).remove::<Poisoned>();
+        world.resource_mut::<Score>().0 = 5;
+
+        let entity_map = snapshot.restore(&mut world);
+        assert!(entity_map.is_empty());
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert_eq!(world.get::<Poisoned>(a), None);
+        assert_eq!(world.get::<Health>(b), Some(&Health(20)));
+        assert_eq!(world.get::<Poisoned>(b), Some(&Poisoned));
+        assert_eq!(world.resource::<Score>(), &Score(1));
+    }
+
+    #[test]
+    fn restore_keeps_unchanged_ticks() {
+        let mut world = World::new();
+        let a = world.spawn(Health(10)).id();
+        let b = world.spawn(Health(20)).id();
+
+        let config = config();
+        let snapshot = config.capture(&mut world);
+        let before = world.entity(b).get_ref::<Health>().unwrap().last_changed();
+
+        world.increment_change_tick();
+        world.get_mut::<Health>(a).unwrap().0 = 0;
+        world.increment_change_tick();
+        let restore_tick = world.change_tick();
+        snapshot.restore(&mut world);
+
+        let a_ref = world.entity(a).get_ref::<Health>().unwrap();
+        assert_eq!(a_ref.last_changed(), restore_tick);
+        let b_ref = world.entity(b).get_ref::<Health>().unwrap();
+        assert_eq!(b_ref.last_changed(), before);
+    }
+
+    #[test]
+    fn restore_spawned_and_despawned_entities() {
+        let mut world = World::new();
+        let existing = world.spawn_empty().id();
+        let kept = world.spawn(Health(10)).id();
+        let despawned = world.spawn(Health(20)).id();
+        let targeting = world.spawn(Target(despawned)).id();
+        world.insert_resource(Score(1));
+
+        let config = config();
+        let snapshot = config.capture(&mut world);
+
+        let spawned = world.spawn(Health(30)).id();
+        world.entity_mut(existing).insert(Health(40));
+        world.despawn(despawned);
+        world.remove_resource::<Score>();
+
+        let entity_map = snapshot.restore(&mut world);
+        assert!(!world.entities().contains_spawned(spawned));
+        assert!(world.entities().contains_spawned(existing));
+        assert_eq!(world.get::<Health>(existing), None);
+        assert_eq!(world.get::<Health>(kept), Some(&Health(10)));
+        assert_eq!(world.resource::<Score>(), &Score(1));
+
+        let respawned = entity_map[&despawned];
+        assert_eq!(world.get::<Health>(respawned), Some(&Health(20)));
+        assert_eq!(world.get::<Target>(targeting), Some(&Target(respawned)));
+
+        let mut healths = world
+            .query::<&Health>()
+            .iter(&world)
+            .map(|health| health.0)
+            .collect::<Vec<_>>();
+        healths.sort();
+        assert_eq!(healths, [10, 20]);
+    }
+
+    #[test]
+    fn restore_removes_added_resource() {
+        let mut world = World::new();
+        let config = config();
+        let snapshot = config.capture(&mut world);
+
+        world.insert_resource(Score(3));
+        snapshot.restore(&mut world);
+        assert!(!world.contains_resource::<Score>());
+    }
+
+    #[test]
+    fn restore_repeatedly() {
+        let mut world = World::new();
+        let entity = world.spawn(Health(10)).id();
+        let config = config();
+        let snapshot = config.capture(&mut world);
+
+        for i in 0..3 {
+            world.get_mut::<Health>(entity).unwrap().0 = i;
+            world.spawn(Health(i));
+            snapshot.restore(&mut world);
+            assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+            assert_eq!(world.query::<&Health>().iter(&world).count(), 1);
+        }
+    }
+
+    #[test]
+    fn restore_skips_lifecycle_events() {
+        let mut world = World::new();
+        world.init_resource::<Lifecycle>();
+        world.add_observer(|_: On<Add, Health>, mut count: ResMut<Lifecycle>| count.0 += 1);
+        world.add_observer(|_: On<Insert, Health>, mut count: ResMut<Lifecycle>| count.0 += 1);
+        world.add_observer(|_: On<Remove, Health>, mut count: ResMut<Lifecycle>| count.0 += 1);
+        let a = world.spawn(Health(10)).id();
+        let b = world.spawn_empty().id();
+        let c = world.spawn(Health(30)).id();
+
+        let config = config();
+        let snapshot = config.capture(&mut world);
+
+        world.entity_mut(a).remove::<Health>();
+        world.entity_mut(b).insert(Health(20));
+        world.get_mut::<Health>(c).unwrap().0 = 0;
+        world.resource_mut::<Lifecycle>().0 = 0;
+
+        snapshot.restore(&mut world);
+        assert_eq!(world.get::<Health>(a), Some(&Health(10)));
+        assert_eq!(world.get::<Health>(b), None);
+        assert_eq!(world.get::<Health>(c), Some(&Health(30)));
+        assert_eq!(world.resource::<Lifecycle>().0, 0);
+        assert_eq!(world.removed::<Health>().count(), 1);
+    }
+
+    #[test]
+    fn restore_sparse_set_components() {
+        let mut world = World::new();
+        let a = world.spawn(Stunned(1)).id();
+        let b = world.spawn((Health(20), Stunned(2))).id();
+        let c = world.spawn(Health(30)).id();
+
+        let config = config();
+        let snapshot = config.capture(&mut world);
+
+        world.get_mut::<Stunned>(a).unwrap().0 = 5;
+        world.entity_mut(b).remove::<Stunned>();
+        world.entity_mut(c).insert(Stunned(3));
+
+        snapshot.restore(&mut world);
+        assert_eq!(world.get::<Stunned>(a), Some(&Stunned(1)));
+        assert_eq!(world.get::<Stunned>(b), Some(&Stunned(2)));
+        assert_eq!(world.get::<Stunned>(c), None);
+    }
+
+    #[test]
+    fn restore_resources_skips_lifecycle_events() {
+        let mut world = World::new();
+        world.init_resource::<Lifecycle>();
+        world.add_observer(|_: On<Add, Score>, mut count: ResMut<Lifecycle>| count.0 += 1);
+        world.add_observer(|_: On<Insert, Score>, mut count: ResMut<Lifecycle>| count.0 += 1);
+        world.add_observer(|_: On<Remove, Score>, mut count: ResMut<Lifecycle>| count.0 += 1);
+        world.insert_resource(Score(1));
+
+        let config = config();
+        let with_score = config.capture(&mut world);
+        world.remove_resource::<Score>();
+        let without_score = config.capture(&mut world);
+        world.resource_mut::<Lifecycle>().0 = 0;
+
+        with_score.restore(&mut world);
+        assert_eq!(world.get_resource::<Score>(), Some(&Score(1)));
+        without_score.restore(&mut world);
+        assert!(!world.contains_resource::<Score>());
+        with_score.restore(&mut world);
+        assert_eq!(world.get_resource::<Score>(), Some(&Score(1)));
+        assert_eq!(world.resource::<Lifecycle>().0, 0);
+    }
+
+    #[test]
+    fn check_change_ticks() {
+        let mut world = World::new();
+        let entity = world.spawn(Health(10)).id();
+        let config = config();
+        let mut snapshot = config.capture(&mut world);
+
+        *world.change_tick.get_mut() += MAX_CHANGE_AGE + CHECK_TICK_THRESHOLD;
+        let check = world.check_change_ticks().unwrap();
+        snapshot.check_change_ticks(check);
+        let before = world
+            .entity(entity)
+            .get_ref::<Health>()
+            .unwrap()
+            .last_changed();
+
+        snapshot.restore(&mut world);
+        let after = world
+            .entity(entity)
+            .get_ref::<Health>()
+            .unwrap()
+            .last_changed();
+        assert_eq!(after, before);
+    }
+}
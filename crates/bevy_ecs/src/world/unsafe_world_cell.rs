@@ -13,7 +13,7 @@ use crate::{
         ContainsEntity, Entities, Entity, EntityAllocator, EntityLocation, EntityNotSpawnedError,
     },
     error::{ErrorHandler, FallbackErrorHandler},
-    lifecycle::RemovedComponentMessages,
+    lifecycle::{MutationRecord, RemovedComponentMessages},
     observer::Observers,
     prelude::Component,
     query::{DebugCheckedUnwrap, QueryAccessError, ReleaseStateQueryData, SingleEntityQueryData},
@@ -21,8 +21,9 @@ use crate::{
     storage::{ComponentSparseSet, Storages, Table},
     world::RawCommandQueue,
 };
-use bevy_platform::sync::atomic::Ordering;
+use bevy_platform::{collections::HashSet, sync::atomic::Ordering};
 use bevy_ptr::{Ptr, UnsafeCellDeref};
+use concurrent_queue::ConcurrentQueue;
 use core::{any::TypeId, cell::UnsafeCell, fmt::Debug, marker::PhantomData, ptr};
 use thiserror::Error;
 
@@ -736,6 +737,22 @@ impl<'w> UnsafeWorldCell<'w> {
         }
     }
 
+    /// # Safety
+    /// It is the caller's responsibility to ensure that there are no outstanding
+    /// references to the components inserted since the last batch of [`Mutate`](crate::lifecycle::Mutate).
+    pub(crate) unsafe fn inserted_components_mut(self) -> &'w mut HashSet<(Entity, ComponentId)> {
+        self.assert_allows_mutable_access();
+        // SAFETY: Caller ensure there are no outstanding references
+        unsafe { &mut (*self.ptr).mutations.inserted }
+    }
+
+    /// Returns the queue of components mutated since the last batch of [`Mutate`](crate::lifecycle::Mutate).
+    #[inline]
+    pub(crate) fn mutated_components(self) -> &'w ConcurrentQueue<(Entity, ComponentId)> {
+        // SAFETY: The queue is only ever accessed through shared references.
+        unsafe { &(*self.ptr).mutations.mutated }
+    }
+
     /// Convenience method for accessing the world's fallback error handler,
     ///
     /// # Safety
@@ -1040,7 +1057,16 @@ impl<'w> UnsafeEntityCell<'w> {
             .map(|(value, cells)| Mut {
                 // SAFETY: returned component is of type T
                 value: value.assert_unique().deref_mut::<T>(),
-                ticks: ComponentTicksMut::from_tick_cells(cells, last_change_tick, change_tick),
+                ticks: ComponentTicksMut {
+                    mutation: T::OBSERVE_MUTATIONS.then(|| {
+                        MutationRecord::new(
+                            self.world.mutated_components(),
+                            self.entity,
+                            component_id,
+                        )
+                    }),
+                    ..ComponentTicksMut::from_tick_cells(cells, last_change_tick, change_tick)
+                },
             })
         }
     }
@@ -1161,7 +1187,16 @@ impl<'w> UnsafeEntityCell<'w> {
             .map(|(value, cells)| MutUntyped {
                 // SAFETY: world access validated by caller and ties world lifetime to `MutUntyped` lifetime
                 value: value.assert_unique(),
-                ticks: ComponentTicksMut::from_tick_cells(cells, self.last_run, self.this_run),
+                ticks: ComponentTicksMut {
+                    mutation: info.observes_mutations().then(|| {
+                        MutationRecord::new(
+                            self.world.mutated_components(),
+                            self.entity,
+                            component_id,
+                        )
+                    }),
+                    ..ComponentTicksMut::from_tick_cells(cells, self.last_run, self.this_run)
+                },
             })
             .ok_or(GetEntityMutByIdError::ComponentNotFound)
         }
@@ -1204,7 +1239,16 @@ impl<'w> UnsafeEntityCell<'w> {
             .map(|(value, cells)| MutUntyped {
                 // SAFETY: world access validated by caller and ties world lifetime to `MutUntyped` lifetime
                 value: value.assert_unique(),
-                ticks: ComponentTicksMut::from_tick_cells(cells, self.last_run, self.this_run),
+                ticks: ComponentTicksMut {
+                    mutation: info.observes_mutations().then(|| {
+                        MutationRecord::new(
+                            self.world.mutated_components(),
+                            self.entity,
+                            component_id,
+                        )
+                    }),
+                    ..ComponentTicksMut::from_tick_cells(cells, self.last_run, self.this_run)
+                },
             })
             .ok_or(GetEntityMutByIdError::ComponentNotFound)
         }
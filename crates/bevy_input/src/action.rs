@@ -0,0 +1,937 @@
+//! Mapping raw device input to user-defined actions.
+//!
+//! Rather than checking `ButtonInput<KeyCode>` for [`KeyCode::Space`] _and_ every connected
+//! [`Gamepad`] for [`GamepadButton::South`], an [`InputMap`] binds both to a single `Jump`
+//! action. Each frame, the bindings are evaluated and the result is stored in an [`ActionState`],
+//! which can be queried much like a [`ButtonInput`](crate::ButtonInput).
+//!
+//! ```
+//! # use bevy_app::{App, Startup, Update};
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_input::action::{ActionState, InputActionPlugin, InputMap};
+//! # use bevy_input::common_conditions::action_just_pressed;
+//! # use bevy_input::gamepad::GamepadButton;
+//! # use bevy_input::keyboard::KeyCode;
+//! #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//! enum PlayerAction {
+//!     Jump,
+//!     Fire,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(InputActionPlugin::<PlayerAction>::default())
+//!     .add_systems(Startup, spawn_player)
+//!     .add_systems(Update, (fire, jump.run_if(action_just_pressed(PlayerAction::Jump))));
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     commands.spawn(
+//!         InputMap::default()
+//!             .with(PlayerAction::Jump, KeyCode::Space)
+//!             .with(PlayerAction::Jump, GamepadButton::South)
+//!             .with(PlayerAction::Fire, GamepadButton::RightTrigger2),
+//!     );
+//! }
+//!
+//! fn jump() {
+//!     // ...
+//! }
+//!
+//! fn fire(actions: Single<&ActionState<PlayerAction>>) {
+//!     let strength = actions.value(&PlayerAction::Fire);
+//!     // ...
+//! }
+//! ```
+//!
+//! For local multiplayer, each player entity gets its own [`InputMap`], which can be restricted
+//! to a single [`Gamepad`] entity with [`InputMap::with_gamepad`].
+
+use crate::InputSystems;
+use alloc::vec::Vec;
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    schedule::{IntoScheduleConfigs, SystemSet},
+    system::Query,
+};
+use bevy_math::Vec2;
+use bevy_platform::collections::{HashMap, HashSet};
+use core::{fmt::Debug, hash::Hash, marker::PhantomData};
+use derive_more::From;
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    bevy_ecs::reflect::ReflectComponent,
+    bevy_reflect::{std_traits::ReflectDefault, Reflect},
+};
+
+#[cfg(all(feature = "serialize", feature = "bevy_reflect"))]
+use bevy_reflect::{ReflectDeserialize, ReflectSerialize};
+
+#[cfg(feature = "gamepad")]
+use crate::gamepad::{Gamepad, GamepadAxis, GamepadButton};
+
+#[cfg(feature = "keyboard")]
+use crate::keyboard::KeyCode;
+
+#[cfg(feature = "mouse")]
+use crate::mouse::MouseButton;
+
+#[cfg(any(feature = "keyboard", feature = "mouse"))]
+use {crate::ButtonInput, bevy_ecs::system::Res};
+
+/// A user-defined action that raw input can be mapped to using an [`InputMap`].
+///
+/// This is implemented for every type that meets the bounds. Usually this is a fieldless enum.
+pub trait Actionlike: Clone + Eq + Hash + Debug + Send + Sync + 'static {}
+
+impl<T: Clone + Eq + Hash + Debug + Send + Sync + 'static> Actionlike for T {}
+
+/// A single input that can be bound to an action in an [`InputMap`].
+///
+/// Every binding produces a "pressed" state and an analog value:
+/// - Keys and mouse buttons have a value of `1.0` while pressed and `0.0` otherwise.
+/// - Gamepad buttons report their analog value (ex: how far a trigger has been pulled).
+/// - Gamepad axes report their current position and count as pressed while outside of their deadzone.
+/// - A [`InputBinding::Chord`] is pressed while _all_ of its inputs are pressed.
+#[derive(Debug, Clone, PartialEq, From)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum InputBinding {
+    /// A key on the keyboard, identified by its physical location.
+    #[cfg(feature = "keyboard")]
+    Keyboard(KeyCode),
+    /// A button on the mouse.
+    #[cfg(feature = "mouse")]
+    Mouse(MouseButton),
+    /// A button on a [`Gamepad`].
+    #[cfg(feature = "gamepad")]
+    GamepadButton(GamepadButton),
+    /// An axis on a [`Gamepad`].
+    #[cfg(feature = "gamepad")]
+    GamepadAxis(GamepadAxis),
+    /// A combination of inputs that must all be pressed at the same time (ex: `Ctrl + S`).
+    ///
+    /// The value of a chord is the value of its last input.
+    #[from(ignore)]
+    Chord(Vec<InputBinding>),
+}
+
+impl InputBinding {
+    /// Creates a [`InputBinding::Chord`] from the given inputs.
+    pub fn chord(inputs: impl IntoIterator<Item = impl Into<InputBinding>>) -> Self {
+        Self::Chord(inputs.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns the pressed state and analog value of this binding.
+    #[cfg_attr(
+        not(any(feature = "keyboard", feature = "mouse", feature = "gamepad")),
+        expect(
+            clippy::only_used_in_recursion,
+            reason = "all input sources are disabled, so only chords remain"
+        )
+    )]
+    fn evaluate(&self, inputs: &ActionInputs) -> (bool, f32) {
+        match self {
+            #[cfg(feature = "keyboard")]
+            InputBinding::Keyboard(key) => {
+                button_value(inputs.keyboard.is_some_and(|keys| keys.pressed(*key)))
+            }
+            #[cfg(feature = "mouse")]
+            InputBinding::Mouse(button) => {
+                button_value(inputs.mouse.is_some_and(|buttons| buttons.pressed(*button)))
+            }
+            #[cfg(feature = "gamepad")]
+            InputBinding::GamepadButton(button) => {
+                inputs
+                    .gamepads()
+                    .fold((false, 0.0), |(pressed, value), gamepad| {
+                        let gamepad_value = gamepad.get(*button).unwrap_or(0.0);
+                        (
+                            pressed || gamepad.pressed(*button),
+                            max_magnitude(value, gamepad_value),
+                        )
+                    })
+            }
+            #[cfg(feature = "gamepad")]
+            InputBinding::GamepadAxis(axis) => {
+                let value = inputs.gamepads().fold(0.0, |value, gamepad| {
+                    max_magnitude(value, gamepad.get(*axis).unwrap_or(0.0))
+                });
+                (value != 0.0, value)
+            }
+            InputBinding::Chord(bindings) => {
+                let mut value = 0.0;
+                for binding in bindings {
+                    let (pressed, binding_value) = binding.evaluate(inputs);
+                    if !pressed {
+                        return (false, 0.0);
+                    }
+                    value = binding_value;
+                }
+                (!bindings.is_empty(), value)
+            }
+        }
+    }
+}
+
+/// A pair of inputs that can be bound to an action in an [`InputMap`] to produce a [`Vec2`],
+/// which can be read using [`ActionState::axis_pair`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, PartialEq, Clone)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    all(feature = "serialize", feature = "bevy_reflect"),
+    reflect(Serialize, Deserialize)
+)]
+pub enum AxisPairBinding {
+    /// Two [`Gamepad`] axes, such as a stick.
+    #[cfg(feature = "gamepad")]
+    GamepadAxes {
+        /// The horizontal axis.
+        x: GamepadAxis,
+        /// The vertical axis.
+        y: GamepadAxis,
+    },
+    /// Four inputs that act like a directional pad (ex: `WASD`).
+    ///
+    /// The resulting [`Vec2`] is clamped to a length of `1.0`.
+    VirtualDPad {
+        /// The input for the positive y direction.
+        up: InputBinding,
+        /// The input for the negative y direction.
+        down: InputBinding,
+        /// The input for the negative x direction.
+        left: InputBinding,
+        /// The input for the positive x direction.
+        right: InputBinding,
+    },
+}
+
+impl AxisPairBinding {
+    /// The left stick of a [`Gamepad`].
+    #[cfg(feature = "gamepad")]
+    pub const LEFT_STICK: Self = Self::GamepadAxes {
+        x: GamepadAxis::LeftStickX,
+        y: GamepadAxis::LeftStickY,
+    };
+
+    /// The right stick of a [`Gamepad`].
+    #[cfg(feature = "gamepad")]
+    pub const RIGHT_STICK: Self = Self::GamepadAxes {
+        x: GamepadAxis::RightStickX,
+        y: GamepadAxis::RightStickY,
+    };
+
+    /// The `WASD` keys.
+    #[cfg(feature = "keyboard")]
+    pub const WASD: Self = Self::VirtualDPad {
+        up: InputBinding::Keyboard(KeyCode::KeyW),
+        down: InputBinding::Keyboard(KeyCode::KeyS),
+        left: InputBinding::Keyboard(KeyCode::KeyA),
+        right: InputBinding::Keyboard(KeyCode::KeyD),
+    };
+
+    /// The arrow keys.
+    #[cfg(feature = "keyboard")]
+    pub const ARROW_KEYS: Self = Self::VirtualDPad {
+        up: InputBinding::Keyboard(KeyCode::ArrowUp),
+        down: InputBinding::Keyboard(KeyCode::ArrowDown),
+        left: InputBinding::Keyboard(KeyCode::ArrowLeft),
+        right: InputBinding::Keyboard(KeyCode::ArrowRight),
+    };
+
+    /// The directional pad of a [`Gamepad`].
+    #[cfg(feature = "gamepad")]
+    pub const DPAD: Self = Self::VirtualDPad {
+        up: InputBinding::GamepadButton(GamepadButton::DPadUp),
+        down: InputBinding::GamepadButton(GamepadButton::DPadDown),
+        left: InputBinding::GamepadButton(GamepadButton::DPadLeft),
+        right: InputBinding::GamepadButton(GamepadButton::DPadRight),
+    };
+
+    fn evaluate(&self, inputs: &ActionInputs) -> Vec2 {
+        match self {
+            #[cfg(feature = "gamepad")]
+            AxisPairBinding::GamepadAxes { x, y } => {
+                inputs.gamepads().fold(Vec2::ZERO, |value, gamepad| {
+                    let gamepad_value = Vec2::new(
+                        gamepad.get(*x).unwrap_or(0.0),
+                        gamepad.get(*y).unwrap_or(0.0),
+                    );
+                    if gamepad_value.length_squared() > value.length_squared() {
+                        gamepad_value
+                    } else {
+                        value
+                    }
+                })
+            }
+            AxisPairBinding::VirtualDPad {
+                up,
+                down,
+                left,
+                right,
+            } => {
+                let value = |binding: &InputBinding| binding.evaluate(inputs).1.abs();
+                Vec2::new(value(right) - value(left), value(up) - value(down)).clamp_length_max(1.0)
+            }
+        }
+    }
+}
+
+/// Binds raw input to actions of type `A`, updating the [`ActionState<A>`] on the same entity.
+///
+/// Games with a single set of bindings usually spawn one entity with an [`InputMap`]. Local
+/// multiplayer games can give each player entity their own bindings and gamepad.
+///
+/// An action can have any number of bindings. It is pressed while _any_ of them are pressed,
+/// and its value is the value with the largest magnitude.
+///
+/// The [`ActionState`] is only updated if an [`InputActionPlugin<A>`] has been added to the [`App`].
+///
+/// [`InputMap`] and [`ActionState`] are components only, they can't be used as resources. Games with a
+/// single set of bindings can access it with [`Single`](bevy_ecs::system::Single) instead.
+#[derive(Debug, Clone, Component)]
+#[require(ActionState<A>)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Clone, Component)
+)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputMap<A: Actionlike> {
+    /// The button-like bindings for each action.
+    bindings: HashMap<A, Vec<InputBinding>>,
+    /// The axis pair bindings for each action.
+    axis_pair_bindings: HashMap<A, Vec<AxisPairBinding>>,
+    /// If set, only input from this [`Gamepad`] entity is used.
+    gamepad: Option<Entity>,
+}
+
+impl<A: Actionlike> Default for InputMap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Default::default(),
+            axis_pair_bindings: Default::default(),
+            gamepad: None,
+        }
+    }
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// Binds the given `input` to the `action`, in addition to its existing bindings.
+    pub fn insert(&mut self, action: A, input: impl Into<InputBinding>) -> &mut Self {
+        self.bindings.entry(action).or_default().push(input.into());
+        self
+    }
+
+    /// Binds the given `input` to the `action`, in addition to its existing bindings.
+    pub fn with(mut self, action: A, input: impl Into<InputBinding>) -> Self {
+        self.insert(action, input);
+        self
+    }
+
+    /// Binds the given pair of inputs to the `action`, in addition to its existing axis pair bindings.
+    pub fn insert_axis_pair(&mut self, action: A, input: AxisPairBinding) -> &mut Self {
+        self.axis_pair_bindings
+            .entry(action)
+            .or_default()
+            .push(input);
+        self
+    }
+
+    /// Binds the given pair of inputs to the `action`, in addition to its existing axis pair bindings.
+    pub fn with_axis_pair(mut self, action: A, input: AxisPairBinding) -> Self {
+        self.insert_axis_pair(action, input);
+        self
+    }
+
+    /// Removes every binding of the `action`.
+    pub fn clear_action(&mut self, action: &A) {
+        self.bindings.remove(action);
+        self.axis_pair_bindings.remove(action);
+    }
+
+    /// Returns the button-like bindings of the `action`.
+    pub fn bindings(&self, action: &A) -> &[InputBinding] {
+        self.bindings
+            .get(action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns the axis pair bindings of the `action`.
+    pub fn axis_pair_bindings(&self, action: &A) -> &[AxisPairBinding] {
+        self.axis_pair_bindings
+            .get(action)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns an iterator over every action with at least one binding.
+    pub fn actions(&self) -> impl Iterator<Item = &A> {
+        self.bindings.keys().chain(
+            self.axis_pair_bindings
+                .keys()
+                .filter(|action| !self.bindings.contains_key(*action)),
+        )
+    }
+
+    /// Only reads gamepad input from the given [`Gamepad`] entity.
+    pub fn with_gamepad(mut self, gamepad: Entity) -> Self {
+        self.gamepad = Some(gamepad);
+        self
+    }
+
+    /// Sets the [`Gamepad`] entity to read input from. If `None`, input from every gamepad is used.
+    pub fn set_gamepad(&mut self, gamepad: Option<Entity>) {
+        self.gamepad = gamepad;
+    }
+
+    /// Returns the [`Gamepad`] entity this map reads input from. If `None`, input from every gamepad is used.
+    pub fn gamepad(&self) -> Option<Entity> {
+        self.gamepad
+    }
+}
+
+/// The current state of a single action in an [`ActionState`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, PartialEq, Clone)
+)]
+pub struct ActionData {
+    /// Whether the action is currently pressed.
+    pub pressed: bool,
+    /// Whether the action was pressed during the last update.
+    pub just_pressed: bool,
+    /// Whether the action was released during the last update.
+    pub just_released: bool,
+    /// The analog value of the action.
+    pub value: f32,
+    /// The two dimensional value of the action.
+    pub axis_pair: Vec2,
+}
+
+/// The current state of every action of type `A`, as produced by an [`InputMap<A>`].
+///
+/// This works like a [`ButtonInput`](crate::ButtonInput) for actions: [`pressed`](Self::pressed),
+/// [`just_pressed`](Self::just_pressed) and [`just_released`](Self::just_released) report the
+/// digital state of an action, while [`value`](Self::value) and [`axis_pair`](Self::axis_pair)
+/// report its analog state.
+///
+/// Input can also be simulated without an [`InputMap`] (ex: for AI controlled players or in tests)
+/// using [`press`](Self::press), [`release`](Self::release) and [`set`](Self::set).
+#[derive(Debug, Clone, Component)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Debug, Default, Clone, Component)
+)]
+pub struct ActionState<A: Actionlike> {
+    actions: HashMap<A, ActionData>,
+    /// Input simulated with [`ActionState::set`], merged with the bound input on every update.
+    #[cfg_attr(feature = "bevy_reflect", reflect(ignore))]
+    simulated: HashMap<A, SimulatedInput>,
+}
+
+/// Input simulated for an action with [`ActionState::set`].
+#[derive(Debug, Clone, Copy)]
+struct SimulatedInput {
+    pressed: bool,
+    value: f32,
+    axis_pair: Vec2,
+}
+
+impl<A: Actionlike> Default for ActionState<A> {
+    fn default() -> Self {
+        Self {
+            actions: Default::default(),
+            simulated: Default::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> ActionState<A> {
+    /// Returns `true` if the `action` is pressed.
+    pub fn pressed(&self, action: &A) -> bool {
+        self.actions.get(action).is_some_and(|data| data.pressed)
+    }
+
+    /// Returns `true` if the `action` was pressed during the last update.
+    pub fn just_pressed(&self, action: &A) -> bool {
+        self.actions
+            .get(action)
+            .is_some_and(|data| data.just_pressed)
+    }
+
+    /// Returns `true` if the `action` was released during the last update.
+    pub fn just_released(&self, action: &A) -> bool {
+        self.actions
+            .get(action)
+            .is_some_and(|data| data.just_released)
+    }
+
+    /// Returns the analog value of the `action`, or `0.0` if it has no state.
+    pub fn value(&self, action: &A) -> f32 {
+        self.actions.get(action).map_or(0.0, |data| data.value)
+    }
+
+    /// Returns the two dimensional value of the `action`, or [`Vec2::ZERO`] if it has no state.
+    pub fn axis_pair(&self, action: &A) -> Vec2 {
+        self.actions
+            .get(action)
+            .map_or(Vec2::ZERO, |data| data.axis_pair)
+    }
+
+    /// Returns the full [`ActionData`] of the `action`, if it has any state.
+    pub fn action_data(&self, action: &A) -> Option<&ActionData> {
+        self.actions.get(action)
+    }
+
+    /// Returns an iterator over every pressed action.
+    pub fn get_pressed(&self) -> impl Iterator<Item = &A> {
+        self.actions
+            .iter()
+            .filter_map(|(action, data)| data.pressed.then_some(action))
+    }
+
+    /// Returns an iterator over every action that was pressed during the last update.
+    pub fn get_just_pressed(&self) -> impl Iterator<Item = &A> {
+        self.actions
+            .iter()
+            .filter_map(|(action, data)| data.just_pressed.then_some(action))
+    }
+
+    /// Returns an iterator over every action that was released during the last update.
+    pub fn get_just_released(&self) -> impl Iterator<Item = &A> {
+        self.actions
+            .iter()
+            .filter_map(|(action, data)| data.just_released.then_some(action))
+    }
+
+    /// Simulates input for the `action` (ex: for AI controlled players or in tests).
+    ///
+    /// The input is held until it is changed or [released](Self::release), and is merged with the
+    /// bindings of the [`InputMap`] by [`update_action_state`]. Like real input, it is reflected in
+    /// the state of the action, including its "just pressed" state, the next time that system runs.
+    pub fn set(&mut self, action: A, pressed: bool, value: f32, axis_pair: Vec2) {
+        self.simulated.insert(
+            action,
+            SimulatedInput {
+                pressed,
+                value,
+                axis_pair,
+            },
+        );
+    }
+
+    /// Simulates pressing the `action` until it is [released](Self::release).
+    ///
+    /// See [`set`](Self::set) for more information.
+    pub fn press(&mut self, action: A) {
+        self.set(action, true, 1.0, Vec2::ZERO);
+    }
+
+    /// Stops simulating input for the `action`.
+    pub fn release(&mut self, action: A) {
+        self.simulated.remove(&action);
+    }
+
+    /// Clears the "just pressed" and "just released" state of the `action`.
+    pub fn clear_just_pressed_and_released(&mut self, action: &A) {
+        if let Some(data) = self.actions.get_mut(action) {
+            data.just_pressed = false;
+            data.just_released = false;
+        }
+    }
+
+    /// Removes the state and simulated input of every action.
+    pub fn reset_all(&mut self) {
+        self.actions.clear();
+        self.simulated.clear();
+    }
+
+    /// Sets the current state of the `action`, updating its "just pressed" and "just released" state
+    /// based on its previous state.
+    fn apply(&mut self, action: A, pressed: bool, value: f32, axis_pair: Vec2) {
+        let data = self.actions.entry(action).or_default();
+        data.just_pressed = pressed && !data.pressed;
+        data.just_released = !pressed && data.pressed;
+        data.pressed = pressed;
+        data.value = value;
+        data.axis_pair = axis_pair;
+    }
+
+    /// Updates the state of every action using the bindings of the `input_map` and the simulated input.
+    ///
+    /// Actions that are no longer bound or simulated are released.
+    fn update(&mut self, input_map: Option<&InputMap<A>>, inputs: &ActionInputs) {
+        let mut actions = self.actions.keys().cloned().collect::<HashSet<_>>();
+        actions.extend(self.simulated.keys().cloned());
+        if let Some(input_map) = input_map {
+            actions.extend(input_map.actions().cloned());
+        }
+
+        for action in actions {
+            let (mut pressed, mut value, mut axis_pair) = self
+                .simulated
+                .get(&action)
+                .map_or((false, 0.0, Vec2::ZERO), |input| {
+                    (input.pressed, input.value, input.axis_pair)
+                });
+            if let Some(input_map) = input_map {
+                for binding in input_map.bindings(&action) {
+                    let (binding_pressed, binding_value) = binding.evaluate(inputs);
+                    pressed |= binding_pressed;
+                    value = max_magnitude(value, binding_value);
+                }
+                for binding in input_map.axis_pair_bindings(&action) {
+                    let binding_axis_pair = binding.evaluate(inputs);
+                    if binding_axis_pair.length_squared() > axis_pair.length_squared() {
+                        axis_pair = binding_axis_pair;
+                    }
+                }
+            }
+            self.apply(action, pressed || axis_pair != Vec2::ZERO, value, axis_pair);
+        }
+    }
+}
+
+/// The raw input available to an [`InputMap`] during an update.
+struct ActionInputs<'a> {
+    #[cfg(feature = "keyboard")]
+    keyboard: Option<&'a ButtonInput<KeyCode>>,
+    #[cfg(feature = "mouse")]
+    mouse: Option<&'a ButtonInput<MouseButton>>,
+    #[cfg(feature = "gamepad")]
+    gamepads: &'a [(Entity, &'a Gamepad)],
+    /// The gamepad the [`InputMap`] is restricted to, if any.
+    #[cfg(feature = "gamepad")]
+    gamepad: Option<Entity>,
+    #[cfg(not(any(feature = "keyboard", feature = "mouse", feature = "gamepad")))]
+    _marker: PhantomData<&'a ()>,
+}
+
+#[cfg(feature = "gamepad")]
+impl<'a> ActionInputs<'a> {
+    /// Returns the gamepads whose input is used by the [`InputMap`].
+    fn gamepads(&self) -> impl Iterator<Item = &'a Gamepad> {
+        let gamepad = self.gamepad;
+        self.gamepads
+            .iter()
+            .filter(move |(entity, _)| gamepad.is_none_or(|gamepad| gamepad == *entity))
+            .map(|(_, gamepad)| *gamepad)
+    }
+}
+
+#[cfg(any(feature = "keyboard", feature = "mouse"))]
+fn button_value(pressed: bool) -> (bool, f32) {
+    (pressed, if pressed { 1.0 } else { 0.0 })
+}
+
+fn max_magnitude(a: f32, b: f32) -> f32 {
+    if b.abs() > a.abs() {
+        b
+    } else {
+        a
+    }
+}
+
+/// Updates every [`ActionState<A>`] using the [`InputMap<A>`] on the same entity, if any,
+/// and the input simulated with [`ActionState::set`].
+///
+/// This is added to [`PreUpdate`] by the [`InputActionPlugin<A>`].
+pub fn update_action_state<A: Actionlike>(
+    #[cfg(feature = "keyboard")] keyboard: Option<Res<ButtonInput<KeyCode>>>,
+    #[cfg(feature = "mouse")] mouse: Option<Res<ButtonInput<MouseButton>>>,
+    #[cfg(feature = "gamepad")] gamepads: Query<(Entity, &Gamepad)>,
+    mut query: Query<(Option<&InputMap<A>>, &mut ActionState<A>)>,
+) {
+    // Collected once and shared by every input map, which may be restricted to a single gamepad
+    #[cfg(feature = "gamepad")]
+    let gamepads = gamepads.iter().collect::<Vec<_>>();
+
+    for (input_map, mut action_state) in &mut query {
+        let inputs = ActionInputs {
+            #[cfg(feature = "keyboard")]
+            keyboard: keyboard.as_deref(),
+            #[cfg(feature = "mouse")]
+            mouse: mouse.as_deref(),
+            #[cfg(feature = "gamepad")]
+            gamepads: &gamepads,
+            #[cfg(feature = "gamepad")]
+            gamepad: input_map.and_then(InputMap::gamepad),
+            #[cfg(not(any(feature = "keyboard", feature = "mouse", feature = "gamepad")))]
+            _marker: PhantomData,
+        };
+        action_state.update(input_map, &inputs);
+    }
+}
+
+/// Set for the systems that update [`ActionState`]s. This runs in [`PreUpdate`], after [`InputSystems`].
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemSet)]
+pub struct InputActionSystems;
+
+/// Updates [`ActionState<A>`] components from [`InputMap<A>`] components every frame.
+pub struct InputActionPlugin<A: Actionlike>(PhantomData<fn() -> A>);
+
+impl<A: Actionlike> Default for InputActionPlugin<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: Actionlike> Plugin for InputActionPlugin<A> {
+    fn build(&self, app: &mut App) {
+        app.configure_sets(PreUpdate, InputActionSystems.after(InputSystems))
+            .add_systems(
+                PreUpdate,
+                update_action_state::<A>.in_set(InputActionSystems),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(feature = "keyboard", feature = "gamepad"))]
+    use super::AxisPairBinding;
+    #[cfg(feature = "keyboard")]
+    use super::InputBinding;
+    #[cfg(any(feature = "keyboard", feature = "gamepad"))]
+    use super::InputMap;
+    use super::{ActionState, InputActionPlugin};
+    #[cfg(feature = "gamepad")]
+    use crate::gamepad::{Gamepad, GamepadAxis, GamepadButton};
+    #[cfg(feature = "keyboard")]
+    use crate::{keyboard::KeyCode, ButtonInput};
+    use bevy_app::App;
+    use bevy_ecs::entity::Entity;
+    use bevy_math::Vec2;
+
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    enum Action {
+        Jump,
+        Save,
+        Move,
+    }
+
+    fn test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(InputActionPlugin::<Action>::default());
+        #[cfg(feature = "keyboard")]
+        app.init_resource::<ButtonInput<KeyCode>>();
+        app
+    }
+
+    fn action_state(app: &App, entity: Entity) -> &ActionState<Action> {
+        app.world().get::<ActionState<Action>>(entity).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "keyboard")]
+    fn keyboard_action_transitions() {
+        let mut app = test_app();
+        let player = app
+            .world_mut()
+            .spawn(InputMap::default().with(Action::Jump, KeyCode::Space))
+            .id();
+
+        app.update();
+        assert!(!action_state(&app, player).pressed(&Action::Jump));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.update();
+        let state = action_state(&app, player);
+        assert!(state.pressed(&Action::Jump));
+        assert!(state.just_pressed(&Action::Jump));
+        assert_eq!(state.value(&Action::Jump), 1.0);
+
+        app.update();
+        let state = action_state(&app, player);
+        assert!(state.pressed(&Action::Jump));
+        assert!(!state.just_pressed(&Action::Jump));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(KeyCode::Space);
+        app.update();
+        let state = action_state(&app, player);
+        assert!(!state.pressed(&Action::Jump));
+        assert!(state.just_released(&Action::Jump));
+        assert_eq!(state.value(&Action::Jump), 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "keyboard")]
+    fn chords_require_every_input() {
+        let mut app = test_app();
+        let player = app
+            .world_mut()
+            .spawn(InputMap::default().with(
+                Action::Save,
+                InputBinding::chord([KeyCode::ControlLeft, KeyCode::KeyS]),
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyS);
+        app.update();
+        assert!(!action_state(&app, player).pressed(&Action::Save));
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ControlLeft);
+        app.update();
+        assert!(action_state(&app, player).just_pressed(&Action::Save));
+    }
+
+    #[test]
+    #[cfg(feature = "gamepad")]
+    fn per_player_gamepads() {
+        let mut app = test_app();
+        let mut first_gamepad = Gamepad::default();
+        first_gamepad.digital_mut().press(GamepadButton::South);
+        first_gamepad.analog_mut().set(GamepadButton::South, 1.0);
+        first_gamepad.analog_mut().set(GamepadAxis::LeftStickX, 0.5);
+        let first_gamepad = app.world_mut().spawn(first_gamepad).id();
+        let second_gamepad = app.world_mut().spawn(Gamepad::default()).id();
+
+        let input_map = InputMap::default()
+            .with(Action::Jump, GamepadButton::South)
+            .with_axis_pair(Action::Move, AxisPairBinding::LEFT_STICK);
+        let first_player = app
+            .world_mut()
+            .spawn(input_map.clone().with_gamepad(first_gamepad))
+            .id();
+        let second_player = app
+            .world_mut()
+            .spawn(input_map.with_gamepad(second_gamepad))
+            .id();
+
+        app.update();
+        let state = action_state(&app, first_player);
+        assert!(state.just_pressed(&Action::Jump));
+        assert_eq!(state.value(&Action::Jump), 1.0);
+        assert_eq!(state.axis_pair(&Action::Move), Vec2::new(0.5, 0.0));
+        assert!(state.pressed(&Action::Move));
+
+        let state = action_state(&app, second_player);
+        assert!(!state.pressed(&Action::Jump));
+        assert_eq!(state.axis_pair(&Action::Move), Vec2::ZERO);
+    }
+
+    #[test]
+    #[cfg(feature = "keyboard")]
+    fn virtual_dpad_is_clamped() {
+        let mut app = test_app();
+        let player = app
+            .world_mut()
+            .spawn(InputMap::default().with_axis_pair(Action::Move, AxisPairBinding::WASD))
+            .id();
+
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.press(KeyCode::KeyW);
+        keys.press(KeyCode::KeyD);
+        app.update();
+        let axis_pair = action_state(&app, player).axis_pair(&Action::Move);
+        assert!((axis_pair.length() - 1.0).abs() < 1e-6);
+        assert!(axis_pair.x > 0.0 && axis_pair.y > 0.0);
+    }
+
+    #[test]
+    #[cfg(feature = "keyboard")]
+    fn unbound_actions_are_released() {
+        let mut app = test_app();
+        let player = app
+            .world_mut()
+            .spawn(InputMap::default().with(Action::Jump, KeyCode::Space))
+            .id();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Space);
+        app.update();
+        assert!(action_state(&app, player).pressed(&Action::Jump));
+
+        app.world_mut()
+            .get_mut::<InputMap<Action>>(player)
+            .unwrap()
+            .clear_action(&Action::Jump);
+        app.update();
+        let state = action_state(&app, player);
+        assert!(!state.pressed(&Action::Jump));
+        assert!(state.just_released(&Action::Jump));
+    }
+
+    #[test]
+    fn simulated_input_without_input_map() {
+        let mut app = test_app();
+        let player = app.world_mut().spawn(ActionState::<Action>::default()).id();
+
+        let mut state = app
+            .world_mut()
+            .get_mut::<ActionState<Action>>(player)
+            .unwrap();
+        state.press(Action::Jump);
+        state.set(Action::Move, false, 0.0, Vec2::new(0.0, 1.0));
+        app.update();
+        let state = action_state(&app, player);
+        assert!(state.just_pressed(&Action::Jump));
+        assert!(state.pressed(&Action::Move));
+        assert_eq!(state.axis_pair(&Action::Move), Vec2::new(0.0, 1.0));
+
+        // Simulated input is held until released
+        app.update();
+        let state = action_state(&app, player);
+        assert!(state.pressed(&Action::Jump));
+        assert!(!state.just_pressed(&Action::Jump));
+
+        let mut state = app
+            .world_mut()
+            .get_mut::<ActionState<Action>>(player)
+            .unwrap();
+        state.release(Action::Jump);
+        app.update();
+        let state = action_state(&app, player);
+        assert!(state.just_released(&Action::Jump));
+        assert!(state.pressed(&Action::Move));
+    }
+
+    #[test]
+    #[cfg(feature = "keyboard")]
+    fn simulated_input_is_merged_with_bindings() {
+        let mut app = test_app();
+        let player = app
+            .world_mut()
+            .spawn(InputMap::default().with(Action::Jump, KeyCode::Space))
+            .id();
+        app.update();
+
+        app.world_mut()
+            .get_mut::<ActionState<Action>>(player)
+            .unwrap()
+            .press(Action::Jump);
+        app.update();
+        assert!(action_state(&app, player).just_pressed(&Action::Jump));
+
+        // The binding doesn't release the simulated press
+        app.update();
+        assert!(action_state(&app, player).pressed(&Action::Jump));
+    }
+}
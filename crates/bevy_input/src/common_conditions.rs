@@ -1,5 +1,8 @@
-use crate::ButtonInput;
-use bevy_ecs::system::Res;
+use crate::{
+    action::{ActionState, Actionlike},
+    ButtonInput,
+};
+use bevy_ecs::system::{Query, Res};
 use core::hash::Hash;
 
 /// Stateful run condition that can be toggled via an input press using [`ButtonInput::just_pressed`].
@@ -100,11 +103,79 @@ where
     move |inputs: Res<ButtonInput<T>>| inputs.just_released(input.clone())
 }
 
+/// Stateful run condition that can be toggled by an action using [`ActionState::just_pressed`].
+///
+/// This is the [`ActionState`] equivalent of [`input_toggle_active`]. The state is toggled once
+/// per frame if the action was just pressed on _any_ entity.
+pub fn action_toggle_active<A: Actionlike>(
+    default: bool,
+    action: A,
+) -> impl FnMut(Query<&ActionState<A>>) -> bool + Clone {
+    let mut active = default;
+    move |actions: Query<&ActionState<A>>| {
+        active ^= actions.iter().any(|actions| actions.just_pressed(&action));
+        active
+    }
+}
+
+/// Run condition that is active if [`ActionState::pressed`] is true for the given action on any entity.
+pub fn action_pressed<A: Actionlike>(
+    action: A,
+) -> impl FnMut(Query<&ActionState<A>>) -> bool + Clone {
+    move |actions: Query<&ActionState<A>>| actions.iter().any(|actions| actions.pressed(&action))
+}
+
+/// Run condition that is active if [`ActionState::just_pressed`] is true for the given action on any entity.
+///
+/// ```no_run
+/// # use bevy_app::{App, NoopPluginGroup as DefaultPlugins, Startup, Update};
+/// # use bevy_ecs::prelude::{Commands, IntoScheduleConfigs};
+/// # use bevy_input::{action::{InputActionPlugin, InputMap}, common_conditions::action_just_pressed, prelude::KeyCode};
+/// #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+/// enum Action {
+///     Jump,
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, InputActionPlugin::<Action>::default()))
+///         .add_systems(Startup, |mut commands: Commands| {
+///             commands.spawn(InputMap::default().with(Action::Jump, KeyCode::Space));
+///         })
+///         .add_systems(Update, jump.run_if(action_just_pressed(Action::Jump)))
+///         .run();
+/// }
+///
+/// # fn jump() {}
+/// ```
+pub fn action_just_pressed<A: Actionlike>(
+    action: A,
+) -> impl FnMut(Query<&ActionState<A>>) -> bool + Clone {
+    move |actions: Query<&ActionState<A>>| {
+        actions.iter().any(|actions| actions.just_pressed(&action))
+    }
+}
+
+/// Run condition that is active if [`ActionState::just_released`] is true for the given action on any entity.
+pub fn action_just_released<A: Actionlike>(
+    action: A,
+) -> impl FnMut(Query<&ActionState<A>>) -> bool + Clone {
+    move |actions: Query<&ActionState<A>>| {
+        actions.iter().any(|actions| actions.just_released(&action))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::prelude::KeyCode;
-    use bevy_ecs::schedule::{IntoScheduleConfigs, Schedule};
+    use crate::{action::update_action_state, prelude::KeyCode};
+    use alloc::vec::Vec;
+    use bevy_ecs::{
+        resource::Resource,
+        schedule::{IntoScheduleConfigs, Schedule},
+        system::ResMut,
+        world::World,
+    };
 
     fn test_system() {}
 
@@ -119,4 +190,57 @@ mod tests {
                 .distributive_run_if(input_just_released(KeyCode::Escape)),
         );
     }
+
+    #[derive(Clone, PartialEq, Eq, Hash, Debug)]
+    enum TestAction {
+        Jump,
+        Fire,
+    }
+
+    #[derive(Resource, Default)]
+    struct Ran(Vec<&'static str>);
+
+    fn record(name: &'static str) -> impl FnMut(ResMut<Ran>) {
+        move |mut ran: ResMut<Ran>| ran.0.push(name)
+    }
+
+    #[test]
+    fn action_conditions() {
+        let mut world = World::new();
+        world.init_resource::<Ran>();
+        let player = world.spawn(ActionState::<TestAction>::default()).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (
+                update_action_state::<TestAction>,
+                record("pressed").run_if(action_pressed(TestAction::Jump)),
+                record("just_pressed").run_if(action_just_pressed(TestAction::Jump)),
+                record("just_released").run_if(action_just_released(TestAction::Jump)),
+                record("toggle").run_if(action_toggle_active(false, TestAction::Jump)),
+                record("fire").run_if(action_pressed(TestAction::Fire)),
+            )
+                .chain(),
+        );
+        let mut run = |world: &mut World| {
+            schedule.run(world);
+            core::mem::take(&mut world.resource_mut::<Ran>().0)
+        };
+
+        assert!(run(&mut world).is_empty());
+
+        let mut actions = world.get_mut::<ActionState<TestAction>>(player).unwrap();
+        actions.press(TestAction::Jump);
+        assert_eq!(run(&mut world), ["pressed", "just_pressed", "toggle"]);
+        assert_eq!(run(&mut world), ["pressed", "toggle"]);
+
+        let mut actions = world.get_mut::<ActionState<TestAction>>(player).unwrap();
+        actions.release(TestAction::Jump);
+        assert_eq!(run(&mut world), ["just_released", "toggle"]);
+        assert_eq!(run(&mut world), ["toggle"]);
+
+        let mut actions = world.get_mut::<ActionState<TestAction>>(player).unwrap();
+        actions.press(TestAction::Jump);
+        assert_eq!(run(&mut world), ["pressed", "just_pressed"]);
+    }
 }
@@ -17,6 +17,7 @@ extern crate std;
 
 extern crate alloc;
 
+pub mod action;
 mod axis;
 mod button_input;
 /// Common run conditions
@@ -45,7 +46,10 @@ pub use button_input::*;
 /// This includes the most common types in this crate, re-exported for your convenience.
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Axis, ButtonInput};
+    pub use crate::{
+        action::{ActionState, AxisPairBinding, InputActionPlugin, InputBinding, InputMap},
+        Axis, ButtonInput,
+    };
 
     #[doc(hidden)]
     #[cfg(feature = "gamepad")]
@@ -362,6 +362,18 @@ impl<T> UnevenSampleAutoCurve<T> {
             core: self.core.map_sample_times(f),
         }
     }
+
+    /// The sample times of this curve, in increasing order.
+    pub fn times(&self) -> &[f32] {
+        &self.core.times
+    }
+
+    /// The samples of this curve, one for each of its [sample times].
+    ///
+    /// [sample times]: UnevenSampleAutoCurve::times
+    pub fn samples(&self) -> &[T] {
+        &self.core.samples
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,730 @@
+//! Built-in verbs for the Bevy Remote Protocol that operate on assets.
+//!
+//! These methods are backed by the [`AssetServer`] and the [`Assets`](bevy_asset::Assets)
+//! resources, and only work with asset types registered through
+//! [`register_asset_reflect`](bevy_asset::AssetApp::register_asset_reflect).
+
+use core::any::TypeId;
+
+use anyhow::anyhow;
+use bevy_asset::{
+    uuid::Uuid, AssetIndex, AssetServer, DependencyLoadState, LoadState,
+    RecursiveDependencyLoadState, ReflectAsset, UntypedAssetEvent, UntypedAssetId, UntypedHandle,
+};
+use bevy_ecs::{
+    reflect::AppTypeRegistry,
+    resource::Resource,
+    system::{In, Local},
+    world::World,
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{
+    serde::{ReflectSerializer, TypedReflectDeserializer},
+    GetPath, TypeRegistry,
+};
+use serde::{de::DeserializeSeed as _, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    builtin_methods::{parse, parse_some},
+    BrpError, BrpResult,
+};
+
+/// The method path for a `assets.list` request.
+pub const BRP_ASSETS_LIST_METHOD: &str = "assets.list";
+
+/// The method path for a `assets.load` request.
+pub const BRP_ASSETS_LOAD_METHOD: &str = "assets.load";
+
+/// The method path for a `assets.reload` request.
+pub const BRP_ASSETS_RELOAD_METHOD: &str = "assets.reload";
+
+/// The method path for a `assets.unload` request.
+pub const BRP_ASSETS_UNLOAD_METHOD: &str = "assets.unload";
+
+/// The method path for a `assets.get` request.
+pub const BRP_ASSETS_GET_METHOD: &str = "assets.get";
+
+/// The method path for a `assets.mutate` request.
+pub const BRP_ASSETS_MUTATE_METHOD: &str = "assets.mutate";
+
+/// The method path for a `assets.watch` request.
+pub const BRP_ASSETS_WATCH_METHOD: &str = "assets.watch";
+
+/// Identifies an asset of a known type.
+///
+/// Serialized as `{ "index": <bits> }` or `{ "uuid": <uuid> }`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAssetId {
+    /// An [`AssetIndex`], in the form returned by [`AssetIndex::to_bits`].
+    Index(u64),
+    /// A stable asset [`Uuid`].
+    Uuid(Uuid),
+}
+
+impl BrpAssetId {
+    /// Returns the [`UntypedAssetId`] of the asset with this id and the given asset `type_id`.
+    pub fn untyped(self, type_id: TypeId) -> UntypedAssetId {
+        match self {
+            BrpAssetId::Index(bits) => UntypedAssetId::Index {
+                type_id,
+                index: AssetIndex::from_bits(bits),
+            },
+            BrpAssetId::Uuid(uuid) => UntypedAssetId::Uuid { type_id, uuid },
+        }
+    }
+}
+
+impl From<UntypedAssetId> for BrpAssetId {
+    fn from(id: UntypedAssetId) -> Self {
+        match id {
+            UntypedAssetId::Index { index, .. } => BrpAssetId::Index(index.to_bits()),
+            UntypedAssetId::Uuid { uuid, .. } => BrpAssetId::Uuid(uuid),
+        }
+    }
+}
+
+/// The load state of an asset or of its dependencies, as tracked by the [`AssetServer`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpLoadState {
+    /// Loading has not started yet.
+    NotLoaded,
+    /// Loading is in progress.
+    Loading,
+    /// Loading has finished.
+    Loaded,
+    /// Loading failed with the given error.
+    Failed(String),
+}
+
+impl From<LoadState> for BrpLoadState {
+    fn from(state: LoadState) -> Self {
+        match state {
+            LoadState::NotLoaded => BrpLoadState::NotLoaded,
+            LoadState::Loading => BrpLoadState::Loading,
+            LoadState::Loaded => BrpLoadState::Loaded,
+            LoadState::Failed(err) => BrpLoadState::Failed(err.to_string()),
+        }
+    }
+}
+
+impl From<DependencyLoadState> for BrpLoadState {
+    fn from(state: DependencyLoadState) -> Self {
+        match state {
+            DependencyLoadState::NotLoaded => BrpLoadState::NotLoaded,
+            DependencyLoadState::Loading => BrpLoadState::Loading,
+            DependencyLoadState::Loaded => BrpLoadState::Loaded,
+            DependencyLoadState::Failed(err) => BrpLoadState::Failed(err.to_string()),
+        }
+    }
+}
+
+impl From<RecursiveDependencyLoadState> for BrpLoadState {
+    fn from(state: RecursiveDependencyLoadState) -> Self {
+        match state {
+            RecursiveDependencyLoadState::NotLoaded => BrpLoadState::NotLoaded,
+            RecursiveDependencyLoadState::Loading => BrpLoadState::Loading,
+            RecursiveDependencyLoadState::Loaded => BrpLoadState::Loaded,
+            RecursiveDependencyLoadState::Failed(err) => BrpLoadState::Failed(err.to_string()),
+        }
+    }
+}
+
+/// `assets.list`: Lists the assets stored in the world.
+///
+/// The server responds with a [`BrpAssetsListResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpAssetsListParams {
+    /// The [full path] of the asset type to list.
+    ///
+    /// If omitted, the assets of every reflected asset type are listed.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    #[serde(default)]
+    pub asset_type: Option<String>,
+}
+
+/// `assets.load`: Starts loading an asset from a path.
+///
+/// The server keeps a strong handle to the asset, so it stays loaded until it is released
+/// with `assets.unload`.
+///
+/// The server responds with a [`BrpAssetsLoadResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetsLoadParams {
+    /// The [full path] of the type of the asset to load.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+
+    /// The [`AssetPath`](bevy_asset::AssetPath) of the asset.
+    pub path: String,
+}
+
+/// `assets.unload`: Releases the handle kept by the server for an asset loaded with `assets.load`.
+///
+/// The asset is unloaded once no other strong handles to it remain.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetsUnloadParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+
+    /// The id of the asset, as returned by `assets.load`.
+    pub id: BrpAssetId,
+}
+
+/// `assets.reload`: Reloads the asset at a path, if it is loaded.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetsReloadParams {
+    /// The [`AssetPath`](bevy_asset::AssetPath) of the asset.
+    pub path: String,
+}
+
+/// `assets.get`: Retrieves the value of an asset.
+///
+/// The server responds with a [`BrpAssetsGetResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetsGetParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+
+    /// The id of the asset.
+    pub id: BrpAssetId,
+}
+
+/// `assets.mutate`: Mutates a field of an asset.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetsMutateParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+
+    /// The id of the asset.
+    pub id: BrpAssetId,
+
+    /// The [path] of the field within the asset.
+    ///
+    /// [path]: bevy_reflect::GetPath
+    pub path: String,
+
+    /// The value to insert at `path`.
+    pub value: Value,
+}
+
+/// `assets.watch`: Streams the [`AssetEvent`](bevy_asset::AssetEvent)s of an asset type.
+///
+/// The server responds with a [`BrpAssetsWatchResponse`] every frame in which events were
+/// written, starting with the frame after the request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetsWatchParams {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+}
+
+/// The response to a `assets.list` request.
+pub type BrpAssetsListResponse = Vec<BrpAssetInfo>;
+
+/// Describes one asset stored in the world.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpAssetInfo {
+    /// The [full path] of the asset type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub asset_type: String,
+
+    /// The id of the asset.
+    pub id: BrpAssetId,
+
+    /// The path the asset was loaded from, if any.
+    pub path: Option<String>,
+
+    /// The load state of the asset, if it is tracked by the [`AssetServer`].
+    pub load_state: Option<BrpLoadState>,
+
+    /// The load state of the direct dependencies of the asset, if it is tracked by the [`AssetServer`].
+    pub dependency_load_state: Option<BrpLoadState>,
+
+    /// The load state of all the dependencies of the asset, if it is tracked by the [`AssetServer`].
+    pub recursive_dependency_load_state: Option<BrpLoadState>,
+}
+
+/// The response to a `assets.load` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpAssetsLoadResponse {
+    /// The id of the loading asset.
+    pub id: BrpAssetId,
+}
+
+/// The response to a `assets.get` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpAssetsGetResponse {
+    /// The value of the requested asset.
+    pub value: Value,
+}
+
+/// A single response to a `assets.watch` request.
+pub type BrpAssetsWatchResponse = Vec<BrpAssetEvent>;
+
+/// A reflected [`AssetEvent`](bevy_asset::AssetEvent).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BrpAssetEvent {
+    /// What happened to the asset.
+    pub kind: BrpAssetEventKind,
+
+    /// The id of the asset.
+    pub id: BrpAssetId,
+}
+
+/// The kind of an [`AssetEvent`](bevy_asset::AssetEvent).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpAssetEventKind {
+    /// The asset was added.
+    Added,
+    /// The asset value was modified.
+    Modified,
+    /// The asset was removed.
+    Removed,
+    /// The last strong handle to the asset was dropped.
+    Unused,
+    /// The asset and all its dependencies finished loading.
+    LoadedWithDependencies,
+}
+
+impl From<UntypedAssetEvent> for BrpAssetEvent {
+    fn from(event: UntypedAssetEvent) -> Self {
+        let kind = match event {
+            UntypedAssetEvent::Added { .. } => BrpAssetEventKind::Added,
+            UntypedAssetEvent::Modified { .. } => BrpAssetEventKind::Modified,
+            UntypedAssetEvent::Removed { .. } => BrpAssetEventKind::Removed,
+            UntypedAssetEvent::Unused { .. } => BrpAssetEventKind::Unused,
+            UntypedAssetEvent::LoadedWithDependencies { .. } => {
+                BrpAssetEventKind::LoadedWithDependencies
+            }
+        };
+        BrpAssetEvent {
+            kind,
+            id: event.id().into(),
+        }
+    }
+}
+
+/// Keeps the assets loaded through `assets.load` alive until they are released with `assets.unload`.
+#[derive(Resource, Default)]
+pub struct BrpLoadedAssets {
+    handles: HashMap<UntypedAssetId, UntypedHandle>,
+}
+
+/// Handles a `assets.list` request coming from a client.
+pub fn process_remote_list_assets_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpAssetsListParams { asset_type } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let asset_server = world.get_resource::<AssetServer>();
+
+    let mut asset_types = match &asset_type {
+        Some(asset_type) => vec![(
+            asset_type.as_str(),
+            get_reflect_asset(&type_registry, asset_type)?,
+        )],
+        None => type_registry
+            .iter()
+            .filter_map(|registration| {
+                let reflect_asset = registration.data::<ReflectAsset>()?;
+                Some((registration.type_info().type_path(), reflect_asset))
+            })
+            .collect(),
+    };
+    asset_types.sort_by_key(|(asset_type, _)| *asset_type);
+
+    let mut response = BrpAssetsListResponse::new();
+    for (asset_type, reflect_asset) in asset_types {
+        if !has_assets_resource(world, reflect_asset) {
+            continue;
+        }
+        response.extend(reflect_asset.ids(world).map(|id| {
+            let path = asset_server
+                .and_then(|asset_server| asset_server.get_path(id))
+                .map(|path| path.to_string());
+            let load_states =
+                asset_server.and_then(|asset_server| asset_server.get_load_states(id));
+            BrpAssetInfo {
+                asset_type: asset_type.to_owned(),
+                id: id.into(),
+                path,
+                load_state: load_states.as_ref().map(|(state, ..)| state.clone().into()),
+                dependency_load_state: load_states
+                    .as_ref()
+                    .map(|(_, state, _)| state.clone().into()),
+                recursive_dependency_load_state: load_states
+                    .as_ref()
+                    .map(|(.., state)| state.clone().into()),
+            }
+        }));
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `assets.load` request coming from a client.
+pub fn process_remote_load_asset_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpAssetsLoadParams { asset_type, path } = parse_some(params)?;
+
+    let type_id = {
+        let app_type_registry = world.resource::<AppTypeRegistry>();
+        let type_registry = app_type_registry.read();
+        get_asset_type(&type_registry, &asset_type)?.0
+    };
+
+    let handle = get_asset_server(world)?
+        .load_builder()
+        .load_erased(type_id, path);
+    let id = handle.id();
+    world
+        .get_resource_or_init::<BrpLoadedAssets>()
+        .handles
+        .insert(id, handle);
+
+    serde_json::to_value(BrpAssetsLoadResponse { id: id.into() }).map_err(BrpError::internal)
+}
+
+/// Handles a `assets.unload` request coming from a client.
+pub fn process_remote_unload_asset_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpAssetsUnloadParams { asset_type, id } = parse_some(params)?;
+
+    let type_id = {
+        let app_type_registry = world.resource::<AppTypeRegistry>();
+        let type_registry = app_type_registry.read();
+        get_asset_type(&type_registry, &asset_type)?.0
+    };
+
+    world
+        .get_resource_mut::<BrpLoadedAssets>()
+        .and_then(|mut loaded| loaded.handles.remove(&id.untyped(type_id)))
+        .ok_or_else(|| {
+            BrpError::resource_error(anyhow!(
+                "Asset `{asset_type}` with id {id:?} was not loaded with `assets.load`"
+            ))
+        })?;
+
+    Ok(Value::Null)
+}
+
+/// Handles a `assets.reload` request coming from a client.
+pub fn process_remote_reload_asset_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpAssetsReloadParams { path } = parse_some(params)?;
+
+    get_asset_server(world)?.reload(path);
+
+    Ok(Value::Null)
+}
+
+/// Handles a `assets.get` request coming from a client.
+pub fn process_remote_get_asset_request(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let BrpAssetsGetParams { asset_type, id } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let (type_id, reflect_asset) = get_asset_type(&type_registry, &asset_type)?;
+    if !has_assets_resource(world, reflect_asset) {
+        return Err(asset_not_present(&asset_type, id));
+    }
+
+    let Some(reflected) = reflect_asset.get(world, id.untyped(type_id)) else {
+        return Err(asset_not_present(&asset_type, id));
+    };
+
+    // Use the `ReflectSerializer` to serialize the value of the asset;
+    // this produces a map with a single item.
+    let reflect_serializer = ReflectSerializer::new(reflected.as_partial_reflect(), &type_registry);
+    let Value::Object(serialized_object) =
+        serde_json::to_value(&reflect_serializer).map_err(BrpError::resource_error)?
+    else {
+        return Err(BrpError::resource_error(anyhow!(
+            "Asset `{asset_type}` could not be serialized"
+        )));
+    };
+
+    // Get the single value out of the map.
+    let value = serialized_object.into_values().next().ok_or_else(|| {
+        BrpError::internal(anyhow!("Unexpected format of serialized asset value"))
+    })?;
+    serde_json::to_value(BrpAssetsGetResponse { value }).map_err(BrpError::internal)
+}
+
+/// Handles a `assets.mutate` request coming from a client.
+pub fn process_remote_mutate_asset_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    let BrpAssetsMutateParams {
+        asset_type,
+        id,
+        path: field_path,
+        value,
+    } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>().clone();
+    let type_registry = app_type_registry.read();
+    let (type_id, reflect_asset) = get_asset_type(&type_registry, &asset_type)?;
+    if !has_assets_resource(world, reflect_asset) {
+        return Err(asset_not_present(&asset_type, id));
+    }
+
+    // Get the actual asset value from the world as a `dyn Reflect`.
+    let reflected_asset = reflect_asset
+        .get_mut(world, id.untyped(type_id))
+        .ok_or_else(|| asset_not_present(&asset_type, id))?;
+
+    // Get the type registration for the field with the given path.
+    let value_registration = type_registry
+        .get_with_type_path(
+            reflected_asset
+                .reflect_path(field_path.as_str())
+                .map_err(BrpError::resource_error)?
+                .reflect_type_path(),
+        )
+        .ok_or_else(|| {
+            BrpError::resource_error(anyhow!("Unknown asset field type: `{}`", asset_type))
+        })?;
+
+    // Use the field's type registration to deserialize the given value.
+    let deserialized_value = TypedReflectDeserializer::new(value_registration, &type_registry)
+        .deserialize(&value)
+        .map_err(BrpError::resource_error)?;
+
+    // Apply the value to the asset.
+    reflected_asset
+        .reflect_path_mut(field_path.as_str())
+        .map_err(BrpError::resource_error)?
+        .try_apply(&*deserialized_value)
+        .map_err(BrpError::resource_error)?;
+
+    Ok(Value::Null)
+}
+
+/// Handles a `assets.watch` request coming from a client.
+///
+/// The events of an asset type are read once per poll, so concurrent requests watching the same
+/// asset type share them.
+pub fn process_remote_watch_assets_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+    mut next_events: Local<HashMap<String, usize>>,
+) -> BrpResult<Option<Value>> {
+    let BrpAssetsWatchParams { asset_type } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let reflect_asset = get_reflect_asset(&type_registry, &asset_type)?;
+    if !has_assets_resource(world, reflect_asset) {
+        return Err(BrpError::resource_not_present(&asset_type));
+    }
+
+    // Skip the events that were written before the first poll.
+    let next_event = next_events.entry(asset_type).or_insert_with(|| {
+        let mut next_event = 0;
+        reflect_asset.read_events(world, &mut next_event);
+        next_event
+    });
+
+    let events = reflect_asset.read_events(world, next_event);
+    if events.is_empty() {
+        return Ok(None);
+    }
+
+    let response: BrpAssetsWatchResponse = events.into_iter().map(Into::into).collect();
+    serde_json::to_value(response)
+        .map(Some)
+        .map_err(BrpError::internal)
+}
+
+/// Returns the [`AssetServer`], or an error if it is missing.
+fn get_asset_server(world: &World) -> Result<&AssetServer, BrpError> {
+    world
+        .get_resource::<AssetServer>()
+        .ok_or_else(|| BrpError::resource_not_present(core::any::type_name::<AssetServer>()))
+}
+
+/// Given an asset type path, returns the associated [`ReflectAsset`].
+fn get_reflect_asset<'r>(
+    type_registry: &'r TypeRegistry,
+    asset_type: &str,
+) -> Result<&'r ReflectAsset, BrpError> {
+    get_asset_type(type_registry, asset_type).map(|(_, reflect_asset)| reflect_asset)
+}
+
+/// Given an asset type path, returns its [`TypeId`] and the associated [`ReflectAsset`].
+fn get_asset_type<'r>(
+    type_registry: &'r TypeRegistry,
+    asset_type: &str,
+) -> Result<(TypeId, &'r ReflectAsset), BrpError> {
+    let registration = type_registry
+        .get_with_type_path(asset_type)
+        .ok_or_else(|| BrpError::resource_error(anyhow!("Unknown asset type: `{asset_type}`")))?;
+    let reflect_asset = registration.data::<ReflectAsset>().ok_or_else(|| {
+        BrpError::resource_error(anyhow!("Asset `{asset_type}` isn't reflectable"))
+    })?;
+    Ok((registration.type_id(), reflect_asset))
+}
+
+/// Returns `true` if the [`Assets`](bevy_asset::Assets) resource of the asset type exists.
+fn has_assets_resource(world: &World, reflect_asset: &ReflectAsset) -> bool {
+    world
+        .components()
+        .get_id(reflect_asset.assets_resource_type_id())
+        .is_some_and(|component_id| world.contains_resource_by_id(component_id))
+}
+
+fn asset_not_present(asset_type: &str, id: BrpAssetId) -> BrpError {
+    BrpError::resource_error(anyhow!("Asset `{asset_type}` with id {id:?} not present"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_asset::{Asset, AssetApp, AssetPlugin, Assets};
+    use bevy_reflect::Reflect;
+    use serde_json::json;
+
+    #[derive(Asset, Reflect)]
+    struct TestAsset {
+        value: u32,
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins((bevy_app::TaskPoolPlugin::default(), AssetPlugin::default()))
+            .init_asset::<TestAsset>()
+            .register_asset_reflect::<TestAsset>()
+            .register_type::<u32>();
+        app
+    }
+
+    const TEST_ASSET: &str = "bevy_remote::assets::tests::TestAsset";
+
+    #[test]
+    fn list_get_and_mutate_assets() {
+        let mut app = app();
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<TestAsset>>()
+            .add(TestAsset { value: 1 });
+        let id = BrpAssetId::from(handle.id().untyped());
+
+        let world = app.world_mut();
+        let response = process_remote_list_assets_request(In(None), world).unwrap();
+        let response = serde_json::from_value::<BrpAssetsListResponse>(response).unwrap();
+        assert_eq!(
+            response,
+            vec![BrpAssetInfo {
+                asset_type: TEST_ASSET.to_owned(),
+                id,
+                path: None,
+                load_state: None,
+                dependency_load_state: None,
+                recursive_dependency_load_state: None,
+            }]
+        );
+
+        let params = json!({ "asset_type": TEST_ASSET, "id": id, "path": "value", "value": 7 });
+        process_remote_mutate_asset_request(In(Some(params)), world).unwrap();
+        assert_eq!(
+            world
+                .resource::<Assets<TestAsset>>()
+                .get(&handle)
+                .unwrap()
+                .value,
+            7
+        );
+
+        let params = json!({ "asset_type": TEST_ASSET, "id": id });
+        let response = process_remote_get_asset_request(In(Some(params)), world).unwrap();
+        let response = serde_json::from_value::<BrpAssetsGetResponse>(response).unwrap();
+        assert_eq!(response.value, json!({ "value": 7 }));
+
+        let missing = BrpAssetId::Index(u64::MAX);
+        let params = json!({ "asset_type": TEST_ASSET, "id": missing });
+        assert!(process_remote_get_asset_request(In(Some(params)), world).is_err());
+        let params = json!({ "asset_type": "Unknown", "id": id });
+        assert!(process_remote_get_asset_request(In(Some(params)), world).is_err());
+    }
+
+    #[test]
+    fn load_and_unload_assets() {
+        let mut app = app();
+        let world = app.world_mut();
+        let params = json!({ "asset_type": TEST_ASSET, "path": "missing.test" });
+        let response = process_remote_load_asset_request(In(Some(params)), world).unwrap();
+        let BrpAssetsLoadResponse { id } = serde_json::from_value(response).unwrap();
+        assert_eq!(world.resource::<BrpLoadedAssets>().handles.len(), 1);
+
+        let params = json!({ "asset_type": TEST_ASSET, "id": id });
+        process_remote_unload_asset_request(In(Some(params.clone())), world).unwrap();
+        assert!(world.resource::<BrpLoadedAssets>().handles.is_empty());
+        assert!(process_remote_unload_asset_request(In(Some(params)), world).is_err());
+    }
+
+    #[test]
+    fn watch_asset_events() {
+        let mut app = app();
+        let system = app
+            .world_mut()
+            .register_system(process_remote_watch_assets_request);
+        let params = json!({ "asset_type": TEST_ASSET });
+
+        let poll = |app: &mut App| {
+            app.world_mut()
+                .run_system_with(system, Some(params.clone()))
+                .unwrap()
+                .unwrap()
+        };
+        assert_eq!(poll(&mut app), None);
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<TestAsset>>()
+            .add(TestAsset { value: 1 });
+        app.update();
+
+        let events =
+            serde_json::from_value::<BrpAssetsWatchResponse>(poll(&mut app).unwrap()).unwrap();
+        assert!(events.contains(&BrpAssetEvent {
+            kind: BrpAssetEventKind::Added,
+            id: handle.id().untyped().into(),
+        }));
+        assert_eq!(poll(&mut app), None);
+    }
+}
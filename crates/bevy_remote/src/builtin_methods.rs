@@ -13,9 +13,11 @@ use bevy_ecs::{
     lifecycle::RemovedComponentEntity,
     message::MessageCursor,
     query::QueryBuilder,
-    reflect::{AppTypeRegistry, ReflectComponent, ReflectEvent, ReflectMessage, ReflectResource},
+    reflect::{
+        AppTypeRegistry, ReflectComponent, ReflectEvent, ReflectMessage, ReflectResource, UndoStack,
+    },
     resource::Resource,
-    schedule::Schedules,
+    schedule::{InternedScheduleLabel, NodeId, Schedule, Schedules, Stepping, SystemKey},
     system::{In, Local},
     world::{DeferredWorld, EntityRef, EntityWorldMut, FilteredEntityRef, Mut, World},
 };
@@ -95,6 +97,12 @@ pub const BRP_TRIGGER_EVENT_METHOD: &str = "world.trigger_event";
 /// The method path for a `world.write_message` request.
 pub const BRP_WRITE_MESSAGE_METHOD: &str = "world.write_message";
 
+/// The method path for a `world.undo` request.
+pub const BRP_UNDO_METHOD: &str = "world.undo";
+
+/// The method path for a `world.redo` request.
+pub const BRP_REDO_METHOD: &str = "world.redo";
+
 /// The method path for a `registry.schema` request.
 pub const BRP_REGISTRY_SCHEMA_METHOD: &str = "registry.schema";
 
@@ -107,6 +115,15 @@ pub const BRP_OBSERVE_METHOD: &str = "world.observe+watch";
 /// The method path for a `schedule.graph` request.
 pub const BRP_SCHEDULE_GRAPH: &str = "schedule.graph";
 
+/// The method path for a `schedule.stepping` request.
+pub const BRP_SCHEDULE_STEPPING: &str = "schedule.stepping";
+
+/// The method path for a `system.run_counts` request.
+pub const BRP_SYSTEM_RUN_COUNTS: &str = "system.run_counts";
+
+/// The method path for a `system.timings` request.
+pub const BRP_SYSTEM_TIMINGS: &str = "system.timings";
+
 /// The method path for a `rpc.discover` request.
 pub const RPC_DISCOVER_METHOD: &str = "rpc.discover";
 
@@ -378,6 +395,72 @@ struct BrpScheduleGraphParams {
     pub schedule_label: String,
 }
 
+/// `system.run_counts` and `system.timings`:
+///
+/// The server responds with [`BrpSystemRunCountsResponse`] or [`BrpSystemTimingsResponse`]
+/// respectively, or a `resource_error` if the given schedule is not found.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+struct BrpSystemStatsParams {
+    /// The schedule whose systems should be described.
+    ///
+    /// If omitted, the systems of every available schedule are described.
+    #[serde(default)]
+    pub schedule_label: Option<String>,
+}
+
+/// `schedule.stepping`: Controls the [`Stepping`] resource.
+///
+/// The server responds with [`BrpSteppingResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct BrpSteppingParams {
+    /// The action to perform.
+    pub action: BrpSteppingAction,
+
+    /// The schedule the action applies to.
+    ///
+    /// Required by every action that targets a schedule or a system.
+    #[serde(default)]
+    pub schedule_label: Option<String>,
+
+    /// The name of the system the action applies to.
+    ///
+    /// Either the full name of the system or its short name.
+    #[serde(default)]
+    pub system: Option<String>,
+}
+
+/// An action performed by a `schedule.stepping` request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BrpSteppingAction {
+    /// Only report the current stepping state.
+    Status,
+    /// See [`Stepping::enable`].
+    Enable,
+    /// See [`Stepping::disable`].
+    Disable,
+    /// See [`Stepping::step_frame`].
+    StepFrame,
+    /// See [`Stepping::continue_frame`].
+    ContinueFrame,
+    /// See [`Stepping::add_schedule`].
+    AddSchedule,
+    /// See [`Stepping::remove_schedule`].
+    RemoveSchedule,
+    /// See [`Stepping::clear_schedule`].
+    ClearSchedule,
+    /// See [`Stepping::always_run_node`].
+    AlwaysRun,
+    /// See [`Stepping::never_run_node`].
+    NeverRun,
+    /// See [`Stepping::set_breakpoint_node`].
+    SetBreakpoint,
+    /// See [`Stepping::clear_breakpoint_node`].
+    ClearBreakpoint,
+    /// See [`Stepping::clear_node`].
+    ClearSystem,
+}
+
 /// Describes the data that is to be fetched in a query.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct BrpQuery {
@@ -463,6 +546,13 @@ pub struct BrpSpawnEntityResponse {
     pub entity: Entity,
 }
 
+/// The response to a `world.undo` or `world.redo` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpUndoResponse {
+    /// The label of the undone or redone transaction, or `None` if there was nothing to undo or redo.
+    pub label: Option<String>,
+}
+
 /// The response to a `world.get_components` request.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
@@ -559,6 +649,67 @@ pub struct BrpScheduleGraphResponse {
     pub schedule_data: ScheduleData,
 }
 
+/// The response to a `system.run_counts` request.
+pub type BrpSystemRunCountsResponse = Vec<BrpSystemRunCount>;
+
+/// The number of times a system has run.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpSystemRunCount {
+    /// The schedule containing the system.
+    pub schedule_label: String,
+    /// The name of the system.
+    pub system: String,
+    /// The number of times the system has run.
+    pub run_count: u64,
+}
+
+/// The response to a `system.timings` request.
+pub type BrpSystemTimingsResponse = Vec<BrpSystemTiming>;
+
+/// The time spent running a system, in seconds.
+///
+/// Run times are only measured once the first `system.timings` request for the schedule has been made,
+/// so the durations only cover the last `measured_run_count` runs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSystemTiming {
+    /// The schedule containing the system.
+    pub schedule_label: String,
+    /// The name of the system.
+    pub system: String,
+    /// The number of times the system has run.
+    pub run_count: u64,
+    /// The number of runs whose duration was measured.
+    pub measured_run_count: u64,
+    /// The duration of the most recent measured run.
+    pub last_run_secs: f64,
+    /// The average duration of a measured run.
+    pub average_run_secs: f64,
+    /// The total duration of all measured runs.
+    pub total_run_secs: f64,
+}
+
+/// The response to a `schedule.stepping` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpSteppingResponse {
+    /// Whether stepping is enabled.
+    pub enabled: bool,
+    /// The schedules stepping applies to, in the order they run.
+    ///
+    /// This is empty until the first frame with stepping enabled has begun.
+    pub schedule_labels: Vec<String>,
+    /// The next system that will run when stepping, if any.
+    pub cursor: Option<BrpSteppingCursor>,
+}
+
+/// The position of the [`Stepping`] cursor.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BrpSteppingCursor {
+    /// The schedule containing the next system.
+    pub schedule_label: String,
+    /// The name of the next system.
+    pub system: String,
+}
+
 /// One query match result: a single entity paired with the requested components.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct BrpQueryRow {
@@ -1067,9 +1218,17 @@ pub fn process_remote_spawn_entity_request(
     let reflect_components =
         deserialize_components(&type_registry, components).map_err(BrpError::component_error)?;
 
-    let entity = world.spawn_empty();
-    let entity_id = entity.id();
-    insert_reflected_components(entity, reflect_components).map_err(BrpError::component_error)?;
+    let entity_id = world.entity_allocator().alloc();
+    // If inserting the components fails, the spawn is reverted as well.
+    UndoStack::try_record(world, BRP_SPAWN_ENTITY_METHOD, &[entity_id], |world| {
+        if let Err(error) = world.spawn_empty_at(entity_id).map(|_| ()) {
+            // The id was never spawned, so hand it back to the allocator.
+            world.entity_allocator_mut().free(entity_id);
+            return Err(BrpError::internal(error));
+        }
+        insert_reflected_components(world.entity_mut(entity_id), reflect_components)
+            .map_err(BrpError::component_error)
+    })?;
 
     let response = BrpSpawnEntityResponse { entity: entity_id };
     serde_json::to_value(response).map_err(BrpError::internal)
@@ -1126,8 +1285,10 @@ pub fn process_remote_insert_components_request(
     let reflect_components =
         deserialize_components(&type_registry, components).map_err(BrpError::component_error)?;
 
-    insert_reflected_components(get_entity_mut(world, entity)?, reflect_components)
-        .map_err(BrpError::component_error)?;
+    UndoStack::try_record(world, BRP_INSERT_COMPONENTS_METHOD, &[entity], |world| {
+        insert_reflected_components(get_entity_mut(world, entity)?, reflect_components)
+            .map_err(BrpError::component_error)
+    })?;
 
     Ok(Value::Null)
 }
@@ -1185,44 +1346,47 @@ pub fn process_remote_mutate_components_request(
             BrpError::component_error(anyhow!("Unknown component type: `{}`", component))
         })?;
 
-    // Get the reflected representation of the component.
-    let mut reflected = component_type
-        .data::<ReflectComponent>()
-        .ok_or_else(|| {
-            BrpError::component_error(anyhow!("Component `{}` isn't registered", component))
-        })?
-        .reflect_mut(world.entity_mut(entity))
-        .ok_or_else(|| {
-            BrpError::component_error(anyhow!("Cannot reflect component `{}`", component))
-        })?;
-
-    // Get the type of the field in the component that is to be
-    // mutated.
-    let value_type: &TypeRegistration = type_registry
-        .get_with_type_path(
-            reflected
-                .reflect_path(path.as_str())
-                .map_err(BrpError::component_error)?
-                .reflect_type_path(),
-        )
-        .ok_or_else(|| {
-            BrpError::component_error(anyhow!("Unknown component field type: `{}`", component))
-        })?;
-
-    // Get the reflected representation of the value to be inserted
-    // into the component.
-    let value: Box<dyn PartialReflect> = TypedReflectDeserializer::new(value_type, &type_registry)
-        .deserialize(&value)
-        .map_err(BrpError::component_error)?;
+    let reflect_component = component_type.data::<ReflectComponent>().ok_or_else(|| {
+        BrpError::component_error(anyhow!("Component `{}` isn't registered", component))
+    })?;
 
-    // Apply the mutation.
-    reflected
-        .reflect_path_mut(path.as_str())
-        .map_err(BrpError::component_error)?
-        .try_apply(value.as_ref())
-        .map_err(BrpError::component_error)?;
+    UndoStack::try_record(world, BRP_MUTATE_COMPONENTS_METHOD, &[entity], |world| {
+        // Get the reflected representation of the component.
+        let mut reflected = reflect_component
+            .reflect_mut(world.entity_mut(entity))
+            .ok_or_else(|| {
+                BrpError::component_error(anyhow!("Cannot reflect component `{}`", component))
+            })?;
+
+        // Get the type of the field in the component that is to be
+        // mutated.
+        let value_type: &TypeRegistration = type_registry
+            .get_with_type_path(
+                reflected
+                    .reflect_path(path.as_str())
+                    .map_err(BrpError::component_error)?
+                    .reflect_type_path(),
+            )
+            .ok_or_else(|| {
+                BrpError::component_error(anyhow!("Unknown component field type: `{}`", component))
+            })?;
+
+        // Get the reflected representation of the value to be inserted
+        // into the component.
+        let value: Box<dyn PartialReflect> =
+            TypedReflectDeserializer::new(value_type, &type_registry)
+                .deserialize(&value)
+                .map_err(BrpError::component_error)?;
+
+        // Apply the mutation.
+        reflected
+            .reflect_path_mut(path.as_str())
+            .map_err(BrpError::component_error)?
+            .try_apply(value.as_ref())
+            .map_err(BrpError::component_error)?;
 
-    Ok(Value::Null)
+        Ok(Value::Null)
+    })
 }
 
 /// Handles a `world.mutate_resources` request coming from a client.
@@ -1301,12 +1465,13 @@ pub fn process_remote_remove_components_request(
         .map_err(BrpError::component_error)?;
 
     // Remove the components.
-    let mut entity_world_mut = get_entity_mut(world, entity)?;
-    for (_, component_id) in component_ids.iter() {
-        entity_world_mut.remove_by_id(*component_id);
-    }
-
-    Ok(Value::Null)
+    UndoStack::try_record(world, BRP_REMOVE_COMPONENTS_METHOD, &[entity], |world| {
+        let mut entity_world_mut = get_entity_mut(world, entity)?;
+        for (_, component_id) in component_ids.iter() {
+            entity_world_mut.remove_by_id(*component_id);
+        }
+        Ok(Value::Null)
+    })
 }
 
 /// Handles a `world.remove_resources` request coming from a client.
@@ -1338,7 +1503,8 @@ pub fn process_remote_despawn_entity_request(
 ) -> BrpResult {
     let BrpDespawnEntityParams { entity } = parse_some(params)?;
 
-    get_entity_mut(world, entity)?.despawn();
+    get_entity(world, entity)?;
+    UndoStack::record_despawn(world, BRP_DESPAWN_COMPONENTS_METHOD, entity);
 
     Ok(Value::Null)
 }
@@ -1353,25 +1519,53 @@ pub fn process_remote_reparent_entities_request(
         parent: maybe_parent,
     } = parse_some(params)?;
 
-    // If `Some`, reparent the entities.
-    if let Some(parent) = maybe_parent {
-        let mut parent_commands =
-            get_entity_mut(world, parent).map_err(|_| BrpError::entity_not_found(parent))?;
-        for entity in entities {
-            if entity == parent {
-                return Err(BrpError::self_reparent(entity));
+    UndoStack::try_record(world, BRP_REPARENT_ENTITIES_METHOD, &entities, |world| {
+        // If `Some`, reparent the entities.
+        if let Some(parent) = maybe_parent {
+            let mut parent_commands =
+                get_entity_mut(world, parent).map_err(|_| BrpError::entity_not_found(parent))?;
+            for &entity in &entities {
+                if entity == parent {
+                    return Err(BrpError::self_reparent(entity));
+                }
+                parent_commands.add_child(entity);
             }
-            parent_commands.add_child(entity);
         }
-    }
-    // If `None`, remove the entities' parents.
-    else {
-        for entity in entities {
-            get_entity_mut(world, entity)?.remove::<ChildOf>();
+        // If `None`, remove the entities' parents.
+        else {
+            for &entity in &entities {
+                get_entity_mut(world, entity)?.remove::<ChildOf>();
+            }
         }
+
+        Ok(Value::Null)
+    })
+}
+
+/// Handles a `world.undo` request coming from a client.
+pub fn process_remote_undo_request(In(_params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    step_undo_stack(world, UndoStack::undo)
+}
+
+/// Handles a `world.redo` request coming from a client.
+pub fn process_remote_redo_request(In(_params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    step_undo_stack(world, UndoStack::redo)
+}
+
+fn step_undo_stack(
+    world: &mut World,
+    step: fn(&mut World) -> Option<alloc::borrow::Cow<'static, str>>,
+) -> BrpResult {
+    if !world.contains_resource::<UndoStack>() {
+        return Err(BrpError::resource_not_present(core::any::type_name::<
+            UndoStack,
+        >()));
     }
 
-    Ok(Value::Null)
+    let response = BrpUndoResponse {
+        label: step(world).map(Into::into),
+    };
+    serde_json::to_value(response).map_err(BrpError::internal)
 }
 
 /// Handles a `world.list_components` request (list all components) coming from a client.
@@ -1798,6 +1992,258 @@ pub fn schedule_graph(In(params): In<Option<Value>>, world: &mut World) -> BrpRe
     serde_json::to_value(BrpScheduleGraphResponse { schedule_data }).map_err(BrpError::internal)
 }
 
+/// Handles a `system.run_counts` request coming from a client.
+pub fn system_run_counts(In(params): In<Option<Value>>, world: &World) -> BrpResult {
+    let BrpSystemStatsParams { schedule_label } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let mut response = BrpSystemRunCountsResponse::new();
+    for schedule in find_schedules(world, schedule_label.as_deref())? {
+        let Ok(systems) = schedule.systems_with_stats() else {
+            continue;
+        };
+        response.extend(systems.map(|(_, system, stats)| BrpSystemRunCount {
+            schedule_label: format!("{:?}", schedule.label()),
+            system: system.name().as_string(),
+            run_count: stats.run_count,
+        }));
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `system.timings` request coming from a client.
+///
+/// Measuring run times is opt-in per schedule, so this starts measuring the described schedules.
+pub fn system_timings(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpSystemStatsParams { schedule_label } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let labels = find_schedules(world, schedule_label.as_deref())?
+        .into_iter()
+        .map(Schedule::label)
+        .collect::<Vec<_>>();
+    let mut schedules = world.resource_mut::<Schedules>();
+    for label in labels {
+        if let Some(schedule) = schedules.get_mut(label) {
+            schedule.set_measure_run_times(true);
+        }
+    }
+
+    let mut response = BrpSystemTimingsResponse::new();
+    for schedule in find_schedules(world, schedule_label.as_deref())? {
+        let Ok(systems) = schedule.systems_with_stats() else {
+            continue;
+        };
+        response.extend(systems.map(|(_, system, stats)| BrpSystemTiming {
+            schedule_label: format!("{:?}", schedule.label()),
+            system: system.name().as_string(),
+            run_count: stats.run_count,
+            measured_run_count: stats.measured_run_count,
+            last_run_secs: stats.last_run_time.as_secs_f64(),
+            average_run_secs: stats.average_run_time().as_secs_f64(),
+            total_run_secs: stats.total_run_time.as_secs_f64(),
+        }));
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `schedule.stepping` request coming from a client.
+///
+/// Stepping only takes effect when the app is built with the `bevy_debug_stepping` feature.
+pub fn schedule_stepping(In(params): In<Option<Value>>, world: &mut World) -> BrpResult {
+    let BrpSteppingParams {
+        action,
+        schedule_label,
+        system,
+    } = parse_some(params)?;
+
+    // Reporting the status shouldn't create the `Stepping` resource.
+    if action == BrpSteppingAction::Status && !world.contains_resource::<Stepping>() {
+        return serde_json::to_value(BrpSteppingResponse {
+            enabled: false,
+            schedule_labels: Vec::new(),
+            cursor: None,
+        })
+        .map_err(BrpError::internal);
+    }
+
+    let label = match schedule_label.as_deref() {
+        Some(schedule_label) => Some(find_schedule(world, schedule_label)?.label()),
+        None => None,
+    };
+    let schedule_target = || {
+        label.ok_or_else(|| {
+            BrpError::resource_error(format!("Stepping action {action:?} requires a schedule"))
+        })
+    };
+    let system_target = || {
+        let label = schedule_target()?;
+        let Some(system) = system.as_deref() else {
+            return Err(BrpError::resource_error(format!(
+                "Stepping action {action:?} requires a system"
+            )));
+        };
+        let schedule = world.resource::<Schedules>().get(label).unwrap();
+        find_system(schedule, system).map(|key| (label, NodeId::System(key)))
+    };
+
+    // Resolve the targets before mutably borrowing `Stepping`.
+    enum Target {
+        None,
+        Schedule(InternedScheduleLabel),
+        Node(InternedScheduleLabel, NodeId),
+    }
+    let target = match action {
+        BrpSteppingAction::Status
+        | BrpSteppingAction::Enable
+        | BrpSteppingAction::Disable
+        | BrpSteppingAction::StepFrame
+        | BrpSteppingAction::ContinueFrame => Target::None,
+        BrpSteppingAction::AddSchedule
+        | BrpSteppingAction::RemoveSchedule
+        | BrpSteppingAction::ClearSchedule => Target::Schedule(schedule_target()?),
+        BrpSteppingAction::AlwaysRun
+        | BrpSteppingAction::NeverRun
+        | BrpSteppingAction::SetBreakpoint
+        | BrpSteppingAction::ClearBreakpoint
+        | BrpSteppingAction::ClearSystem => {
+            let (label, node) = system_target()?;
+            Target::Node(label, node)
+        }
+    };
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    match (action, target) {
+        (BrpSteppingAction::Enable, _) => {
+            stepping.enable();
+        }
+        (BrpSteppingAction::Disable, _) => {
+            stepping.disable();
+        }
+        (BrpSteppingAction::StepFrame, _) => {
+            stepping.step_frame();
+        }
+        (BrpSteppingAction::ContinueFrame, _) => {
+            stepping.continue_frame();
+        }
+        (BrpSteppingAction::AddSchedule, Target::Schedule(label)) => {
+            stepping.add_schedule(label);
+        }
+        (BrpSteppingAction::RemoveSchedule, Target::Schedule(label)) => {
+            stepping.remove_schedule(label);
+        }
+        (BrpSteppingAction::ClearSchedule, Target::Schedule(label)) => {
+            stepping.clear_schedule(label);
+        }
+        (BrpSteppingAction::AlwaysRun, Target::Node(label, node)) => {
+            stepping.always_run_node(label, node);
+        }
+        (BrpSteppingAction::NeverRun, Target::Node(label, node)) => {
+            stepping.never_run_node(label, node);
+        }
+        (BrpSteppingAction::SetBreakpoint, Target::Node(label, node)) => {
+            stepping.set_breakpoint_node(label, node);
+        }
+        (BrpSteppingAction::ClearBreakpoint, Target::Node(label, node)) => {
+            stepping.clear_breakpoint_node(label, node);
+        }
+        (BrpSteppingAction::ClearSystem, Target::Node(label, node)) => {
+            stepping.clear_node(label, node);
+        }
+        _ => {}
+    }
+
+    let enabled = stepping.is_enabled();
+    let schedule_labels = stepping
+        .schedules()
+        .map(|labels| labels.iter().map(|label| format!("{label:?}")).collect())
+        .unwrap_or_default();
+    let cursor = stepping.cursor();
+
+    let cursor = cursor.map(|(label, node)| {
+        let system = world
+            .resource::<Schedules>()
+            .get(label)
+            .and_then(|schedule| schedule.systems().ok())
+            .and_then(|mut systems| systems.find(|(key, _)| node == NodeId::System(*key)))
+            .map_or_else(
+                || format!("{node:?}"),
+                |(_, system)| system.name().as_string(),
+            );
+        BrpSteppingCursor {
+            schedule_label: format!("{label:?}"),
+            system,
+        }
+    });
+
+    serde_json::to_value(BrpSteppingResponse {
+        enabled,
+        schedule_labels,
+        cursor,
+    })
+    .map_err(BrpError::internal)
+}
+
+/// Returns the schedule with the given label, or every available schedule if no label is given.
+fn find_schedules<'w>(
+    world: &'w World,
+    schedule_label: Option<&str>,
+) -> Result<Vec<&'w Schedule>, BrpError> {
+    match schedule_label {
+        Some(schedule_label) => Ok(vec![find_schedule(world, schedule_label)?]),
+        None => Ok(world
+            .resource::<Schedules>()
+            .iter()
+            .map(|(_, schedule)| schedule)
+            .collect()),
+    }
+}
+
+/// Returns the schedule whose label is formatted as `schedule_label`.
+fn find_schedule<'w>(world: &'w World, schedule_label: &str) -> Result<&'w Schedule, BrpError> {
+    world
+        .resource::<Schedules>()
+        .iter()
+        .find(|(label, _schedule)| format!("{:?}", label) == schedule_label)
+        .map(|(_, schedule)| schedule)
+        .ok_or_else(|| {
+            BrpError::resource_error(format!(
+                "Schedule with label={:} not found. This may be because this schedule is currently running",
+                schedule_label
+            ))
+        })
+}
+
+/// Returns the key of the system in `schedule` with the given full or short name.
+fn find_system(schedule: &Schedule, name: &str) -> Result<SystemKey, BrpError> {
+    let systems = schedule.systems().map_err(|_| {
+        BrpError::resource_error(format!(
+            "Schedule with label={:?} has not been initialized yet",
+            schedule.label()
+        ))
+    })?;
+
+    systems
+        .filter(|(_, system)| {
+            let system_name = system.name();
+            *system_name == *name || system_name.shortname().to_string() == name
+        })
+        .map(|(key, _)| key)
+        .next()
+        .ok_or_else(|| {
+            BrpError::resource_error(format!(
+                "System `{name}` not found in schedule with label={:?}",
+                schedule.label()
+            ))
+        })
+}
+
 /// Immutably retrieves an entity from the [`World`], returning an error if the
 /// entity isn't present.
 fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, BrpError> {
@@ -2088,6 +2534,47 @@ mod tests {
         insert_reflected_components(e, deserialized_components).expect("FAIL");
     }
 
+    #[test]
+    fn undo_redo_mutation() {
+        #[derive(Reflect, Component)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        let atr = AppTypeRegistry::default();
+        atr.write().register::<Health>();
+        let mut world = World::new();
+        world.insert_resource(atr);
+        world.init_resource::<UndoStack>();
+        let entity = world.spawn(Health(10)).id();
+
+        let params = serde_json::to_value(&BrpMutateComponentsParams {
+            entity,
+            component: "bevy_remote::builtin_methods::tests::Health".to_owned(),
+            path: ".0".to_owned(),
+            value: serde_json::json!(5),
+        })
+        .expect("FAIL");
+        assert_eq!(
+            process_remote_mutate_components_request(In(Some(params)), &mut world),
+            Ok(Null)
+        );
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 5);
+
+        let undone = process_remote_undo_request(In(None), &mut world).expect("FAIL");
+        assert_eq!(
+            undone,
+            serde_json::json!({ "label": BRP_MUTATE_COMPONENTS_METHOD })
+        );
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 10);
+
+        process_remote_redo_request(In(None), &mut world).expect("FAIL");
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 5);
+        assert_eq!(
+            process_remote_redo_request(In(None), &mut world),
+            Ok(serde_json::json!({ "label": null }))
+        );
+    }
+
     #[test]
     fn trigger_reflect_only_event() {
         #[derive(Event, Reflect)]
@@ -2487,4 +2974,124 @@ mod tests {
             .dependency
             .contains(&(apply_deferred_index, f2_index)));
     }
+
+    #[test]
+    fn system_run_counts_and_timings() {
+        fn f1() {}
+        fn f2() {}
+
+        #[derive(ScheduleLabel, Hash, Clone, PartialEq, Eq, Debug)]
+        struct MySchedule;
+
+        #[derive(ScheduleLabel, Hash, Clone, PartialEq, Eq, Debug)]
+        struct Uninitialized;
+
+        let mut world = World::default();
+        let mut schedule = Schedule::new(MySchedule);
+        schedule.add_systems((f1, f2));
+        world.add_schedule(schedule);
+        world.add_schedule(Schedule::new(Uninitialized));
+
+        world.run_schedule(MySchedule);
+
+        // The first request starts measuring run times.
+        let params = serde_json::to_value(&BrpSystemStatsParams {
+            schedule_label: Some("MySchedule".to_string()),
+        })
+        .unwrap();
+        let response = system_timings(In(Some(params.clone())), &mut world).unwrap();
+        let response = serde_json::from_value::<BrpSystemTimingsResponse>(response).unwrap();
+        for timing in &response {
+            assert_eq!(timing.run_count, 1);
+            assert_eq!(timing.measured_run_count, 0);
+        }
+        assert!(world.schedule_scope(MySchedule, |_, schedule| schedule.measures_run_times()));
+
+        world.run_schedule(MySchedule);
+        world.run_schedule(MySchedule);
+        world.run_schedule(MySchedule);
+
+        // Uninitialized schedules are skipped.
+        let response = system_run_counts(In(None), &world).unwrap();
+        let response = serde_json::from_value::<BrpSystemRunCountsResponse>(response).unwrap();
+        assert_eq!(response.len(), 2);
+        for run_count in &response {
+            assert_eq!(run_count.schedule_label, "MySchedule");
+            assert_eq!(run_count.run_count, 4);
+        }
+
+        let response = system_timings(In(Some(params)), &mut world).unwrap();
+        let response = serde_json::from_value::<BrpSystemTimingsResponse>(response).unwrap();
+        assert_eq!(response.len(), 2);
+        assert!(response.iter().any(|timing| timing.system.ends_with("f1")));
+        for timing in &response {
+            assert_eq!(timing.run_count, 4);
+            assert_eq!(timing.measured_run_count, 3);
+            assert!(timing.total_run_secs >= timing.last_run_secs);
+        }
+
+        let params = serde_json::to_value(&BrpSystemStatsParams {
+            schedule_label: Some("Missing".to_string()),
+        })
+        .unwrap();
+        assert!(system_timings(In(Some(params)), &mut world).is_err());
+    }
+
+    #[test]
+    fn schedule_stepping_controls() {
+        fn f1() {}
+
+        #[derive(ScheduleLabel, Hash, Clone, PartialEq, Eq, Debug)]
+        struct MySchedule;
+
+        let mut world = World::default();
+        let mut schedule = Schedule::new(MySchedule);
+        schedule.add_systems(f1);
+        world.add_schedule(schedule);
+        world.run_schedule(MySchedule);
+
+        let request = |world: &mut World, value: Value| {
+            schedule_stepping(In(Some(value)), world)
+                .map(|response| serde_json::from_value::<BrpSteppingResponse>(response).unwrap())
+        };
+
+        let response = request(&mut world, serde_json::json!({ "action": "status" })).unwrap();
+        assert!(!response.enabled);
+        assert!(response.cursor.is_none());
+        assert!(!world.contains_resource::<Stepping>());
+
+        request(
+            &mut world,
+            serde_json::json!({ "action": "add_schedule", "schedule_label": "MySchedule" }),
+        )
+        .unwrap();
+        assert!(world.contains_resource::<Stepping>());
+        request(
+            &mut world,
+            serde_json::json!({
+                "action": "always_run",
+                "schedule_label": "MySchedule",
+                "system": "f1",
+            }),
+        )
+        .unwrap();
+
+        // Actions targeting systems need a schedule and a known system.
+        assert!(request(&mut world, serde_json::json!({ "action": "add_schedule" })).is_err());
+        assert!(request(
+            &mut world,
+            serde_json::json!({ "action": "never_run", "schedule_label": "MySchedule" }),
+        )
+        .is_err());
+        assert!(request(
+            &mut world,
+            serde_json::json!({
+                "action": "set_breakpoint",
+                "schedule_label": "MySchedule",
+                "system": "missing",
+            }),
+        )
+        .is_err());
+        assert!(request(&mut world, serde_json::json!({ "action": "jump" })).is_err());
+    }
 }
@@ -3,7 +3,8 @@
 //! Adding the [`RemotePlugin`] to your [`App`] will setup everything needed without
 //! starting any transports. To start accepting remote connections you will need to
 //! add a second plugin like the [`RemoteHttpPlugin`](http::RemoteHttpPlugin) to enable communication
-//! over HTTP. These *remote clients* can inspect and alter the state of the
+//! over HTTP, or the `RemoteWebSocketPlugin` to enable communication over WebSocket, which
+//! requires the `websocket` feature. These *remote clients* can inspect and alter the state of the
 //! entity-component system.
 //!
 //! The Bevy Remote Protocol is based on the JSON-RPC 2.0 protocol.
@@ -463,6 +464,26 @@
 //!
 //! `result`: null.
 //!
+//! ### `world.undo`
+//!
+//! Revert the most recent transaction recorded in the [`UndoStack`](bevy_ecs::reflect::UndoStack) resource.
+//! This method has no parameters.
+//!
+//! While the `UndoStack` resource is present, the changes made by `world.spawn_entity`,
+//! `world.despawn_entity`, `world.insert_components`, `world.remove_components`,
+//! `world.mutate_components` and `world.reparent_entities` are recorded in it.
+//!
+//! `result`:
+//! - `label`: The label of the reverted transaction, or null if there was nothing to undo.
+//!
+//! ### `world.redo`
+//!
+//! Reapply the most recently undone transaction of the [`UndoStack`](bevy_ecs::reflect::UndoStack) resource.
+//! This method has no parameters.
+//!
+//! `result`:
+//! - `label`: The label of the reapplied transaction, or null if there was nothing to redo.
+//!
 //! ### `registry.schema`
 //!
 //! Retrieve schema information about registered types in the Bevy app's type registry.
@@ -478,6 +499,164 @@
 //! This contains schema information about that type, including field definitions, type information, reflect type information, and other metadata
 //! helpful for understanding the structure of the type.
 //!
+//! ### `schedule.list`
+//!
+//! List the labels of all schedules. This method has no parameters.
+//!
+//! `result`:
+//! - `schedule_labels`: The labels of the schedules available for inspection.
+//! - `unavailable_schedule_labels`: The labels of the schedules that are currently running.
+//! - `empty_schedule_labels`: The labels that don't have a schedule.
+//!
+//! ### `schedule.graph`
+//!
+//! Describe the systems, system sets, ordering edges and ambiguities of a schedule.
+//!
+//! `params`:
+//! - `schedule_label`: The label of the schedule to describe, as listed by `schedule.list`.
+//!
+//! `result`:
+//! - `schedule_data`: The [`ScheduleData`](bevy_dev_tools::schedule_data::serde::ScheduleData) of the schedule.
+//!
+//! ### `system.run_counts`
+//!
+//! Retrieve the number of times each system has run.
+//!
+//! `params` (optional):
+//! - `schedule_label`: The label of the schedule whose systems are described, as listed by `schedule.list`.
+//!   When omitted, the systems of every available schedule are described.
+//!
+//! `result`: An array of objects, one per system:
+//! - `schedule_label`: The label of the schedule containing the system.
+//! - `system`: The name of the system.
+//! - `run_count`: The number of times the system has run.
+//!
+//! ### `system.timings`
+//!
+//! Retrieve the time spent running each system.
+//!
+//! Run times are only measured for schedules which opted in with
+//! [`Schedule::set_measure_run_times`](bevy_ecs::schedule::Schedule::set_measure_run_times).
+//! This method opts in the described schedules, so their durations cover the runs after the first request.
+//!
+//! `params` (optional):
+//! - `schedule_label`: The label of the schedule whose systems are described, as listed by `schedule.list`.
+//!   When omitted, the systems of every available schedule are described.
+//!
+//! `result`: An array of objects, one per system:
+//! - `schedule_label`: The label of the schedule containing the system.
+//! - `system`: The name of the system.
+//! - `run_count`: The number of times the system has run.
+//! - `measured_run_count`: The number of runs whose duration was measured.
+//! - `last_run_secs`: The duration of the most recent measured run, in seconds.
+//! - `average_run_secs`: The average duration of a measured run, in seconds.
+//! - `total_run_secs`: The total duration of all measured runs, in seconds.
+//!
+//! ### `schedule.stepping`
+//!
+//! Control system stepping through the [`Stepping`](bevy_ecs::schedule::Stepping) resource,
+//! inserting it if needed (the `status` action never inserts it). Stepping only takes effect when Bevy is compiled with the
+//! `bevy_debug_stepping` feature, and queued changes are applied at the start of the next frame.
+//!
+//! `params`:
+//! - `action`: One of `status`, `enable`, `disable`, `step_frame`, `continue_frame`,
+//!   `add_schedule`, `remove_schedule`, `clear_schedule`, `always_run`, `never_run`,
+//!   `set_breakpoint`, `clear_breakpoint` or `clear_system`.
+//! - `schedule_label` (optional): The label of the schedule the action applies to.
+//!   Required by the schedule and system actions.
+//! - `system` (optional): The full or short name of the system the action applies to.
+//!   Required by the system actions.
+//!
+//! `result`:
+//! - `enabled`: Whether stepping is enabled.
+//! - `schedule_labels`: The labels of the schedules stepping applies to, in execution order.
+//! - `cursor`: The `schedule_label` and `system` that will run next when stepping, or null.
+//!
+//! ### `assets.list`
+//!
+//! List the assets stored in the world. Only asset types registered with
+//! [`register_asset_reflect`](bevy_asset::AssetApp::register_asset_reflect) are visible to the
+//! asset methods. Requires the `bevy_asset` feature.
+//!
+//! `params` (optional):
+//! - `asset_type`: The [fully-qualified type name] of the asset type to list.
+//!   When omitted, the assets of every reflected asset type are listed.
+//!
+//! `result`: An array of objects, one per asset:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//! - `id`: The id of the asset, either `{ "index": <u64> }` or `{ "uuid": <uuid> }`.
+//! - `path`: The path the asset was loaded from, or null.
+//! - `load_state`, `dependency_load_state`, `recursive_dependency_load_state`: One of
+//!   `not_loaded`, `loading`, `loaded` or `{ "failed": <error> }`, or null if the asset isn't
+//!   tracked by the `AssetServer`.
+//!
+//! ### `assets.load`
+//!
+//! Start loading an asset. The server keeps the asset loaded until it is released with
+//! `assets.unload`.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//! - `path`: The asset path to load from.
+//!
+//! `result`:
+//! - `id`: The id of the loading asset.
+//!
+//! ### `assets.unload`
+//!
+//! Release an asset loaded with `assets.load`. The asset is unloaded once nothing else uses it.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//! - `id`: The id of the asset, as returned by `assets.load`.
+//!
+//! `result`: null.
+//!
+//! ### `assets.reload`
+//!
+//! Reload the asset at a path, if it is loaded.
+//!
+//! `params`:
+//! - `path`: The asset path to reload.
+//!
+//! `result`: null.
+//!
+//! ### `assets.get`
+//!
+//! Retrieve the value of an asset.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//! - `id`: The id of the asset, as returned by `assets.list`.
+//!
+//! `result`:
+//! - `value`: The serialized value of the asset.
+//!
+//! ### `assets.mutate`
+//!
+//! Mutate a field of an asset.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//! - `id`: The id of the asset, as returned by `assets.list`.
+//! - `path`: The path of the field within the asset. See
+//!   [`GetPath`](bevy_reflect::GetPath#syntax) for more information on formatting this string.
+//! - `value`: The value to insert at `path`.
+//!
+//! `result`: null.
+//!
+//! ### `assets.watch`
+//!
+//! Stream the asset events of an asset type. Responses are sent every frame in which events
+//! were written, starting after the request.
+//!
+//! `params`:
+//! - `asset_type`: The [fully-qualified type name] of the asset type.
+//!
+//! `result`: An array of objects, one per event:
+//! - `kind`: One of `added`, `modified`, `removed`, `unused` or `loaded_with_dependencies`.
+//! - `id`: The id of the asset.
+//!
 //! ### `rpc.discover`
 //!
 //! Discover available remote methods and server information. This follows the [`OpenRPC` specification for service discovery](https://spec.open-rpc.org/#service-discovery-method).
@@ -556,10 +735,14 @@ use serde::{ser::SerializeMap, Deserialize, Serialize};
 use serde_json::Value;
 use std::sync::RwLock;
 
+#[cfg(feature = "bevy_asset")]
+pub mod assets;
 pub mod builtin_methods;
 #[cfg(feature = "http")]
 pub mod http;
 pub mod schemas;
+#[cfg(feature = "websocket")]
+pub mod websocket;
 
 const CHANNEL_SIZE: usize = 16;
 
@@ -770,6 +953,16 @@ impl RemotePlugin {
             builtin_methods::process_remote_observe_watching_request,
             to_main,
         )
+        .with_method(
+            builtin_methods::BRP_UNDO_METHOD,
+            builtin_methods::process_remote_undo_request,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_REDO_METHOD,
+            builtin_methods::process_remote_redo_request,
+            to_main,
+        )
         .with_method(
             builtin_methods::BRP_REGISTRY_SCHEMA_METHOD,
             builtin_methods::export_registry_types,
@@ -785,6 +978,61 @@ impl RemotePlugin {
             builtin_methods::schedule_graph,
             to_main,
         )
+        .with_method(
+            builtin_methods::BRP_SCHEDULE_STEPPING,
+            builtin_methods::schedule_stepping,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_SYSTEM_RUN_COUNTS,
+            builtin_methods::system_run_counts,
+            to_main,
+        )
+        .with_method(
+            builtin_methods::BRP_SYSTEM_TIMINGS,
+            builtin_methods::system_timings,
+            to_main,
+        )
+    }
+
+    /// Adds the built-in asset methods, which always run in the main world.
+    #[cfg(feature = "bevy_asset")]
+    fn add_asset_methods(self) -> Self {
+        self.with_method(
+            assets::BRP_ASSETS_LIST_METHOD,
+            assets::process_remote_list_assets_request,
+            true,
+        )
+        .with_method(
+            assets::BRP_ASSETS_LOAD_METHOD,
+            assets::process_remote_load_asset_request,
+            true,
+        )
+        .with_method(
+            assets::BRP_ASSETS_UNLOAD_METHOD,
+            assets::process_remote_unload_asset_request,
+            true,
+        )
+        .with_method(
+            assets::BRP_ASSETS_RELOAD_METHOD,
+            assets::process_remote_reload_asset_request,
+            true,
+        )
+        .with_method(
+            assets::BRP_ASSETS_GET_METHOD,
+            assets::process_remote_get_asset_request,
+            true,
+        )
+        .with_method(
+            assets::BRP_ASSETS_MUTATE_METHOD,
+            assets::process_remote_mutate_asset_request,
+            true,
+        )
+        .with_watching_method(
+            assets::BRP_ASSETS_WATCH_METHOD,
+            assets::process_remote_watch_assets_request,
+            true,
+        )
     }
 }
 
@@ -793,6 +1041,11 @@ impl Default for RemotePlugin {
         let mut t = Self::empty();
         t = t.add_default_methods(true);
 
+        #[cfg(feature = "bevy_asset")]
+        {
+            t = t.add_asset_methods();
+        }
+
         #[cfg(feature = "bevy_render")]
         {
             t = t.add_default_methods(false);
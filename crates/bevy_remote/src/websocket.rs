@@ -0,0 +1,475 @@
+//! The BRP transport using JSON-RPC over WebSocket.
+//!
+//! Adding the [`RemoteWebSocketPlugin`] to your [`App`] causes Bevy to accept
+//! WebSocket connections (by default, on port 15704) while your app is running.
+//!
+//! Each text message sent by a client is a JSON-RPC request or batch of requests,
+//! exactly as it would be `POST`ed to the [`RemoteHttpPlugin`](crate::http::RemoteHttpPlugin).
+//! Requests are processed concurrently, so responses may arrive out of order: clients
+//! should match them to their requests by `id`.
+//!
+//! Watching methods (those whose name contains `+watch`) keep pushing responses carrying
+//! the `id` of their request every time their value changes, until the connection is closed
+//! or the client sends an [`rpc.unwatch`](RPC_UNWATCH_METHOD) request.
+
+#![cfg(not(target_family = "wasm"))]
+
+use crate::{error_codes, BrpBatch, BrpError, BrpMessage, BrpRequest, BrpResponse, BrpSender};
+use anyhow::Result as AnyhowResult;
+use async_channel::Sender;
+use async_io::Async;
+use async_tungstenite::tungstenite::Message;
+use bevy_app::{App, Plugin, Startup};
+use bevy_ecs::{
+    resource::Resource,
+    system::{Commands, Res},
+};
+use bevy_tasks::{futures_lite::StreamExt, IoTaskPool, Task};
+use core::net::{IpAddr, Ipv4Addr, SocketAddr};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    net::{TcpListener, TcpStream},
+};
+
+/// The default port that Bevy will listen on for WebSocket connections.
+///
+/// This is the port following the ones used by the [`RemoteHttpPlugin`](crate::http::RemoteHttpPlugin).
+pub const DEFAULT_WEBSOCKET_PORT: u16 = 15704;
+
+/// The default host address that Bevy will use for its WebSocket server.
+pub const DEFAULT_WEBSOCKET_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1));
+
+/// The method path for a `rpc.unwatch` request.
+///
+/// This request is handled by the WebSocket transport itself: it stops the watching
+/// request of the same connection whose `id` is given in the `id` parameter.
+pub const RPC_UNWATCH_METHOD: &str = "rpc.unwatch";
+
+/// Add this plugin to your [`App`] to allow remote connections over WebSocket to inspect and
+/// modify entities. It requires the [`RemotePlugin`](super::RemotePlugin).
+///
+/// This BRP transport cannot be used when targeting WASM.
+///
+/// The defaults are:
+/// - [`DEFAULT_WEBSOCKET_ADDR`] : 127.0.0.1.
+/// - [`DEFAULT_WEBSOCKET_PORT`] : 15704.
+///
+/// Using port `0` lets the operating system pick a free port; the address the server is
+/// actually bound to is available in the [`WebSocketServerAddress`] resource.
+pub struct RemoteWebSocketPlugin {
+    /// The address that Bevy will bind to.
+    address: IpAddr,
+    /// The port that Bevy will listen on.
+    port: u16,
+}
+
+impl Default for RemoteWebSocketPlugin {
+    fn default() -> Self {
+        Self {
+            address: DEFAULT_WEBSOCKET_ADDR,
+            port: DEFAULT_WEBSOCKET_PORT,
+        }
+    }
+}
+
+impl Plugin for RemoteWebSocketPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WebSocketServerAddress(SocketAddr::new(
+            self.address,
+            self.port,
+        )))
+        .add_systems(Startup, start_websocket_server);
+    }
+}
+
+impl RemoteWebSocketPlugin {
+    /// Set the IP address that the server will use.
+    #[must_use]
+    pub fn with_address(mut self, address: impl Into<IpAddr>) -> Self {
+        self.address = address.into();
+        self
+    }
+
+    /// Set the remote port that the server will listen on.
+    #[must_use]
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+}
+
+/// A resource containing the socket address of the WebSocket server.
+///
+/// Before the server starts, this is the address requested in the [`RemoteWebSocketPlugin`].
+/// Once it has started, this is the address the server is bound to. Changing it has no effect.
+#[derive(Debug, Resource)]
+pub struct WebSocketServerAddress(pub SocketAddr);
+
+/// The parameters of a `rpc.unwatch` request.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct RpcUnwatchParams {
+    /// The `id` of the watching request to stop.
+    id: Value,
+}
+
+/// A system that starts up the Bevy Remote Protocol WebSocket server.
+fn start_websocket_server(
+    mut commands: Commands,
+    request_sender: Res<BrpSender>,
+    address: Res<WebSocketServerAddress>,
+) {
+    let listener = match Async::<TcpListener>::bind(address.0) {
+        Ok(listener) => listener,
+        Err(err) => {
+            bevy_log::error!(
+                "Failed to start the BRP WebSocket server on {}: {err}",
+                address.0
+            );
+            return;
+        }
+    };
+    if let Ok(local_address) = listener.get_ref().local_addr() {
+        commands.insert_resource(WebSocketServerAddress(local_address));
+    }
+
+    IoTaskPool::get()
+        .spawn(listen(listener, request_sender.clone()))
+        .detach();
+}
+
+/// The Bevy Remote Protocol WebSocket server main loop.
+async fn listen(
+    listener: Async<TcpListener>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    loop {
+        let (client, _) = listener.accept().await?;
+
+        let request_sender = request_sender.clone();
+        IoTaskPool::get()
+            .spawn(async move {
+                let _ = handle_client(client, request_sender).await;
+            })
+            .detach();
+    }
+}
+
+async fn handle_client(
+    client: Async<TcpStream>,
+    request_sender: Sender<BrpMessage>,
+) -> AnyhowResult<()> {
+    let (mut socket_sender, mut socket_receiver) =
+        async_tungstenite::accept_async(client).await?.split();
+
+    // Responses are produced concurrently, so they are funneled through a channel to a single
+    // task owning the sending half of the socket.
+    let (response_sender, response_receiver) = async_channel::unbounded::<String>();
+    let writer = IoTaskPool::get().spawn(async move {
+        while let Ok(response) = response_receiver.recv().await {
+            if socket_sender.send(Message::text(response)).await.is_err() {
+                break;
+            }
+        }
+        let _ = socket_sender.close(None).await;
+    });
+
+    // Dropping a watch task drops its receiver, which ends the watching request.
+    let mut watches: HashMap<String, Task<()>> = HashMap::new();
+    while let Some(message) = socket_receiver.next().await {
+        let batch = match message? {
+            Message::Text(text) => serde_json::from_str::<BrpBatch>(text.as_str()),
+            Message::Binary(bytes) => serde_json::from_slice::<BrpBatch>(&bytes),
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        watches.retain(|_, task| !task.is_finished());
+
+        match batch {
+            Ok(BrpBatch::Single(request)) => {
+                process_single_request(request, &request_sender, &response_sender, &mut watches)
+                    .await;
+            }
+            Ok(BrpBatch::Batch(requests)) => {
+                let request_sender = request_sender.clone();
+                let response_sender = response_sender.clone();
+                IoTaskPool::get()
+                    .spawn(async move {
+                        let mut responses = Vec::new();
+                        for request in requests {
+                            responses.push(process_batched_request(request, &request_sender).await);
+                        }
+                        send_response(&response_sender, &responses).await;
+                    })
+                    .detach();
+            }
+            Err(err) => {
+                let response = BrpResponse::new(None, Err(invalid_request(err)));
+                send_response(&response_sender, &response).await;
+            }
+        }
+    }
+
+    drop(watches);
+    drop(response_sender);
+    writer.await;
+
+    Ok(())
+}
+
+/// Processes a single request coming from a client, answering through `response_sender`.
+async fn process_single_request(
+    request: Value,
+    request_sender: &Sender<BrpMessage>,
+    response_sender: &Sender<String>,
+    watches: &mut HashMap<String, Task<()>>,
+) {
+    // Reach in and get the request ID early so that we can report it even when parsing fails.
+    let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+    let request: BrpRequest = match serde_json::from_value(request) {
+        Ok(request) => request,
+        Err(err) => {
+            let response = BrpResponse::new(id, Err(invalid_request(err)));
+            send_response(response_sender, &response).await;
+            return;
+        }
+    };
+
+    if request.method == RPC_UNWATCH_METHOD {
+        let result = request
+            .params
+            .ok_or_else(|| invalid_params("Params not provided"))
+            .and_then(|params| {
+                serde_json::from_value::<RpcUnwatchParams>(params).map_err(invalid_params)
+            })
+            .and_then(|RpcUnwatchParams { id }| {
+                watches
+                    .remove(&id.to_string())
+                    .map(|_| Value::Null)
+                    .ok_or_else(|| invalid_params(format!("No watching request with id {id}")))
+            });
+        send_response(response_sender, &BrpResponse::new(request.id, result)).await;
+        return;
+    }
+
+    let watch = request.method.contains("+watch");
+    let size = if watch { 8 } else { 1 };
+    let (result_sender, result_receiver) = async_channel::bounded(size);
+
+    let _ = request_sender
+        .send(BrpMessage {
+            method: request.method,
+            params: request.params,
+            sender: result_sender,
+        })
+        .await;
+
+    let response_sender = response_sender.clone();
+    if watch {
+        let key = request.id.as_ref().unwrap_or(&Value::Null).to_string();
+        let task = IoTaskPool::get().spawn(async move {
+            while let Ok(result) = result_receiver.recv().await {
+                let response = BrpResponse::new(request.id.clone(), result);
+                send_response(&response_sender, &response).await;
+            }
+        });
+        watches.insert(key, task);
+    } else {
+        IoTaskPool::get()
+            .spawn(async move {
+                if let Ok(result) = result_receiver.recv().await {
+                    let response = BrpResponse::new(request.id, result);
+                    send_response(&response_sender, &response).await;
+                }
+            })
+            .detach();
+    }
+}
+
+/// Processes a request that is part of a batch, returning its response.
+async fn process_batched_request(
+    request: Value,
+    request_sender: &Sender<BrpMessage>,
+) -> BrpResponse {
+    let id = request.as_object().and_then(|map| map.get("id")).cloned();
+
+    let request: BrpRequest = match serde_json::from_value(request) {
+        Ok(request) => request,
+        Err(err) => return BrpResponse::new(id, Err(invalid_request(err))),
+    };
+
+    if request.method.contains("+watch") || request.method == RPC_UNWATCH_METHOD {
+        return BrpResponse::new(
+            request.id,
+            Err(invalid_request(
+                "Watching can not be used in batch requests",
+            )),
+        );
+    }
+
+    let (result_sender, result_receiver) = async_channel::bounded(1);
+    let _ = request_sender
+        .send(BrpMessage {
+            method: request.method,
+            params: request.params,
+            sender: result_sender,
+        })
+        .await;
+
+    let result = result_receiver
+        .recv()
+        .await
+        .unwrap_or_else(|err| Err(BrpError::internal(err)));
+    BrpResponse::new(request.id, result)
+}
+
+async fn send_response(response_sender: &Sender<String>, response: &impl Serialize) {
+    if let Ok(serialized) = serde_json::to_string(response) {
+        let _ = response_sender.send(serialized).await;
+    }
+}
+
+fn invalid_request(err: impl ToString) -> BrpError {
+    BrpError {
+        code: error_codes::INVALID_REQUEST,
+        message: err.to_string(),
+        data: None,
+    }
+}
+
+fn invalid_params(err: impl ToString) -> BrpError {
+    BrpError {
+        code: error_codes::INVALID_PARAMS,
+        message: err.to_string(),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BrpResult, RemotePlugin};
+    use async_tungstenite::WebSocketStream;
+    use bevy_app::TaskPoolPlugin;
+    use bevy_ecs::system::{In, Local};
+    use bevy_tasks::futures_lite::future::block_on;
+    use core::time::Duration;
+    use serde_json::json;
+    use std::time::Instant;
+
+    fn counter_watch(
+        In(_params): In<Option<Value>>,
+        mut count: Local<u32>,
+    ) -> BrpResult<Option<Value>> {
+        *count += 1;
+        Ok(Some(json!(*count)))
+    }
+
+    async fn receive(socket: &mut WebSocketStream<Async<TcpStream>>) -> Value {
+        loop {
+            if let Message::Text(text) = socket.next().await.unwrap().unwrap() {
+                return serde_json::from_str(text.as_str()).unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn websocket_requests_and_watches() {
+        let mut app = App::new();
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            RemotePlugin::default().with_watching_method_main("test.counter+watch", counter_watch),
+            RemoteWebSocketPlugin::default().with_port(0),
+        ));
+        app.update();
+
+        let address = app.world().resource::<WebSocketServerAddress>().0;
+        assert_ne!(address.port(), 0);
+
+        let client = std::thread::spawn(move || {
+            block_on(async move {
+                let stream = Async::<TcpStream>::connect(address).await.unwrap();
+                let (mut socket, _) =
+                    async_tungstenite::client_async(format!("ws://{address}"), stream)
+                        .await
+                        .unwrap();
+
+                let mut responses = Vec::new();
+
+                // Watch the counter alongside an instant request.
+                let request =
+                    json!({ "jsonrpc": "2.0", "id": "watch", "method": "test.counter+watch" });
+                socket
+                    .send(Message::text(request.to_string()))
+                    .await
+                    .unwrap();
+                let request =
+                    json!({ "jsonrpc": "2.0", "id": 1, "method": "world.list_resources" });
+                socket
+                    .send(Message::text(request.to_string()))
+                    .await
+                    .unwrap();
+
+                let mut watch_updates = 0;
+                let mut listed_resources = false;
+                while watch_updates < 2 || !listed_resources {
+                    let response = receive(&mut socket).await;
+                    match &response["id"] {
+                        Value::String(id) if id == "watch" => watch_updates += 1,
+                        id if *id == json!(1) => listed_resources = true,
+                        id => panic!("unexpected response id {id}"),
+                    }
+                    responses.push(response);
+                }
+
+                // Stop watching; further responses may still be in flight.
+                let request = json!({ "jsonrpc": "2.0", "id": 2, "method": "rpc.unwatch", "params": { "id": "watch" } });
+                socket
+                    .send(Message::text(request.to_string()))
+                    .await
+                    .unwrap();
+                loop {
+                    let response = receive(&mut socket).await;
+                    if response["id"] == json!(2) {
+                        assert_eq!(response["result"], Value::Null);
+                        break;
+                    }
+                }
+
+                // Unknown watches and malformed messages are reported as errors.
+                socket
+                    .send(Message::text(request.to_string()))
+                    .await
+                    .unwrap();
+                let response = receive(&mut socket).await;
+                assert_eq!(
+                    response["error"]["code"],
+                    json!(error_codes::INVALID_PARAMS)
+                );
+                socket.send(Message::text("not json")).await.unwrap();
+                let response = receive(&mut socket).await;
+                assert_eq!(
+                    response["error"]["code"],
+                    json!(error_codes::INVALID_REQUEST)
+                );
+
+                socket.close(None).await.unwrap();
+                responses
+            })
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while !client.is_finished() {
+            assert!(Instant::now() < deadline, "WebSocket client timed out");
+            app.update();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let responses = client.join().unwrap();
+        assert!(responses
+            .iter()
+            .all(|response| response.get("error").is_none()));
+    }
+}
@@ -0,0 +1,595 @@
+use crate::{
+    bsn_asset::parse::{
+        parse_bsn, BsnEntry, BsnError, BsnErrorKind, BsnField, BsnFields, BsnLocation, BsnScene,
+        BsnType, BsnValue, BsnValueKind,
+    },
+    InheritSceneAsset, NameEntityReference, ReflectComponentTemplate, ReflectSceneRelationship,
+    ResolveContext, ResolveSceneError, ResolvedScene, Scene, SceneDependencies, ScenePatch,
+};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_asset::{io::Reader, AssetLoader, AssetPath, LoadContext, LoadFromPath, ReflectHandle};
+use bevy_ecs::{
+    name::Name,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectFromWorld},
+    world::{FromWorld, World},
+};
+use bevy_platform::collections::HashMap;
+use bevy_reflect::{
+    array::DynamicArray,
+    enums::{DynamicEnum, DynamicVariant, VariantInfo},
+    list::DynamicList,
+    prelude::ReflectDefault,
+    structs::DynamicStruct,
+    tuple::DynamicTuple,
+    tuple_struct::DynamicTupleStruct,
+    PartialReflect, ReflectFromReflect, TypeInfo, TypePath, TypeRegistration, TypeRegistry,
+    TypeRegistryArc,
+};
+use core::any::TypeId;
+use thiserror::Error;
+use tracing::warn;
+
+/// An [`AssetLoader`] that loads `.bsn` files as [`ScenePatch`] assets.
+///
+/// Component and relationship types are looked up by (full or short) type path in the
+/// [`AppTypeRegistry`]. Components must register [`ReflectComponent`] and either [`ReflectDefault`],
+/// [`ReflectFromWorld`], or be fully specified in the file. Relationship targets used for related scene
+/// lists must register [`ReflectSceneRelationship`].
+#[derive(TypePath)]
+pub struct BsnLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BsnLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BsnLoader {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`BsnLoader`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BsnLoaderError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to read the scene file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file is not valid UTF-8.
+    #[error("The scene file is not valid UTF-8: {0}")]
+    Utf8(#[from] core::str::Utf8Error),
+    /// The file could not be parsed, or it references types that could not be resolved.
+    #[error("Failed to load the scene file: {0}")]
+    Bsn(#[from] BsnError),
+}
+
+impl AssetLoader for BsnLoader {
+    type Asset = ScenePatch;
+    type Settings = ();
+    type Error = BsnLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let source = core::str::from_utf8(&bytes)?;
+        let scene = {
+            let type_registry = self.type_registry.read();
+            load_bsn(source, &type_registry, load_context)?
+        };
+        Ok(ScenePatch::load_with(load_context, scene))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bsn"]
+    }
+}
+
+/// Parses `source` as BSN and resolves every type it references using `type_registry`. Asset paths
+/// (for inherited scenes and [`Handle`](bevy_asset::Handle) fields) are loaded with `load_from_path`.
+///
+/// The returned [`Scene`] can be passed to [`ScenePatch::load_with`], or spawned directly once its
+/// dependencies have loaded.
+pub fn load_bsn(
+    source: &str,
+    type_registry: &TypeRegistry,
+    load_from_path: &mut dyn LoadFromPath,
+) -> Result<BsnAssetScene, BsnError> {
+    let scene = parse_bsn(source)?;
+    let mut resolver = BsnTypeResolver {
+        registry: type_registry,
+        load_from_path,
+        names: HashMap::default(),
+    };
+    resolver.scene(&scene)
+}
+
+/// A [`Scene`] loaded from a `.bsn` asset (see [`BsnLoader`]). All types have already been resolved
+/// using reflection, so this can be resolved without access to the type registry.
+pub struct BsnAssetScene {
+    entries: Vec<BsnAssetEntry>,
+}
+
+enum BsnAssetEntry {
+    Inherit(AssetPath<'static>),
+    Name {
+        name: String,
+        index: usize,
+    },
+    Component(ReflectComponentTemplate),
+    Related {
+        relationship: ReflectSceneRelationship,
+        scenes: Vec<BsnAssetScene>,
+    },
+}
+
+impl BsnAssetScene {
+    /// Resolves the entries of this scene in the current entity scope. Related scenes share the
+    /// scope of the file they are defined in, so names can be referenced across the whole file.
+    fn resolve_entries(
+        self,
+        context: &mut ResolveContext,
+        scene: &mut ResolvedScene,
+    ) -> Result<(), ResolveSceneError> {
+        for entry in self.entries {
+            match entry {
+                BsnAssetEntry::Inherit(path) => InheritSceneAsset(path).resolve(context, scene)?,
+                BsnAssetEntry::Name { name, index } => NameEntityReference {
+                    name: Name::new(name),
+                    index,
+                }
+                .resolve(context, scene)?,
+                BsnAssetEntry::Component(component) => {
+                    let type_id = component.component_type_id();
+                    let mut component = Some(component);
+                    let template =
+                        scene.get_or_insert_erased_template_with(context, type_id, || {
+                            Box::new(component.take().unwrap())
+                        });
+                    if let Some(component) = component {
+                        let template: &mut dyn core::any::Any = template;
+                        if let Some(template) = template.downcast_mut::<ReflectComponentTemplate>()
+                        {
+                            template.merge_patch(component.patch());
+                        } else {
+                            warn!(
+                                "The patch of {} in a .bsn asset was ignored, since the inherited \
+                                scene defines it with a template that can't be patched by reflection",
+                                component.patch().reflect_type_path()
+                            );
+                        }
+                    }
+                }
+                BsnAssetEntry::Related {
+                    relationship,
+                    scenes,
+                } => {
+                    let related = scene.get_or_insert_related_resolved_scenes_by_id(
+                        relationship.relationship_type_id(),
+                        || relationship.new_related_scenes(),
+                    );
+                    for related_scene in scenes {
+                        let mut resolved_scene = ResolvedScene::default();
+                        related_scene.resolve_entries(context, &mut resolved_scene)?;
+                        related.scenes.push(resolved_scene);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Scene for BsnAssetScene {
+    fn resolve(
+        self,
+        context: &mut ResolveContext,
+        scene: &mut ResolvedScene,
+    ) -> Result<(), ResolveSceneError> {
+        context.new_entity_scope(|context| self.resolve_entries(context, scene))
+    }
+
+    fn register_dependencies(&self, dependencies: &mut SceneDependencies) {
+        for entry in &self.entries {
+            match entry {
+                BsnAssetEntry::Inherit(path) => dependencies.register::<ScenePatch>(path.clone()),
+                BsnAssetEntry::Related { scenes, .. } => {
+                    for scene in scenes {
+                        scene.register_dependencies(dependencies);
+                    }
+                }
+                BsnAssetEntry::Name { .. } | BsnAssetEntry::Component(_) => {}
+            }
+        }
+    }
+}
+
+/// Converts parsed BSN into a [`BsnAssetScene`], using the type registry to build reflected values.
+struct BsnTypeResolver<'a> {
+    registry: &'a TypeRegistry,
+    load_from_path: &'a mut dyn LoadFromPath,
+    /// Maps entity names to their index in the (single) entity scope of the file.
+    names: HashMap<String, usize>,
+}
+
+impl<'a> BsnTypeResolver<'a> {
+    fn scene(&mut self, scene: &BsnScene) -> Result<BsnAssetScene, BsnError> {
+        let mut entries = Vec::with_capacity(scene.entries.len());
+        for entry in &scene.entries {
+            entries.push(match entry {
+                BsnEntry::Inherit(path) => BsnAssetEntry::Inherit(AssetPath::from(path.clone())),
+                BsnEntry::Name(name) => {
+                    let next_index = self.names.len();
+                    let index = *self.names.entry(name.clone()).or_insert(next_index);
+                    BsnAssetEntry::Name {
+                        name: name.clone(),
+                        index,
+                    }
+                }
+                BsnEntry::Component(ty) => BsnAssetEntry::Component(self.component(ty)?),
+                BsnEntry::Related {
+                    path,
+                    location,
+                    scenes,
+                } => {
+                    let registration = self.registration(path, *location)?;
+                    let relationship = registration
+                        .data::<ReflectSceneRelationship>()
+                        .ok_or_else(|| {
+                            BsnError::new(*location, BsnErrorKind::NotAComponent(path.clone()))
+                        })?
+                        .clone();
+                    let scenes = scenes
+                        .iter()
+                        .map(|scene| self.scene(scene))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    BsnAssetEntry::Related {
+                        relationship,
+                        scenes,
+                    }
+                }
+            });
+        }
+        Ok(BsnAssetScene { entries })
+    }
+
+    fn registration(
+        &self,
+        path: &str,
+        location: BsnLocation,
+    ) -> Result<&'a TypeRegistration, BsnError> {
+        self.registry
+            .get_with_type_path(path)
+            .or_else(|| self.registry.get_with_short_type_path(path))
+            .ok_or_else(|| BsnError::new(location, BsnErrorKind::UnknownType(path.to_string())))
+    }
+
+    fn type_info(
+        &self,
+        type_id: TypeId,
+        type_path: &str,
+        location: BsnLocation,
+    ) -> Result<&'static TypeInfo, BsnError> {
+        self.registry.get_type_info(type_id).ok_or_else(|| {
+            BsnError::new(location, BsnErrorKind::UnknownType(type_path.to_string()))
+        })
+    }
+
+    fn component(&mut self, ty: &BsnType) -> Result<ReflectComponentTemplate, BsnError> {
+        // `Type::Variant` paths are resolved by looking up the enum type without the variant.
+        let (registration, patch) = match self.registration(&ty.path, ty.location) {
+            Ok(registration) => {
+                let patch = self.fields(registration.type_info(), ty)?;
+                (registration, patch)
+            }
+            Err(error) => {
+                let variant = ty.last_segment();
+                let Some(enum_path) = ty
+                    .path
+                    .strip_suffix(variant)
+                    .and_then(|path| path.strip_suffix("::"))
+                else {
+                    return Err(error);
+                };
+                let Ok(registration) = self.registration(enum_path, ty.location) else {
+                    return Err(error);
+                };
+                let patch = self.value_of_type(registration.type_info(), ty)?;
+                (registration, patch)
+            }
+        };
+        let type_path = registration.type_info().type_path();
+        let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+            BsnError::new(ty.location, BsnErrorKind::NotAComponent(type_path.into()))
+        })?;
+        let constructible = registration.data::<ReflectDefault>().is_some()
+            || registration.data::<ReflectFromWorld>().is_some()
+            || registration
+                .data::<ReflectFromReflect>()
+                .is_some_and(|from_reflect| from_reflect.from_reflect(&*patch).is_some());
+        if !constructible {
+            return Err(BsnError::new(
+                ty.location,
+                BsnErrorKind::NotConstructible(type_path.into()),
+            ));
+        }
+        Ok(ReflectComponentTemplate::new(
+            registration.type_id(),
+            reflect_component.clone(),
+            patch,
+        ))
+    }
+
+    fn value(
+        &mut self,
+        type_info: &'static TypeInfo,
+        value: &BsnValue,
+    ) -> Result<Box<dyn PartialReflect>, BsnError> {
+        match (&value.kind, type_info) {
+            (BsnValueKind::Type(ty), _) => self.value_of_type(type_info, ty),
+            (BsnValueKind::Tuple(values), TypeInfo::Tuple(info)) => {
+                if values.len() != info.field_len() {
+                    return Err(mismatch(type_info, value));
+                }
+                let mut tuple = DynamicTuple::default();
+                for (field, value) in info.iter().zip(values) {
+                    let field_info =
+                        self.type_info(field.type_id(), field.type_path(), value.location)?;
+                    tuple.insert_boxed(self.value(field_info, value)?);
+                }
+                tuple.set_represented_type(Some(type_info));
+                Ok(Box::new(tuple))
+            }
+            (BsnValueKind::Tuple(values), TypeInfo::TupleStruct(_)) => {
+                self.tuple_struct(type_info, values)
+            }
+            (BsnValueKind::List(values), TypeInfo::List(info)) => {
+                let item_info =
+                    self.type_info(info.item_ty().id(), info.item_ty().path(), value.location)?;
+                let mut list = DynamicList::default();
+                for value in values {
+                    list.push_box(self.value(item_info, value)?);
+                }
+                list.set_represented_type(Some(type_info));
+                Ok(Box::new(list))
+            }
+            (BsnValueKind::List(values), TypeInfo::Array(info)) => {
+                if values.len() != info.capacity() {
+                    return Err(mismatch(type_info, value));
+                }
+                let item_info =
+                    self.type_info(info.item_ty().id(), info.item_ty().path(), value.location)?;
+                let items = values
+                    .iter()
+                    .map(|value| self.value(item_info, value))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let mut array = DynamicArray::new(items.into_boxed_slice());
+                array.set_represented_type(Some(type_info));
+                Ok(Box::new(array))
+            }
+            (_, TypeInfo::Opaque(_)) => self.opaque(type_info, value),
+            (_, TypeInfo::Map(_) | TypeInfo::Set(_)) => Err(BsnError::new(
+                value.location,
+                BsnErrorKind::UnsupportedType(type_info.type_path().into()),
+            )),
+            _ => Err(mismatch(type_info, value)),
+        }
+    }
+
+    /// Converts a `Type { ... }`, `Type(...)` or `Type` value into a value of the type described by `type_info`.
+    fn value_of_type(
+        &mut self,
+        type_info: &'static TypeInfo,
+        ty: &BsnType,
+    ) -> Result<Box<dyn PartialReflect>, BsnError> {
+        if let TypeInfo::Enum(info) = type_info {
+            let variant_name = ty.last_segment();
+            let Some(variant) = info.variant(variant_name) else {
+                return Err(BsnError::new(
+                    ty.location,
+                    BsnErrorKind::UnknownVariant {
+                        type_path: type_info.type_path().into(),
+                        variant: variant_name.into(),
+                    },
+                ));
+            };
+            let dynamic_variant = match (variant, &ty.fields) {
+                (VariantInfo::Unit(_), BsnFields::Unit) => DynamicVariant::Unit,
+                (VariantInfo::Tuple(variant), BsnFields::Tuple(values)) => {
+                    if values.len() != variant.field_len() {
+                        return Err(type_mismatch(type_info, ty));
+                    }
+                    let mut tuple = DynamicTuple::default();
+                    for (field, value) in variant.iter().zip(values) {
+                        let field_info =
+                            self.type_info(field.type_id(), field.type_path(), value.location)?;
+                        tuple.insert_boxed(self.value(field_info, value)?);
+                    }
+                    DynamicVariant::Tuple(tuple)
+                }
+                (VariantInfo::Struct(variant), BsnFields::Named(fields)) => {
+                    let mut dynamic_struct = DynamicStruct::default();
+                    for BsnField {
+                        name,
+                        location,
+                        value,
+                    } in fields
+                    {
+                        let Some(field) = variant.field(name) else {
+                            return Err(unknown_field(type_info, name, *location));
+                        };
+                        let field_info =
+                            self.type_info(field.type_id(), field.type_path(), value.location)?;
+                        dynamic_struct.insert_boxed(name, self.value(field_info, value)?);
+                    }
+                    DynamicVariant::Struct(dynamic_struct)
+                }
+                _ => return Err(type_mismatch(type_info, ty)),
+            };
+            let mut dynamic_enum = DynamicEnum::new(variant_name, dynamic_variant);
+            dynamic_enum.set_represented_type(Some(type_info));
+            return Ok(Box::new(dynamic_enum));
+        }
+        self.fields(type_info, ty)
+    }
+
+    /// Converts the fields of `ty` into a (potentially partial) struct or tuple struct value.
+    fn fields(
+        &mut self,
+        type_info: &'static TypeInfo,
+        ty: &BsnType,
+    ) -> Result<Box<dyn PartialReflect>, BsnError> {
+        match (type_info, &ty.fields) {
+            (TypeInfo::Struct(info), BsnFields::Named(fields)) => {
+                let mut dynamic_struct = DynamicStruct::default();
+                for BsnField {
+                    name,
+                    location,
+                    value,
+                } in fields
+                {
+                    let Some(field) = info.field(name) else {
+                        return Err(unknown_field(type_info, name, *location));
+                    };
+                    let field_info =
+                        self.type_info(field.type_id(), field.type_path(), value.location)?;
+                    dynamic_struct.insert_boxed(name, self.value(field_info, value)?);
+                }
+                dynamic_struct.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic_struct))
+            }
+            (TypeInfo::Struct(_), BsnFields::Unit) => {
+                let mut dynamic_struct = DynamicStruct::default();
+                dynamic_struct.set_represented_type(Some(type_info));
+                Ok(Box::new(dynamic_struct))
+            }
+            (TypeInfo::TupleStruct(_), BsnFields::Tuple(values)) => {
+                self.tuple_struct(type_info, values)
+            }
+            (TypeInfo::TupleStruct(_), BsnFields::Unit) => {
+                let mut tuple_struct = DynamicTupleStruct::default();
+                tuple_struct.set_represented_type(Some(type_info));
+                Ok(Box::new(tuple_struct))
+            }
+            (TypeInfo::Opaque(_) | TypeInfo::Map(_) | TypeInfo::Set(_), _) => Err(BsnError::new(
+                ty.location,
+                BsnErrorKind::UnsupportedType(type_info.type_path().into()),
+            )),
+            _ => Err(type_mismatch(type_info, ty)),
+        }
+    }
+
+    /// Converts `values` into a (potentially partial) tuple struct value. `type_info` must be a [`TypeInfo::TupleStruct`].
+    fn tuple_struct(
+        &mut self,
+        type_info: &'static TypeInfo,
+        values: &[BsnValue],
+    ) -> Result<Box<dyn PartialReflect>, BsnError> {
+        let TypeInfo::TupleStruct(info) = type_info else {
+            unreachable!("tuple_struct is only called with tuple struct type info");
+        };
+        let mut tuple_struct = DynamicTupleStruct::default();
+        for (index, value) in values.iter().enumerate() {
+            let Some(field) = info.field_at(index) else {
+                return Err(mismatch(type_info, value));
+            };
+            let field_info = self.type_info(field.type_id(), field.type_path(), value.location)?;
+            tuple_struct.insert_boxed(self.value(field_info, value)?);
+        }
+        tuple_struct.set_represented_type(Some(type_info));
+        Ok(Box::new(tuple_struct))
+    }
+
+    fn opaque(
+        &mut self,
+        type_info: &'static TypeInfo,
+        value: &BsnValue,
+    ) -> Result<Box<dyn PartialReflect>, BsnError> {
+        let type_id = type_info.type_id();
+        macro_rules! parse_number {
+            ($text:expr, $($ty:ty),*) => {
+                $(
+                    if type_id == TypeId::of::<$ty>() {
+                        return $text
+                            .parse::<$ty>()
+                            .map(|number| Box::new(number) as Box<dyn PartialReflect>)
+                            .map_err(|_| mismatch(type_info, value));
+                    }
+                )*
+            };
+        }
+        match &value.kind {
+            BsnValueKind::Number(text) => {
+                parse_number!(
+                    text, f32, f64, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+                );
+            }
+            BsnValueKind::Bool(boolean) if type_id == TypeId::of::<bool>() => {
+                return Ok(Box::new(*boolean));
+            }
+            BsnValueKind::String(string) => {
+                if type_id == TypeId::of::<String>() {
+                    return Ok(Box::new(string.clone()));
+                }
+                if type_id == TypeId::of::<Cow<'static, str>>() {
+                    return Ok(Box::new(Cow::<'static, str>::Owned(string.clone())));
+                }
+                if let Some(reflect_handle) = self.registry.get_type_data::<ReflectHandle>(type_id)
+                {
+                    let handle = self.load_from_path.load_from_path_erased(
+                        reflect_handle.asset_type_id(),
+                        AssetPath::from(string.clone()),
+                    );
+                    return Ok(reflect_handle.typed(handle).into_partial_reflect());
+                }
+            }
+            _ => {}
+        }
+        Err(mismatch(type_info, value))
+    }
+}
+
+fn mismatch(type_info: &TypeInfo, value: &BsnValue) -> BsnError {
+    BsnError::new(
+        value.location,
+        BsnErrorKind::MismatchedValue {
+            type_path: type_info.type_path().into(),
+            found: value.kind.describe(),
+        },
+    )
+}
+
+fn type_mismatch(type_info: &TypeInfo, ty: &BsnType) -> BsnError {
+    let found = match &ty.fields {
+        BsnFields::Unit => alloc::format!("'{}'", ty.path),
+        BsnFields::Named(_) => alloc::format!("'{} {{ .. }}'", ty.path),
+        BsnFields::Tuple(_) => alloc::format!("'{}(..)'", ty.path),
+    };
+    BsnError::new(
+        ty.location,
+        BsnErrorKind::MismatchedValue {
+            type_path: type_info.type_path().into(),
+            found,
+        },
+    )
+}
+
+fn unknown_field(type_info: &TypeInfo, field: &str, location: BsnLocation) -> BsnError {
+    BsnError::new(
+        location,
+        BsnErrorKind::UnknownField {
+            type_path: type_info.type_path().into(),
+            field: field.into(),
+        },
+    )
+}
@@ -0,0 +1,30 @@
+//! Loading and saving scenes as `.bsn` assets.
+
+mod loader;
+mod parse;
+mod reflect;
+mod saver;
+
+pub use loader::*;
+pub use parse::{BsnError, BsnErrorKind, BsnLocation};
+pub use reflect::*;
+pub use saver::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::AssetApp;
+use bevy_ecs::hierarchy::Children;
+
+/// Adds support for loading `.bsn` files as [`ScenePatch`](crate::ScenePatch) assets using the [`BsnLoader`].
+///
+/// This requires the [`ScenePlugin`](crate::ScenePlugin). It also registers [`ReflectSceneRelationship`]
+/// for [`Children`], so that `Children [ ... ]` can be used in `.bsn` files.
+#[derive(Default)]
+pub struct BsnAssetPlugin;
+
+impl Plugin for BsnAssetPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset_loader::<BsnLoader>()
+            .register_type::<Children>()
+            .register_type_data::<Children, ReflectSceneRelationship>();
+    }
+}
@@ -0,0 +1,840 @@
+use alloc::{format, string::String, vec::Vec};
+use core::fmt;
+use thiserror::Error;
+
+/// A line / column position in a `.bsn` source file. Both values start at 1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BsnLocation {
+    /// The line number, starting at 1.
+    pub line: usize,
+    /// The column number (in characters), starting at 1.
+    pub column: usize,
+}
+
+impl fmt::Display for BsnLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// An error produced while parsing or resolving a `.bsn` file. Every error is tied to the [`BsnLocation`] it occurred at.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{kind} at {location}")]
+pub struct BsnError {
+    /// Where in the source the error occurred.
+    pub location: BsnLocation,
+    /// What went wrong.
+    pub kind: BsnErrorKind,
+}
+
+impl BsnError {
+    pub(crate) fn new(location: BsnLocation, kind: BsnErrorKind) -> Self {
+        Self { location, kind }
+    }
+}
+
+/// The kind of a [`BsnError`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum BsnErrorKind {
+    /// The source contained a character that does not start any BSN token.
+    #[error("unexpected character '{0}'")]
+    UnexpectedCharacter(char),
+    /// A string or block comment was not closed before the end of the file.
+    #[error("unterminated {0}")]
+    Unterminated(&'static str),
+    /// A string contained an unknown escape sequence.
+    #[error("invalid escape sequence '\\{0}'")]
+    InvalidEscape(char),
+    /// The parser found a token it did not expect.
+    #[error("expected {expected}, found {found}")]
+    UnexpectedToken {
+        /// A description of what the parser expected.
+        expected: &'static str,
+        /// A description of what the parser found.
+        found: String,
+    },
+    /// The source used a feature of the `bsn!` macro that cannot be expressed in a `.bsn` asset.
+    #[error("{0} are not supported in .bsn assets")]
+    Unsupported(&'static str),
+    /// A type path could not be found in the type registry.
+    #[error("the type '{0}' is not registered in the type registry")]
+    UnknownType(String),
+    /// A type was found, but it is not a reflected component or relationship target.
+    #[error("the type '{0}' is not a component. Consider adding #[reflect(Component)]")]
+    NotAComponent(String),
+    /// A component cannot be constructed from a partial patch.
+    #[error("the component '{0}' cannot be constructed. Consider adding #[reflect(Default)]")]
+    NotConstructible(String),
+    /// A field does not exist on the target type.
+    #[error("the type '{type_path}' has no field '{field}'")]
+    UnknownField {
+        /// The type that was being patched.
+        type_path: String,
+        /// The name of the missing field.
+        field: String,
+    },
+    /// An enum variant does not exist on the target type.
+    #[error("the enum '{type_path}' has no variant '{variant}'")]
+    UnknownVariant {
+        /// The enum type.
+        type_path: String,
+        /// The name of the missing variant.
+        variant: String,
+    },
+    /// A value does not fit the type it is assigned to.
+    #[error("expected a value of type '{type_path}', found {found}")]
+    MismatchedValue {
+        /// The expected type.
+        type_path: String,
+        /// A description of the value that was found.
+        found: String,
+    },
+    /// The target type cannot be expressed in a `.bsn` file.
+    #[error("values of type '{0}' are not supported in .bsn assets")]
+    UnsupportedType(String),
+}
+
+/// A parsed `.bsn` scene: a single entity described by a list of entries.
+#[derive(Debug, Default)]
+pub(crate) struct BsnScene {
+    pub(crate) entries: Vec<BsnEntry>,
+}
+
+#[derive(Debug)]
+pub(crate) enum BsnEntry {
+    /// `:"path/to/scene.bsn"`
+    Inherit(String),
+    /// `#Name`
+    Name(String),
+    /// `Type { ... }`, `Type(...)`, `Type` or `Type::Variant ...`
+    Component(BsnType),
+    /// `RelationshipTarget [ ... ]`
+    Related {
+        path: String,
+        location: BsnLocation,
+        scenes: Vec<BsnScene>,
+    },
+}
+
+#[derive(Debug)]
+pub(crate) struct BsnType {
+    pub(crate) path: String,
+    pub(crate) location: BsnLocation,
+    pub(crate) fields: BsnFields,
+}
+
+impl BsnType {
+    /// The final segment of this type's path (ex: the variant name in `Enum::Variant`).
+    pub(crate) fn last_segment(&self) -> &str {
+        last_path_segment(&self.path)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) enum BsnFields {
+    Unit,
+    Named(Vec<BsnField>),
+    Tuple(Vec<BsnValue>),
+}
+
+#[derive(Debug)]
+pub(crate) struct BsnField {
+    pub(crate) name: String,
+    pub(crate) location: BsnLocation,
+    pub(crate) value: BsnValue,
+}
+
+#[derive(Debug)]
+pub(crate) struct BsnValue {
+    pub(crate) location: BsnLocation,
+    pub(crate) kind: BsnValueKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum BsnValueKind {
+    Bool(bool),
+    /// The number is kept as text until the target type is known.
+    Number(String),
+    String(String),
+    Type(BsnType),
+    Tuple(Vec<BsnValue>),
+    List(Vec<BsnValue>),
+}
+
+impl BsnValueKind {
+    /// A short description of this value, used in error messages.
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            BsnValueKind::Bool(value) => format!("boolean {value}"),
+            BsnValueKind::Number(value) => format!("number {value}"),
+            BsnValueKind::String(value) => format!("string {value:?}"),
+            BsnValueKind::Type(ty) => format!("'{}'", ty.path),
+            BsnValueKind::Tuple(_) => "a tuple".into(),
+            BsnValueKind::List(_) => "a list".into(),
+        }
+    }
+}
+
+pub(crate) fn last_path_segment(path: &str) -> &str {
+    // Generic arguments can contain `::`, so only look at the part of the path before them.
+    let end = path.find('<').unwrap_or(path.len());
+    path[..end].rsplit("::").next().unwrap_or(path)
+}
+
+/// Parses the contents of a `.bsn` file.
+pub(crate) fn parse_bsn(source: &str) -> Result<BsnScene, BsnError> {
+    let tokens = tokenize(source)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+    };
+    let scene = parser.parse_scene_entries(&[])?;
+    parser.expect_end()?;
+    Ok(scene)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    String(String),
+    Number(String),
+    Punct(char),
+    PathSeparator,
+    End,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(ident) => format!("'{ident}'"),
+            Token::String(value) => format!("string {value:?}"),
+            Token::Number(value) => format!("number {value}"),
+            Token::Punct(punct) => format!("'{punct}'"),
+            Token::PathSeparator => "'::'".into(),
+            Token::End => "end of file".into(),
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: core::iter::Peekable<core::str::Chars<'a>>,
+    location: BsnLocation,
+}
+
+impl Lexer<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        if c == '\n' {
+            self.location.line += 1;
+            self.location.column = 1;
+        } else {
+            self.location.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), BsnError> {
+        loop {
+            match self.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.next();
+                }
+                Some('/') => {
+                    let start = self.location;
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+                    match lookahead.next() {
+                        Some('/') => {
+                            while self.peek().is_some_and(|c| c != '\n') {
+                                self.next();
+                            }
+                        }
+                        Some('*') => {
+                            self.next();
+                            self.next();
+                            let mut depth = 1;
+                            while depth > 0 {
+                                match self.next() {
+                                    Some('*') if self.peek() == Some('/') => {
+                                        self.next();
+                                        depth -= 1;
+                                    }
+                                    Some('/') if self.peek() == Some('*') => {
+                                        self.next();
+                                        depth += 1;
+                                    }
+                                    Some(_) => {}
+                                    None => {
+                                        return Err(BsnError::new(
+                                            start,
+                                            BsnErrorKind::Unterminated("block comment"),
+                                        ))
+                                    }
+                                }
+                            }
+                        }
+                        _ => return Ok(()),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn string(&mut self, start: BsnLocation) -> Result<String, BsnError> {
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => {
+                    let escape_location = self.location;
+                    let escaped = match self.next() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('0') => '\0',
+                        Some('\\') => '\\',
+                        Some('"') => '"',
+                        Some('\'') => '\'',
+                        Some('u') => self.unicode_escape().ok_or_else(|| {
+                            BsnError::new(escape_location, BsnErrorKind::InvalidEscape('u'))
+                        })?,
+                        Some(other) => {
+                            return Err(BsnError::new(
+                                escape_location,
+                                BsnErrorKind::InvalidEscape(other),
+                            ))
+                        }
+                        None => break,
+                    };
+                    value.push(escaped);
+                }
+                Some(c) => value.push(c),
+                None => break,
+            }
+        }
+        Err(BsnError::new(start, BsnErrorKind::Unterminated("string")))
+    }
+
+    /// Parses the `{XXXX}` part of a `\u{XXXX}` escape, like in Rust string literals.
+    fn unicode_escape(&mut self) -> Option<char> {
+        if self.next()? != '{' {
+            return None;
+        }
+        let mut digits = String::new();
+        loop {
+            match self.next()? {
+                '}' => break,
+                '_' => {}
+                c if c.is_ascii_hexdigit() && digits.len() < 6 => digits.push(c),
+                _ => return None,
+            }
+        }
+        char::from_u32(u32::from_str_radix(&digits, 16).ok()?)
+    }
+
+    fn number(&mut self) -> String {
+        let mut value = String::new();
+        while let Some(c) = self.peek() {
+            let is_exponent_sign =
+                (c == '-' || c == '+') && value.ends_with(['e', 'E']) && !value.starts_with("0x");
+            if c.is_ascii_alphanumeric() || c == '_' || c == '.' || is_exponent_sign {
+                if c != '_' {
+                    value.push(c);
+                }
+                self.next();
+            } else {
+                break;
+            }
+        }
+        value
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, BsnLocation)>, BsnError> {
+    let mut lexer = Lexer {
+        chars: source.chars().peekable(),
+        location: BsnLocation { line: 1, column: 1 },
+    };
+    let mut tokens = Vec::new();
+    loop {
+        lexer.skip_whitespace_and_comments()?;
+        let location = lexer.location;
+        let Some(c) = lexer.peek() else {
+            tokens.push((Token::End, location));
+            return Ok(tokens);
+        };
+        let token = if c.is_alphabetic() || c == '_' {
+            let mut ident = String::new();
+            while let Some(c) = lexer.peek().filter(|c| c.is_alphanumeric() || *c == '_') {
+                ident.push(c);
+                lexer.next();
+            }
+            Token::Ident(ident)
+        } else if c.is_ascii_digit() {
+            Token::Number(lexer.number())
+        } else if c == '"' {
+            lexer.next();
+            Token::String(lexer.string(location)?)
+        } else if c == ':' {
+            lexer.next();
+            if lexer.peek() == Some(':') {
+                lexer.next();
+                Token::PathSeparator
+            } else {
+                Token::Punct(':')
+            }
+        } else if c.is_ascii_punctuation() {
+            lexer.next();
+            Token::Punct(c)
+        } else {
+            return Err(BsnError::new(
+                location,
+                BsnErrorKind::UnexpectedCharacter(c),
+            ));
+        };
+        tokens.push((token, location));
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, BsnLocation)>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position].0
+    }
+
+    fn peek_nth(&self, n: usize) -> &Token {
+        let index = (self.position + n).min(self.tokens.len() - 1);
+        &self.tokens[index].0
+    }
+
+    fn location(&self) -> BsnLocation {
+        self.tokens[self.position].1
+    }
+
+    fn bump(&mut self) -> (Token, BsnLocation) {
+        let token = self.tokens[self.position].clone();
+        if self.position < self.tokens.len() - 1 {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn is_punct(&self, punct: char) -> bool {
+        *self.peek() == Token::Punct(punct)
+    }
+
+    fn unexpected<T>(&self, expected: &'static str) -> Result<T, BsnError> {
+        Err(BsnError::new(
+            self.location(),
+            BsnErrorKind::UnexpectedToken {
+                expected,
+                found: self.peek().describe(),
+            },
+        ))
+    }
+
+    fn expect_punct(&mut self, punct: char, expected: &'static str) -> Result<(), BsnError> {
+        if self.is_punct(punct) {
+            self.bump();
+            Ok(())
+        } else {
+            self.unexpected(expected)
+        }
+    }
+
+    fn expect_end(&self) -> Result<(), BsnError> {
+        if *self.peek() == Token::End {
+            Ok(())
+        } else {
+            self.unexpected("a scene entry")
+        }
+    }
+
+    fn unsupported<T>(&self, feature: &'static str) -> Result<T, BsnError> {
+        Err(BsnError::new(
+            self.location(),
+            BsnErrorKind::Unsupported(feature),
+        ))
+    }
+
+    /// Parses scene entries until the end of the file or one of the `terminators` is found.
+    fn parse_scene_entries(&mut self, terminators: &[char]) -> Result<BsnScene, BsnError> {
+        let mut scene = BsnScene::default();
+        loop {
+            match self.peek() {
+                Token::End => break,
+                Token::Punct(c) if terminators.contains(c) => break,
+                _ => scene.entries.push(self.parse_entry()?),
+            }
+        }
+        Ok(scene)
+    }
+
+    fn parse_entry(&mut self) -> Result<BsnEntry, BsnError> {
+        match self.peek().clone() {
+            Token::Punct(':') => {
+                self.bump();
+                match self.bump() {
+                    (Token::String(path), _) => Ok(BsnEntry::Inherit(path)),
+                    (Token::Ident(_), location) => Err(BsnError::new(
+                        location,
+                        BsnErrorKind::Unsupported("inherited scene functions"),
+                    )),
+                    (token, location) => Err(BsnError::new(
+                        location,
+                        BsnErrorKind::UnexpectedToken {
+                            expected: "an inherited scene asset path",
+                            found: token.describe(),
+                        },
+                    )),
+                }
+            }
+            Token::Punct('#') => {
+                self.bump();
+                match self.bump() {
+                    (Token::Ident(name), _) => Ok(BsnEntry::Name(name)),
+                    (Token::Punct('{'), location) => Err(BsnError::new(
+                        location,
+                        BsnErrorKind::Unsupported("name expressions"),
+                    )),
+                    (token, location) => Err(BsnError::new(
+                        location,
+                        BsnErrorKind::UnexpectedToken {
+                            expected: "an entity name",
+                            found: token.describe(),
+                        },
+                    )),
+                }
+            }
+            Token::Punct('{') => self.unsupported("scene expressions"),
+            Token::Punct('@') => self.unsupported("explicit templates"),
+            Token::Ident(_) => {
+                let location = self.location();
+                let path = self.parse_path()?;
+                if self.is_punct('[') {
+                    self.bump();
+                    let scenes = self.parse_scene_list()?;
+                    Ok(BsnEntry::Related {
+                        path,
+                        location,
+                        scenes,
+                    })
+                } else if self.is_punct('(') && *self.peek_nth(1) == Token::Punct(')') {
+                    self.unsupported("scene functions")
+                } else {
+                    let fields = self.parse_fields()?;
+                    Ok(BsnEntry::Component(BsnType {
+                        path,
+                        location,
+                        fields,
+                    }))
+                }
+            }
+            _ => self.unexpected("a scene entry"),
+        }
+    }
+
+    /// Parses the items of a related scene list. The opening `[` has already been consumed.
+    fn parse_scene_list(&mut self) -> Result<Vec<BsnScene>, BsnError> {
+        let mut scenes = Vec::new();
+        loop {
+            if self.is_punct(']') {
+                self.bump();
+                return Ok(scenes);
+            }
+            let scene = if self.is_punct('(') {
+                self.bump();
+                let scene = self.parse_scene_entries(&[')'])?;
+                self.expect_punct(')', "')'")?;
+                scene
+            } else {
+                let scene = self.parse_scene_entries(&[',', ']'])?;
+                if scene.entries.is_empty() {
+                    return self.unexpected("a scene");
+                }
+                scene
+            };
+            scenes.push(scene);
+            if self.is_punct(',') {
+                self.bump();
+            } else if !self.is_punct(']') {
+                return self.unexpected("',' or ']'");
+            }
+        }
+    }
+
+    /// Parses a (potentially generic) type path, ex: `foo::Bar<u32>`.
+    fn parse_path(&mut self) -> Result<String, BsnError> {
+        let mut path = String::new();
+        loop {
+            match self.bump() {
+                (Token::Ident(ident), _) => path.push_str(&ident),
+                (token, location) => {
+                    return Err(BsnError::new(
+                        location,
+                        BsnErrorKind::UnexpectedToken {
+                            expected: "an identifier",
+                            found: token.describe(),
+                        },
+                    ))
+                }
+            }
+            if self.is_punct('<') {
+                self.parse_generics(&mut path)?;
+            }
+            if *self.peek() == Token::PathSeparator {
+                self.bump();
+                path.push_str("::");
+            } else {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn parse_generics(&mut self, path: &mut String) -> Result<(), BsnError> {
+        let mut depth = 0;
+        loop {
+            let (token, location) = self.bump();
+            match token {
+                Token::Punct('<') => {
+                    depth += 1;
+                    path.push('<');
+                }
+                Token::Punct('>') => {
+                    depth -= 1;
+                    path.push('>');
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                Token::Punct(',') => path.push_str(", "),
+                Token::Punct(c @ ('&' | ';' | '(' | ')' | '[' | ']')) => path.push(c),
+                Token::Punct('-') => path.push('-'),
+                Token::Ident(ident) | Token::Number(ident) => path.push_str(&ident),
+                Token::PathSeparator => path.push_str("::"),
+                token => {
+                    return Err(BsnError::new(
+                        location,
+                        BsnErrorKind::UnexpectedToken {
+                            expected: "a generic argument",
+                            found: token.describe(),
+                        },
+                    ))
+                }
+            }
+        }
+    }
+
+    fn parse_fields(&mut self) -> Result<BsnFields, BsnError> {
+        if self.is_punct('{') {
+            self.bump();
+            let mut fields = Vec::new();
+            loop {
+                if self.is_punct('}') {
+                    self.bump();
+                    return Ok(BsnFields::Named(fields));
+                }
+                let (name, location) = match self.bump() {
+                    (Token::Ident(name), location) => (name, location),
+                    (token, location) => {
+                        return Err(BsnError::new(
+                            location,
+                            BsnErrorKind::UnexpectedToken {
+                                expected: "a field name",
+                                found: token.describe(),
+                            },
+                        ))
+                    }
+                };
+                self.expect_punct(':', "':'")?;
+                let value = self.parse_value()?;
+                fields.push(BsnField {
+                    name,
+                    location,
+                    value,
+                });
+                if self.is_punct(',') {
+                    self.bump();
+                } else if !self.is_punct('}') {
+                    return self.unexpected("',' or '}'");
+                }
+            }
+        } else if self.is_punct('(') {
+            self.bump();
+            Ok(BsnFields::Tuple(self.parse_values(')')?))
+        } else {
+            Ok(BsnFields::Unit)
+        }
+    }
+
+    /// Parses a comma separated list of values. The opening delimiter has already been consumed.
+    fn parse_values(&mut self, close: char) -> Result<Vec<BsnValue>, BsnError> {
+        let mut values = Vec::new();
+        loop {
+            if self.is_punct(close) {
+                self.bump();
+                return Ok(values);
+            }
+            values.push(self.parse_value()?);
+            if self.is_punct(',') {
+                self.bump();
+            } else if !self.is_punct(close) {
+                return self.unexpected(if close == ')' {
+                    "',' or ')'"
+                } else {
+                    "',' or ']'"
+                });
+            }
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<BsnValue, BsnError> {
+        let location = self.location();
+        let kind = match self.peek().clone() {
+            Token::String(value) => {
+                self.bump();
+                BsnValueKind::String(value)
+            }
+            Token::Number(value) => {
+                self.bump();
+                BsnValueKind::Number(value)
+            }
+            Token::Punct('-') => {
+                self.bump();
+                match self.bump() {
+                    (Token::Number(value), _) => BsnValueKind::Number(format!("-{value}")),
+                    (Token::Ident(value), _) if value == "inf" => {
+                        BsnValueKind::Number("-inf".into())
+                    }
+                    (token, location) => {
+                        return Err(BsnError::new(
+                            location,
+                            BsnErrorKind::UnexpectedToken {
+                                expected: "a number",
+                                found: token.describe(),
+                            },
+                        ))
+                    }
+                }
+            }
+            Token::Ident(ident) if ident == "true" || ident == "false" => {
+                self.bump();
+                BsnValueKind::Bool(ident == "true")
+            }
+            Token::Ident(ident) if ident == "inf" || ident == "NaN" => {
+                self.bump();
+                BsnValueKind::Number(ident)
+            }
+            Token::Ident(_) => {
+                let path = self.parse_path()?;
+                let fields = self.parse_fields()?;
+                BsnValueKind::Type(BsnType {
+                    path,
+                    location,
+                    fields,
+                })
+            }
+            Token::Punct('(') => {
+                self.bump();
+                BsnValueKind::Tuple(self.parse_values(')')?)
+            }
+            Token::Punct('[') => {
+                self.bump();
+                BsnValueKind::List(self.parse_values(']')?)
+            }
+            Token::Punct('#') => return self.unsupported("entity references"),
+            Token::Punct('{') => return self.unsupported("expressions"),
+            _ => return self.unexpected("a value"),
+        };
+        Ok(BsnValue { location, kind })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_error_location() {
+        let error = parse_bsn("Position {\n    x: 1.0\n    y: 2.0\n}").unwrap_err();
+        assert_eq!(error.location, BsnLocation { line: 3, column: 5 });
+        assert!(matches!(error.kind, BsnErrorKind::UnexpectedToken { .. }));
+
+        let error = parse_bsn("#Root\nName \"oops").unwrap_err();
+        assert_eq!(error.location, BsnLocation { line: 2, column: 6 });
+        assert_eq!(error.kind, BsnErrorKind::Unterminated("string"));
+    }
+
+    #[test]
+    fn parse_entries() {
+        let scene = parse_bsn(
+            r#"
+            // The root entity
+            :"base.bsn"
+            #Root
+            Position { x: 1.0, y: -2 }
+            Team::Red
+            Children [
+                #A Position { x: 3. },
+                (
+                    #B
+                    Tags(["a", "b"], (1, true))
+                ),
+            ]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(scene.entries.len(), 5);
+        assert!(matches!(&scene.entries[0], BsnEntry::Inherit(path) if path == "base.bsn"));
+        assert!(matches!(&scene.entries[1], BsnEntry::Name(name) if name == "Root"));
+        let BsnEntry::Component(position) = &scene.entries[2] else {
+            panic!("expected a component");
+        };
+        assert_eq!(position.path, "Position");
+        assert!(matches!(&position.fields, BsnFields::Named(fields) if fields.len() == 2));
+        let BsnEntry::Component(team) = &scene.entries[3] else {
+            panic!("expected a component");
+        };
+        assert_eq!(team.last_segment(), "Red");
+        let BsnEntry::Related { path, scenes, .. } = &scene.entries[4] else {
+            panic!("expected related scenes");
+        };
+        assert_eq!(path, "Children");
+        assert_eq!(scenes.len(), 2);
+        assert_eq!(scenes[0].entries.len(), 2);
+        assert_eq!(scenes[1].entries.len(), 2);
+    }
+
+    #[test]
+    fn parse_string_escapes() {
+        // Strings are written by the saver with `{:?}`, so all of its escapes must be parsed back
+        let value = "tab\t quote\" escape\u{1b} bell\u{7} é";
+        let tokens = tokenize(&format!("{value:?}")).unwrap();
+        assert_eq!(tokens[0].0, Token::String(value.into()));
+
+        let error = tokenize(r#""\u{110000}""#).unwrap_err();
+        assert_eq!(error.kind, BsnErrorKind::InvalidEscape('u'));
+    }
+
+    #[test]
+    fn parse_unsupported() {
+        let error = parse_bsn("Position { x: {1.0 + 2.0} }").unwrap_err();
+        assert_eq!(error.kind, BsnErrorKind::Unsupported("expressions"));
+        assert_eq!(
+            error.location,
+            BsnLocation {
+                line: 1,
+                column: 15
+            }
+        );
+    }
+}
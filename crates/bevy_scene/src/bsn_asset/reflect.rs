@@ -0,0 +1,261 @@
+use crate::{ErasedComponentTemplate, RelatedResolvedScenes};
+use alloc::{boxed::Box, string::String};
+use bevy_ecs::{
+    bundle::BundleWriter,
+    error::BevyError,
+    ptr::OwningPtr,
+    reflect::{AppTypeRegistry, ReflectComponent, ReflectFromWorld},
+    relationship::RelationshipTarget,
+    template::TemplateContext,
+};
+use bevy_reflect::{
+    prelude::ReflectDefault, tuple_struct::DynamicTupleStruct, ApplyError, FromType,
+    PartialReflect, Reflect, ReflectFromReflect, ReflectRef, TypeInfo, TypeRegistry,
+};
+use core::{alloc::Layout, any::TypeId, ptr::NonNull};
+use thiserror::Error;
+
+/// An [`ErasedComponentTemplate`] that inserts a [`Component`] using reflection. This is how components
+/// described in `.bsn` assets are stored in a [`ResolvedScene`](crate::ResolvedScene).
+///
+/// The `patch` is usually a partial value (ex: a [`DynamicStruct`](bevy_reflect::structs::DynamicStruct) with only some of the fields set). When the
+/// template is applied, the component is constructed with [`FromReflect`](bevy_reflect::FromReflect), falling back
+/// to [`Default`] (or [`FromWorld`]) with the patch applied on top, and written to the entity's bundle along with
+/// the rest of the scene's components.
+///
+/// Unlike [`Template`]-based components, a [`ReflectComponentTemplate`] is keyed by the [`TypeId`]
+/// of the _component_ in the [`ResolvedScene`](crate::ResolvedScene). This makes patches of the same
+/// component in inheriting `.bsn` assets merge together.
+///
+/// [`Component`]: bevy_ecs::component::Component
+/// [`FromWorld`]: bevy_ecs::world::FromWorld
+/// [`Template`]: bevy_ecs::template::Template
+pub struct ReflectComponentTemplate {
+    component_type_id: TypeId,
+    reflect_component: ReflectComponent,
+    patch: Box<dyn PartialReflect>,
+}
+
+impl ReflectComponentTemplate {
+    /// Creates a new template for the component with the given `component_type_id`, which will insert
+    /// the `patch` using `reflect_component`.
+    pub fn new(
+        component_type_id: TypeId,
+        reflect_component: ReflectComponent,
+        patch: Box<dyn PartialReflect>,
+    ) -> Self {
+        Self {
+            component_type_id,
+            reflect_component,
+            patch,
+        }
+    }
+
+    /// The [`TypeId`] of the component inserted by this template.
+    pub fn component_type_id(&self) -> TypeId {
+        self.component_type_id
+    }
+
+    /// The (potentially partial) reflected value of the component.
+    pub fn patch(&self) -> &dyn PartialReflect {
+        &*self.patch
+    }
+
+    /// Merges `patch` on top of the current patch. Struct fields and tuple struct fields are merged
+    /// recursively, every other kind of value replaces the current value.
+    pub fn merge_patch(&mut self, patch: &dyn PartialReflect) {
+        self.patch = merge_patches(&*self.patch, patch);
+    }
+}
+
+impl ReflectComponentTemplate {
+    /// Builds the component value, using [`ReflectFromReflect`], then [`ReflectDefault`] and then
+    /// [`ReflectFromWorld`] (the same fallbacks as [`ReflectComponent::insert`]).
+    fn build_component(
+        &self,
+        context: &mut TemplateContext,
+        registry: &TypeRegistry,
+    ) -> Result<Box<dyn Reflect>, ReflectComponentTemplateError> {
+        let type_id = self.component_type_id;
+        let type_path = || {
+            registry
+                .get_type_info(type_id)
+                .map_or("<unregistered>", TypeInfo::type_path)
+                .into()
+        };
+        let component = if let Some(component) = registry
+            .get_type_data::<ReflectFromReflect>(type_id)
+            .and_then(|from_reflect| from_reflect.from_reflect(&*self.patch))
+        {
+            component
+        } else {
+            let mut component = if let Some(default) =
+                registry.get_type_data::<ReflectDefault>(type_id)
+            {
+                default.default()
+            } else if let Some(from_world) = registry.get_type_data::<ReflectFromWorld>(type_id) {
+                context
+                    .entity
+                    .world_scope(|world| from_world.from_world(world))
+            } else {
+                return Err(ReflectComponentTemplateError::NotConstructible(type_path()));
+            };
+            component
+                .try_apply(&*self.patch)
+                .map_err(|error| ReflectComponentTemplateError::Apply(type_path(), error))?;
+            component
+        };
+        if component.as_any().type_id() != type_id {
+            return Err(ReflectComponentTemplateError::NotConstructible(type_path()));
+        }
+        Ok(component)
+    }
+}
+
+/// An error that occurs when applying a [`ReflectComponentTemplate`].
+#[derive(Error, Debug)]
+pub enum ReflectComponentTemplateError {
+    /// The world does not have an [`AppTypeRegistry`].
+    #[error("The world does not have an AppTypeRegistry")]
+    MissingTypeRegistry,
+    /// The component could not be constructed from the patch.
+    #[error("Couldn't create an instance of {0} using the reflected FromReflect, Default or FromWorld traits")]
+    NotConstructible(String),
+    /// The patch could not be applied to the component.
+    #[error("Couldn't apply the patch to {0}: {1}")]
+    Apply(String, ApplyError),
+}
+
+fn merge_patches(
+    current: &dyn PartialReflect,
+    patch: &dyn PartialReflect,
+) -> Box<dyn PartialReflect> {
+    match (current.reflect_ref(), patch.reflect_ref()) {
+        (ReflectRef::Struct(current_struct), ReflectRef::Struct(patch_struct)) => {
+            let mut merged = current_struct.to_dynamic_struct();
+            for (name, value) in patch_struct.iter_fields() {
+                let value = match current_struct.field(name) {
+                    Some(current_value) => merge_patches(current_value, value),
+                    None => value.to_dynamic(),
+                };
+                merged.insert_boxed(name, value);
+            }
+            Box::new(merged)
+        }
+        (ReflectRef::TupleStruct(current_tuple), ReflectRef::TupleStruct(patch_tuple)) => {
+            let mut merged = DynamicTupleStruct::default();
+            merged.set_represented_type(current.get_represented_type_info());
+            for index in 0..current_tuple.field_len().max(patch_tuple.field_len()) {
+                let value = match (current_tuple.field(index), patch_tuple.field(index)) {
+                    (Some(current_value), Some(value)) => merge_patches(current_value, value),
+                    (Some(value), None) | (None, Some(value)) => value.to_dynamic(),
+                    (None, None) => unreachable!(),
+                };
+                merged.insert_boxed(value);
+            }
+            Box::new(merged)
+        }
+        _ => patch.to_dynamic(),
+    }
+}
+
+impl ErasedComponentTemplate for ReflectComponentTemplate {
+    unsafe fn apply(
+        &self,
+        context: &mut TemplateContext,
+        bundle_writer: &mut BundleWriter,
+    ) -> Result<(), BevyError> {
+        let Some(registry) = context.entity.get_resource::<AppTypeRegistry>().cloned() else {
+            return Err(ReflectComponentTemplateError::MissingTypeRegistry.into());
+        };
+        let component = {
+            let registry = registry.read();
+            self.build_component(context, &registry)?
+        };
+        let component_id = context
+            .entity
+            .world_scope(|world| self.reflect_component.register_component(world));
+        let layout = Layout::for_value(&*component);
+        let component = Box::into_raw(component).cast::<u8>();
+        // SAFETY:
+        // - The caller verifies that `bundle_writer` is always used with the same World, which is
+        //   the World `component_id` was registered in.
+        // - `build_component` checked that `component` is a value of the component type, which
+        //   matches `component_id` and `layout`.
+        // - `push_component_by_id` moves the value out of the box, so the box is deallocated
+        //   without dropping its contents.
+        unsafe {
+            bundle_writer.push_component_by_id(
+                component_id,
+                OwningPtr::new(NonNull::new_unchecked(component)),
+                layout,
+            );
+            if layout.size() != 0 {
+                alloc::alloc::dealloc(component, layout);
+            }
+        }
+        Ok(())
+    }
+
+    fn clone_template(&self) -> Box<dyn ErasedComponentTemplate> {
+        Box::new(Self {
+            component_type_id: self.component_type_id,
+            reflect_component: self.reflect_component.clone(),
+            patch: self.patch.to_dynamic(),
+        })
+    }
+
+    fn template_type_id(&self) -> TypeId {
+        self.component_type_id
+    }
+}
+
+/// Type data that allows a [`RelationshipTarget`] to be used for related scene lists in `.bsn` assets,
+/// such as `Children [ ... ]`.
+///
+/// [`BsnAssetPlugin`](crate::BsnAssetPlugin) registers this for [`Children`](bevy_ecs::hierarchy::Children).
+/// For other relationships, register it like so:
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::Reflect;
+/// # use bevy_scene::ReflectSceneRelationship;
+/// #[derive(Component, Reflect)]
+/// #[relationship(relationship_target = Likers)]
+/// struct Likes(Entity);
+///
+/// #[derive(Component, Reflect)]
+/// #[relationship_target(relationship = Likes)]
+/// struct Likers(Vec<Entity>);
+///
+/// App::new()
+///     .register_type::<Likers>()
+///     .register_type_data::<Likers, ReflectSceneRelationship>();
+/// ```
+#[derive(Clone)]
+pub struct ReflectSceneRelationship {
+    relationship_type_id: TypeId,
+    new_related_scenes: fn() -> RelatedResolvedScenes,
+}
+
+impl ReflectSceneRelationship {
+    /// The [`TypeId`] of the [`Relationship`](bevy_ecs::relationship::Relationship) component inserted on related entities.
+    pub fn relationship_type_id(&self) -> TypeId {
+        self.relationship_type_id
+    }
+
+    /// Creates a new, empty [`RelatedResolvedScenes`] for this relationship.
+    pub fn new_related_scenes(&self) -> RelatedResolvedScenes {
+        (self.new_related_scenes)()
+    }
+}
+
+impl<T: RelationshipTarget> FromType<T> for ReflectSceneRelationship {
+    fn from_type() -> Self {
+        Self {
+            relationship_type_id: TypeId::of::<T::Relationship>(),
+            new_related_scenes: RelatedResolvedScenes::new::<T::Relationship>,
+        }
+    }
+}
@@ -0,0 +1,359 @@
+use crate::{
+    BsnLoader, ReflectComponentTemplate, ReflectSceneRelationship, ResolvedScene, ScenePatch,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use bevy_asset::{
+    io::Writer,
+    saver::{AssetSaver, SavedAsset},
+    AssetPath, AsyncWriteExt, ReflectHandle,
+};
+use bevy_ecs::{
+    name::Name,
+    reflect::AppTypeRegistry,
+    world::{FromWorld, World},
+};
+use bevy_reflect::{
+    enums::VariantType, structs::Struct, PartialReflect, ReflectRef, TypeInfo, TypePath,
+    TypeRegistry, TypeRegistryArc,
+};
+use core::{any::Any, fmt::Write};
+use thiserror::Error;
+
+/// An [`AssetSaver`] that writes the [`ResolvedScene`] of a [`ScenePatch`] as a `.bsn` file, which can be loaded
+/// again using [`BsnLoader`].
+///
+/// Only scenes made of reflected components (such as scenes loaded by [`BsnLoader`]) can be saved. See [`write_bsn`].
+#[derive(TypePath)]
+pub struct BsnSaver {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BsnSaver {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.resource::<AppTypeRegistry>();
+        BsnSaver {
+            type_registry: type_registry.0.clone(),
+        }
+    }
+}
+
+/// Possible errors that can be produced by [`BsnSaver`].
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum BsnSaverError {
+    /// An [IO Error](std::io::Error)
+    #[error("Error while trying to write the scene file: {0}")]
+    Io(#[from] std::io::Error),
+    /// The [`ScenePatch`] has not been resolved yet, so there is no [`ResolvedScene`] to save.
+    #[error("The scene has not been resolved yet and cannot be saved")]
+    UnresolvedScene,
+    /// The [`ResolvedScene`] cannot be expressed as BSN.
+    #[error(transparent)]
+    Write(#[from] BsnWriteError),
+}
+
+impl AssetSaver for BsnSaver {
+    type Asset = ScenePatch;
+    type Settings = ();
+    type OutputLoader = BsnLoader;
+    type Error = BsnSaverError;
+
+    async fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, '_, Self::Asset>,
+        _settings: &Self::Settings,
+        _asset_path: AssetPath<'_>,
+    ) -> Result<(), Self::Error> {
+        let resolved = asset
+            .resolved
+            .as_deref()
+            .ok_or(BsnSaverError::UnresolvedScene)?;
+        let bsn = write_bsn(&resolved.scene, &self.type_registry.read())?;
+        writer.write_all(bsn.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+/// An error that occurs when writing a [`ResolvedScene`] as BSN.
+#[derive(Error, Debug)]
+pub enum BsnWriteError {
+    /// The scene contains a component [`Template`](bevy_ecs::template::Template) that is not backed by reflection.
+    /// Only scenes made of [`ReflectComponentTemplate`]s can be written.
+    #[error("The scene contains a component template that is not backed by reflection")]
+    UnsupportedTemplate,
+    /// The scene contains bundle templates (ex: observers), which cannot be expressed in BSN.
+    #[error("The scene contains bundle templates (ex: observers), which cannot be written as BSN")]
+    UnsupportedBundleTemplate,
+    /// The scene contains a relationship that does not have [`ReflectSceneRelationship`] registered for its target.
+    #[error(
+        "The scene contains a relationship ({0}) without a registered ReflectSceneRelationship"
+    )]
+    UnregisteredRelationship(&'static str),
+    /// The scene inherits from a scene that does not have an asset path.
+    #[error("The scene inherits from a scene without an asset path")]
+    InheritedSceneWithoutPath,
+    /// The scene contains an entity name that is not a valid identifier.
+    #[error("The entity name {0:?} is not a valid BSN identifier")]
+    InvalidName(String),
+    /// The scene contains a value that cannot be expressed in BSN.
+    #[error("Values of type {0} cannot be written as BSN")]
+    UnsupportedValue(String),
+}
+
+/// Writes `scene` as BSN text. `type_registry` is used to look up the type paths of relationship targets and
+/// the asset paths of [`Handle`](bevy_asset::Handle)s.
+///
+/// Components are written as the patches they were defined with (ex: only the fields that were set in a `.bsn` file).
+/// This does not write inherited scenes, only the path to inherit from.
+pub fn write_bsn(
+    scene: &ResolvedScene,
+    type_registry: &TypeRegistry,
+) -> Result<String, BsnWriteError> {
+    let mut writer = BsnWriter {
+        output: String::new(),
+        registry: type_registry,
+    };
+    writer.scene(scene, 0)?;
+    Ok(writer.output)
+}
+
+struct BsnWriter<'a> {
+    output: String,
+    registry: &'a TypeRegistry,
+}
+
+impl BsnWriter<'_> {
+    fn indent(&mut self, depth: usize) {
+        for _ in 0..depth {
+            self.output.push_str("    ");
+        }
+    }
+
+    fn scene(&mut self, scene: &ResolvedScene, depth: usize) -> Result<(), BsnWriteError> {
+        if scene.has_bundle_templates() {
+            return Err(BsnWriteError::UnsupportedBundleTemplate);
+        }
+        if let Some(inherited) = scene.inherited_scene() {
+            let path = inherited
+                .path()
+                .ok_or(BsnWriteError::InheritedSceneWithoutPath)?;
+            self.indent(depth);
+            writeln!(self.output, ":{:?}", path.to_string()).unwrap();
+        }
+        for template in scene.component_templates() {
+            let template: &dyn Any = template;
+            self.indent(depth);
+            if let Some(name) = template.downcast_ref::<Name>() {
+                if !is_identifier(name.as_str()) {
+                    return Err(BsnWriteError::InvalidName(name.as_str().into()));
+                }
+                writeln!(self.output, "#{}", name.as_str()).unwrap();
+            } else if let Some(template) = template.downcast_ref::<ReflectComponentTemplate>() {
+                self.component(template.patch())?;
+                self.output.push('\n');
+            } else {
+                return Err(BsnWriteError::UnsupportedTemplate);
+            }
+        }
+
+        let mut related = scene.related_resolved_scenes().collect::<Vec<_>>();
+        // Related scenes are stored in a hash map, so sort them to keep the output stable.
+        related.sort_by_key(|(_, related)| related.relationship_name);
+        for (relationship_type_id, related) in related {
+            let relationship_target = self
+                .registry
+                .iter_with_data::<ReflectSceneRelationship>()
+                .find(|(_, data)| data.relationship_type_id() == relationship_type_id)
+                .map(|(registration, _)| self.registered_type_path(registration.type_info()))
+                .ok_or(BsnWriteError::UnregisteredRelationship(
+                    related.relationship_name,
+                ))?;
+            self.indent(depth);
+            writeln!(self.output, "{relationship_target} [").unwrap();
+            for related_scene in &related.scenes {
+                self.indent(depth + 1);
+                self.output.push_str("(\n");
+                self.scene(related_scene, depth + 2)?;
+                self.indent(depth + 1);
+                self.output.push_str("),\n");
+            }
+            self.indent(depth);
+            self.output.push_str("]\n");
+        }
+        Ok(())
+    }
+
+    /// Returns the short type path of the given type if it unambiguously refers to that type in the registry.
+    /// Otherwise returns the full type path.
+    fn registered_type_path(&self, type_info: &TypeInfo) -> &'static str {
+        let table = type_info.type_path_table();
+        match self.registry.get_with_short_type_path(table.short_path()) {
+            Some(registration) if registration.type_id() == type_info.type_id() => {
+                table.short_path()
+            }
+            _ => table.path(),
+        }
+    }
+
+    /// Writes a top-level component. Enum components are written with their full `Type::Variant` path.
+    fn component(&mut self, value: &dyn PartialReflect) -> Result<(), BsnWriteError> {
+        let type_info = value
+            .get_represented_type_info()
+            .ok_or_else(|| BsnWriteError::UnsupportedValue(value.reflect_type_path().into()))?;
+        let type_path = self.registered_type_path(type_info);
+        match value.reflect_ref() {
+            ReflectRef::Enum(_) => {
+                self.output.push_str(type_path);
+                self.output.push_str("::");
+                self.value(value)
+            }
+            ReflectRef::Struct(value_struct) => {
+                self.output.push_str(type_path);
+                self.fields(value_struct)
+            }
+            ReflectRef::TupleStruct(tuple_struct) => {
+                self.output.push_str(type_path);
+                self.values(tuple_struct.iter_fields(), '(', ')')
+            }
+            _ => Err(BsnWriteError::UnsupportedValue(
+                value.reflect_type_path().into(),
+            )),
+        }
+    }
+
+    fn fields(&mut self, value_struct: &dyn Struct) -> Result<(), BsnWriteError> {
+        if value_struct.field_len() == 0 {
+            return Ok(());
+        }
+        self.output.push_str(" {");
+        for (index, (name, field)) in value_struct.iter_fields().enumerate() {
+            if index > 0 {
+                self.output.push(',');
+            }
+            write!(self.output, " {name}: ").unwrap();
+            self.value(field)?;
+        }
+        self.output.push_str(" }");
+        Ok(())
+    }
+
+    fn value(&mut self, value: &dyn PartialReflect) -> Result<(), BsnWriteError> {
+        match value.reflect_ref() {
+            ReflectRef::Struct(value_struct) => {
+                self.output.push_str(&short_type_path(value)?);
+                self.fields(value_struct)?;
+            }
+            ReflectRef::TupleStruct(tuple_struct) => {
+                self.output.push_str(&short_type_path(value)?);
+                self.values(tuple_struct.iter_fields(), '(', ')')?;
+            }
+            ReflectRef::Tuple(tuple) => self.values(tuple.iter_fields(), '(', ')')?,
+            ReflectRef::List(list) => self.values(list.iter(), '[', ']')?,
+            ReflectRef::Array(array) => self.values(array.iter(), '[', ']')?,
+            ReflectRef::Enum(value_enum) => {
+                self.output.push_str(value_enum.variant_name());
+                match value_enum.variant_type() {
+                    VariantType::Unit => {}
+                    VariantType::Tuple => {
+                        self.values(
+                            value_enum.iter_fields().map(|field| field.value()),
+                            '(',
+                            ')',
+                        )?;
+                    }
+                    VariantType::Struct => {
+                        self.output.push_str(" {");
+                        for (index, field) in value_enum.iter_fields().enumerate() {
+                            if index > 0 {
+                                self.output.push(',');
+                            }
+                            write!(self.output, " {}: ", field.name().unwrap()).unwrap();
+                            self.value(field.value())?;
+                        }
+                        self.output.push_str(" }");
+                    }
+                }
+            }
+            ReflectRef::Opaque(opaque) => self.opaque(opaque)?,
+            _ => {
+                return Err(BsnWriteError::UnsupportedValue(
+                    value.reflect_type_path().into(),
+                ))
+            }
+        }
+        Ok(())
+    }
+
+    fn values<'v>(
+        &mut self,
+        values: impl Iterator<Item = &'v dyn PartialReflect>,
+        open: char,
+        close: char,
+    ) -> Result<(), BsnWriteError> {
+        self.output.push(open);
+        for (index, value) in values.enumerate() {
+            if index > 0 {
+                self.output.push_str(", ");
+            }
+            self.value(value)?;
+        }
+        self.output.push(close);
+        Ok(())
+    }
+
+    fn opaque(&mut self, value: &dyn PartialReflect) -> Result<(), BsnWriteError> {
+        macro_rules! write_number {
+            ($($ty:ty),*) => {
+                $(
+                    if let Some(number) = value.try_downcast_ref::<$ty>() {
+                        write!(self.output, "{number:?}").unwrap();
+                        return Ok(());
+                    }
+                )*
+            };
+        }
+        write_number!(f32, f64, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+        if let Some(boolean) = value.try_downcast_ref::<bool>() {
+            write!(self.output, "{boolean}").unwrap();
+        } else if let Some(string) = value.try_downcast_ref::<String>() {
+            write!(self.output, "{string:?}").unwrap();
+        } else if let Some(string) = value.try_downcast_ref::<alloc::borrow::Cow<'static, str>>() {
+            write!(self.output, "{string:?}").unwrap();
+        } else if let Some(handle) = value.try_as_reflect().and_then(|value| {
+            self.registry
+                .get_type_data::<ReflectHandle>(value.type_id())
+                .and_then(|reflect_handle| reflect_handle.downcast_handle_untyped(value.as_any()))
+        }) {
+            let path = handle.path().ok_or_else(|| {
+                BsnWriteError::UnsupportedValue(format!(
+                    "{} (the handle has no asset path)",
+                    value.reflect_type_path()
+                ))
+            })?;
+            write!(self.output, "{:?}", path.to_string()).unwrap();
+        } else {
+            return Err(BsnWriteError::UnsupportedValue(
+                value.reflect_type_path().into(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn short_type_path(value: &dyn PartialReflect) -> Result<String, BsnWriteError> {
+    value
+        .get_represented_type_info()
+        .map(|type_info| type_info.type_path_table().short_path().into())
+        .ok_or_else(|| BsnWriteError::UnsupportedValue(value.reflect_type_path().into()))
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
@@ -476,21 +476,38 @@
 //!
 //! ## .bsn Asset Format
 //!
-//! In future releases, Bevy intends to offer a `.bsn` asset format.
-//! This would allow you to define your scenes on disk,
-//! creating/modifying them in various authoring tools and using asset hot-reloading.
-//!
-//! This format is intended to have broad syntactic compatibility with the `bsn!` macro,
-//! making it easy to port your content between both the macro and the asset form.
+//! Scenes can also be defined on disk as `.bsn` files, which are loaded as [`ScenePatch`] assets by the
+//! [`BsnLoader`] (added by the [`BsnAssetPlugin`]). This allows you to create and modify scenes in various authoring tools and use asset hot-reloading.
+//!
+//! The format is intended to have broad syntactic compatibility with the `bsn!` macro,
+//! making it easy to port your content between both the macro and the asset form:
+//!
+//! ```text
+//! :"base_enemy.bsn"
+//! #Goblin
+//! Health { current: 50, max: 50 }
+//! Team::Red
+//! Children [
+//!     #Weapon Sword { damage: 10.0 },
+//!     (
+//!         #Hat
+//!         Mesh3d("models/hat.glb#Mesh0/Primitive0")
+//!     ),
+//! ]
+//! ```
 //!
-//! Bevy does not currently have support for `.bsn` files:
-//! for now, you should use existing non-Bevy asset formats like glTF,
-//! search for ecosystem implementations or stick to `bsn!` macro calls.
+//! Unlike `bsn!` macro calls, `.bsn` asset files do not support expressions, closures, scene functions
+//! or other dynamic features. Types are resolved by (full or short) type path through the
+//! [`AppTypeRegistry`]: components must be reflected with `#[reflect(Component)]`
+//! (and usually `#[reflect(Default)]`, so that fields can be patched individually).
+//! Relationship targets (such as `Children`) must register [`ReflectSceneRelationship`].
+//! Strings assigned to [`Handle`] fields are loaded as asset paths.
 //!
-//! When planning, be aware that `.bsn` asset files, unlike `bsn!` macro calls,
-//! will not support expressions or other dynamic features directly.
+//! Resolved scenes made of reflected components can be written back out as `.bsn` using [`write_bsn`] or the [`BsnSaver`].
 //!
 //! [`Template`]: bevy_ecs::template::Template
+//! [`AppTypeRegistry`]: bevy_ecs::reflect::AppTypeRegistry
+//! [`Handle`]: bevy_asset::Handle
 //! [`FromTemplate`]: bevy_ecs::template::FromTemplate
 //! [`Asset`]: bevy_asset::Asset
 //! [`Entity`]: bevy_ecs::entity::Entity
@@ -518,6 +535,7 @@ pub mod macro_utils;
 
 extern crate alloc;
 
+mod bsn_asset;
 mod resolved_scene;
 mod scene;
 mod scene_list;
@@ -526,6 +544,7 @@ mod spawn;
 mod spawn_system;
 
 pub use bevy_scene_macros::*;
+pub use bsn_asset::*;
 pub use resolved_scene::*;
 pub use scene::*;
 pub use scene_list::*;
@@ -560,7 +579,7 @@ impl Plugin for ScenePlugin {
 
 #[cfg(test)]
 mod tests {
-    use crate::{self as bevy_scene, ScenePlugin};
+    use crate::{self as bevy_scene, BsnAssetPlugin, ScenePlugin};
     use crate::{prelude::*, ScenePatch};
     use alloc::sync::Arc;
     use bevy_app::{App, TaskPoolPlugin};
@@ -569,9 +588,10 @@ mod tests {
     use bevy_asset::{Asset, AssetApp, AssetLoader, AssetPlugin, AssetServer, Assets, Handle};
     use bevy_ecs::lifecycle::HookContext;
     use bevy_ecs::prelude::*;
+    use bevy_ecs::reflect::AppTypeRegistry;
     use bevy_ecs::relationship::Relationship;
     use bevy_ecs::world::DeferredWorld;
-    use bevy_reflect::TypePath;
+    use bevy_reflect::{prelude::ReflectDefault, Reflect, TypePath};
     use std::path::Path;
     use std::sync::Mutex;
 
@@ -730,6 +750,232 @@ mod tests {
         assert_eq!(name.as_str(), "Y");
     }
 
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component, Default)]
+    struct ReflectedPosition {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component, Default)]
+    enum ReflectedTeam {
+        #[default]
+        Blue,
+        Red,
+    }
+
+    #[derive(Component, Reflect, Default, PartialEq, Debug)]
+    #[reflect(Component, Default)]
+    struct ReflectedTags(Vec<String>, Option<u8>);
+
+    fn bsn_asset_app(dir: &Dir) -> App {
+        let mut app = App::new();
+        let dir = dir.clone();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSourceBuilder::new(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        );
+        app.add_plugins((
+            TaskPoolPlugin::default(),
+            AssetPlugin::default(),
+            ScenePlugin,
+            BsnAssetPlugin,
+        ))
+        .register_type::<ReflectedPosition>()
+        .register_type::<ReflectedTeam>()
+        .register_type::<ReflectedTags>();
+        app
+    }
+
+    const BASE_BSN: &str = r#"
+        ReflectedPosition { y: 2.0 }
+        ReflectedTeam::Red
+        Children [ #X ]
+    "#;
+
+    const SCENE_BSN: &str = r#"
+        :"base.bsn"
+        // Patches the inherited position
+        #Root
+        ReflectedPosition { x: 1.0 }
+        Children [
+            #Y ReflectedTags(["a", "b"], Some(3)),
+        ]
+    "#;
+
+    #[test]
+    fn bsn_asset_loading() {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("base.bsn"), BASE_BSN);
+        dir.insert_asset_text(Path::new("scene.bsn"), SCENE_BSN);
+        let mut app = bsn_asset_app(&dir);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<ScenePatch> = asset_server.load("scene.bsn");
+        run_app_until(&mut app, || {
+            asset_server.is_loaded_with_dependencies(&handle)
+        });
+
+        let id = app.world_mut().spawn(ScenePatchInstance(handle)).id();
+        app.update();
+
+        let world = app.world();
+        let root = world.entity(id);
+        assert_eq!(root.get::<Name>().unwrap().as_str(), "Root");
+        assert_eq!(
+            *root.get::<ReflectedPosition>().unwrap(),
+            ReflectedPosition {
+                x: 1.,
+                y: 2.,
+                z: 0.
+            }
+        );
+        assert_eq!(*root.get::<ReflectedTeam>().unwrap(), ReflectedTeam::Red);
+
+        let children = root.get::<Children>().unwrap();
+        assert_eq!(children.len(), 2);
+        let x = world.entity(children[0]);
+        assert_eq!(x.get::<Name>().unwrap().as_str(), "X");
+        let y = world.entity(children[1]);
+        assert_eq!(y.get::<Name>().unwrap().as_str(), "Y");
+        assert_eq!(
+            *y.get::<ReflectedTags>().unwrap(),
+            ReflectedTags(vec!["a".into(), "b".into()], Some(3))
+        );
+    }
+
+    #[test]
+    fn bsn_asset_writing() {
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("base.bsn"), BASE_BSN);
+        dir.insert_asset_text(Path::new("scene.bsn"), SCENE_BSN);
+        let mut app = bsn_asset_app(&dir);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let handle: Handle<ScenePatch> = asset_server.load("scene.bsn");
+        run_app_until(&mut app, || {
+            asset_server.is_loaded_with_dependencies(&handle)
+        });
+
+        let patch = app
+            .world()
+            .resource::<Assets<ScenePatch>>()
+            .get(&handle)
+            .unwrap();
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let bsn = crate::write_bsn(&patch.resolved.as_ref().unwrap().scene, &registry).unwrap();
+        assert_eq!(
+            bsn,
+            r#":"base.bsn"
+#Root
+ReflectedPosition { y: 2.0, x: 1.0 }
+Children [
+    (
+        #Y
+        ReflectedTags(["a", "b"], Some(3))
+    ),
+]
+"#
+        );
+    }
+
+    #[test]
+    fn bsn_asset_errors() {
+        let dir = Dir::default();
+        let app = bsn_asset_app(&dir);
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let mut asset_server = app.world().resource::<AssetServer>().clone();
+
+        let Err(error) = crate::load_bsn(
+            "#Root\nReflectedPosition {\n    w: 1.0,\n}",
+            &registry,
+            &mut asset_server,
+        ) else {
+            panic!("expected an error");
+        };
+        assert_eq!(error.location, crate::BsnLocation { line: 3, column: 5 });
+        assert!(matches!(
+            error.kind,
+            crate::BsnErrorKind::UnknownField { .. }
+        ));
+
+        let Err(error) = crate::load_bsn("ReflectedTeam::Green", &registry, &mut asset_server)
+        else {
+            panic!("expected an error");
+        };
+        assert!(matches!(
+            error.kind,
+            crate::BsnErrorKind::UnknownVariant { .. }
+        ));
+
+        let Err(error) = crate::load_bsn(
+            "ReflectedPosition { x: \"one\" }",
+            &registry,
+            &mut asset_server,
+        ) else {
+            panic!("expected an error");
+        };
+        assert_eq!(
+            error.location,
+            crate::BsnLocation {
+                line: 1,
+                column: 24
+            }
+        );
+        assert!(matches!(
+            error.kind,
+            crate::BsnErrorKind::MismatchedValue { .. }
+        ));
+    }
+
+    #[test]
+    fn bsn_loader_with_other_scene_loaders() {
+        #[derive(TypePath)]
+        struct FakeSceneLoader;
+
+        impl AssetLoader for FakeSceneLoader {
+            type Asset = ScenePatch;
+            type Error = std::io::Error;
+            type Settings = ();
+
+            async fn load(
+                &self,
+                _reader: &mut dyn bevy_asset::io::Reader,
+                _settings: &Self::Settings,
+                load_context: &mut bevy_asset::LoadContext<'_>,
+            ) -> Result<Self::Asset, Self::Error> {
+                Ok(ScenePatch::load_with(load_context, bsn! { #Fake }))
+            }
+
+            fn extensions(&self) -> &[&str] {
+                &["fake_bsn"]
+            }
+        }
+
+        let dir = Dir::default();
+        dir.insert_asset_text(Path::new("fake.fake_bsn"), "");
+        dir.insert_asset_text(Path::new("real.bsn"), "#Real");
+        let mut app = bsn_asset_app(&dir);
+        app.register_asset_loader(FakeSceneLoader);
+
+        let asset_server = app.world().resource::<AssetServer>().clone();
+        let fake: Handle<ScenePatch> = asset_server.load("fake.fake_bsn");
+        let real: Handle<ScenePatch> = asset_server.load("real.bsn");
+        run_app_until(&mut app, || {
+            asset_server.is_loaded(&fake) && asset_server.is_loaded(&real)
+        });
+
+        let fake = app.world_mut().spawn(ScenePatchInstance(fake)).id();
+        let real = app.world_mut().spawn(ScenePatchInstance(real)).id();
+        app.update();
+
+        let world = app.world();
+        assert_eq!(world.get::<Name>(fake).unwrap().as_str(), "Fake");
+        assert_eq!(world.get::<Name>(real).unwrap().as_str(), "Real");
+    }
+
     #[test]
     fn inline_scene_patching() {
         let mut app = test_app();
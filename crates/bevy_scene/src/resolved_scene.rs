@@ -357,7 +357,7 @@ impl ResolvedScene {
     ) -> Result<(), ApplySceneError> {
         self.set_current_entity_in_scope(context);
         for template in &self.component_templates {
-            if skip_templates.should_skip((**template).type_id()) {
+            if skip_templates.should_skip(template.template_type_id()) {
                 continue;
             }
             // SAFETY: bundle_writer is used with the same World across all template.apply calls,
@@ -480,6 +480,17 @@ impl ResolvedScene {
         context: &mut ResolveContext,
         type_id: TypeId,
         default: fn() -> Box<dyn ErasedComponentTemplate>,
+    ) -> &'a mut dyn ErasedComponentTemplate {
+        self.get_or_insert_erased_template_with(context, type_id, default)
+    }
+
+    /// Same as [`ResolvedScene::get_or_insert_erased_template`], but accepts any [`FnOnce`] as the `default` function.
+    /// This is useful when the default template is built from runtime data, such as reflected type information.
+    pub fn get_or_insert_erased_template_with<'a>(
+        &'a mut self,
+        context: &mut ResolveContext,
+        type_id: TypeId,
+        default: impl FnOnce() -> Box<dyn ErasedComponentTemplate>,
     ) -> &'a mut dyn ErasedComponentTemplate {
         let mut is_inherited = false;
         let index = self.template_indices.entry(type_id).or_insert_with(|| {
@@ -559,6 +570,42 @@ impl ResolvedScene {
             .or_insert_with(RelatedResolvedScenes::new::<R>)
     }
 
+    /// Same as [`ResolvedScene::get_or_insert_related_resolved_scenes`], but for a relationship identified by the
+    /// [`TypeId`] of its [`Relationship`] component. If the entry does not exist, it will be created using `new`.
+    /// _For correctness, `new` should create the [`RelatedResolvedScenes`] for the relationship with the given `type_id`_.
+    pub fn get_or_insert_related_resolved_scenes_by_id(
+        &mut self,
+        type_id: TypeId,
+        new: impl FnOnce() -> RelatedResolvedScenes,
+    ) -> &mut RelatedResolvedScenes {
+        self.related.entry(type_id).or_insert_with(new)
+    }
+
+    /// Iterates the component [`Template`]s in this [`ResolvedScene`], in the order they will be applied.
+    /// This ignores scene inheritance.
+    pub fn component_templates(&self) -> impl Iterator<Item = &dyn ErasedComponentTemplate> {
+        self.component_templates.iter().map(|template| &**template)
+    }
+
+    /// Returns true if this [`ResolvedScene`] has bundle [`Template`]s (ex: observers added with [`on`](crate::on)).
+    pub fn has_bundle_templates(&self) -> bool {
+        !self.bundle_templates.is_empty()
+    }
+
+    /// Iterates the [`RelatedResolvedScenes`] in this [`ResolvedScene`], along with the [`TypeId`] of their [`Relationship`].
+    pub fn related_resolved_scenes(
+        &self,
+    ) -> impl Iterator<Item = (TypeId, &RelatedResolvedScenes)> {
+        self.related
+            .iter()
+            .map(|(type_id, related)| (*type_id, related))
+    }
+
+    /// Returns the handle of the inherited [`ScenePatch`], if this [`ResolvedScene`] inherits from one.
+    pub fn inherited_scene(&self) -> Option<&Handle<ScenePatch>> {
+        self.inherited.as_ref().map(|inherited| &inherited.handle)
+    }
+
     /// Configures this [`ResolvedScene`] to inherit from the given [`ScenePatch`].
     ///
     /// If this [`ResolvedScene`] already inherits from a scene, it will return [`InheritSceneError::MultipleInheritance`].
@@ -724,6 +771,14 @@ pub trait ErasedComponentTemplate: Any + Send + Sync {
 
     /// Clones this template. See [`Clone`].
     fn clone_template(&self) -> Box<dyn ErasedComponentTemplate>;
+
+    /// The [`TypeId`] used to identify this template in a [`ResolvedScene`]. Templates with the same id
+    /// are considered the "same" template when patching and inheriting scenes.
+    ///
+    /// Defaults to the [`TypeId`] of the template type.
+    fn template_type_id(&self) -> TypeId {
+        Any::type_id(self)
+    }
 }
 
 impl<T: Template<Output: Component> + Send + Sync + 'static> ErasedComponentTemplate for T {
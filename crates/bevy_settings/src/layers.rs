@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+
+use bevy_ecs::resource::Resource;
+use bevy_log::warn;
+
+/// A source of settings values. When several layers provide a value for the same field, the
+/// layer that comes last in this list wins.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SettingsLayer {
+    /// The value of the settings resource before preferences were loaded, which is usually its
+    /// [`Default`] value.
+    #[default]
+    Default,
+    /// A read-only settings file installed alongside the app, see
+    /// [`PreferencesPlugin::with_system_dir`](crate::PreferencesPlugin::with_system_dir).
+    System,
+    /// The user preferences file, which is the only layer that gets saved.
+    User,
+    /// An environment variable, see
+    /// [`PreferencesPlugin::with_env_prefix`](crate::PreferencesPlugin::with_env_prefix).
+    Environment,
+    /// A `--set group.field=value` command-line argument, see
+    /// [`PreferencesPlugin::with_args`](crate::PreferencesPlugin::with_args).
+    CommandLine,
+}
+
+/// Records which [`SettingsLayer`] each settings value was loaded from.
+///
+/// Values are identified by their dotted path within the settings file, such as
+/// `"audio_settings.volume"`. Only values which don't come from [`SettingsLayer::Default`] are
+/// recorded. This reflects the state of the settings when they were last loaded: changing a
+/// settings resource at runtime doesn't update it.
+#[derive(Resource, Debug, Default, Clone)]
+pub struct SettingsSources {
+    sources: HashMap<String, SettingsLayer>,
+}
+
+impl SettingsSources {
+    /// Returns the layer that the value at `path` was loaded from.
+    ///
+    /// If `path` refers to a table, such as a whole settings group, this returns the layer that
+    /// replaced the table as a whole, or [`SettingsLayer::Default`] if its values were merged
+    /// from several layers.
+    pub fn source(&self, path: &str) -> SettingsLayer {
+        let mut path = path;
+        loop {
+            if let Some(layer) = self.sources.get(path) {
+                return *layer;
+            }
+            match path.rsplit_once('.') {
+                Some((parent, _)) => path = parent,
+                None => return SettingsLayer::Default,
+            }
+        }
+    }
+
+    /// Iterates over the paths of all values which don't come from [`SettingsLayer::Default`],
+    /// along with the layer they were loaded from.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, SettingsLayer)> {
+        self.sources
+            .iter()
+            .map(|(path, layer)| (path.as_str(), *layer))
+    }
+
+    /// Forgets where the values of a settings group came from, except for the layers above the
+    /// user preferences file, so that the group can be merged again after a reload.
+    pub(crate) fn reset_group(&mut self, group: &str) {
+        let prefix = format!("{group}.");
+        self.sources.retain(|key, layer| {
+            *layer > SettingsLayer::User || (key != group && !key.starts_with(&prefix))
+        });
+    }
+
+    /// Records that the value at `path` was replaced by `layer`, unless a layer with higher
+    /// precedence already provides it.
+    fn record(&mut self, path: &str, value: &toml::Value, layer: SettingsLayer) {
+        if self.source(path) > layer {
+            return;
+        }
+        let prefix = format!("{path}.");
+        self.sources
+            .retain(|key, source| *source > layer || !key.starts_with(&prefix));
+        match value {
+            toml::Value::Table(table) if !table.is_empty() => {
+                self.sources.remove(path);
+                for (key, value) in table {
+                    self.record(&format!("{path}.{key}"), value, layer);
+                }
+            }
+            _ => {
+                self.sources.insert(path.to_string(), layer);
+            }
+        }
+    }
+}
+
+/// The layers of a single settings file, kept around so that saving only writes out the values
+/// which belong in the user preferences file.
+#[derive(Default)]
+pub(crate) struct FileLayers {
+    /// The system settings file.
+    pub(crate) system: toml::Table,
+    /// The default and system layers merged together.
+    pub(crate) base: toml::Table,
+    /// The user preferences file, as it was last loaded or saved.
+    pub(crate) user: toml::Table,
+    /// The environment and command-line layers merged together.
+    pub(crate) overrides: toml::Table,
+}
+
+impl FileLayers {
+    /// Returns the values of `current` which should be written to the user preferences file.
+    ///
+    /// Values equal to the ones from the layers below the user file are left out. Values that
+    /// still have the value of an environment or command-line override keep the value they had
+    /// in the user file, so temporary overrides are never saved.
+    pub(crate) fn changed_values(&self, current: &toml::Table) -> toml::Table {
+        changed_values(
+            current,
+            Some(&self.base),
+            Some(&self.user),
+            Some(&self.overrides),
+        )
+    }
+}
+
+fn changed_values(
+    current: &toml::Table,
+    base: Option<&toml::Table>,
+    user: Option<&toml::Table>,
+    overrides: Option<&toml::Table>,
+) -> toml::Table {
+    let mut changed = toml::Table::new();
+    for (key, value) in current {
+        let base = base.and_then(|table| table.get(key));
+        let user = user.and_then(|table| table.get(key));
+        let over = overrides.and_then(|table| table.get(key));
+
+        if let toml::Value::Table(value) = value
+            && base.is_none_or(toml::Value::is_table)
+            && over.is_none_or(toml::Value::is_table)
+        {
+            let table = changed_values(
+                value,
+                base.and_then(toml::Value::as_table),
+                user.and_then(toml::Value::as_table),
+                over.and_then(toml::Value::as_table),
+            );
+            if !table.is_empty() {
+                changed.insert(key.clone(), toml::Value::Table(table));
+            }
+        } else if over == Some(value) {
+            // The value is still the temporary override, so keep the one from the user file.
+            if let Some(user) = user {
+                changed.insert(key.clone(), user.clone());
+            }
+        } else if base != Some(value) {
+            changed.insert(key.clone(), value.clone());
+        }
+    }
+    changed
+}
+
+/// Merges the values of `layer` into `resolved`, replacing any values that are already present.
+pub(crate) fn merge_layer(
+    resolved: &mut toml::Table,
+    layer: &toml::Table,
+    source: SettingsLayer,
+    sources: &mut SettingsSources,
+) {
+    merge_table(resolved, layer, source, sources, "");
+}
+
+fn merge_table(
+    resolved: &mut toml::Table,
+    layer: &toml::Table,
+    source: SettingsLayer,
+    sources: &mut SettingsSources,
+    prefix: &str,
+) {
+    for (key, value) in layer {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match (resolved.get_mut(key), value) {
+            (Some(toml::Value::Table(resolved)), toml::Value::Table(layer)) => {
+                merge_table(resolved, layer, source, sources, &path);
+            }
+            _ => {
+                resolved.insert(key.clone(), value.clone());
+                sources.record(&path, value, source);
+            }
+        }
+    }
+}
+
+/// Parses the value of an override as TOML, falling back to a plain string so that
+/// `--set graphics.preset=high` works without quotes.
+fn parse_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()))
+}
+
+/// Inserts `value` at a dotted `path`, creating intermediate tables as needed.
+fn insert_path(table: &mut toml::Table, path: &str, value: toml::Value) {
+    let mut table = table;
+    let mut keys = path.split('.').peekable();
+    while let Some(key) = keys.next() {
+        if keys.peek().is_none() {
+            table.insert(key.to_string(), value);
+            return;
+        }
+        let entry = table
+            .entry(key)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        if !entry.is_table() {
+            *entry = toml::Value::Table(toml::Table::new());
+        }
+        table = entry.as_table_mut().unwrap();
+    }
+}
+
+/// Collects the settings overrides from environment variables named
+/// `<PREFIX>__<GROUP>__<FIELD>`.
+pub(crate) fn environment_overrides(
+    prefix: &str,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> toml::Table {
+    let prefix = format!("{prefix}__");
+    let mut table = toml::Table::new();
+    for (name, value) in vars {
+        let Some(path) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+        let path = path
+            .split("__")
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(".");
+        insert_path(&mut table, &path, parse_value(&value));
+    }
+    table
+}
+
+/// Collects the settings overrides from `--set group.field=value` and
+/// `--set=group.field=value` command-line arguments. Other arguments are ignored.
+pub(crate) fn command_line_overrides(args: &[String]) -> toml::Table {
+    let mut table = toml::Table::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let assignment = if arg == "--set" {
+            args.next().map(String::as_str)
+        } else {
+            arg.strip_prefix("--set=")
+        };
+        let Some(assignment) = assignment else {
+            continue;
+        };
+        let Some((path, value)) = assignment.split_once('=') else {
+            warn!("Ignoring settings override `{assignment}`, expected `group.field=value`");
+            continue;
+        };
+        insert_path(&mut table, path.trim(), parse_value(value.trim()));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(source: &str) -> toml::Table {
+        toml::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn overrides_are_parsed() {
+        let args = [
+            "--set",
+            "audio.volume=0.5",
+            "--fullscreen",
+            "--set=video.preset=high",
+        ]
+        .map(String::from);
+        assert_eq!(
+            command_line_overrides(&args),
+            table("audio.volume = 0.5\nvideo.preset = 'high'")
+        );
+
+        let vars = [
+            ("GAME__AUDIO__MUTED".to_string(), "true".to_string()),
+            ("OTHER__AUDIO__VOLUME".to_string(), "1.0".to_string()),
+        ];
+        assert_eq!(
+            environment_overrides("GAME", vars),
+            table("audio.muted = true")
+        );
+    }
+
+    #[test]
+    fn layers_resolve_by_precedence() {
+        let mut sources = SettingsSources::default();
+        let mut resolved = table("audio = { volume = 1.0, muted = false, device = 'default' }");
+        merge_layer(
+            &mut resolved,
+            &table("audio.device = 'speakers'"),
+            SettingsLayer::System,
+            &mut sources,
+        );
+        merge_layer(
+            &mut resolved,
+            &table("audio.volume = 0.5\naudio.device = 'headset'"),
+            SettingsLayer::User,
+            &mut sources,
+        );
+        merge_layer(
+            &mut resolved,
+            &table("audio.volume = 0.2"),
+            SettingsLayer::CommandLine,
+            &mut sources,
+        );
+
+        assert_eq!(
+            resolved,
+            table("audio = { volume = 0.2, muted = false, device = 'headset' }")
+        );
+        assert_eq!(sources.source("audio.volume"), SettingsLayer::CommandLine);
+        assert_eq!(sources.source("audio.device"), SettingsLayer::User);
+        assert_eq!(sources.source("audio.muted"), SettingsLayer::Default);
+    }
+
+    #[test]
+    fn only_changed_values_are_saved() {
+        let layers = FileLayers {
+            system: toml::Table::new(),
+            base: table("audio = { volume = 1.0, muted = false, device = 'default' }"),
+            user: table("audio = { volume = 0.5, device = 'default' }"),
+            overrides: table("audio.volume = 0.2"),
+        };
+
+        // The override is still active, so the user's volume is kept.
+        let current = table("audio = { volume = 0.2, muted = true, device = 'default' }");
+        assert_eq!(
+            layers.changed_values(&current),
+            table("audio = { volume = 0.5, muted = true }")
+        );
+
+        // The volume was changed at runtime, so it's saved.
+        let current = table("audio = { volume = 0.8, muted = false, device = 'default' }");
+        assert_eq!(
+            layers.changed_values(&current),
+            table("audio = { volume = 0.8 }")
+        );
+    }
+}
@@ -15,10 +15,11 @@ extern crate self as bevy_settings;
 
 use core::any::TypeId;
 use core::time::Duration;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
 use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::{
+    change_detection::Mut,
     change_detection::Tick,
     reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
     resource::Resource,
@@ -26,7 +27,7 @@ use bevy_ecs::{
     world::World,
 };
 pub use bevy_ecs_macros::SettingsGroup;
-use bevy_log::warn;
+use bevy_log::{info, warn};
 use bevy_reflect::{
     prelude::ReflectDefault,
     serde::{TypedReflectDeserializer, TypedReflectSerializer},
@@ -34,20 +35,39 @@ use bevy_reflect::{
     TypeRegistry,
 };
 
+mod layers;
+mod migration;
+
 #[cfg(not(target_arch = "wasm32"))]
 mod store_fs;
 
 #[cfg(target_arch = "wasm32")]
 mod store_wasm;
 
+#[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+mod watcher;
+
 use bevy_time::{Time, Timer, TimerMode};
+use layers::{command_line_overrides, environment_overrides, merge_layer, FileLayers};
+pub use layers::{SettingsLayer, SettingsSources};
+use migration::MigrationOutcome;
+pub use migration::{SettingsMigrationFn, SettingsMigrations, SETTINGS_VERSION_KEY};
 use serde::de::DeserializeSeed;
 #[cfg(not(target_arch = "wasm32"))]
 use store_fs::PreferencesStore;
+use thiserror::Error;
 
 #[cfg(target_arch = "wasm32")]
 use store_wasm::PreferencesStore;
 
+/// Exports used by macros.
+///
+/// These are not meant to be used directly and are subject to breaking changes.
+#[doc(hidden)]
+pub mod __macro_exports {
+    pub use bevy_reflect::PartialReflect;
+}
+
 /// Plugin to orchestrate loading and saving of user preferences.
 ///
 /// You are required to provide a unique application name, so that your preferences don't overwrite
@@ -89,9 +109,56 @@ use store_wasm::PreferencesStore;
 /// Saving is crash-resistant: if the app crashes in the middle of a save, the preferences file
 /// will not be corrupted (it writes to a temporary file first, then uses atomic operations to
 /// replace the previous file).
+///
+/// Each settings value is resolved from a stack of [`SettingsLayer`]s, from lowest to highest
+/// precedence:
+/// 1. the value of the settings resource before loading, usually its [`Default`] value,
+/// 2. a read-only system settings file, see [`with_system_dir`](Self::with_system_dir),
+/// 3. the user preferences file,
+/// 4. environment variables, see [`with_env_prefix`](Self::with_env_prefix),
+/// 5. `--set group.field=value` command-line arguments, see [`with_args`](Self::with_args).
+///
+/// The [`SettingsSources`] resource records which layer each value came from. Saving only
+/// writes out the values which differ from the default and system layers, and never saves
+/// environment or command-line overrides.
+///
+/// When the `file_watcher` cargo feature is enabled, the preferences directory is watched, and
+/// settings groups which are changed by the user or an external tool while the app runs are
+/// reloaded into their resources. You can also reload preferences with the
+/// [`ReloadPreferences`] command.
+///
+/// Values are validated as they are loaded, using the field attributes of
+/// `#[derive(SettingsGroup)]`. Values that fail validation are skipped with a warning, and the
+/// field keeps its previous value.
+///
+/// Settings groups can be versioned with `#[settings_group(version = 1)]`, which stores the
+/// version in the settings file. Settings files written with an older version of a group are
+/// upgraded by the [`SettingsMigrations`] registered with
+/// [`with_migration`](Self::with_migration) before they are deserialized.
 pub struct PreferencesPlugin {
     /// The unique name of the application.
     pub app_name: String,
+    /// The directory containing read-only settings files that are installed with the app.
+    ///
+    /// Files in this directory have the same names as the preferences files, and take
+    /// precedence over the default values of settings, but not over the user's preferences.
+    /// This is ignored on platforms without filesystems.
+    pub system_dir: Option<PathBuf>,
+    /// The prefix of environment variables that override settings.
+    ///
+    /// With a prefix of `MYAPP`, the variable `MYAPP__AUDIO_SETTINGS__VOLUME=0.5` sets the
+    /// `volume` field of the `audio_settings` group. Names are matched case-insensitively.
+    pub env_prefix: Option<String>,
+    /// The command-line arguments to read `--set group.field=value` overrides from.
+    ///
+    /// Values are parsed as TOML, and treated as strings if they aren't valid TOML.
+    /// Other arguments are ignored.
+    pub args: Vec<String>,
+    /// If set, will override the default "watch for changes" setting. By default "watch for
+    /// changes" will be `false` unless the `file_watcher` cargo feature is set.
+    pub watch_for_changes_override: Option<bool>,
+    /// The migrations which upgrade settings files written by older versions of the app.
+    pub migrations: SettingsMigrations,
 }
 
 impl PreferencesPlugin {
@@ -99,8 +166,71 @@ impl PreferencesPlugin {
     pub fn new(app_name: &str) -> Self {
         Self {
             app_name: app_name.to_string(),
+            system_dir: None,
+            env_prefix: None,
+            args: Vec::new(),
+            watch_for_changes_override: None,
+            migrations: SettingsMigrations::default(),
         }
     }
+
+    /// Registers a migration for the settings group of `T`, from `from_version` to
+    /// `from_version + 1`. See [`SettingsMigrations`].
+    pub fn with_migration<T: SettingsGroup>(
+        mut self,
+        from_version: u32,
+        migration: impl Fn(toml::Table) -> toml::Table + Send + Sync + 'static,
+    ) -> Self {
+        self.migrations.add_for::<T>(from_version, migration);
+        self
+    }
+
+    /// Loads read-only system settings files from the given directory.
+    /// See [`system_dir`](Self::system_dir).
+    pub fn with_system_dir(mut self, system_dir: impl Into<PathBuf>) -> Self {
+        self.system_dir = Some(system_dir.into());
+        self
+    }
+
+    /// Reads settings overrides from environment variables with the given prefix.
+    /// See [`env_prefix`](Self::env_prefix).
+    pub fn with_env_prefix(mut self, env_prefix: &str) -> Self {
+        self.env_prefix = Some(env_prefix.to_string());
+        self
+    }
+
+    /// Reads settings overrides from the given command-line arguments.
+    /// See [`args`](Self::args).
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Reads settings overrides from the arguments the app was started with.
+    /// See [`args`](Self::args).
+    pub fn with_command_line_args(self) -> Self {
+        self.with_args(std::env::args().skip(1))
+    }
+
+    /// Collects the environment and command-line overrides, in that order.
+    fn overrides(&self) -> [(SettingsLayer, toml::Table); 2] {
+        let environment = match &self.env_prefix {
+            Some(prefix) => environment_overrides(
+                prefix,
+                std::env::vars_os().filter_map(|(name, value)| {
+                    Some((name.into_string().ok()?, value.into_string().ok()?))
+                }),
+            ),
+            None => toml::Table::new(),
+        };
+        [
+            (SettingsLayer::Environment, environment),
+            (
+                SettingsLayer::CommandLine,
+                command_line_overrides(&self.args),
+            ),
+        ]
+    }
 }
 
 impl Plugin for PreferencesPlugin {
@@ -116,24 +246,82 @@ impl Plugin for PreferencesPlugin {
         let app_types = app_types.clone();
         let types = app_types.read();
 
+        let overrides = self.overrides();
         let world = app.world_mut();
-        let file_index = build_preferences_registry(&app_name, &types, last_save);
+        let mut file_index = build_preferences_registry(&app_name, &types, last_save);
 
         // Now load each of the toml files we discovered, and apply their properties to
         // the resources in the world.
+        let mut sources = SettingsSources::default();
+        let loader = SettingsLoader {
+            app_name: &app_name,
+            system_dir: self.system_dir.as_ref(),
+            overrides: &overrides,
+            versions: &file_index.versions,
+            migrations: &self.migrations,
+        };
+        let mut layers = HashMap::new();
         for (filename, manifest) in file_index.files.iter() {
-            load_settings_file(world, &app_name, filename, manifest, &types);
+            layers.insert(
+                *filename,
+                loader.load(world, filename, manifest, &types, &mut sources),
+            );
+        }
+        file_index.layers = layers;
+
+        for (_, overrides) in overrides.iter() {
+            for group in overrides.keys() {
+                if !file_index
+                    .layers
+                    .values()
+                    .any(|layers| layers.base.contains_key(group))
+                {
+                    warn!("Ignoring overrides for unknown settings group `{group}`");
+                }
+            }
         }
 
         // Cache the index so that we don't have to do it again when saving (and also makes
         // saving more deterministic).
         drop(types);
         world.insert_resource::<PreferencesFileRegistry>(file_index);
+        world.insert_resource(sources);
+        world.insert_resource(self.migrations.clone());
 
         app.add_systems(PostUpdate, handle_delayed_save);
+
+        if self
+            .watch_for_changes_override
+            .unwrap_or(cfg!(feature = "file_watcher"))
+        {
+            watch_for_changes(app, &app_name);
+        }
+    }
+}
+
+/// Watches the preferences directory, and reloads settings files when they change.
+#[cfg(all(feature = "file_watcher", not(target_arch = "wasm32")))]
+fn watch_for_changes(app: &mut App, app_name: &str) {
+    let store = PreferencesStore::new(app_name);
+    let Some(path) = store.base_path() else {
+        return;
+    };
+    match watcher::SettingsWatcher::new(path, Duration::from_millis(300)) {
+        Ok(watcher) => {
+            app.insert_resource(watcher)
+                .add_systems(bevy_app::PreUpdate, watcher::reload_changed_preferences);
+        }
+        Err(error) => warn!("Could not watch preferences directory {path:?}: {error}"),
     }
 }
 
+#[cfg(not(all(feature = "file_watcher", not(target_arch = "wasm32"))))]
+fn watch_for_changes(_app: &mut App, _app_name: &str) {
+    warn!(
+        "Watching preferences for changes requires the `file_watcher` cargo feature and a filesystem"
+    );
+}
+
 /// Trait which identifies a type as corresponding to a section with a settings file.
 ///
 /// You can override the name of the section with `settings_group(group = "<name>")`.
@@ -159,8 +347,69 @@ pub trait SettingsGroup: Resource {
     /// The name of the configuration file that contains this settings group.
     // TODO: Eventually convert this into an enum which represents various configuration sources.
     fn settings_source() -> Option<&'static str>;
+
+    /// The version of the layout of this settings group, which is stored in the settings file.
+    ///
+    /// Increase this with `settings_group(version = <version>)` when renaming or restructuring
+    /// fields, and register a migration from the previous version in [`SettingsMigrations`].
+    /// If several resources share a settings group, the group uses the highest version.
+    fn settings_version() -> u32 {
+        0
+    }
+
+    /// Checks whether `value` is a valid value for the field named `field`.
+    ///
+    /// Values are validated before they are applied to the resource when loading settings, and
+    /// rejected if this returns an error. The derive macro implements this from the
+    /// `#[settings_group(range = ..)]`, `#[settings_group(one_of = [..])]` and
+    /// `#[settings_group(validate = ..)]` field attributes.
+    fn validate_field(
+        field: &str,
+        value: &dyn PartialReflect,
+    ) -> Result<(), SettingsValidationError> {
+        let _ = (field, value);
+        Ok(())
+    }
+}
+
+/// An error returned when a settings value fails validation.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SettingsValidationError {
+    /// The value is outside of the range allowed by `#[settings_group(range = ..)]`.
+    #[error("`{field}` is {value}, which is outside of the range {range}")]
+    OutOfRange {
+        /// The name of the field.
+        field: &'static str,
+        /// The rejected value.
+        value: String,
+        /// The allowed range.
+        range: &'static str,
+    },
+    /// The value isn't one of the values allowed by `#[settings_group(one_of = [..])]`.
+    #[error("`{field}` is {value}, which isn't one of {allowed}")]
+    NotAllowed {
+        /// The name of the field.
+        field: &'static str,
+        /// The rejected value.
+        value: String,
+        /// The allowed values.
+        allowed: &'static str,
+    },
+    /// The value was rejected by the function given to `#[settings_group(validate = ..)]`.
+    #[error("`{field}` is {value}, which is invalid: {message}")]
+    Invalid {
+        /// The name of the field.
+        field: &'static str,
+        /// The rejected value.
+        value: String,
+        /// The reason the value was rejected.
+        message: String,
+    },
 }
 
+/// A function which validates the value of a field, see [`SettingsGroup::validate_field`].
+type ValidateField = fn(&str, &dyn PartialReflect) -> Result<(), SettingsValidationError>;
+
 /// Reflected data from a [`SettingsGroup`].
 #[derive(Clone)]
 pub struct ReflectSettingsGroup {
@@ -170,6 +419,10 @@ pub struct ReflectSettingsGroup {
     settings_key_name: Option<&'static str>,
     /// The name of the settings file, defaults to "settings".
     settings_source: Option<&'static str>,
+    /// The version of the settings group.
+    settings_version: u32,
+    /// Validates the values of fields before they are applied.
+    validate_field: ValidateField,
 }
 
 impl<T: SettingsGroup + FromReflect + TypePath> FromType<T> for ReflectSettingsGroup {
@@ -178,6 +431,8 @@ impl<T: SettingsGroup + FromReflect + TypePath> FromType<T> for ReflectSettingsG
             settings_group_name: T::settings_group_name(),
             settings_key_name: T::settings_key_name(),
             settings_source: T::settings_source(),
+            settings_version: T::settings_version(),
+            validate_field: T::validate_field,
         }
     }
 
@@ -205,6 +460,12 @@ struct PreferencesFileRegistry {
     /// List of known preferences files, determined by scanning reflection registry.
     files: HashMap<&'static str, PreferenceFileManifest>,
 
+    /// The settings layers of each preferences file, used to decide which values to save.
+    layers: HashMap<&'static str, FileLayers>,
+
+    /// The version of each settings group.
+    versions: HashMap<&'static str, u32>,
+
     /// Timer used for batched saving.
     save_timer: Timer,
 }
@@ -272,6 +533,147 @@ impl Command for SavePreferencesDeferred {
     }
 }
 
+/// A [`Command`] which reloads preferences files from storage, and applies the settings groups
+/// that changed since they were last loaded or saved to their resources.
+///
+/// This is issued automatically when the `file_watcher` cargo feature is enabled and a settings
+/// file changes. Files that fail to parse are skipped, so that a half-edited file doesn't
+/// reset any settings.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub enum ReloadPreferences {
+    /// Reload all preferences files.
+    #[default]
+    All,
+    /// Reload the preferences file with the given name, without the file extension.
+    File(String),
+}
+
+impl Command for ReloadPreferences {
+    type Out = ();
+
+    fn apply(self, world: &mut World) {
+        let Some(registry) = world.get_resource::<PreferencesFileRegistry>() else {
+            warn!(
+                "Preferences registry not found - did you forget to install the PreferencesPlugin?"
+            );
+            return;
+        };
+        let store = PreferencesStore::new(&registry.app_name);
+        let default_migrations = SettingsMigrations::default();
+        let migrations = world
+            .get_resource::<SettingsMigrations>()
+            .unwrap_or(&default_migrations);
+        let filenames: Vec<&'static str> = registry
+            .files
+            .keys()
+            .copied()
+            .filter(|filename| match &self {
+                ReloadPreferences::All => true,
+                ReloadPreferences::File(name) => name == filename,
+            })
+            .collect();
+
+        let mut reloaded = Vec::new();
+        for filename in filenames {
+            let mut user = match store.load(filename) {
+                Some(user) => user,
+                None if !store.exists(filename) => toml::Table::new(),
+                None => {
+                    warn!("Could not reload {filename}.toml, keeping the current settings");
+                    continue;
+                }
+            };
+            migrate_settings_file(filename, &mut user, &registry.versions, migrations);
+            reloaded.push((filename, user));
+        }
+
+        for (filename, user) in reloaded {
+            reload_settings_file(world, filename, user);
+        }
+    }
+}
+
+/// Applies the settings groups of a reloaded user preferences file which differ from the last
+/// time the file was loaded or saved.
+fn reload_settings_file(world: &mut World, filename: &str, user: toml::Table) {
+    let Some(app_types) = world.get_resource::<AppTypeRegistry>() else {
+        return;
+    };
+    let app_types = app_types.clone();
+    let types = app_types.read();
+
+    world.resource_scope(|world, mut registry: Mut<PreferencesFileRegistry>| {
+        let registry = &mut *registry;
+        let (Some(manifest), Some(layers)) = (
+            registry.files.get_mut(filename),
+            registry.layers.get_mut(filename),
+        ) else {
+            return;
+        };
+
+        let mut changed_groups: Vec<String> = user
+            .keys()
+            .chain(layers.user.keys())
+            .filter(|group| user.get(*group) != layers.user.get(*group))
+            .cloned()
+            .collect();
+        changed_groups.sort();
+        changed_groups.dedup();
+        layers.user = user;
+        if changed_groups.is_empty() {
+            return;
+        }
+
+        let mut resolved = layers.base.clone();
+        let mut sources = SettingsSources::default();
+        merge_layer(
+            &mut resolved,
+            &layers.user,
+            SettingsLayer::User,
+            &mut sources,
+        );
+        merge_layer(
+            &mut resolved,
+            &layers.overrides,
+            SettingsLayer::CommandLine,
+            &mut sources,
+        );
+        let changed: toml::Table = changed_groups
+            .iter()
+            .filter_map(|group| Some((group.clone(), resolved.get(group)?.clone())))
+            .collect();
+        // Changes made at runtime to other groups of this file still have to be saved.
+        let pending_changes = has_preferences_changed(world, manifest);
+        apply_settings_to_world(world, Some(&changed), manifest, &types);
+
+        // The reloaded values are already in the file, so they don't need to be saved again,
+        // unless the file has to be saved anyway, in which case they're saved as they are.
+        if !pending_changes {
+            manifest.last_save = world.change_tick();
+        }
+
+        if let Some(mut sources) = world.get_resource_mut::<SettingsSources>() {
+            for group in changed_groups.iter() {
+                sources.reset_group(group);
+                for (layer, table) in [
+                    (SettingsLayer::System, &layers.system),
+                    (SettingsLayer::User, &layers.user),
+                ] {
+                    if let Some(value) = table.get(group) {
+                        let group_table = toml::Table::from_iter([(group.clone(), value.clone())]);
+                        merge_layer(&mut layers.base.clone(), &group_table, layer, &mut sources);
+                    }
+                }
+            }
+        }
+
+        info!(
+            "Reloaded settings {} from {filename}.toml",
+            changed_groups.join(", ")
+        );
+    });
+}
+
 fn save_preferences(world: &mut World, use_async: bool, force: bool) {
     let this_run = world.change_tick();
     let Some(registry) = world.get_resource::<PreferencesFileRegistry>() else {
@@ -284,15 +686,33 @@ fn save_preferences(world: &mut World, use_async: bool, force: bool) {
     let app_types = app_types.clone();
     let types = app_types.read();
 
+    let mut saved = Vec::new();
     for (filename, manifest) in registry.files.iter() {
         if force || has_preferences_changed(world, manifest) {
-            let table = resources_to_toml(world, &types, manifest);
+            let mut table = resources_to_toml(world, &types, manifest);
+            if let Some(layers) = registry.layers.get(filename) {
+                // Only write the values that belong in the user's preferences file.
+                table = layers.changed_values(&table);
+            }
+            let mut versioned = table.clone();
+            for (group, value) in versioned.iter_mut() {
+                if let Some(&version) = registry.versions.get(group.as_str())
+                    && version > 0
+                    && let Some(group_table) = value.as_table_mut()
+                {
+                    group_table.insert(
+                        SETTINGS_VERSION_KEY.to_string(),
+                        toml::Value::Integer(version.into()),
+                    );
+                }
+            }
             let store = PreferencesStore::new(&registry.app_name);
             if use_async {
-                store.save_async(filename, table);
+                store.save_async(filename, versioned);
             } else {
-                store.save(filename, table);
+                store.save(filename, versioned);
             }
+            saved.push((*filename, table));
         }
     }
 
@@ -301,6 +721,11 @@ fn save_preferences(world: &mut World, use_async: bool, force: bool) {
     for (_, manifest) in registry.files.iter_mut() {
         manifest.last_save = this_run;
     }
+    for (filename, table) in saved {
+        if let Some(layers) = registry.layers.get_mut(filename) {
+            layers.user = table;
+        }
+    }
 }
 
 fn has_preferences_changed(world: &World, manifest: &PreferenceFileManifest) -> bool {
@@ -334,9 +759,6 @@ fn resources_to_toml(
             continue;
         };
 
-        let settings_group = reflect_settings_group.settings_group_name;
-        let settings_key = reflect_settings_group.settings_key_name;
-
         let Some(component_id) = world.components().get_id(*tid) else {
             continue;
         };
@@ -349,40 +771,98 @@ fn resources_to_toml(
             continue;
         };
 
-        let serializer = TypedReflectSerializer::new(reflect.as_partial_reflect(), types);
+        insert_settings_group(
+            &mut table,
+            reflect.as_partial_reflect(),
+            reflect_settings_group,
+            types,
+        );
+    }
 
-        let toml_value = if let Some(settings_key) = settings_key {
-            // convert toml value into a key value pair if settings_key is set. settings_key is only set for enums
-            toml::Value::Table(toml::Table::from_iter([(
-                settings_key.to_string(),
-                toml::Value::try_from(serializer).unwrap(),
-            )]))
-        } else {
-            // Otherwise, the whole struct is serialized into toml
-            toml::Value::try_from(serializer).unwrap()
-        };
+    table
+}
 
-        match (
-            toml_value.as_table(),
-            table
-                .get_mut(settings_group)
-                .and_then(|value| value.as_table_mut()),
-        ) {
-            (Some(from), Some(to)) => {
-                // Merge the tables
-                for (key, value) in from.iter() {
-                    to.insert(key.clone(), value.clone());
-                }
-            }
-            _ => {
-                table.insert(settings_group.to_string(), toml_value);
-            }
+/// Serializes the settings resources of a file as they are before any settings are loaded:
+/// resources that already exist use their current value, and all others use their default.
+fn defaults_to_toml(
+    world: &World,
+    types: &TypeRegistry,
+    manifest: &PreferenceFileManifest,
+) -> toml::Table {
+    let mut table = resources_to_toml(world, types, manifest);
+
+    for tid in manifest.resource_types.iter() {
+        let ty = types.get(*tid).unwrap();
+        let exists = world
+            .components()
+            .get_id(*tid)
+            .and_then(|cid| world.resource_entities().get(cid))
+            .is_some();
+        if exists {
+            continue;
+        }
+
+        let (Some(reflect_settings_group), Some(reflect_default)) = (
+            ty.data::<ReflectSettingsGroup>(),
+            ty.data::<ReflectDefault>(),
+        ) else {
+            continue;
         };
+
+        let default_value = reflect_default.default();
+        insert_settings_group(
+            &mut table,
+            default_value.as_partial_reflect(),
+            reflect_settings_group,
+            types,
+        );
     }
 
     table
 }
 
+/// Serializes a settings resource into its section of a settings table, merging it with other
+/// resources that share the same section.
+fn insert_settings_group(
+    table: &mut toml::Table,
+    reflect: &dyn PartialReflect,
+    reflect_settings_group: &ReflectSettingsGroup,
+    types: &TypeRegistry,
+) {
+    let settings_group = reflect_settings_group.settings_group_name;
+    let settings_key = reflect_settings_group.settings_key_name;
+
+    let serializer = TypedReflectSerializer::new(reflect, types);
+
+    let toml_value = if let Some(settings_key) = settings_key {
+        // convert toml value into a key value pair if settings_key is set. settings_key is only set for enums
+        toml::Value::Table(toml::Table::from_iter([(
+            settings_key.to_string(),
+            toml::Value::try_from(serializer).unwrap(),
+        )]))
+    } else {
+        // Otherwise, the whole struct is serialized into toml
+        toml::Value::try_from(serializer).unwrap()
+    };
+
+    match (
+        toml_value.as_table(),
+        table
+            .get_mut(settings_group)
+            .and_then(|value| value.as_table_mut()),
+    ) {
+        (Some(from), Some(to)) => {
+            // Merge the tables
+            for (key, value) in from.iter() {
+                to.insert(key.clone(), value.clone());
+            }
+        }
+        _ => {
+            table.insert(settings_group.to_string(), toml_value);
+        }
+    };
+}
+
 /// Builds the preferences file registry by scanning the type registry for settings resources.
 /// This is separated from loading to enable testing without file I/O.
 ///
@@ -398,6 +878,8 @@ fn build_preferences_registry(
     let mut file_index = PreferencesFileRegistry {
         app_name: app_name.to_string(),
         files: HashMap::new(),
+        layers: HashMap::new(),
+        versions: HashMap::new(),
         save_timer: Timer::new(Duration::from_secs(1), TimerMode::Once),
     };
     file_index.save_timer.pause(); // Ensure timer is initially paused
@@ -424,27 +906,121 @@ fn build_preferences_registry(
             });
         pending_file.last_save = last_save;
         pending_file.resource_types.push(ty.type_id());
+
+        let version = file_index
+            .versions
+            .entry(reflect_group.settings_group_name)
+            .or_default();
+        *version = (*version).max(reflect_group.settings_version);
     }
 
     file_index
 }
 
-/// Loads a single settings file and applies its values to the world's resources.
-fn load_settings_file(
-    world: &mut World,
-    app_name: &str,
+/// The inputs shared by all of the settings files that are loaded by the [`PreferencesPlugin`].
+struct SettingsLoader<'a> {
+    app_name: &'a str,
+    system_dir: Option<&'a PathBuf>,
+    overrides: &'a [(SettingsLayer, toml::Table)],
+    versions: &'a HashMap<&'static str, u32>,
+    migrations: &'a SettingsMigrations,
+}
+
+impl SettingsLoader<'_> {
+    /// Loads a single settings file along with the layers around it, and applies the resolved
+    /// values to the world's resources.
+    ///
+    /// Returns the layers of the file, which are needed to decide which values to save.
+    fn load(
+        &self,
+        world: &mut World,
+        filename: &str,
+        manifest: &PreferenceFileManifest,
+        types: &TypeRegistry,
+        sources: &mut SettingsSources,
+    ) -> FileLayers {
+        let mut resolved = defaults_to_toml(world, types, manifest);
+
+        // Load the system file, if any
+        let mut system = load_system_file(self.system_dir, filename).unwrap_or_default();
+        migrate_settings_file(filename, &mut system, self.versions, self.migrations);
+        merge_layer(&mut resolved, &system, SettingsLayer::System, sources);
+        let base = resolved.clone();
+
+        // Load the TOML file
+        let store = PreferencesStore::new(self.app_name);
+        let user = store.load(filename);
+        if user.is_none() {
+            warn!("Filename {filename}.toml not found");
+        }
+        let mut user = user.unwrap_or_default();
+        migrate_settings_file(filename, &mut user, self.versions, self.migrations);
+        merge_layer(&mut resolved, &user, SettingsLayer::User, sources);
+
+        // Only apply the overrides for groups that belong to this file.
+        let mut file_overrides = toml::Table::new();
+        for (layer, table) in self.overrides {
+            let table = table
+                .iter()
+                .filter(|(group, _)| base.contains_key(*group))
+                .map(|(group, value)| (group.clone(), value.clone()))
+                .collect();
+            merge_layer(&mut resolved, &table, *layer, sources);
+            merge_layer(
+                &mut file_overrides,
+                &table,
+                *layer,
+                &mut SettingsSources::default(),
+            );
+        }
+
+        apply_settings_to_world(world, Some(&resolved), manifest, types);
+
+        FileLayers {
+            system,
+            base,
+            user,
+            overrides: file_overrides,
+        }
+    }
+}
+
+/// Upgrades the settings groups of a settings file which were written with an older version,
+/// and removes the stored versions from the table.
+fn migrate_settings_file(
     filename: &str,
-    manifest: &PreferenceFileManifest,
-    types: &TypeRegistry,
+    table: &mut toml::Table,
+    versions: &HashMap<&'static str, u32>,
+    migrations: &SettingsMigrations,
 ) {
-    // Load the TOML file
-    let store = PreferencesStore::new(app_name);
-    let toml = store.load(filename);
-    if toml.is_none() {
-        warn!("Filename {filename}.toml not found");
+    for (group, value) in table.iter_mut() {
+        let current = versions.get(group.as_str()).copied().unwrap_or(0);
+        match migrations.migrate(group, value, current) {
+            MigrationOutcome::UpToDate => {}
+            MigrationOutcome::Migrated { from } => {
+                info!(
+                    "Migrated settings group `{group}` in {filename}.toml from version {from} to {current}"
+                );
+            }
+            MigrationOutcome::Downgrade { stored } => {
+                warn!(
+                    "Settings group `{group}` in {filename}.toml has version {stored}, which is newer than version {current} supported by this app. Unknown values will be ignored, and lost if the file is saved"
+                );
+            }
+        }
     }
+}
 
-    apply_settings_to_world(world, toml.as_ref(), manifest, types);
+/// Loads a read-only system settings file, if there is one.
+#[cfg(not(target_arch = "wasm32"))]
+fn load_system_file(system_dir: Option<&PathBuf>, filename: &str) -> Option<toml::Table> {
+    store_fs::decode_toml_file(&system_dir?.join(format!("{filename}.toml")))
+}
+
+/// System settings files aren't supported without a filesystem.
+#[cfg(target_arch = "wasm32")]
+fn load_system_file(_system_dir: Option<&PathBuf>, _filename: &str) -> Option<toml::Table> {
+    None
 }
 
 /// Applies settings from a TOML table to the world's resources.
@@ -491,7 +1067,12 @@ fn apply_settings_to_world(
                     value
                 };
 
-                load_properties(value, &mut *reflect, types);
+                load_properties(
+                    value,
+                    &mut *reflect,
+                    types,
+                    reflect_settings_group.validate_field,
+                );
             }
         } else {
             // The resource does not exist, so create a default.
@@ -511,7 +1092,12 @@ fn apply_settings_to_world(
                     value
                 };
 
-                load_properties(value, &mut *default_value, types);
+                load_properties(
+                    value,
+                    &mut *default_value,
+                    types,
+                    reflect_settings_group.validate_field,
+                );
             }
 
             // Now add the new resource to the world.
@@ -520,7 +1106,12 @@ fn apply_settings_to_world(
     }
 }
 
-fn load_properties(value: &toml::Value, resource: &mut dyn PartialReflect, types: &TypeRegistry) {
+fn load_properties(
+    value: &toml::Value,
+    resource: &mut dyn PartialReflect,
+    types: &TypeRegistry,
+    validate_field: ValidateField,
+) {
     let Some(tinfo) = resource.get_represented_type_info() else {
         return;
     };
@@ -540,7 +1131,21 @@ fn load_properties(value: &toml::Value, resource: &mut dyn PartialReflect, types
                         if let Ok(field_value) = deserializer.deserialize(toml_field_value.clone())
                         {
                             // Should be safe to unwrap here since we know the field exists (above).
-                            st_reflect.field_at_mut(idx).unwrap().apply(&*field_value);
+                            let target = st_reflect.field_at_mut(idx).unwrap();
+
+                            // Validate a copy of the field, so that invalid values never reach
+                            // the resource.
+                            let validation = match target.reflect_clone() {
+                                Ok(mut candidate) => {
+                                    candidate.apply(&*field_value);
+                                    validate_field(field, candidate.as_partial_reflect())
+                                }
+                                Err(_) => validate_field(field, &*field_value),
+                            };
+                            match validation {
+                                Ok(()) => target.apply(&*field_value),
+                                Err(error) => warn!("Ignoring invalid settings value: {error}"),
+                            }
                         }
                     }
                 }
@@ -1058,4 +1663,197 @@ mod tests {
         let refresh_rate = world.get_resource::<CounterRefreshRateSettings>().unwrap();
         assert_eq!(*refresh_rate, CounterRefreshRateSettings::Fast);
     }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        fn validate_name(name: &str) -> Result<(), &'static str> {
+            if name.is_empty() {
+                Err("the name can't be empty")
+            } else {
+                Ok(())
+            }
+        }
+
+        #[derive(Resource, SettingsGroup, Reflect, Default)]
+        #[reflect(Resource, SettingsGroup, Default)]
+        struct ValidatedSettings {
+            #[settings_group(range = 0.0..=1.0)]
+            volume: f32,
+            #[settings_group(one_of = ["low", "high"])]
+            quality: String,
+            #[settings_group(validate = validate_name)]
+            name: String,
+        }
+
+        let mut world = World::new();
+        let mut types = TypeRegistry::default();
+        types.register::<ValidatedSettings>();
+        world.insert_resource(ValidatedSettings {
+            volume: 0.5,
+            quality: "low".to_string(),
+            name: "player".to_string(),
+        });
+
+        let manifest = PreferenceFileManifest {
+            last_save: Tick::new(0),
+            resource_types: vec![TypeId::of::<ValidatedSettings>()],
+        };
+
+        let table: toml::Table =
+            toml::from_str("validated_settings = { volume = 2.0, quality = 'ultra', name = '' }")
+                .unwrap();
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+
+        let settings = world.resource::<ValidatedSettings>();
+        assert_eq!(settings.volume, 0.5);
+        assert_eq!(settings.quality, "low");
+        assert_eq!(settings.name, "player");
+
+        let table: toml::Table =
+            toml::from_str("validated_settings = { volume = 0.8, quality = 'high', name = 'me' }")
+                .unwrap();
+        apply_settings_to_world(&mut world, Some(&table), &manifest, &types);
+
+        let settings = world.resource::<ValidatedSettings>();
+        assert_eq!(settings.volume, 0.8);
+        assert_eq!(settings.quality, "high");
+        assert_eq!(settings.name, "me");
+    }
+
+    #[test]
+    fn test_reload_applies_changed_groups() {
+        let mut world = World::new();
+        let app_types = AppTypeRegistry::default();
+        {
+            let mut types = app_types.write();
+            types.register::<CounterSettings>();
+            types.register::<AudioSettings>();
+        }
+        world.insert_resource(app_types.clone());
+        world.insert_resource(CounterSettings { count: 1 });
+        world.insert_resource(AudioSettings { volume: 0.5 });
+
+        let mut registry = build_preferences_registry("test_app", &app_types.read(), Tick::new(0));
+        let user: toml::Table = toml::from_str("counter_settings.count = 1").unwrap();
+        registry.layers.insert(
+            "settings",
+            FileLayers {
+                user,
+                ..Default::default()
+            },
+        );
+        world.insert_resource(registry);
+        world.init_resource::<SettingsSources>();
+
+        // Reloading the same file doesn't touch the resource.
+        let user: toml::Table = toml::from_str("counter_settings.count = 1").unwrap();
+        reload_settings_file(&mut world, "settings", user);
+        world.resource_mut::<CounterSettings>().count = 5;
+        let user: toml::Table = toml::from_str("counter_settings.count = 1").unwrap();
+        reload_settings_file(&mut world, "settings", user);
+        assert_eq!(world.resource::<CounterSettings>().count, 5);
+
+        // An edited group is applied.
+        let user: toml::Table = toml::from_str("counter_settings.count = 7").unwrap();
+        reload_settings_file(&mut world, "settings", user);
+        assert_eq!(world.resource::<CounterSettings>().count, 7);
+        assert_eq!(
+            world
+                .resource::<SettingsSources>()
+                .source("counter_settings.count"),
+            SettingsLayer::User
+        );
+        assert_eq!(world.resource::<AudioSettings>().volume, 0.5);
+    }
+
+    #[test]
+    fn test_reload_keeps_pending_changes_to_other_groups() {
+        #[derive(Resource, SettingsGroup, Reflect, Default)]
+        #[reflect(Resource, SettingsGroup, Default)]
+        struct GraphicsSettings {
+            vsync: bool,
+        }
+
+        let mut world = World::new();
+        let app_types = AppTypeRegistry::default();
+        {
+            let mut types = app_types.write();
+            types.register::<CounterSettings>();
+            types.register::<GraphicsSettings>();
+        }
+        world.insert_resource(app_types.clone());
+        world.insert_resource(CounterSettings { count: 1 });
+        world.insert_resource(GraphicsSettings { vsync: false });
+
+        let mut registry =
+            build_preferences_registry("test_app", &app_types.read(), world.change_tick());
+        registry.layers.insert("settings", FileLayers::default());
+        world.insert_resource(registry);
+        world.init_resource::<SettingsSources>();
+        world.increment_change_tick();
+        let has_changed = |world: &World| {
+            let registry = world.resource::<PreferencesFileRegistry>();
+            has_preferences_changed(world, &registry.files["settings"])
+        };
+
+        // Reloaded values don't need to be saved.
+        let user: toml::Table = toml::from_str("counter_settings.count = 7").unwrap();
+        reload_settings_file(&mut world, "settings", user);
+        assert_eq!(world.resource::<CounterSettings>().count, 7);
+        assert!(!has_changed(&world));
+
+        // A change to another group of the file is still saved after a reload.
+        world.increment_change_tick();
+        world.resource_mut::<GraphicsSettings>().vsync = true;
+        let user: toml::Table = toml::from_str("counter_settings.count = 8").unwrap();
+        reload_settings_file(&mut world, "settings", user);
+        assert_eq!(world.resource::<CounterSettings>().count, 8);
+        assert!(has_changed(&world));
+    }
+
+    #[test]
+    fn test_old_settings_files_are_migrated() {
+        #[derive(Resource, SettingsGroup, Reflect, Default)]
+        #[reflect(Resource, SettingsGroup, Default)]
+        #[settings_group(version = 2)]
+        struct GraphicsSettings {
+            render_scale: f32,
+            vsync: bool,
+        }
+
+        let mut types = TypeRegistry::default();
+        types.register::<GraphicsSettings>();
+        let registry = build_preferences_registry("test_app", &types, Tick::new(0));
+        assert_eq!(registry.versions.get("graphics_settings"), Some(&2));
+
+        let mut migrations = SettingsMigrations::default();
+        // Version 1 renamed `scale` to `render_scale`.
+        migrations.add_for::<GraphicsSettings>(0, |mut table| {
+            if let Some(scale) = table.remove("scale") {
+                table.insert("render_scale".to_string(), scale);
+            }
+            table
+        });
+        // Version 2 replaced `vsync_mode` with a boolean.
+        migrations.add_for::<GraphicsSettings>(1, |mut table| {
+            if let Some(mode) = table.remove("vsync_mode") {
+                let vsync = mode.as_str() != Some("off");
+                table.insert("vsync".to_string(), toml::Value::Boolean(vsync));
+            }
+            table
+        });
+
+        let mut table: toml::Table =
+            toml::from_str("graphics_settings = { scale = 0.75, vsync_mode = 'adaptive' }")
+                .unwrap();
+        migrate_settings_file("settings", &mut table, &registry.versions, &migrations);
+
+        let mut world = World::new();
+        let manifest = registry.files.get("settings").unwrap();
+        apply_settings_to_world(&mut world, Some(&table), manifest, &types);
+
+        let graphics = world.resource::<GraphicsSettings>();
+        assert_eq!(graphics.render_scale, 0.75);
+        assert!(graphics.vsync);
+    }
 }
@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+
+use bevy_ecs::resource::Resource;
+use bevy_platform::sync::Arc;
+
+use crate::SettingsGroup;
+
+/// The key which stores the version of a settings group in settings files.
+///
+/// The version is only written for groups with a version above zero, and groups without a
+/// version are treated as version zero.
+pub const SETTINGS_VERSION_KEY: &str = "_version";
+
+/// A function which migrates the table of a settings group from one version to the next.
+pub type SettingsMigrationFn = Arc<dyn Fn(toml::Table) -> toml::Table + Send + Sync>;
+
+/// A registry of migrations for settings groups, which upgrade settings files written by older
+/// versions of the app before they are deserialized.
+///
+/// The version of a settings group is set with `#[settings_group(version = 2)]`. When a settings
+/// file contains an older version of the group, the migrations from its version up to the
+/// current one run in order, each receiving the table produced by the previous one. Versions
+/// without a migration are skipped, so you only need to register migrations for versions that
+/// change the layout of the group.
+///
+/// ```
+/// # use bevy_settings::SettingsMigrations;
+/// let mut migrations = SettingsMigrations::default();
+/// // Version 1 renamed `volume` to `master_volume`.
+/// migrations.add("audio_settings", 0, |mut table| {
+///     if let Some(volume) = table.remove("volume") {
+///         table.insert("master_volume".to_string(), volume);
+///     }
+///     table
+/// });
+/// ```
+#[derive(Resource, Default, Clone)]
+pub struct SettingsMigrations {
+    migrations: HashMap<String, HashMap<u32, SettingsMigrationFn>>,
+}
+
+/// The result of migrating a settings group.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MigrationOutcome {
+    /// The group already had the current version.
+    UpToDate,
+    /// The group was migrated from an older version.
+    Migrated { from: u32 },
+    /// The group was written by a newer version of the app, and was left untouched.
+    Downgrade { stored: u32 },
+}
+
+impl SettingsMigrations {
+    /// Registers a migration for the settings group named `group`, from `from_version` to
+    /// `from_version + 1`. This replaces any previous migration for the same version.
+    pub fn add(
+        &mut self,
+        group: &str,
+        from_version: u32,
+        migration: impl Fn(toml::Table) -> toml::Table + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.migrations
+            .entry(group.to_string())
+            .or_default()
+            .insert(from_version, Arc::new(migration));
+        self
+    }
+
+    /// Registers a migration for the settings group of `T`, from `from_version` to
+    /// `from_version + 1`. See [`add`](Self::add).
+    pub fn add_for<T: SettingsGroup>(
+        &mut self,
+        from_version: u32,
+        migration: impl Fn(toml::Table) -> toml::Table + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add(T::settings_group_name(), from_version, migration)
+    }
+
+    /// Migrates the table of the settings group named `group` to the `current` version, and
+    /// removes the stored version from it.
+    pub(crate) fn migrate(
+        &self,
+        group: &str,
+        value: &mut toml::Value,
+        current: u32,
+    ) -> MigrationOutcome {
+        let Some(table) = value.as_table_mut() else {
+            return MigrationOutcome::UpToDate;
+        };
+        let stored = match table.remove(SETTINGS_VERSION_KEY) {
+            Some(toml::Value::Integer(version)) => u32::try_from(version).unwrap_or(0),
+            _ => 0,
+        };
+        if stored > current {
+            return MigrationOutcome::Downgrade { stored };
+        }
+        if stored == current {
+            return MigrationOutcome::UpToDate;
+        }
+
+        let mut migrated = core::mem::take(table);
+        if let Some(migrations) = self.migrations.get(group) {
+            for version in stored..current {
+                if let Some(migration) = migrations.get(&version) {
+                    migrated = migration(migrated);
+                }
+            }
+        }
+        *table = migrated;
+        MigrationOutcome::Migrated { from: stored }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(source: &str) -> toml::Value {
+        toml::Value::Table(toml::from_str(source).unwrap())
+    }
+
+    fn migrations() -> SettingsMigrations {
+        let mut migrations = SettingsMigrations::default();
+        // Version 1 renamed `volume` to `master_volume`.
+        migrations.add("audio", 0, |mut table| {
+            if let Some(volume) = table.remove("volume") {
+                table.insert("master_volume".to_string(), volume);
+            }
+            table
+        });
+        // Version 2 didn't change the layout, and version 3 turned the volume into a percentage.
+        migrations.add("audio", 2, |mut table| {
+            if let Some(volume) = table.get("master_volume").and_then(toml::Value::as_float) {
+                table.insert(
+                    "master_volume".to_string(),
+                    toml::Value::Integer((volume * 100.0) as i64),
+                );
+            }
+            table
+        });
+        migrations
+    }
+
+    #[test]
+    fn migrations_run_in_order() {
+        let migrations = migrations();
+
+        // A file written before the group had a version.
+        let mut value = group("volume = 0.5\nmuted = true");
+        assert_eq!(
+            migrations.migrate("audio", &mut value, 3),
+            MigrationOutcome::Migrated { from: 0 }
+        );
+        assert_eq!(value, group("master_volume = 50\nmuted = true"));
+
+        // A file written by version 2 only runs the last migration.
+        let mut value = group("_version = 2\nmaster_volume = 0.25");
+        assert_eq!(
+            migrations.migrate("audio", &mut value, 3),
+            MigrationOutcome::Migrated { from: 2 }
+        );
+        assert_eq!(value, group("master_volume = 25"));
+
+        let mut value = group("_version = 3\nmaster_volume = 25");
+        assert_eq!(
+            migrations.migrate("audio", &mut value, 3),
+            MigrationOutcome::UpToDate
+        );
+        assert_eq!(value, group("master_volume = 25"));
+    }
+
+    #[test]
+    fn newer_versions_are_left_untouched() {
+        let migrations = migrations();
+
+        let mut value = group("_version = 4\nmaster_volume = 25\nnew_field = 1");
+        assert_eq!(
+            migrations.migrate("audio", &mut value, 3),
+            MigrationOutcome::Downgrade { stored: 4 }
+        );
+        assert_eq!(value, group("master_volume = 25\nnew_field = 1"));
+    }
+}
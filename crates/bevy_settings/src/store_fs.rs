@@ -102,6 +102,22 @@ impl PreferencesStore {
         let file_path = base_path.join(format!("{filename}.toml"));
         decode_toml_file(&file_path)
     }
+
+    /// Returns true if the preferences file exists, even if it can't be loaded.
+    ///
+    /// # Arguments
+    /// * `filename` - The name of the preferences file, without the file extension.
+    pub(crate) fn exists(&self, filename: &str) -> bool {
+        self.base_path
+            .as_ref()
+            .is_some_and(|base_path| base_path.join(format!("{filename}.toml")).exists())
+    }
+
+    /// The directory containing the preferences files, if there is one.
+    #[cfg(feature = "file_watcher")]
+    pub(crate) fn base_path(&self) -> Option<&PathBuf> {
+        self.base_path.as_ref()
+    }
 }
 
 /// Load a preferences file from disk in TOML format.
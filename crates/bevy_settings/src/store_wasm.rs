@@ -56,6 +56,18 @@ impl PreferencesStore {
         });
     }
 
+    /// Returns true if the preferences file exists, even if it can't be loaded.
+    ///
+    /// # Arguments
+    /// * `filename` - The name of the preferences file, without the file extension.
+    pub(crate) fn exists(&self, filename: &str) -> bool {
+        if let Ok(Some(storage)) = window().unwrap().local_storage() {
+            matches!(storage.get_item(&self.storage_key(filename)), Ok(Some(_)))
+        } else {
+            false
+        }
+    }
+
     /// Deserialize a [`toml::Table`]. If the file does not exist, `None` will
     /// be returned.
     ///
@@ -0,0 +1,77 @@
+use core::time::Duration;
+use std::{collections::HashSet, path::Path};
+
+use bevy_ecs::{resource::Resource, system::Commands, system::Res};
+use bevy_log::error;
+use crossbeam_channel::Receiver;
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{self, EventKind, RecommendedWatcher, RecursiveMode},
+    DebounceEventResult, Debouncer, RecommendedCache,
+};
+
+use crate::ReloadPreferences;
+
+/// Watches the preferences directory for changes to settings files.
+///
+/// This uses [`notify_debouncer_full`] to retrieve "debounced" filesystem events, so that a
+/// file which is saved several times in quick succession is only reloaded once.
+#[derive(Resource)]
+pub(crate) struct SettingsWatcher {
+    _watcher: Debouncer<RecommendedWatcher, RecommendedCache>,
+    receiver: Receiver<String>,
+}
+
+impl SettingsWatcher {
+    /// Creates a new [`SettingsWatcher`] that watches for changes to the settings files in the
+    /// given directory, creating the directory if it doesn't exist yet.
+    pub(crate) fn new(path: &Path, debounce_wait_time: Duration) -> Result<Self, notify::Error> {
+        std::fs::create_dir_all(path)?;
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let mut watcher = new_debouncer(
+            debounce_wait_time,
+            None,
+            move |result: DebounceEventResult| match result {
+                Ok(events) => {
+                    for event in events.iter() {
+                        if !matches!(
+                            event.kind,
+                            EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                        ) {
+                            continue;
+                        }
+                        for filename in event.paths.iter().filter_map(|p| settings_file_name(p)) {
+                            // The receiver only goes away with the watcher.
+                            let _ = sender.send(filename);
+                        }
+                    }
+                }
+                Err(errors) => errors.iter().for_each(|error| {
+                    error!("Encountered a settings watcher error {error:?}");
+                }),
+            },
+        )?;
+        watcher.watch(path, RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            _watcher: watcher,
+            receiver,
+        })
+    }
+}
+
+/// Returns the name of the settings file at `path` without its extension, or `None` if it isn't
+/// a settings file. This skips the temporary files written while saving.
+fn settings_file_name(path: &Path) -> Option<String> {
+    if path.extension()? != "toml" {
+        return None;
+    }
+    Some(path.file_stem()?.to_str()?.to_string())
+}
+
+/// Reloads the settings files that changed since the last frame.
+pub(crate) fn reload_changed_preferences(watcher: Res<SettingsWatcher>, mut commands: Commands) {
+    let changed: HashSet<String> = watcher.receiver.try_iter().collect();
+    for filename in changed {
+        commands.queue(ReloadPreferences::File(filename));
+    }
+}
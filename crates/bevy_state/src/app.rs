@@ -107,6 +107,7 @@ impl AppExtStates for SubApp {
                 entered: Some(state),
                 // makes no difference: the state didn't exist before anyways
                 allow_same_state_transitions: true,
+                stack_operation: None,
             });
             enable_state_scoped_entities::<S>(self);
         } else {
@@ -132,6 +133,7 @@ impl AppExtStates for SubApp {
                 entered: Some(state),
                 // makes no difference: the state didn't exist before anyways
                 allow_same_state_transitions: true,
+                stack_operation: None,
             });
             enable_state_scoped_entities::<S>(self);
         } else {
@@ -146,6 +148,7 @@ impl AppExtStates for SubApp {
                 // Not configurable for the moment. This controls whether inserting a state with the same value as a pre-existing state should run state transitions.
                 // Leaving it at `true` makes state insertion idempotent. Neat!
                 allow_same_state_transitions: true,
+                stack_operation: None,
             });
         }
 
@@ -171,6 +174,7 @@ impl AppExtStates for SubApp {
                 exited: None,
                 entered: state,
                 allow_same_state_transitions: S::ALLOW_SAME_STATE_TRANSITIONS,
+                stack_operation: None,
             });
             enable_state_scoped_entities::<S>(self);
         } else {
@@ -202,6 +206,7 @@ impl AppExtStates for SubApp {
                 entered: state,
                 // makes no difference: the state didn't exist before anyways
                 allow_same_state_transitions: true,
+                stack_operation: None,
             });
             enable_state_scoped_entities::<S>(self);
         } else {
@@ -22,6 +22,18 @@ pub trait CommandsStatesExt {
     /// Note that commands introduce sync points to the ECS schedule, so modifying `NextState`
     /// directly may be more efficient depending on your use-case.
     fn set_state_if_neq<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Pushes a state on top of the current state, pausing it.
+    ///
+    /// Internally this schedules a command that calls [`NextState::push`](crate::prelude::NextState::push)
+    /// on the [`NextState<S>`](crate::prelude::NextState) resource.
+    fn push_state<S: FreelyMutableState>(&mut self, state: S);
+
+    /// Pops the current state, resuming the state below it.
+    ///
+    /// Internally this schedules a command that calls [`NextState::pop`](crate::prelude::NextState::pop)
+    /// on the [`NextState<S>`](crate::prelude::NextState) resource.
+    fn pop_state<S: FreelyMutableState>(&mut self);
 }
 
 impl CommandsStatesExt for Commands<'_, '_> {
@@ -46,4 +58,27 @@ impl CommandsStatesExt for Commands<'_, '_> {
             next.set_if_neq(state);
         });
     }
+
+    fn push_state<S: FreelyMutableState>(&mut self, state: S) {
+        self.queue(move |w: &mut World| {
+            let mut next = w.resource_mut::<NextState<S>>();
+            if !matches!(*next, NextState::Unchanged) {
+                debug!(
+                    "overwriting next state {:?} with a push of {state:?}",
+                    *next
+                );
+            }
+            next.push(state);
+        });
+    }
+
+    fn pop_state<S: FreelyMutableState>(&mut self) {
+        self.queue(move |w: &mut World| {
+            let mut next = w.resource_mut::<NextState<S>>();
+            if !matches!(*next, NextState::Unchanged) {
+                debug!("overwriting next state {:?} with a pop", *next);
+            }
+            next.pop();
+        });
+    }
 }
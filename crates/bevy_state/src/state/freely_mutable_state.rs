@@ -1,3 +1,5 @@
+use alloc::vec;
+
 use bevy_ecs::{
     message::MessageWriter,
     prelude::Schedule,
@@ -5,7 +7,12 @@ use bevy_ecs::{
     system::{Commands, IntoSystem, ResMut},
 };
 
-use super::{states::States, take_next_state, transitions::*, NextState, PreviousState, State};
+use super::{
+    resources::{take_state_change, StateChange},
+    states::States,
+    transitions::*,
+    NextState, PreviousState, State, StateStack,
+};
 
 /// This trait allows a state to be mutated directly using the [`NextState<S>`](crate::state::NextState) resource.
 ///
@@ -48,23 +55,44 @@ pub trait FreelyMutableState: States {
 
 fn apply_state_transition<S: FreelyMutableState>(
     event: MessageWriter<StateTransitionEvent<S>>,
-    commands: Commands,
+    mut commands: Commands,
     current_state: Option<ResMut<State<S>>>,
     previous_state: Option<ResMut<PreviousState<S>>>,
     next_state: Option<ResMut<NextState<S>>>,
+    stack: Option<ResMut<StateStack<S>>>,
 ) {
-    let Some((next_state, allow_same_state_transitions)) = take_next_state(next_state) else {
+    let Some(change) = take_state_change(next_state) else {
         return;
     };
     let Some(current_state) = current_state else {
         return;
     };
-    internal_apply_state_transition(
+    let (next_state, allow_same_state_transitions, stack_operation) = match change {
+        StateChange::Set(next_state, allow_same_state_transitions) => {
+            (next_state, allow_same_state_transitions, None)
+        }
+        StateChange::Push(next_state) => {
+            let paused = current_state.get().clone();
+            match stack {
+                Some(mut stack) => stack.0.push(paused),
+                None => commands.insert_resource(StateStack(vec![paused])),
+            }
+            (next_state, true, Some(StateStackOperation::Push))
+        }
+        StateChange::Pop => {
+            let Some(resumed) = stack.and_then(|mut stack| stack.0.pop()) else {
+                return;
+            };
+            (resumed, true, Some(StateStackOperation::Pop))
+        }
+    };
+    internal_apply_stack_transition(
         event,
         commands,
         Some(current_state),
         previous_state,
         Some(next_state),
         allow_same_state_transitions,
+        stack_operation,
     );
 }
@@ -974,4 +974,69 @@ mod tests {
         assert_eq!(transitions[7], "sub enter");
         assert_eq!(transitions[8], "computed enter");
     }
+
+    #[test]
+    fn push_and_pop_run_pause_and_resume_schedules() {
+        let mut world = World::new();
+        setup_state_transitions_in_world(&mut world);
+        MessageRegistry::register_message::<StateTransitionEvent<SimpleState>>(&mut world);
+        world.init_resource::<State<SimpleState>>();
+        let mut schedules = world.remove_resource::<Schedules>().unwrap();
+        let apply_changes = schedules.get_mut(StateTransition).unwrap();
+        SimpleState::register_state(apply_changes);
+
+        world.init_resource::<TransitionTracker>();
+        fn register_transition(string: &'static str) -> impl Fn(ResMut<TransitionTracker>) {
+            move |mut transitions: ResMut<TransitionTracker>| transitions.0.push(string)
+        }
+
+        schedules.add_systems(OnExit(SimpleState::A), register_transition("exit A"));
+        schedules.add_systems(OnPause(SimpleState::A), register_transition("pause A"));
+        schedules.add_systems(OnResume(SimpleState::A), register_transition("resume A"));
+        schedules.add_systems(OnEnter(SimpleState::A), register_transition("enter A"));
+        schedules.add_systems(OnExit(SimpleState::B(true)), register_transition("exit B"));
+        schedules.add_systems(
+            OnEnter(SimpleState::B(true)),
+            register_transition("enter B"),
+        );
+        world.insert_resource(schedules);
+
+        world.init_resource::<NextState<SimpleState>>();
+        world.run_schedule(StateTransition);
+        assert!(!world.contains_resource::<StateStack<SimpleState>>());
+
+        world
+            .resource_mut::<NextState<SimpleState>>()
+            .push(SimpleState::B(true));
+        world.run_schedule(StateTransition);
+        assert_eq!(
+            world.resource::<State<SimpleState>>().0,
+            SimpleState::B(true)
+        );
+        assert_eq!(
+            world.resource::<StateStack<SimpleState>>().get(),
+            &[SimpleState::A]
+        );
+        assert_eq!(
+            world.resource::<TransitionTracker>().0,
+            ["pause A", "enter B"]
+        );
+
+        world.resource_mut::<TransitionTracker>().0.clear();
+        world.resource_mut::<NextState<SimpleState>>().pop();
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<SimpleState>>().0, SimpleState::A);
+        assert!(world.resource::<StateStack<SimpleState>>().is_empty());
+        assert_eq!(
+            world.resource::<TransitionTracker>().0,
+            ["exit B", "resume A"]
+        );
+
+        // Popping an empty stack does nothing.
+        world.resource_mut::<TransitionTracker>().0.clear();
+        world.resource_mut::<NextState<SimpleState>>().pop();
+        world.run_schedule(StateTransition);
+        assert_eq!(world.resource::<State<SimpleState>>().0, SimpleState::A);
+        assert!(world.resource::<TransitionTracker>().0.is_empty());
+    }
 }
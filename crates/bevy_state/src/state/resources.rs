@@ -1,3 +1,4 @@
+use alloc::vec::Vec;
 use core::ops::Deref;
 
 use bevy_ecs::{
@@ -188,6 +189,10 @@ pub enum NextState<S: FreelyMutableState> {
     ///
     /// This will not trigger state transitions schedules if the target state is the same as the current one.
     PendingIfNeq(S),
+    /// There is a pending push of state `S` onto the [`StateStack<S>`]
+    Push(S),
+    /// There is a pending pop of the current state off the [`StateStack<S>`]
+    Pop,
 }
 
 impl<S: FreelyMutableState> NextState<S> {
@@ -209,26 +214,129 @@ impl<S: FreelyMutableState> NextState<S> {
         }
     }
 
+    /// Tentatively push `state` on top of the current state.
+    ///
+    /// The current state is paused rather than exited: it's moved onto the [`StateStack<S>`],
+    /// and [`OnPause`](crate::state::OnPause) runs for it instead of [`OnExit`](crate::state::OnExit).
+    /// [`OnEnter`](crate::state::OnEnter) runs for the pushed state as usual.
+    ///
+    /// Only states that aren't [`SubStates`](crate::state::SubStates) have a stack:
+    /// for sub states, this behaves like [`set`](Self::set).
+    pub fn push(&mut self, state: S) {
+        *self = Self::Push(state);
+    }
+
+    /// Tentatively pop the current state, resuming the state below it on the [`StateStack<S>`].
+    ///
+    /// [`OnExit`](crate::state::OnExit) runs for the current state, and
+    /// [`OnResume`](crate::state::OnResume) runs for the resumed state instead of
+    /// [`OnEnter`](crate::state::OnEnter). If the stack is empty, nothing happens.
+    ///
+    /// Only states that aren't [`SubStates`](crate::state::SubStates) have a stack:
+    /// for sub states, this does nothing.
+    pub fn pop(&mut self) {
+        *self = Self::Pop;
+    }
+
     /// Remove any pending changes to [`State<S>`]
     pub fn reset(&mut self) {
         *self = Self::Unchanged;
     }
 }
 
-pub(crate) fn take_next_state<S: FreelyMutableState>(
+/// The states paused below the current [`State<S>`], from the bottom of the stack to the top.
+///
+/// States are added to the stack by [`NextState::push`] and removed by [`NextState::pop`].
+/// [`NextState::set`] only replaces the current state, and leaves the paused states untouched.
+///
+/// This resource is inserted into the world the first time a state is pushed.
+///
+/// ```
+/// use bevy_state::prelude::*;
+/// use bevy_ecs::prelude::*;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
+/// enum GameState {
+///     #[default]
+///     InGame,
+///     Paused,
+/// }
+///
+/// fn is_game_paused(stack: Option<Res<StateStack<GameState>>>) -> bool {
+///     stack.is_some_and(|stack| stack.contains(&GameState::InGame))
+/// }
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(bevy_reflect::Reflect),
+    reflect(Resource, Default, Debug, PartialEq)
+)]
+pub struct StateStack<S: States>(pub(crate) Vec<S>);
+
+impl<S: States> StateStack<S> {
+    /// Get the paused states, from the bottom of the stack to the top.
+    pub fn get(&self) -> &[S] {
+        &self.0
+    }
+
+    /// Get the state that will be resumed by the next [`NextState::pop`], if any.
+    pub fn top(&self) -> Option<&S> {
+        self.0.last()
+    }
+}
+
+impl<S: States> Default for StateStack<S> {
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<S: States> Deref for StateStack<S> {
+    type Target = [S];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A state change taken from [`NextState<S>`].
+pub(crate) enum StateChange<S> {
+    /// Replace the current state, with the given `allow_same_state_transitions`.
+    Set(S, bool),
+    /// Pause the current state and enter the given state.
+    Push(S),
+    /// Exit the current state and resume the top of the stack.
+    Pop,
+}
+
+pub(crate) fn take_state_change<S: FreelyMutableState>(
     next_state: Option<ResMut<NextState<S>>>,
-) -> Option<(S, bool)> {
+) -> Option<StateChange<S>> {
     let mut next_state = next_state?;
 
-    match core::mem::take(next_state.bypass_change_detection()) {
-        NextState::Pending(x) => {
-            next_state.set_changed();
-            Some((x, true))
-        }
-        NextState::PendingIfNeq(x) => {
-            next_state.set_changed();
-            Some((x, false))
+    let change = match core::mem::take(next_state.bypass_change_detection()) {
+        NextState::Pending(x) => StateChange::Set(x, true),
+        NextState::PendingIfNeq(x) => StateChange::Set(x, false),
+        NextState::Push(x) => StateChange::Push(x),
+        NextState::Pop => StateChange::Pop,
+        NextState::Unchanged => return None,
+    };
+    next_state.set_changed();
+    Some(change)
+}
+
+/// Takes the pending state change of a state without a [`StateStack<S>`].
+///
+/// A push is treated as a plain transition, and a pop is ignored.
+pub(crate) fn take_next_state<S: FreelyMutableState>(
+    next_state: Option<ResMut<NextState<S>>>,
+) -> Option<(S, bool)> {
+    match take_state_change(next_state)? {
+        StateChange::Set(x, allow_same_state_transitions) => {
+            Some((x, allow_same_state_transitions))
         }
-        NextState::Unchanged => None,
+        StateChange::Push(x) => Some((x, true)),
+        StateChange::Pop => None,
     }
 }
@@ -24,6 +24,24 @@ pub struct OnEnter<S: States>(pub S);
 #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash, Default)]
 pub struct OnExit<S: States>(pub S);
 
+/// The label of a [`Schedule`] that **only** runs whenever the provided state
+/// is covered by another state pushed with [`NextState::push`].
+///
+/// The paused state isn't exited, so [`OnExit`] doesn't run for it.
+///
+/// [`NextState::push`]: crate::state::NextState::push
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct OnPause<S: States>(pub S);
+
+/// The label of a [`Schedule`] that **only** runs whenever the provided state
+/// is uncovered by popping the state above it with [`NextState::pop`].
+///
+/// The resumed state isn't entered again, so [`OnEnter`] doesn't run for it.
+///
+/// [`NextState::pop`]: crate::state::NextState::pop
+#[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct OnResume<S: States>(pub S);
+
 /// The label of a [`Schedule`] that **only** runs whenever [`State<S>`]
 /// exits AND enters the provided `exited` and `entered` states.
 ///
@@ -72,6 +90,40 @@ pub struct StateTransitionEvent<S: States> {
     pub entered: Option<S>,
     /// Allow running state transition events when `exited` and `entered` are the same
     pub allow_same_state_transitions: bool,
+    /// The [`StateStack`] operation that caused this transition, if any.
+    ///
+    /// When a state is pushed, `exited` is paused rather than exited. When a
+    /// state is popped, `entered` is resumed rather than entered.
+    ///
+    /// [`StateStack`]: crate::state::StateStack
+    pub stack_operation: Option<StateStackOperation>,
+}
+
+impl<S: States> StateTransitionEvent<S> {
+    /// Returns true if the `exited` state was paused by a push rather than
+    /// exited.
+    pub fn is_pause(&self) -> bool {
+        self.stack_operation == Some(StateStackOperation::Push)
+    }
+
+    /// Returns true if the `entered` state was resumed by a pop rather than
+    /// entered.
+    pub fn is_resume(&self) -> bool {
+        self.stack_operation == Some(StateStackOperation::Pop)
+    }
+}
+
+/// An operation on the [`StateStack`] of a state.
+///
+/// [`StateStack`]: crate::state::StateStack
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StateStackOperation {
+    /// The current state was paused and a new state was pushed on top of it,
+    /// with [`NextState::push`](crate::state::NextState::push).
+    Push,
+    /// The current state was exited and the state below it was resumed, with
+    /// [`NextState::pop`](crate::state::NextState::pop).
+    Pop,
 }
 
 /// Applies state transitions and runs transitions schedules in order.
@@ -136,12 +188,36 @@ impl<S: States> Default for ApplyStateTransition<S> {
 /// The `new_state` is an option to allow for removal - `None` will trigger the
 /// removal of the `State<S>` resource from the [`World`].
 pub(crate) fn internal_apply_state_transition<S: States>(
+    event: MessageWriter<StateTransitionEvent<S>>,
+    commands: Commands,
+    current_state: Option<ResMut<State<S>>>,
+    previous_state: Option<ResMut<PreviousState<S>>>,
+    new_state: Option<S>,
+    allow_same_state_transitions: bool,
+) {
+    internal_apply_stack_transition(
+        event,
+        commands,
+        current_state,
+        previous_state,
+        new_state,
+        allow_same_state_transitions,
+        None,
+    );
+}
+
+/// Like [`internal_apply_state_transition`], but records the [`StateStack`]
+/// operation that caused the transition in the transition event.
+///
+/// [`StateStack`]: crate::state::StateStack
+pub(crate) fn internal_apply_stack_transition<S: States>(
     mut event: MessageWriter<StateTransitionEvent<S>>,
     mut commands: Commands,
     current_state: Option<ResMut<State<S>>>,
     mut previous_state: Option<ResMut<PreviousState<S>>>,
     new_state: Option<S>,
     allow_same_state_transitions: bool,
+    stack_operation: Option<StateStackOperation>,
 ) {
     match new_state {
         Some(entered) => {
@@ -161,6 +237,7 @@ pub(crate) fn internal_apply_state_transition<S: States>(
                         exited: Some(exited.clone()),
                         entered: Some(entered.clone()),
                         allow_same_state_transitions,
+                        stack_operation,
                     });
 
                     if let Some(ref mut previous_state) = previous_state {
@@ -177,6 +254,7 @@ pub(crate) fn internal_apply_state_transition<S: States>(
                         exited: None,
                         entered: Some(entered.clone()),
                         allow_same_state_transitions,
+                        stack_operation,
                     });
 
                     // When [`State<S>`] is initialized, there can be stale data in
@@ -197,6 +275,7 @@ pub(crate) fn internal_apply_state_transition<S: States>(
                     exited: Some(exited.clone()),
                     entered: None,
                     allow_same_state_transitions,
+                    stack_operation,
                 });
 
                 if let Some(ref mut previous_state) = previous_state {
@@ -250,11 +329,16 @@ pub(crate) fn run_enter<S: States>(
     if transition.entered == transition.exited && !transition.allow_same_state_transitions {
         return;
     }
+    let resumed = transition.is_resume();
     let Some(entered) = transition.entered else {
         return;
     };
 
-    let _ = world.try_run_schedule(OnEnter(entered));
+    if resumed {
+        let _ = world.try_run_schedule(OnResume(entered));
+    } else {
+        let _ = world.try_run_schedule(OnEnter(entered));
+    }
 }
 
 pub(crate) fn run_exit<S: States>(
@@ -267,11 +351,16 @@ pub(crate) fn run_exit<S: States>(
     if transition.entered == transition.exited && !transition.allow_same_state_transitions {
         return;
     }
+    let paused = transition.is_pause();
     let Some(exited) = transition.exited else {
         return;
     };
 
-    let _ = world.try_run_schedule(OnExit(exited));
+    if paused {
+        let _ = world.try_run_schedule(OnPause(exited));
+    } else {
+        let _ = world.try_run_schedule(OnExit(exited));
+    }
 }
 
 pub(crate) fn run_transition<S: States>(
@@ -159,6 +159,9 @@ where
 /// Despawns entities marked with [`DespawnOnExit<S>`] when their state no
 /// longer matches the world state.
 ///
+/// A state that is paused by [`NextState::push`](crate::state::NextState::push) is still
+/// alive, so its entities are only despawned once it's popped or replaced.
+///
 /// If the entity has already been despawned no warning will be emitted.
 pub fn despawn_entities_on_exit_state<S: States>(
     mut commands: Commands,
@@ -174,6 +177,9 @@ pub fn despawn_entities_on_exit_state<S: States>(
     if transition.entered == transition.exited && !transition.allow_same_state_transitions {
         return;
     }
+    if transition.is_pause() {
+        return;
+    }
     let Some(exited) = &transition.exited else {
         return;
     };
@@ -237,6 +243,9 @@ impl<S: States + Default> Default for DespawnOnEnter<S> {
 /// Despawns entities marked with [`DespawnOnEnter<S>`] when their state
 /// matches the world state.
 ///
+/// A state that is resumed by [`NextState::pop`](crate::state::NextState::pop) isn't
+/// entered again, so its entities aren't despawned.
+///
 /// If the entity has already been despawned no warning will be emitted.
 pub fn despawn_entities_on_enter_state<S: States>(
     mut commands: Commands,
@@ -252,6 +261,9 @@ pub fn despawn_entities_on_enter_state<S: States>(
     if transition.entered == transition.exited && !transition.allow_same_state_transitions {
         return;
     }
+    if transition.is_resume() {
+        return;
+    }
     let Some(entered) = &transition.entered else {
         return;
     };
@@ -387,4 +399,49 @@ mod tests {
         // the app's next state is the same as its previous.
         assert!(app.world().get_entity(entity).is_ok());
     }
+
+    #[test]
+    fn despawn_on_exit_keeps_paused_state() {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, States)]
+        enum State {
+            Game,
+            Menu,
+        }
+
+        let mut app = App::new();
+        app.add_plugins(StatesPlugin);
+
+        app.insert_state(State::Game);
+        app.update();
+
+        let game = app.world_mut().spawn(DespawnOnExit(State::Game)).id();
+        let menu_enter = app.world_mut().spawn(DespawnOnEnter(State::Game)).id();
+
+        app.world_mut().commands().push_state(State::Menu);
+        app.update();
+
+        // the game state was paused, not exited.
+        assert!(app.world().get_entity(game).is_ok());
+
+        let menu = app.world_mut().spawn(DespawnOnExit(State::Menu)).id();
+
+        app.world_mut().commands().pop_state::<State>();
+        app.update();
+
+        assert_eq!(
+            app.world()
+                .resource::<bevy_state::state::State<State>>()
+                .get(),
+            &State::Game
+        );
+        assert!(app.world().get_entity(menu).is_err());
+        // the game state was resumed, not entered.
+        assert!(app.world().get_entity(menu_enter).is_ok());
+        assert!(app.world().get_entity(game).is_ok());
+
+        app.world_mut().commands().set_state(State::Menu);
+        app.update();
+
+        assert!(app.world().get_entity(game).is_err());
+    }
 }
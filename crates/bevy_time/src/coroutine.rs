@@ -0,0 +1,88 @@
+use bevy_ecs::coroutine::AsyncWorld;
+use core::{future::Future, time::Duration};
+
+use crate::Time;
+
+/// Extension trait for [`AsyncWorld`] that provides time-based waiting.
+pub trait AsyncTimeExt {
+    /// Waits until `duration` has elapsed on the default [`Time`] clock.
+    ///
+    /// The coroutine resumes on the first frame where the elapsed time is past the deadline,
+    /// so it may oversleep by up to a frame.
+    ///
+    /// # Usage
+    ///
+    /// ```
+    /// # use bevy_ecs::{coroutine::AsyncWorld, prelude::*};
+    /// # use bevy_time::AsyncTimeExt;
+    /// # use core::time::Duration;
+    /// #[derive(Component)]
+    /// struct Banner;
+    ///
+    /// fn flash_banner(mut commands: Commands) {
+    ///     commands.spawn_coroutine(|world: AsyncWorld| async move {
+    ///         let banner = world.run(|world| world.spawn(Banner).id()).await;
+    ///         world.sleep(Duration::from_secs(2)).await;
+    ///         world.run(move |world| world.despawn(banner)).await;
+    ///     });
+    /// }
+    /// # bevy_ecs::system::assert_is_system(flash_banner);
+    /// ```
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send + 'static;
+}
+
+impl AsyncTimeExt for AsyncWorld {
+    fn sleep(&self, duration: Duration) -> impl Future<Output = ()> + Send + 'static {
+        let world = self.clone();
+        async move {
+            // Coroutines are resumed in `Update`, where `Time` is the virtual clock, so sleeping
+            // stops while the game is paused and follows its relative speed.
+            let deadline = world.run(|world| world.resource::<Time>().elapsed()).await + duration;
+            world
+                .wait_until(move |world| world.resource::<Time>().elapsed() >= deadline)
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use bevy_app::{App, Startup};
+    use bevy_ecs::{component::Component, coroutine::AsyncWorld, system::Commands};
+
+    use crate::{AsyncTimeExt, TimePlugin, TimeUpdateStrategy};
+
+    #[derive(Component)]
+    struct DummyComponent;
+
+    #[test]
+    fn coroutines_should_sleep_with_time_plugin_enabled() {
+        fn spawn_coroutine(mut commands: Commands) {
+            commands.spawn_coroutine(|world: AsyncWorld| async move {
+                world.sleep(Duration::from_secs_f32(0.5)).await;
+                world.run(|world| world.spawn(DummyComponent).id()).await;
+            });
+        }
+
+        let mut app = App::new();
+        app.add_plugins(TimePlugin)
+            .add_systems(Startup, spawn_coroutine)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(
+                0.2,
+            )));
+
+        for frame in 0..6 {
+            app.update();
+            let dummy_count = app
+                .world_mut()
+                .query::<&DummyComponent>()
+                .iter(app.world())
+                .count();
+
+            // The coroutine starts sleeping at 0.0s, and wakes up once 0.6s have elapsed
+            assert_eq!(dummy_count, usize::from(frame >= 3));
+        }
+    }
+}
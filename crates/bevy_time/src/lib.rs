@@ -14,6 +14,7 @@ extern crate alloc;
 
 /// Common run conditions
 pub mod common_conditions;
+mod coroutine;
 mod delayed_commands;
 mod fixed;
 mod real;
@@ -22,6 +23,7 @@ mod time;
 mod timer;
 mod virt;
 
+pub use coroutine::*;
 pub use delayed_commands::*;
 pub use fixed::*;
 pub use real::*;
@@ -35,7 +37,9 @@ pub use virt::*;
 /// This includes the most common types in this crate, re-exported for your convenience.
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{DelayedCommandsExt, Fixed, Real, Time, Timer, TimerMode, Virtual};
+    pub use crate::{
+        AsyncTimeExt, DelayedCommandsExt, Fixed, Real, Time, Timer, TimerMode, Virtual,
+    };
 }
 
 use bevy_app::{prelude::*, RunFixedMainLoop};
@@ -0,0 +1,156 @@
+//! A tool for processing assets without running an app.
+//!
+//! This processes the assets in `assets/` into `imported_assets/Default/` with Bevy's asset
+//! processor, using the loaders and processors registered by the plugins below, and exits.
+//!
+//! To run this tool, use the following command from the directory containing `assets/`:
+//!
+//! ```sh
+//! cargo run -p process-assets -- [--assets <dir>] [--output <dir>] [--log <file>] [--report <file>] [--dry-run]
+//! ```
+//!
+//! - `--assets`: The directory to read source assets from. Defaults to `assets`.
+//! - `--output`: The directory to write processed assets to. Defaults to `imported_assets/Default`.
+//! - `--log`: The processor transaction log file. Defaults to `imported_assets/log`.
+//! - `--report`: A file to write a JSON report of the processing run to.
+//! - `--dry-run`: Only list the assets that would be processed, and why.
+//!
+//! The exit code is `0` if every asset was processed, `1` if some assets failed to process and `2`
+//! if processing could not run at all.
+
+#![expect(
+    clippy::print_stdout,
+    clippy::print_stderr,
+    reason = "Allowed in tools."
+)]
+
+use std::{env, fs, path::PathBuf, process::ExitCode};
+
+use bevy_app::{App, TaskPoolPlugin};
+use bevy_asset::{
+    processor::{process_assets_once, AssetProcessor, FileTransactionLogFactory},
+    AssetApp, AssetMode, AssetPlugin,
+};
+use bevy_image::{CompressedImageFormats, ImageLoader, ImagePlugin};
+
+struct Args {
+    assets: PathBuf,
+    output: PathBuf,
+    log: PathBuf,
+    report: Option<PathBuf>,
+    dry_run: bool,
+}
+
+impl Args {
+    fn parse() -> Result<Self, String> {
+        let current_dir = env::current_dir().map_err(|err| err.to_string())?;
+        let mut args = Args {
+            assets: current_dir.join("assets"),
+            output: current_dir.join("imported_assets/Default"),
+            log: current_dir.join("imported_assets/log"),
+            report: None,
+            dry_run: false,
+        };
+
+        let mut raw_args = env::args().skip(1);
+        while let Some(arg) = raw_args.next() {
+            let mut path = || {
+                raw_args
+                    .next()
+                    .map(|path| current_dir.join(path))
+                    .ok_or_else(|| format!("Missing value for `{arg}`"))
+            };
+            match arg.as_str() {
+                "--assets" => args.assets = path()?,
+                "--output" => args.output = path()?,
+                "--log" => args.log = path()?,
+                "--report" => args.report = Some(path()?),
+                "--dry-run" => args.dry_run = true,
+                _ => return Err(format!("Unknown argument `{arg}`")),
+            }
+        }
+        Ok(args)
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins((
+        TaskPoolPlugin::default(),
+        AssetPlugin {
+            file_path: args.assets.to_string_lossy().into_owned(),
+            processed_file_path: args.output.to_string_lossy().into_owned(),
+            mode: AssetMode::Processed,
+            use_asset_processor_override: Some(true),
+            watch_for_changes_override: Some(false),
+            ..Default::default()
+        },
+        ImagePlugin::default(),
+    ))
+    // Without a GPU, only uncompressed images can be loaded.
+    .register_asset_loader(ImageLoader::new(CompressedImageFormats::NONE));
+
+    let processor = app.world().resource::<AssetProcessor>().clone();
+    processor
+        .data()
+        .set_log_factory(Box::new(FileTransactionLogFactory {
+            file_path: args.log,
+        }))
+        .unwrap();
+
+    if args.dry_run {
+        return match bevy_tasks::block_on(processor.dry_run()) {
+            Ok(entries) => {
+                for entry in entries {
+                    println!("{}: {:?}", entry.path, entry.reason);
+                }
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("Failed to check assets: {err}");
+                ExitCode::from(2)
+            }
+        };
+    }
+
+    let output = match process_assets_once(&mut app) {
+        Ok(output) => output,
+        Err(err) => {
+            eprintln!("Failed to process assets: {err}");
+            return ExitCode::from(2);
+        }
+    };
+
+    if let Some(report_path) = args.report {
+        let report = serde_json::to_string_pretty(&output.report).unwrap();
+        if let Err(err) = fs::write(&report_path, report) {
+            eprintln!("Failed to write report to {}: {err}", report_path.display());
+            return ExitCode::from(2);
+        }
+    }
+
+    let mut failed = 0;
+    for (path, err) in output.errors() {
+        eprintln!("Failed to process {path}: {err}");
+        failed += 1;
+    }
+    println!(
+        "Handled {} assets in {:?}, {failed} failed",
+        output.report.assets.len(),
+        output.report.duration
+    );
+
+    if output.is_success() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}